@@ -22,15 +22,99 @@ echo "  🩹  Fixing types..."
 for t in lib_*/*.d.ts; do
   replace 's/find(text: string): any;/find(text: string): Match | undefined;/g' $t
   replace 's/findAt(text: string, start: number): any;/findAt(text: string, start: number): Match | undefined;/g' $t
+  replace 's/findInRange(text: string, start: number, end: number): any;/findInRange(text: string, start: number, end: number): Match | undefined;/g' $t
+  replace 's/findOrNull(text: string): any;/findOrNull(text: string): Match | null;/g' $t
+  replace 's/findUtf16(text: string): any;/findUtf16(text: string): Match | undefined;/g' $t
+  replace 's/findAllUtf16(text: string): any;/findAllUtf16(text: string): Match[];/g' $t
+  replace 's/findWithContext(text: string, context_chars: number): any;/findWithContext(text: string, context_chars: number): ContextMatch | undefined;/g' $t
+  replace 's/findAllWithContext(text: string, context_chars: number): any;/findAllWithContext(text: string, context_chars: number): ContextMatch[];/g' $t
+  replace 's/findWithPosition(text: string): any;/findWithPosition(text: string): PositionedMatch | undefined;/g' $t
+  replace 's/matchExactlyAt(text: string, start: number): any;/matchExactlyAt(text: string, start: number): Match | undefined;/g' $t
+  replace 's/findOn(text: RRegexText): any;/findOn(text: RRegexText): Match | undefined;/g' $t
+  replace 's/findAllOn(text: RRegexText): any;/findAllOn(text: RRegexText): Match[];/g' $t
+  replace 's/findWithin(text: string, max_bytes: number): any;/findWithin(text: string, max_bytes: number): Match | undefined;/g' $t
   replace 's/findAll(text: string): any;/findAll(text: string): Match[];/g' $t
   replace 's/findAll(text: string): any;/findAll(text: string): Match[];/g' $t
+  replace 's/findLast(text: string): any;/findLast(text: string): Match | undefined;/g' $t
+  replace 's/findAfter(text: string, offset: number): any;/findAfter(text: string, offset: number): Match | undefined;/g' $t
+  replace 's/findBefore(text: string, offset: number): any;/findBefore(text: string, offset: number): Match | undefined;/g' $t
+  replace 's/findLastIndex(text: string): any;/findLastIndex(text: string): ByteRange | undefined;/g' $t
+  replace 's/findNth(text: string, n: number): any;/findNth(text: string, n: number): Match | undefined;/g' $t
+  replace 's/findAllLimited(text: string, max: number): any;/findAllLimited(text: string, max: number): Match[];/g' $t
+  replace 's/findAllPaged(text: string, page_size: number): any;/findAllPaged(text: string, page_size: number): Match[][];/g' $t
+  replace 's/findAllCharLen(text: string): any;/findAllCharLen(text: string): CharLenMatch[];/g' $t
+  replace 's/findAllMerged(text: string, max_gap: number): any;/findAllMerged(text: string, max_gap: number): ByteRange[];/g' $t
+  replace 's/findAllColumnar(text: string): object;/findAllColumnar(text: string): ColumnarMatches;/g' $t
+  replace 's/findAllOverlapping(text: string): any;/findAllOverlapping(text: string): Match[];/g' $t
+  replace 's/matchingLines(text: string): any;/matchingLines(text: string): MatchingLine[];/g' $t
+  replace 's/matchOccurrences(text: string): Map<any, any>;/matchOccurrences(text: string): Map<string, number>;/g' $t
+  replace 's/findAllWithLine(text: string): any;/findAllWithLine(text: string): LineMatch[];/g' $t
+  replace 's/matchStats(text: string): any;/matchStats(text: string): MatchStats;/g' $t
+  replace 's/literalAlternatives(): any;/literalAlternatives(): string[] | undefined;/g' $t
+  replace 's/complexity(): any;/complexity(): Complexity;/g' $t
   replace 's/split(text: string): any;/split(text: string): string[];/g' $t
   replace 's/splitn(text: string, limit: number): any;/splitn(text: string, limit: number): string[];/g' $t
+  replace 's/splitnInfo(text: string, limit: number): any;/splitnInfo(text: string, limit: number): SplitInfo;/g' $t
+  replace 's/splitBounded(text: string, max_parts: number): any;/splitBounded(text: string, max_parts: number): SplitInfo;/g' $t
+  replace 's/splitIndices(text: string): any;/splitIndices(text: string): Match[];/g' $t
+  replace 's/splitMap(text: string, f: Function): Array<any>;/splitMap<T>(text: string, f: (segment: string, index: number) => T): T[];/g' $t
+  replace 's/splitFields(text: string): any;/splitFields(text: string): Field[];/g' $t
+  replace 's/partition(text: string): any;/partition(text: string): Segment[];/g' $t
   replace 's/syntax(): any;/syntax(): Hir;/g' $t
+  replace 's/syntaxWithOptions(options: any): any;/syntaxWithOptions(options: SyntaxOptions): Hir;/g' $t
+  replace 's/ast(): any;/ast(): Ast;/g' $t
+  replace 's/astWithComments(): any;/astWithComments(): AstWithComments;/g' $t
+  replace 's/hirToString(hir: any): string;/hirToString(hir: Hir): string;/g' $t
+  replace 's/classCaseFold(hir: any): any;/classCaseFold(hir: Hir): Hir;/g' $t
+  replace 's/classNegate(hir: any): any;/classNegate(hir: Hir): Hir;/g' $t
+  replace 's/classContains(class_unicode: any, ch: string): boolean;/classContains(class_unicode: ClassUnicode, ch: string): boolean;/g' $t
+  replace 's/byteClassContains(class_bytes: any, byte: number): boolean;/byteClassContains(class_bytes: ClassBytes, byte: number): boolean;/g' $t
+  replace 's/byteClassRanges(class_bytes: any): any;/byteClassRanges(class_bytes: ClassBytes): ClassBytesRange[];/g' $t
+  replace 's/validate(pattern: string): any;/validate(pattern: string): RegexError | null;/g' $t
+  replace 's/words(text: string): any;/words(text: string): Match[];/g' $t
+  replace 's/fromHir(hir: any): RRegex;/fromHir(hir: Hir): RRegex;/g' $t
+  replace 's/toJSON(): any;/toJSON(): RRegexJSON;/g' $t
+  replace 's/fromJSON(json: any): RRegex;/fromJSON(json: RRegexJSON): RRegex;/g' $t
   replace 's/captureNames(): any\[\];/captureNames(): (string | null)[];/g' $t
+  replace 's/captureNameIndex(): any;/captureNameIndex(): Record<string, number>;/g' $t
   replace 's/captures(text: string): any;/captures(text: string): Captures | undefined;/g' $t
+  replace 's/capturesAt(text: string, start: number): any;/capturesAt(text: string, start: number): Captures | undefined;/g' $t
+  replace 's/capturesRead(text: string, locations: RRegexLocations): any;/capturesRead(text: string, locations: RRegexLocations): Match | undefined;/g' $t
+  replace 's/capturesInto(text: string, target: object): boolean;/capturesInto(text: string, target: Record<string, string | undefined>): boolean;/g' $t
+  replace 's/capturesTyped(text: string, kinds: Array<any>): any;/capturesTyped(text: string, kinds: ("string" | "number" | "boolean" | undefined)[]): TypedCaptures | undefined;/g' $t
   replace 's/capturesAll(text: string): any;/capturesAll(text: string): Captures[];/g' $t
+  replace 's/matchAllCompat(text: string): any;/matchAllCompat(text: string): RegExpMatchArray[];/g' $t
+  replace 's/capturesTree(text: string): any;/capturesTree(text: string): CaptureNode | undefined;/g' $t
+  replace 's/findAllGroup(text: string, group: number): any;/findAllGroup(text: string, group: number): Match[];/g' $t
+  replace 's/findAllNamedGroup(text: string, name: string): any;/findAllNamedGroup(text: string, name: string): Match[];/g' $t
+  replace 's/at(index: number): any;/at(index: number): ByteRange | undefined;/g' $t
+  replace 's/next(offset: number): any;/next(offset: number): ByteRange | undefined;/g' $t
+  replace 's/prev(offset: number): any;/prev(offset: number): ByteRange | undefined;/g' $t
+  replace 's/next(): any;/next(): Captures | undefined;/g' $t
+  replace 's/drain(): any;/drain(): Match[];/g' $t
+  replace 's/exec(text: string): any;/exec(text: string): RegExpExecArray | null;/g' $t
+  replace 's/finish(): any;/finish(): Match[];/g' $t
+  replace 's/isMatchMany(texts: (string)\[\]): any\[\];/isMatchMany(texts: string[]): boolean[];/g' $t
+  replace 's/isMatchChunks(chunks: (string)\[\]): boolean;/isMatchChunks(chunks: string[]): boolean;/g' $t
+  replace 's/filterNonMatching(texts: (string)\[\]): (string)\[\];/filterNonMatching(texts: string[]): string[];/g' $t
+  replace 's/findFirstMany(texts: (string)\[\]): any;/findFirstMany(texts: string[]): (Match | undefined)[];/g' $t
+  replace 's/shortestMatchRange(text: string): any;/shortestMatchRange(text: string): ByteRange | undefined;/g' $t
+  replace 's/replaceAllMaybe(text: string, rep: string): any;/replaceAllMaybe(text: string, rep: string): MaybeReplacement;/g' $t
+  replace 's/replaceAllWithSpans(text: string, rep: string): any;/replaceAllWithSpans(text: string, rep: string): SpannedReplacement;/g' $t
+  replace 's/previewReplaceAll(text: string, rep: string): any;/previewReplaceAll(text: string, rep: string): ReplacementChange[];/g' $t
+  replace 's/replaceAllCounted(text: string, rep: string): any;/replaceAllCounted(text: string, rep: string): CountedReplacement;/g' $t
+  replace 's/replaceMap(text: string, mapping: any): string;/replaceMap(text: string, mapping: Record<string, string>): string;/g' $t
+  replace 's/replaceAllFnChunked(text: string, f: Function, chunk_matches: number, progress?: Function): string;/replaceAllFnChunked(text: string, f: (captures: Captures) => string, chunk_matches: number, progress?: (count: number) => void): string;/g' $t
+  replace 's/findAllWhere(text: string, predicate: Function): any;/findAllWhere(text: string, predicate: (match: Match) => unknown): Match[];/g' $t
+  replace 's/forEachMatch(text: string, f: Function): void;/forEachMatch(text: string, f: (match: Match) => boolean | void): void;/g' $t
+  replace 's/replaceAllChunked(text: string, rep: string, f: Function): void;/replaceAllChunked(text: string, rep: string, f: (chunk: string) => void): void;/g' $t
+  replace 's/replaceAllFnPos(text: string, f: Function): string;/replaceAllFnPos(text: string, f: (captures: Captures, start: number, end: number) => string): string;/g' $t
+  replace 's/replaceAllIf(text: string, predicate: Function, rep: string): string;/replaceAllIf(text: string, predicate: (captures: Captures) => unknown, rep: string): string;/g' $t
+  replace 's/replaceAllFnNamed(text: string, f: Function): string;/replaceAllFnNamed(text: string, f: (groups: Record<string, string | undefined>) => string): string;/g' $t
+  replace 's/conflictingGroupNames(): any;/conflictingGroupNames(): GroupNameConflict[];/g' $t
   replace 's/matches(text: string): any\[\];/matches(text: string): number[];/g' $t
+  replace 's/matchesOrderedByPosition(text: string): any\[\];/matchesOrderedByPosition(text: string): number[];/g' $t
+  replace 's/matchesAt(text: string, start: number): any\[\];/matchesAt(text: string, start: number): number[];/g' $t
 done
 
 echo "  📦  Bundling Cloudflare bundle..."
@@ -7,7 +7,19 @@ static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 mod types;
 mod rregex;
 mod rregexset;
+mod rregexsetbuilder;
+mod rregexbuilder;
+mod rregexcapturesiter;
+mod rregexlocations;
+mod rregexnavigator;
+mod rregexreplacement;
+mod rregexscanner;
+mod rregexstateful;
+mod rregextext;
 mod escape;
+mod syntax;
+mod validate;
+mod words;
 
 #[allow(dead_code)]
 pub fn set_panic_hook() {
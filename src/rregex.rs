@@ -1,13 +1,58 @@
+use std::borrow::Cow;
+use std::cell::OnceCell;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::convert::TryInto;
 
+use crate::rregexcapturesiter::RRegexCapturesIter;
+use crate::rregexlocations::RRegexLocations;
+use crate::rregexnavigator::RRegexNavigator;
+use crate::rregexreplacement::RRegexReplacement;
+use crate::rregexscanner::RRegexScanner;
+use crate::rregexstateful::RRegexStateful;
+use crate::rregextext::RRegexText;
+use crate::types::Ast;
+use crate::types::ByteRange;
+use crate::types::CaptureNode;
+use crate::types::CharLenMatch;
+use crate::types::Complexity;
+use crate::types::ContextMatch;
+use crate::types::CountedReplacement;
+use crate::types::Field;
 use crate::types::Hir;
+use crate::types::LineMatch;
 use crate::types::Match;
-use regex_syntax::Parser;
+use crate::types::MatchStats;
+use crate::types::MatchingLine;
+use crate::types::MaybeReplacement;
+use crate::types::PositionedMatch;
+use crate::types::RRegexJson;
+use crate::types::RebasedMatch;
+use crate::types::RegexError;
+use crate::types::RegexFlags;
+use crate::types::ReplacementChange;
+use crate::types::ReplacementSpan;
+use crate::types::Segment;
+use crate::types::SpannedReplacement;
+use crate::types::SplitInfo;
+use crate::types::Substring;
+use crate::types::SyntaxOptions;
+use crate::types::TextPosition;
+use regex_automata::nfa::thompson;
+use regex_automata::nfa::thompson::pikevm::PikeVM;
+use regex_syntax::ast;
+use regex_syntax::hir;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsValue;
 
 type Result<T> = std::result::Result<T, serde_wasm_bindgen::Error>;
 
+thread_local! {
+    /// The regexes compiled by `RRegex.getOrCompile`, keyed by pattern.
+    static CACHE: RefCell<HashMap<String, regex::Regex>> = RefCell::new(HashMap::new());
+}
+
 /// A compiled regular expression for matching Unicode strings.
 ///
 /// A `RRegex` can be used to search haystacks, split haystacks into substrings
@@ -48,8 +93,17 @@ type Result<T> = std::result::Result<T, serde_wasm_bindgen::Error>;
 ///
 /// @see https://docs.rs/regex/latest/regex/
 #[wasm_bindgen]
+#[derive(Clone)]
 pub struct RRegex {
-    regex: regex::Regex,
+    regex: Option<regex::Regex>,
+    flags: RegexFlags,
+    hir: OnceCell<hir::Hir>,
+    full: OnceCell<regex::Regex>,
+    suffix: OnceCell<regex::Regex>,
+    longest: OnceCell<PikeVM>,
+    literal: OnceCell<Option<String>>,
+    required: OnceCell<Vec<String>>,
+    size: OnceCell<usize>,
 }
 
 #[wasm_bindgen]
@@ -62,7 +116,155 @@ impl RRegex {
     pub fn new(re: &str) -> Result<RRegex> {
         let r = regex::Regex::new(re).map_err(serde_wasm_bindgen::Error::new)?;
 
-        Ok(RRegex { regex: r })
+        Ok(RRegex::from(r))
+    }
+
+    /// Compiles a regular expression like the constructor does, but when an
+    /// invalid expression is given the error thrown is a `RegexError` object
+    /// that includes the kind of error and the span of the pattern where the
+    /// parser failed.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// try {
+    ///   RRegex.tryNew("(?P<name>a")
+    /// } catch (err) {
+    ///   expect(err.kind).toBe("GroupUnclosed")
+    ///   expect(err.span).toEqual({ start: 0, end: 1 })
+    /// }
+    /// ```
+    ///
+    /// @param {string} re - The regular expression to compile
+    /// @returns {RRegex}
+    /// @throws {RegexError}
+    #[wasm_bindgen(skip_jsdoc, js_name = tryNew)]
+    pub fn try_new(re: &str) -> Result<RRegex> {
+        match regex::Regex::new(re) {
+            Ok(regex) => Ok(RRegex::from(regex)),
+            Err(err) => {
+                let error: JsValue = RegexError::new(re, err).try_into()?;
+                Err(serde_wasm_bindgen::Error::from(error))
+            }
+        }
+    }
+
+    /// Returns a regex for `pattern` from a global cache, compiling it and
+    /// adding it to the cache the first time the pattern is seen. This
+    /// avoids recompiling the same patterns used across many modules.
+    ///
+    /// Every call returns a new `RRegex`, which shares the compiled program
+    /// of the cached one. The cache is never evicted on its own, call
+    /// `clearCache` to free it. If an invalid expression is given, then an
+    /// error is returned and nothing is cached.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = RRegex.getOrCompile("\\d+")
+    /// expect(re.isMatch("42")).toBe(true)
+    /// ```
+    ///
+    /// @param {string} pattern - The regular expression to compile
+    /// @returns {RRegex}
+    #[wasm_bindgen(skip_jsdoc, js_name = getOrCompile)]
+    pub fn get_or_compile(pattern: &str) -> Result<RRegex> {
+        CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            if let Some(regex) = cache.get(pattern) {
+                return Ok(RRegex::from(regex.clone()));
+            }
+
+            let regex = regex::Regex::new(pattern).map_err(serde_wasm_bindgen::Error::new)?;
+            cache.insert(pattern.to_owned(), regex.clone());
+            Ok(RRegex::from(regex))
+        })
+    }
+
+    /// Removes every regex from the cache used by `getOrCompile`. The regexes
+    /// it already returned keep working.
+    ///
+    /// @returns {void}
+    #[wasm_bindgen(skip_jsdoc, js_name = clearCache)]
+    pub fn clear_cache() {
+        CACHE.with(|cache| cache.borrow_mut().clear());
+    }
+
+    /// Compiles a regular expression that matches any of the given words
+    /// literally. Every word is escaped, so meta characters like `.` or `*`
+    /// don't need to be escaped by the caller.
+    ///
+    /// Duplicate words are ignored, and the remaining ones are tried in the
+    /// order they were given. When `wordBoundary` is true, the words only
+    /// match as whole words, i.e., the union is wrapped with `\b`. An empty
+    /// list of words results in a regex that never matches.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = RRegex.unionOf(["a.b", "c*"])
+    /// expect(re.toString()).toBe("a\\.b|c\\*")
+    /// expect(re.isMatch("axb")).toBe(false)
+    ///
+    /// const words = RRegex.unionOf(["cat", "dog"], true)
+    /// expect(words.isMatch("concatenate")).toBe(false)
+    /// ```
+    ///
+    /// @param {string[]} words - The literal words to match
+    /// @param {boolean} [wordBoundary] - Whether to only match whole words
+    /// @returns {RRegex}
+    #[wasm_bindgen(skip_jsdoc, js_name = unionOf)]
+    pub fn union_of(words: Vec<String>, word_boundary: Option<bool>) -> Result<RRegex> {
+        let mut escaped: Vec<String> = Vec::with_capacity(words.len());
+        for word in words.iter() {
+            let word = regex::escape(word);
+            if !escaped.contains(&word) {
+                escaped.push(word);
+            }
+        }
+
+        let pattern = if escaped.is_empty() {
+            String::from(r"[^\x{0}-\x{10FFFF}]")
+        } else if word_boundary.unwrap_or(false) {
+            format!(r"\b(?:{})\b", escaped.join("|"))
+        } else {
+            escaped.join("|")
+        };
+
+        RRegex::new(&pattern)
+    }
+
+    /// Compiles a regular expression from an `Hir`, such as the one returned
+    /// by `syntax`, possibly after transforming it.
+    ///
+    /// The tree is turned into a pattern with `hirToString` and then compiled,
+    /// so the `toString` of the regex returned is that pattern. An error is
+    /// thrown if the object isn't a valid `Hir`, or if the tree can't be
+    /// compiled, e.g., because it can match invalid UTF-8.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const hir = new RRegex("a|b").syntax()
+    /// const re = RRegex.fromHir(hir)
+    /// expect(re.isMatch("b")).toBe(true)
+    /// ```
+    ///
+    /// @param {Hir} hir - The syntax tree to compile
+    /// @returns {RRegex}
+    #[wasm_bindgen(skip_jsdoc, js_name = fromHir)]
+    pub fn from_hir(hir: JsValue) -> Result<RRegex> {
+        let hir: Hir<hir::Hir> = serde_wasm_bindgen::from_value(hir)?;
+
+        RRegex::new(&hir.into_inner().to_string())
     }
 
     /// Returns true if and only if there is a match for the regex in the
@@ -70,7 +272,10 @@ impl RRegex {
     ///
     /// It is recommended to use this method if all you need to do is test
     /// a match, since the underlying matching engine may be able to do less
-    /// work.
+    /// work. When the pattern is a plain literal, like `foo`, this is a
+    /// substring search that doesn't run the regex engine at all. And when
+    /// every match must contain some literals, like `foo` and `bar` in
+    /// `foo\d+bar`, texts missing any of them are rejected before running it.
     ///
     /// # Example
     ///
@@ -88,8 +293,17 @@ impl RRegex {
     /// @param {string} text - The string against which to match the regular expression
     /// @return {boolean}
     #[wasm_bindgen(skip_jsdoc, js_name = isMatch)]
-    pub fn is_match(&self, text: &str) -> bool {
-        self.regex.is_match(text)
+    pub fn is_match(&self, text: &str) -> Result<bool> {
+        let regex = self.regex()?;
+        Ok(match self.literal() {
+            Some(literal) => text.contains(literal),
+            None => {
+                self.required_literals()
+                    .iter()
+                    .all(|literal| text.contains(literal.as_str()))
+                    && regex.is_match(text)
+            }
+        })
     }
 
     /// Returns the same as is_match, but starts the search at the given offset.
@@ -102,475 +316,4992 @@ impl RRegex {
     /// @param {number} start - Zero-based index at which to start matching
     /// @return {boolean}
     #[wasm_bindgen(skip_jsdoc, js_name = isMatchAt)]
-    pub fn is_match_at(&self, text: &str, start: usize) -> bool {
+    pub fn is_match_at(&self, text: &str, start: usize) -> Result<bool> {
         if text.len() < start {
-            false
+            Ok(false)
         } else {
-            self.regex.is_match_at(text, start)
+            Ok(self.regex()?.is_match_at(text, start))
         }
     }
 
-    /// Returns the start and end byte range of the leftmost-first match in
-    /// `text`. If no match exists, then `undefined` is returned.
+    /// Returns true if and only if there is a match for the regex within the
+    /// byte range `start..end` of `text`.
     ///
-    /// Note that this should only be used if you want to discover the position
-    /// of the match. Testing the existence of a match is faster if you use
-    /// `isMatch`.
+    /// The text before `start` is still taken into consideration for things
+    /// like `\b`, exactly like `isMatchAt` does, while the search stops at
+    /// `end` as if the text ended there, so `$` and `\z` match at `end`.
     ///
-    /// # Example
+    /// If the range is out of bounds or doesn't fall on UTF-8 character
+    /// boundaries, then `false` is returned.
     ///
-    /// Find the start and end location of the first word with exactly 13
-    /// Unicode word characters:
+    /// # Example
     ///
     /// ```typescript
     /// import { RRegex } from "rregex"
     ///
-    /// const text = "I categorically deny having triskaidekaphobia."
-    /// const m = new RRegex("\\b\\w{13}\\b").find(text)
-    /// expect(m.start).toBe(2)
-    /// expect(m.end).toBe(15)
+    /// const re = new RRegex("\\d+")
+    /// expect(re.isMatchWithin("ab 12 cd", 0, 3)).toBe(false)
+    /// expect(re.isMatchWithin("ab 12 cd", 0, 4)).toBe(true)
     /// ```
     ///
-    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.find
-    /// @param {string} text - The string against which to match the regular expression
-    /// @return {Match}
-    #[wasm_bindgen(skip_jsdoc)]
-    pub fn find(&self, text: &str) -> Result<JsValue> {
-        self.find_at(text, 0)
-    }
-
-    /// Returns the same as find, but starts the search at the given
-    /// offset.
-    ///
-    /// The significance of the starting point is that it takes the surrounding
-    /// context into consideration. For example, the `\A` anchor can only
-    /// match when `start == 0`.
-    ///
-    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.find_at
     /// @param {string} text - The string against which to match the regular expression
     /// @param {number} start - Zero-based index at which to start matching
-    /// @returns {Match}
-    #[wasm_bindgen(skip_jsdoc, js_name = findAt)]
-    pub fn find_at(&self, text: &str, start: usize) -> Result<JsValue> {
-        if start > text.len() {
-            return Ok(JsValue::UNDEFINED);
-        };
-
-        let r = self.regex.find_at(text, start);
-
-        match r {
-            Some(m) => Match::from(m).try_into(),
-            None => Ok(JsValue::UNDEFINED),
+    /// @param {number} end - Zero-based index at which to stop matching
+    /// @return {boolean}
+    #[wasm_bindgen(skip_jsdoc, js_name = isMatchWithin)]
+    pub fn is_match_within(&self, text: &str, start: usize, end: usize) -> Result<bool> {
+        if start > end || !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+            Ok(false)
+        } else {
+            Ok(self.regex()?.is_match_at(&text[..end], start))
         }
     }
 
-    /// Returns an array for each successive non-overlapping match in `text``,
-    /// returning the start and end byte indices with respect to `text`.
+    /// Returns true if and only if there is a match for the regex within the
+    /// first `maxBytes` bytes of `text`, without scanning the rest of it.
     ///
-    /// # Example
+    /// The search stops at `maxBytes` as if the text ended there, so `$` and
+    /// `\z` match at the end of the window. If `maxBytes` falls inside a
+    /// UTF-8 character, the window ends before that character.
     ///
-    /// Find the start and end location of every word with exactly 13 Unicode
-    /// word characters:
+    /// # Example
     ///
     /// ```typescript
     /// import { RRegex } from "rregex"
     ///
-    /// const text = "Retroactively relinquishing remunerations is reprehensible."
-    /// const matches = new RRegex("\\b\\w{13}\\b").findAll(text)
+    /// const re = new RRegex("\\d+")
+    /// expect(re.isMatchBudgeted("abc 123", 4)).toBe(false)
+    /// expect(re.isMatchBudgeted("abc 123", 5)).toBe(true)
     /// ```
     ///
-    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.find_iter
+    /// @see findWithin
     /// @param {string} text - The string against which to match the regular expression
-    /// @returns {Match}
-    #[wasm_bindgen(skip_jsdoc, js_name = findAll)]
-    pub fn find_all(&self, text: &str) -> Result<JsValue> {
-        let matches: Vec<Match> = self.regex.find_iter(text).map(Match::from).collect();
-        serde_wasm_bindgen::to_value(&matches)
+    /// @param {number} maxBytes - Maximum number of bytes of `text` to scan
+    /// @return {boolean}
+    #[wasm_bindgen(skip_jsdoc, js_name = isMatchBudgeted)]
+    pub fn is_match_budgeted(&self, text: &str, max_bytes: usize) -> Result<bool> {
+        self.is_match(&text[..floor_char_boundary(text, max_bytes)])
     }
 
-    /// Returns a list of the capture names in this regex.
+    /// Returns the same as `isMatch`, for a text prepared with `prepare`.
     ///
-    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.capture_names
-    #[wasm_bindgen(skip_jsdoc, js_name = captureNames)]
-    pub fn capture_names(&self) -> Vec<JsValue> {
-        self.regex
-            .capture_names()
-            .map(|item| match item {
-                Some(name) => JsValue::from(name),
-                None => JsValue::NULL,
-            })
-            .collect()
+    /// @see RRegexText
+    /// @param {RRegexText} text - The prepared text against which to match the regular expression
+    /// @return {boolean}
+    #[wasm_bindgen(skip_jsdoc, js_name = isMatchOn)]
+    pub fn is_match_on(&self, text: &RRegexText) -> Result<bool> {
+        self.is_match(&text.text)
     }
 
-
-    /// Returns the capture groups corresponding to the leftmost-first
-    /// match in `text`. Capture group `0` always corresponds to the entire
-    /// match. If no match is found, then `undefined` is returned.
-    ///
-    /// You should only use `captures` if you need access to the location of
-    /// capturing group matches. Otherwise, `find` is faster for discovering
-    /// the location of the overall match.
+    /// Returns true if and only if the regex matches the entire string
+    /// given, as if the pattern was written as `\A(?:...)\z`.
     ///
-    /// # Examples
+    /// This is useful to validate input, where a pattern like `\d+` should
+    /// not accept `"12abc"` even though `isMatch` does.
     ///
-    /// Say you have some text with movie names and their release years,
-    /// like "'Citizen Kane' (1941)". It'd be nice if we could search for text
-    /// looking like that, while also extracting the movie name and its release
-    /// year separately.
+    /// # Example
     ///
     /// ```typescript
     /// import { RRegex } from "rregex"
     ///
-    /// const re = new RRegex("'([^']+)'\\s+\\((\\d{4})\)")
-    /// const text = "Not my favorite movie: 'Citizen Kane' (1941)."
-    /// const caps = re.captures(text)
-    /// expect(caps.get[1].value).toBe("Citizen Kane")
-    /// expect(caps.get[2].value).toBe("1941")
-    /// expect(caps.get[0].value).toBe("'Citizen Kane' (1941)")
+    /// const re = new RRegex("\\d+")
+    /// expect(re.isFullMatch("123")).toBe(true)
+    /// expect(re.isFullMatch("12abc")).toBe(false)
     /// ```
     ///
-    /// Note that the full match is at capture group `0`. Each subsequent
-    /// capture group is indexed by the order of its opening `(`.
+    /// @param {string} text - The string against which to match the regular expression
+    /// @return {boolean}
+    #[wasm_bindgen(skip_jsdoc, js_name = isFullMatch)]
+    pub fn is_full_match(&self, text: &str) -> Result<bool> {
+        Ok(self.full()?.is_match(text))
+    }
+
+    /// Returns true if and only if there is a match for the regex that
+    /// starts at the beginning of `text`, even if the pattern isn't anchored
+    /// with `^` or `\A`.
     ///
-    /// We can make this example a bit clearer by using *named* capture groups:
+    /// # Example
     ///
     /// ```typescript
     /// import { RRegex } from "rregex"
     ///
-    /// const re = new RRegex("'(?P<title>[^']+)'\\s+\\((?P<year>\\d{4})\)")
-    /// const text = "Not my favorite movie: 'Citizen Kane' (1941)."
-    /// const caps = re.captures(text)
-    /// expect(caps.name["title"].value).toBe("Citizen Kane")
-    /// expect(caps.name["year"].value).toBe("1941")
-    /// expect(caps.get[0].value).toBe("'Citizen Kane' (1941)")
+    /// const re = new RRegex("\\d+")
+    /// expect(re.startsWithMatch("12abc")).toBe(true)
+    /// expect(re.startsWithMatch("abc12")).toBe(false)
     /// ```
     ///
-    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.captures
     /// @param {string} text - The string against which to match the regular expression
-    /// @returns {Captures|undefined}
-    #[wasm_bindgen(skip_jsdoc)]
-    pub fn captures(&self, text: &str) -> Result<JsValue> {
-        if let Some(captures) = self.regex.captures(text) {
-            Match::captures(captures, self.regex.capture_names())
-        } else {
-            Ok(JsValue::undefined())
-        }
+    /// @return {boolean}
+    #[wasm_bindgen(skip_jsdoc, js_name = startsWithMatch)]
+    pub fn starts_with_match(&self, text: &str) -> Result<bool> {
+        // The leftmost match starts at 0 whenever any match does.
+        Ok(self.regex()?.find_at(text, 0).is_some_and(|m| m.start() == 0))
     }
 
-    /// Returns a list with all the non-overlapping capture groups matched
-    /// in `text`. This is operationally the same as `findAll`, except it
-    /// returns information about capturing group matches.
+    /// Returns true if and only if there is a match for the regex that ends
+    /// at the end of `text`, even if the pattern isn't anchored with `$` or
+    /// `\z`.
     ///
-    /// # Example
+    /// This considers every match that could end there, not only the ones
+    /// `findAll` reports. For example, `aa` doesn't match at the end of
+    /// `"aaa"` for `findAll`, but this still returns true.
     ///
-    /// We can use this to find all movie titles and their release years in
-    /// some text, where the movie is formatted like "'Title' (xxxx)":
+    /// # Example
     ///
     /// ```typescript
     /// import { RRegex } from "rregex"
     ///
-    /// const re = new RRegex("'(?P<title>[^']+)'\\s+\\((?P<year>\\d{4})\\)")
-    /// const text = "'Citizen Kane' (1941), 'The Wizard of Oz' (1939), 'M' (1931)."
-    /// for caps of re.captures_iter(text) {
-    ///     console.log(
-    ///         "Movie:", caps.name["title"].value, ","
-    ///         "Released:", caps.name["year"].value
-    ///     );
-    /// }
-    /// // Output:
-    /// // Movie: Citizen Kane, Released: 1941
-    /// // Movie: The Wizard of Oz, Released: 1939
-    /// // Movie: M, Released: 1931
+    /// const re = new RRegex("\\d+")
+    /// expect(re.endsWithMatch("abc12")).toBe(true)
+    /// expect(re.endsWithMatch("12abc")).toBe(false)
     /// ```
     ///
-    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.captures_iter
     /// @param {string} text - The string against which to match the regular expression
-    /// @returns {Captures[]}
-    #[wasm_bindgen(skip_jsdoc, js_name = capturesAll)]
-    pub fn captures_all(&self, text: &str) -> Result<JsValue> {
-        let names = self.regex.capture_names();
-        let result = js_sys::Array::new();
-        for captures in self.regex.captures_iter(text) {
-            let c = Match::captures(captures, names.clone())?;
-            result.push(&c);
-        }
-
-        Ok(JsValue::from(result))
+    /// @return {boolean}
+    #[wasm_bindgen(skip_jsdoc, js_name = endsWithMatch)]
+    pub fn ends_with_match(&self, text: &str) -> Result<bool> {
+        Ok(self.suffix()?.is_match(text))
     }
 
-    /// Returns the number of captures.
-    ///
-    /// This includes all named and unnamed groups, including the implicit
-    /// unnamed group that is always present and corresponds to the entire match.
+    /// Returns, for every string in `texts`, whether there is a match for the
+    /// regex in it. The results are in the same order as the input.
     ///
-    /// Since the implicit unnamed group is always included in this length, the
-    /// length returned is guaranteed to be greater than zero.
+    /// This is the same as calling `isMatch` on every element, but it only
+    /// crosses the boundary between JavaScript and WebAssembly once.
     ///
-    /// ## Example
+    /// # Example
     ///
     /// ```typescript
-    ///  import { RRegex } from "rregex"
+    /// import { RRegex } from "rregex"
     ///
-    ///  const re1 = new RRegex("(?P<y>\\d{4})-(?P<m>\\d{2})-(?P<d>\\d{2})")
-    ///  expect(re1.capturesLength()).toEqual(4)
+    /// const re = new RRegex("^\\d+$")
+    /// expect(re.isMatchMany(["123", "abc", "4"])).toEqual([true, false, true])
+    /// ```
     ///
-    ///  const re2 = new RRegex("foo")
-    ///  expect(re2.capturesLength()).toEqual(1)
+    /// @param {string[]} texts - The strings against which to match the regular expression
+    /// @returns {boolean[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = isMatchMany)]
+    pub fn is_match_many(&self, texts: Vec<String>) -> Result<Vec<JsValue>> {
+        let regex = self.regex()?;
+        Ok(texts
+            .iter()
+            .map(|text| JsValue::from(regex.is_match(text)))
+            .collect())
+    }
+
+    /// Returns true if and only if there is a match for the regex in the
+    /// concatenation of `chunks`, e.g., the pieces of a rope, including a match
+    /// that spans the boundary between two or more chunks.
     ///
-    ///  const re3 = new RRegex("(foo)")
-    ///  expect(re3.capturesLength()).toEqual(2)
+    /// The chunks are joined into a single buffer on the WebAssembly side, so
+    /// they don't have to be joined into a new string in JavaScript first.
+    /// Anchors like `^` and `\A` apply to the joined text, not to each chunk.
     ///
-    ///  const re4 = new RRegex("(?<a>.(?<b>.))(.)(?:.)(?<c>.)")
-    ///  expect(re4.capturesLength()).toEqual(5)
+    /// # Example
     ///
-    ///  const re5 = new RRegex("[a&&b]")
-    ///  expect(re5.capturesLength()).toEqual(1)
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("hello world")
+    /// expect(re.isMatchChunks(["say hel", "lo wor", "ld!"])).toBe(true)
+    /// expect(re.isMatchChunks(["hello", "world"])).toBe(false)
     /// ```
     ///
-    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.captures_len
-    #[wasm_bindgen(js_name = capturesLength)]
-    pub fn captures_len(&self) -> usize {
-        self.regex.captures_len()
+    /// @param {string[]} chunks - The pieces of the string against which to match the regular expression
+    /// @returns {boolean}
+    #[wasm_bindgen(skip_jsdoc, js_name = isMatchChunks)]
+    pub fn is_match_chunks(&self, chunks: Vec<String>) -> Result<bool> {
+        self.is_match(&chunks.concat())
     }
 
-    /// Replaces the leftmost-first match with the replacement provided.
-    /// The replacement can be a regular string (where `$N` and `$name` are
-    /// expanded to match capture groups) or a function that takes the matches'
-    /// `Captures` and returns the replaced string.
+    /// Returns true if and only if there is no match for the regex in the
+    /// string given, i.e., the opposite of `isMatch`.
     ///
-    /// If no match is found, then a copy of the string is returned unchanged.
+    /// # Example
     ///
-    /// # Replacement string syntax
+    /// ```typescript
+    /// import { RRegex } from "rregex"
     ///
-    /// All instances of `$name` in the replacement text is replaced with the
-    /// corresponding capture group `name`.
+    /// const re = new RRegex("\\d")
+    /// expect(["a", "b1", "c"].filter((s) => re.isNoMatch(s))).toEqual(["a", "c"])
+    /// ```
     ///
-    /// `name` may be an integer corresponding to the index of the
-    /// capture group (counted by order of opening parenthesis where `0` is the
-    /// entire match) or it can be a name (consisting of letters, digits or
-    /// underscores) corresponding to a named capture group.
+    /// @param {string} text - The string against which to match the regular expression
+    /// @return {boolean}
+    #[wasm_bindgen(skip_jsdoc, js_name = isNoMatch)]
+    pub fn is_no_match(&self, text: &str) -> Result<bool> {
+        Ok(!self.is_match(text)?)
+    }
+
+    /// Returns the strings in `texts` for which there is no match for the
+    /// regex, in the same order as the input.
     ///
-    /// If `name` isn't a valid capture group (whether the name doesn't exist
-    /// or isn't a valid index), then it is replaced with the empty string.
+    /// This is the same as filtering `texts` with `isNoMatch`, but it only
+    /// crosses the boundary between JavaScript and WebAssembly once.
     ///
-    /// The longest possible name is used. e.g., `$1a` looks up the capture
-    /// group named `1a` and not the capture group at index `1`. To exert more
-    /// precise control over the name, use braces, e.g., `${1}a`.
+    /// # Example
     ///
-    /// To write a literal `$` use `$$`.
+    /// ```typescript
+    /// import { RRegex } from "rregex"
     ///
-    /// # Examples
+    /// const re = new RRegex("\\d")
+    /// expect(re.filterNonMatching(["a", "b1", "c"])).toEqual(["a", "c"])
+    /// ```
     ///
-    /// Note that this function is polymorphic with respect to the replacement.
-    /// In typical usage, this can just be a normal string:
+    /// @param {string[]} texts - The strings against which to match the regular expression
+    /// @returns {string[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = filterNonMatching)]
+    pub fn filter_non_matching(&self, texts: Vec<String>) -> Result<Vec<String>> {
+        let mut result = Vec::with_capacity(texts.len());
+        for text in texts {
+            if !self.is_match(&text)? {
+                result.push(text);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Returns, for every string in `texts`, the match `find` returns for it
+    /// or `undefined` if there is no match. The results are in the same
+    /// order as the input.
+    ///
+    /// The offsets of each match are relative to its own input string.
+    ///
+    /// # Example
     ///
     /// ```typescript
     /// import { RRegex } from "rregex"
     ///
-    /// const re = new RRegex("[^01]+")
-    /// expect(re.replace("1078910", "").toBe("1010")
+    /// const re = new RRegex("\\d+")
+    /// const matches = re.findFirstMany(["id 42", "none", "7"])
+    /// expect(matches[0]).toEqual({ value: "42", start: 3, end: 5 })
+    /// expect(matches[1]).toBe(undefined)
+    /// expect(matches[2]).toEqual({ value: "7", start: 0, end: 1 })
     /// ```
     ///
-    /// Here's the example using this expansion technique with named capture
-    /// groups:
+    /// @param {string[]} texts - The strings against which to match the regular expression
+    /// @returns {(Match|undefined)[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = findFirstMany)]
+    pub fn find_first_many(&self, texts: Vec<String>) -> Result<JsValue> {
+        let matches = texts
+            .iter()
+            .map(|text| self.find_substring_at(text, 0))
+            .collect::<Result<Vec<_>>>()?;
+        serde_wasm_bindgen::to_value(&matches)
+    }
+
+    /// Returns the start and end byte range of the leftmost-first match in
+    /// `text`. If no match exists, then `undefined` is returned.
+    ///
+    /// Note that this should only be used if you want to discover the position
+    /// of the match. Testing the existence of a match is faster if you use
+    /// `isMatch`.
+    ///
+    /// # Example
+    ///
+    /// Find the start and end location of the first word with exactly 13
+    /// Unicode word characters:
     ///
     /// ```typescript
     /// import { RRegex } from "rregex"
     ///
-    /// const re = new RRegex("(?P<last>[^,\\s]+),\\s+(?P<first>\\S+)")
-    /// const result = re.replace("Springsteen, Bruce", "$first $last")
-    /// expect(result).toBe("Bruce Springsteen")
+    /// const text = "I categorically deny having triskaidekaphobia."
+    /// const m = new RRegex("\\b\\w{13}\\b").find(text)
+    /// expect(m.start).toBe(2)
+    /// expect(m.end).toBe(15)
     /// ```
     ///
-    /// Note that using `$2` instead of `$first` or `$1` instead of `$last`
-    /// would produce the same result. To write a literal `$` use `$$`.
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.find
+    /// @param {string} text - The string against which to match the regular expression
+    /// @return {Match}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn find(&self, text: &str) -> Result<JsValue> {
+        self.find_at(text, 0)
+    }
+
+    /// Returns the same match as `find`, or `null` instead of `undefined`
+    /// if there is no match.
     ///
-    /// Sometimes the replacement string requires use of curly braces to
-    /// delineate a capture group replacement and surrounding literal text.
-    /// For example, if we wanted to join two words together with an
-    /// underscore:
+    /// This is the method to reach for when both whether a match exists and
+    /// where it is are needed: it runs a single search, whereas calling
+    /// `isMatch` and then `find` searches the text twice.
+    ///
+    /// # Example
     ///
     /// ```typescript
     /// import { RRegex } from "rregex"
     ///
-    /// const re = new RRegex("(?P<first>\\w+)\\s+(?P<second>\\w+)")
-    /// const result = re.replace("deep fried", "${first}_$second")
-    /// expect(result).toBe("deep_fried")
+    /// const re = new RRegex("\\d+")
+    /// expect(re.findOrNull("ab 12")).toEqual({ start: 3, end: 5, value: "12" })
+    /// expect(re.findOrNull("ab")).toBe(null)
     /// ```
     ///
-    /// Without the curly braces, the capture group name `first_` would be
-    /// used, and since it doesn't exist, it would be replaced with the empty
-    /// string.
-    ///
-    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.replace
     /// @param {string} text - The string against which to match the regular expression
-    /// @param {string} rep - It's a string, it will replace the substring matched by `pattern`.
-    /// @returns {string}
-    #[wasm_bindgen(skip_jsdoc)]
-    pub fn replace(&self, text: &str, rep: &str) -> String {
-        self.regex.replace(text, rep).into_owned()
+    /// @returns {Match|null}
+    #[wasm_bindgen(skip_jsdoc, js_name = findOrNull)]
+    pub fn find_or_null(&self, text: &str) -> Result<JsValue> {
+        let m = self.find_at(text, 0)?;
+        if m.is_undefined() {
+            return Ok(JsValue::NULL);
+        }
+
+        Ok(m)
     }
 
-    /// Replaces at most `limit` non-overlapping matches in `text` with the
-    /// replacement provided. If `limit` is 0, then all non-overlapping matches
-    /// are replaced.
+    /// Returns the same as find, but starts the search at the given
+    /// offset.
     ///
-    /// See the documentation for `replace` for details on how to access
-    /// capturing group matches in the replacement string.
+    /// The significance of the starting point is that it takes the surrounding
+    /// context into consideration. For example, the `\A` anchor can only
+    /// match when `start == 0`.
     ///
-    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.replacen
+    /// When the regex was built with `longestMatch(true)`, the longest match
+    /// at the leftmost position is returned instead.
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.find_at
     /// @param {string} text - The string against which to match the regular expression
-    /// @param {number} limit - Max number of replacement
-    /// @param {string} rep - It's a string, it will replace the substring matched by `pattern`.
-    /// @returns {string}
-    #[wasm_bindgen(skip_jsdoc)]
-    pub fn replacen(&self, text: &str, limit: usize, rep: &str) -> String {
-        self.regex.replacen(text, limit, rep).into_owned()
+    /// @param {number} start - Zero-based index at which to start matching
+    /// @returns {Match}
+    #[wasm_bindgen(skip_jsdoc, js_name = findAt)]
+    pub fn find_at(&self, text: &str, start: usize) -> Result<JsValue> {
+        if start > text.len() {
+            return Ok(JsValue::UNDEFINED);
+        };
+
+        match self.find_substring_at(text, start)? {
+            Some(m) => serde_wasm_bindgen::to_value(&m),
+            None => Ok(JsValue::UNDEFINED),
+        }
     }
 
-    /// Replaces all non-overlapping matches in `text` with the replacement
-    /// provided. This is the same as calling `replacen` with `limit` set to
-    /// `0`.
+    /// Returns the same match as `find` within the byte range `start..end`
+    /// of `text`, with offsets relative to the whole `text`, e.g., to scan
+    /// again only the region of a document that was edited.
     ///
-    /// See the documentation for `replace` for details on how to access
-    /// capturing group matches in the replacement string.
+    /// Like `isMatchWithin`, the text before `start` is still taken into
+    /// consideration for things like `\b`, while the search stops at `end`
+    /// as if the text ended there, so `$` and `\z` match at `end`.
+    ///
+    /// If the range is out of bounds or doesn't fall on UTF-8 character
+    /// boundaries, then `undefined` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("\\d+")
+    /// expect(re.findInRange("1 22 333", 2, 6)).toEqual({ start: 2, end: 4, value: "22" })
+    /// expect(re.findInRange("1 22 333", 6, 7)).toEqual({ start: 6, end: 7, value: "3" })
+    /// ```
     ///
-    /// See the documentation for `replace` for details on how to access capturing group matches in the replacement string.
-    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.replace_all
     /// @param {string} text - The string against which to match the regular expression
-    /// @param {string} rep - It's a string, it will replace the substring matched by `pattern`.
-    /// @returns {string}
-    #[wasm_bindgen(skip_jsdoc, js_name = replaceAll)]
-    pub fn replace_all(&self, text: &str, rep: &str) -> String {
-        self.regex.replace_all(text, rep).into_owned()
+    /// @param {number} start - Zero-based index at which to start matching
+    /// @param {number} end - Zero-based index at which to stop matching
+    /// @returns {Match|undefined}
+    #[wasm_bindgen(skip_jsdoc, js_name = findInRange)]
+    pub fn find_in_range(&self, text: &str, start: usize, end: usize) -> Result<JsValue> {
+        if start > end || !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+            return Ok(JsValue::UNDEFINED);
+        }
+
+        self.find_at(&text[..end], start)
     }
 
-    /// Returns an iterator of substrings of `text` delimited by a match of the
-    /// regular expression. Namely, each element of the iterator corresponds to
-    /// text that *isn't* matched by the regular expression.
+    /// Returns the same match as `find`, along with up to `contextChars`
+    /// characters of `text` on each side of it, or `undefined` if there is
+    /// no match.
     ///
-    /// This method will *not* copy the text given.
+    /// Characters are counted as Unicode scalar values, so the context never
+    /// splits a multibyte character, and it's shorter when the match is close
+    /// to the start or the end of `text`.
     ///
     /// # Example
     ///
-    /// To split a string delimited by arbitrary amounts of spaces or tabs:
-    ///
     /// ```typescript
     /// import { RRegex } from "rregex"
     ///
-    /// const re = new RRegex(r"[ \\t]+")
-    /// const fields = re.split("a b \t  c\td    e")
-    /// expect(fields).toEqual(["a", "b", "c", "d", "e"])
+    /// const m = new RRegex("fox").findWithContext("the quick brown fox jumps", 6)
+    /// expect(m).toEqual({ start: 16, end: 19, value: "fox", before: "brown ", after: " jumps" })
     /// ```
     ///
-    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.split
     /// @param {string} text - The string against which to match the regular expression
-    /// @returns {string[]}
-    #[wasm_bindgen(skip_jsdoc)]
-    pub fn split(&self, text: &str) -> Vec<String> {
-        self.regex.split(text).map(|s| s.to_string()).collect()
+    /// @param {number} contextChars - The number of characters to include on each side
+    /// @returns {ContextMatch|undefined}
+    #[wasm_bindgen(skip_jsdoc, js_name = findWithContext)]
+    pub fn find_with_context(&self, text: &str, context_chars: usize) -> Result<JsValue> {
+        match self.find_substring_at(text, 0)? {
+            Some(m) => serde_wasm_bindgen::to_value(&ContextMatch::new(
+                text,
+                m.start(),
+                m.end(),
+                context_chars,
+            )),
+            None => Ok(JsValue::UNDEFINED),
+        }
     }
 
-    /// Returns an iterator of at most `limit` substrings of `text` delimited
-    /// by a match of the regular expression. (A `limit` of `0` will return no
-    /// substrings.) Namely, each element of the iterator corresponds to text
-    /// that *isn't* matched by the regular expression. The remainder of the
-    /// string that is not split will be the last element in the iterator.
+    /// Returns the same matches as `findAll`, each along with up to
+    /// `contextChars` characters on each side, like `findWithContext`.
     ///
-    /// This method will *not* copy the text given.
+    /// The context of every match is taken from `text` independently, so the
+    /// contexts of nearby matches may overlap each other and the matches.
     ///
     /// # Example
     ///
-    /// Get the first two words in some text:
-    ///
     /// ```typescript
     /// import { RRegex } from "rregex"
     ///
-    /// const re = new RRegex(r"\\W+")
-    /// const fields = re.splitn("Hey! How are you?", 3)
-    /// expect(fields).toEqual(["Hey", "How", "are you?"])
+    /// const matches = new RRegex("\\d").findAllWithContext("a1b2c", 1)
+    /// expect(matches.map((m) => m.before + m.value + m.after)).toEqual(["a1b", "b2c"])
     /// ```
-    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.splitn
+    ///
     /// @param {string} text - The string against which to match the regular expression
-    /// @param {number} limit - Max number result elements
-    /// @returns {string[]}
-    #[wasm_bindgen(skip_jsdoc)]
-    pub fn splitn(&self, text: &str, limit: usize) -> Vec<String> {
-        self.regex
-            .splitn(text, limit)
-            .map(|s| s.to_string())
-            .collect()
+    /// @param {number} contextChars - The number of characters to include on each side
+    /// @returns {ContextMatch[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = findAllWithContext)]
+    pub fn find_all_with_context(&self, text: &str, context_chars: usize) -> Result<JsValue> {
+        let matches: Vec<ContextMatch> = self
+            .find_substrings(text)?
+            .map(|m| ContextMatch::new(text, m.start(), m.end(), context_chars))
+            .collect();
+        serde_wasm_bindgen::to_value(&matches)
     }
 
-    /// Returns the end location of a match in the text given.
-    ///
-    /// This method may have the same performance characteristics as
-    /// `isMatch`, except it provides an end location for a match. In
-    /// particular, the location returned *may be shorter* than the proper end
-    /// of the leftmost-first match that you would find via `Regex.find`.
+    /// Returns the same match as `find`, with the line and column of its start
+    /// and end instead of just their byte offsets, or `undefined` if there
+    /// is no match.
     ///
-    /// Note that it is not guaranteed that this routine finds the shortest or
-    /// "earliest" possible match. Instead, the main idea of this API is that
-    /// it returns the offset at the point at which the internal regex engine
-    /// has determined that a match has occurred. This may vary depending on
-    /// which internal regex engine is used, and thus, the offset itself may
-    /// change.
+    /// Lines are zero-based and split on `\n`. Columns are zero-based and
+    /// counted in UTF-16 code units, like JS string indices, so they can be
+    /// used with editor APIs directly.
     ///
     /// # Example
     ///
-    /// Typically, `a+` would match the entire first sequence of `a` in some
-    /// text, but `shortest_match` can give up as soon as it sees the first
-    /// `a`.
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const m = new RRegex("\\d+").findWithPosition("ab\n😀 12")
+    /// expect(m.start).toEqual({ offset: 8, line: 1, column: 3 })
+    /// expect(m.end).toEqual({ offset: 10, line: 1, column: 5 })
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {PositionedMatch|undefined}
+    #[wasm_bindgen(skip_jsdoc, js_name = findWithPosition)]
+    pub fn find_with_position(&self, text: &str) -> Result<JsValue> {
+        match self.find_substring_at(text, 0)? {
+            Some(m) => serde_wasm_bindgen::to_value(&PositionedMatch {
+                start: TextPosition::new(text, m.start()),
+                end: TextPosition::new(text, m.end()),
+                value: m.as_str(),
+            }),
+            None => Ok(JsValue::UNDEFINED),
+        }
+    }
+
+    /// Returns the same match as `find`, but with `start` and `end` counted
+    /// in UTF-16 code units instead of bytes, or `undefined` if there is no
+    /// match.
+    ///
+    /// These are the offsets JS strings use, so they can be given to
+    /// `String.prototype.slice` directly. A character outside the Basic
+    /// Multilingual Plane, like an emoji, counts as two code units.
+    ///
+    /// # Example
     ///
     /// ```typescript
     /// import { RRegex } from "rregex"
     ///
-    /// const text = "aaaaa"
-    /// const = new RRegex("a+").shortest_match(text)
-    /// expect(pos).toBe(1)
+    /// const text = "😀 12"
+    /// const m = new RRegex("\\d+").findUtf16(text)
+    /// expect(m).toEqual({ start: 3, end: 5, value: "12" })
+    /// expect(text.slice(m.start, m.end)).toBe("12")
     /// ```
     ///
-    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.shortest_match
     /// @param {string} text - The string against which to match the regular expression
-    /// @returns {number|undefined}
-    #[wasm_bindgen(skip_jsdoc, js_name = shortestMatch)]
-    pub fn shortest_match(&self, text: &str) -> Option<usize> {
-        self.regex.shortest_match(text)
+    /// @returns {Match|undefined}
+    #[wasm_bindgen(skip_jsdoc, js_name = findUtf16)]
+    pub fn find_utf16(&self, text: &str) -> Result<JsValue> {
+        match self.find_substring_at(text, 0)? {
+            Some(m) => {
+                let start = text[..m.start()].encode_utf16().count();
+                serde_wasm_bindgen::to_value(&RebasedMatch {
+                    start,
+                    end: start + m.as_str().encode_utf16().count(),
+                    value: m.as_str(),
+                })
+            }
+            None => Ok(JsValue::UNDEFINED),
+        }
     }
 
-    /// Returns the same as `shortest_match`, but starts the search at the
-    /// given offset.
+    /// Returns the match that starts exactly at `start`, or `undefined` if
+    /// there is none, even if there is a match later in `text`. Like
+    /// `findAt`, the text before `start` is still taken into consideration
+    /// for things like `\b`.
+    ///
+    /// This is how the sticky `y` flag of JS regular expressions behaves,
+    /// which is handy to write a tokenizer.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("\\d+")
+    /// expect(re.matchExactlyAt("ab12", 2).value).toBe("12")
+    /// expect(re.matchExactlyAt("ab12", 1)).toBe(undefined)
+    /// ```
     ///
-    /// The significance of the starting point is that it takes the surrounding
-    /// context into consideration. For example, the `\A` anchor can only match
-    /// when `start == 0`.
-    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.shortest_match_at
     /// @param {string} text - The string against which to match the regular expression
-    /// @param {number} start - Zero-based index at which to start matching
-    /// @returns {number|undefined}
-    #[wasm_bindgen(skip_jsdoc, js_name = shortestMatchAt)]
-    pub fn shortest_match_at(&self, text: &str, start: usize) -> Option<usize> {
-        if text.len() < start {
-            None
-        } else {
-            self.regex.shortest_match_at(text, start)
+    /// @param {number} start - Zero-based index at which the match must start
+    /// @returns {Match}
+    #[wasm_bindgen(skip_jsdoc, js_name = matchExactlyAt)]
+    pub fn match_exactly_at(&self, text: &str, start: usize) -> Result<JsValue> {
+        if start > text.len() {
+            return Ok(JsValue::UNDEFINED);
+        }
+
+        match self.find_substring_at(text, start)? {
+            Some(m) if m.start() == start => serde_wasm_bindgen::to_value(&m),
+            _ => Ok(JsValue::UNDEFINED),
         }
     }
 
-    /// Returns the regular expression into a high level intermediate
-    /// representation.
-    pub fn syntax(&self) -> Result<JsValue> {
-        let mut parser = Parser::new();
-        let hir = parser
-            .parse(self.regex.as_str())
-            .map_err(serde_wasm_bindgen::Error::new)?;
+    /// Returns the same as `find`, for a text prepared with `prepare`.
+    ///
+    /// @see RRegexText
+    /// @param {RRegexText} text - The prepared text against which to match the regular expression
+    /// @returns {Match}
+    #[wasm_bindgen(skip_jsdoc, js_name = findOn)]
+    pub fn find_on(&self, text: &RRegexText) -> Result<JsValue> {
+        self.find(&text.text)
+    }
 
-        Hir::from(&hir).try_into()
+    /// Returns the same as `find`, but only scans the first `maxBytes` bytes
+    /// of `text`, as if the text ended there. This bounds how much work a
+    /// single call does on a huge string, and the search can be resumed later
+    /// with `findAt`.
+    ///
+    /// If `maxBytes` doesn't fall on a UTF-8 character boundary, then it's
+    /// moved back to the closest boundary before it.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("\\d+")
+    /// expect(re.findWithin("ab 12 cd", 3)).toBe(undefined)
+    /// expect(re.findWithin("ab 12 cd", 4).value).toBe("1")
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {number} maxBytes - The number of bytes of `text` to scan
+    /// @returns {Match}
+    #[wasm_bindgen(skip_jsdoc, js_name = findWithin)]
+    pub fn find_within(&self, text: &str, max_bytes: usize) -> Result<JsValue> {
+        self.find_at(&text[..floor_char_boundary(text, max_bytes)], 0)
     }
 
-    /// Returns a string representing the regular expression
-    #[wasm_bindgen(js_name = toString)]
-    pub fn as_string(&self) -> String {
-        self.regex.as_str().to_owned()
+    /// Returns an array for each successive non-overlapping match in `text``,
+    /// returning the start and end byte indices with respect to `text`.
+    ///
+    /// # Example
+    ///
+    /// Find the start and end location of every word with exactly 13 Unicode
+    /// word characters:
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const text = "Retroactively relinquishing remunerations is reprehensible."
+    /// const matches = new RRegex("\\b\\w{13}\\b").findAll(text)
+    /// ```
+    ///
+    /// When the regex was built with `longestMatch(true)`, each match is the
+    /// longest one at its leftmost position instead.
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.find_iter
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {Match}
+    #[wasm_bindgen(skip_jsdoc, js_name = findAll)]
+    pub fn find_all(&self, text: &str) -> Result<JsValue> {
+        let matches: Vec<Substring> = self.find_substrings(text)?.collect();
+        serde_wasm_bindgen::to_value(&matches)
+    }
+
+    /// Returns the same matches as `findAll`, each with a `charLen` field
+    /// holding the number of Unicode scalar values in the match, which is
+    /// what a user would call its length.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const [m] = new RRegex("\\S+").findAllCharLen("héllo")
+    /// expect(m.end - m.start).toBe(6)
+    /// expect(m.charLen).toBe(5)
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {CharLenMatch[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = findAllCharLen)]
+    pub fn find_all_char_len(&self, text: &str) -> Result<JsValue> {
+        let matches: Vec<CharLenMatch> = self
+            .find_substrings(text)?
+            .map(CharLenMatch::from)
+            .collect();
+        serde_wasm_bindgen::to_value(&matches)
+    }
+
+    /// Returns the byte ranges of the successive non-overlapping matches in
+    /// `text`, where matches separated by at most `maxGap` bytes are merged
+    /// into a single range, e.g., to highlight them as one span.
+    ///
+    /// With a `maxGap` of `0`, only matches that are directly adjacent are
+    /// merged. A merged range covers the text between its matches too.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("\\d")
+    /// expect(re.findAllMerged("12 3  4", 0)).toEqual([{ start: 0, end: 2 }, { start: 3, end: 4 }, { start: 6, end: 7 }])
+    /// expect(re.findAllMerged("12 3  4", 1)).toEqual([{ start: 0, end: 4 }, { start: 6, end: 7 }])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {number} maxGap - The largest number of bytes between two matches that are merged
+    /// @returns {ByteRange[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = findAllMerged)]
+    pub fn find_all_merged(&self, text: &str, max_gap: usize) -> Result<JsValue> {
+        let mut ranges: Vec<ByteRange> = Vec::new();
+        for m in self.find_substrings(text)? {
+            match ranges.last_mut() {
+                Some(last) if m.start() - last.end <= max_gap => last.end = m.end(),
+                _ => ranges.push(ByteRange {
+                    start: m.start(),
+                    end: m.end(),
+                }),
+            }
+        }
+
+        serde_wasm_bindgen::to_value(&ranges)
+    }
+
+    /// Returns the same matches as `findAll`, but with `start` and `end`
+    /// counted in UTF-16 code units instead of bytes, like `findUtf16`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const text = "😀1 δ2"
+    /// const matches = new RRegex("\\d").findAllUtf16(text)
+    /// expect(matches.map((m) => text.slice(m.start, m.end))).toEqual(["1", "2"])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {Match[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = findAllUtf16)]
+    pub fn find_all_utf16(&self, text: &str) -> Result<JsValue> {
+        let mut matches: Vec<RebasedMatch> = Vec::new();
+        let mut last = 0;
+        let mut units = 0;
+        for m in self.find_substrings(text)? {
+            units += text[last..m.start()].encode_utf16().count();
+            let start = units;
+            units += m.as_str().encode_utf16().count();
+            last = m.end();
+            matches.push(RebasedMatch {
+                start,
+                end: units,
+                value: m.as_str(),
+            });
+        }
+
+        serde_wasm_bindgen::to_value(&matches)
+    }
+
+    /// Returns the same as `findAll`, for a text prepared with `prepare`.
+    ///
+    /// @see RRegexText
+    /// @param {RRegexText} text - The prepared text against which to match the regular expression
+    /// @returns {Match[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = findAllOn)]
+    pub fn find_all_on(&self, text: &RRegexText) -> Result<JsValue> {
+        self.find_all(&text.text)
+    }
+
+    /// Returns the same matches as `findAll`, but stops after collecting
+    /// `max` of them. This keeps the array returned bounded, even for inputs
+    /// that produce millions of tiny matches.
+    ///
+    /// A `max` of `0` means no limit, i.e., it behaves like `findAll`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const matches = new RRegex("\\d").findAllLimited("1 2 3 4", 2)
+    /// expect(matches.map((m) => m.value)).toEqual(["1", "2"])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {number} max - The maximum number of matches to return
+    /// @returns {Match[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = findAllLimited)]
+    pub fn find_all_limited(&self, text: &str, max: usize) -> Result<JsValue> {
+        let limit = if max == 0 { usize::MAX } else { max };
+        let matches: Vec<Substring> = self.find_substrings(text)?.take(limit).collect();
+        serde_wasm_bindgen::to_value(&matches)
+    }
+
+    /// Returns the same matches as `findAll`, grouped in pages of at most
+    /// `pageSize` matches each, e.g., to render many results incrementally.
+    ///
+    /// Only the last page can hold fewer than `pageSize` matches, and there
+    /// are no pages when nothing matches. A `pageSize` of `0` is an error.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const pages = new RRegex("\\d").findAllPaged("1 2 3", 2)
+    /// expect(pages.map((page) => page.map((m) => m.value))).toEqual([["1", "2"], ["3"]])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {number} pageSize - The maximum number of matches in a page
+    /// @returns {Match[][]}
+    #[wasm_bindgen(skip_jsdoc, js_name = findAllPaged)]
+    pub fn find_all_paged(&self, text: &str, page_size: usize) -> Result<JsValue> {
+        if page_size == 0 {
+            return Err(serde_wasm_bindgen::Error::new("page size must be greater than 0"));
+        }
+
+        let matches: Vec<Substring> = self.find_substrings(text)?.collect();
+        let pages: Vec<&[Substring]> = matches.chunks(page_size).collect();
+        serde_wasm_bindgen::to_value(&pages)
+    }
+
+    /// Calls `f` with every successive non-overlapping match in `text`, in
+    /// order, without collecting them in an array like `findAll` does.
+    ///
+    /// Returning `false` from `f` stops the iteration early. Any other
+    /// value, including `undefined`, continues it. If `f` throws, then the
+    /// error is thrown from here.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const values = []
+    /// new RRegex("\\d").forEachMatch("1 2 3", (m) => {
+    ///   values.push(m.value)
+    ///   return m.value !== "2"
+    /// })
+    /// expect(values).toEqual(["1", "2"])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {(match: Match) => boolean | void} f - Called with every match
+    /// @returns {void}
+    #[wasm_bindgen(skip_jsdoc, js_name = forEachMatch)]
+    pub fn for_each_match(&self, text: &str, f: &js_sys::Function) -> Result<()> {
+        for m in self.find_substrings(text)? {
+            let value = serde_wasm_bindgen::to_value(&m)?;
+            let result = f
+                .call1(&JsValue::NULL, &value)
+                .map_err(serde_wasm_bindgen::Error::from)?;
+            if result == JsValue::FALSE {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the successive non-overlapping matches in `text` for which
+    /// `predicate` returns a truthy value.
+    ///
+    /// The predicate is called with every `Match` during the search, in the
+    /// order the matches appear in `text`, so rejected matches never end up
+    /// in an array. If `predicate` throws, then the error is thrown from here.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("\\d+")
+    /// const matches = re.findAllWhere("1 22 333", (m) => m.value.length > 1)
+    /// expect(matches.map((m) => m.value)).toEqual(["22", "333"])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {(match: Match) => unknown} predicate - Whether to keep a match
+    /// @returns {Match[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = findAllWhere)]
+    pub fn find_all_where(&self, text: &str, predicate: &js_sys::Function) -> Result<JsValue> {
+        let matches = js_sys::Array::new();
+        for m in self.find_substrings(text)? {
+            let value = serde_wasm_bindgen::to_value(&m)?;
+            let keep = predicate
+                .call1(&JsValue::NULL, &value)
+                .map_err(serde_wasm_bindgen::Error::from)?;
+            if keep.is_truthy() {
+                matches.push(&value);
+            }
+        }
+
+        Ok(matches.into())
+    }
+
+    /// Returns every match in `text`, including the ones that overlap.
+    ///
+    /// Instead of resuming the search at the end of the previous match, like
+    /// `findAll` does, each search resumes one character past the start of the
+    /// previous match. This reports the match `find` would report at every
+    /// offset where a match can begin, which is useful to find all the overlapping
+    /// occurrences of a fixed width pattern.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const matches = new RRegex("\\d\\d").findAllOverlapping("1234")
+    /// expect(matches.map((m) => m.value)).toEqual(["12", "23", "34"])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {Match[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = findAllOverlapping)]
+    pub fn find_all_overlapping(&self, text: &str) -> Result<JsValue> {
+        let mut matches: Vec<Substring> = Vec::new();
+        let mut start = 0;
+        while let Some(m) = self.find_substring_at(text, start)? {
+            start = match text[m.start()..].chars().next() {
+                Some(c) => m.start() + c.len_utf8(),
+                None => text.len() + 1,
+            };
+            matches.push(m);
+            if start > text.len() {
+                break;
+            }
+        }
+
+        serde_wasm_bindgen::to_value(&matches)
+    }
+
+    /// Returns the start and end byte range of the last successive
+    /// non-overlapping match in `text`. If no match exists, then `undefined`
+    /// is returned.
+    ///
+    /// Matches are still searched from left to right, so this returns the
+    /// last match `findAll` would return.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const m = new RRegex("\\d").findLast("a1 b2 c3")
+    /// expect(m).toEqual({ value: "3", start: 7, end: 8 })
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {Match|undefined}
+    #[wasm_bindgen(skip_jsdoc, js_name = findLast)]
+    pub fn find_last(&self, text: &str) -> Result<JsValue> {
+        match self.find_substrings(text)?.last() {
+            Some(m) => serde_wasm_bindgen::to_value(&m),
+            None => Ok(JsValue::UNDEFINED),
+        }
+    }
+
+    /// Returns the first match in `text` that starts at or after the byte
+    /// `offset`, e.g., to find the next match from the cursor in an editor.
+    /// This is the same as `findAt`, except that an `offset` that is out of
+    /// bounds or isn't on a UTF-8 character boundary returns `undefined`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("\\d+")
+    /// expect(re.findAfter("1 22 333", 2)).toEqual({ start: 2, end: 4, value: "22" })
+    /// expect(re.findAfter("1 22 333", 3)).toEqual({ start: 3, end: 4, value: "2" })
+    /// ```
+    ///
+    /// @see findBefore
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {number} offset - Zero-based index at which to start matching
+    /// @returns {Match|undefined}
+    #[wasm_bindgen(skip_jsdoc, js_name = findAfter)]
+    pub fn find_after(&self, text: &str, offset: usize) -> Result<JsValue> {
+        if !text.is_char_boundary(offset) {
+            return Ok(JsValue::UNDEFINED);
+        }
+
+        self.find_at(text, offset)
+    }
+
+    /// Returns the last of the successive non-overlapping matches in `text`
+    /// that ends at or before the byte `offset`, e.g., to find the previous
+    /// match from the cursor in an editor. If there is none, or `offset` is
+    /// out of bounds or isn't on a UTF-8 character boundary, then `undefined`
+    /// is returned.
+    ///
+    /// Matches are still searched from left to right, so this is one of the
+    /// matches `findAll` would return.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("\\d+")
+    /// expect(re.findBefore("1 22 333", 6)).toEqual({ start: 2, end: 4, value: "22" })
+    /// expect(re.findBefore("1 22 333", 0)).toBe(undefined)
+    /// ```
+    ///
+    /// @see findAfter
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {number} offset - Zero-based index at which matches must end
+    /// @returns {Match|undefined}
+    #[wasm_bindgen(skip_jsdoc, js_name = findBefore)]
+    pub fn find_before(&self, text: &str, offset: usize) -> Result<JsValue> {
+        if !text.is_char_boundary(offset) {
+            return Ok(JsValue::UNDEFINED);
+        }
+
+        match self.find_substrings(text)?.take_while(|m| m.end() <= offset).last() {
+            Some(m) => serde_wasm_bindgen::to_value(&m),
+            None => Ok(JsValue::UNDEFINED),
+        }
+    }
+
+    /// Returns the byte offsets of the last non-overlapping match in `text`,
+    /// like `findLast` but without copying the matched text. If no match
+    /// exists, then `undefined` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const range = new RRegex("/").findLastIndex("a/b/c")
+    /// expect(range).toEqual({ start: 3, end: 4 })
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {ByteRange|undefined}
+    #[wasm_bindgen(skip_jsdoc, js_name = findLastIndex)]
+    pub fn find_last_index(&self, text: &str) -> Result<JsValue> {
+        match self.find_substrings(text)?.last() {
+            Some(m) => serde_wasm_bindgen::to_value(&ByteRange {
+                start: m.start(),
+                end: m.end(),
+            }),
+            None => Ok(JsValue::UNDEFINED),
+        }
+    }
+
+    /// Returns the `n`-th (zero-based) successive non-overlapping match in
+    /// `text`, or `undefined` if there are `n` matches or fewer.
+    ///
+    /// The matches before it are skipped without creating an object for
+    /// each of them.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const m = new RRegex("\\w").findNth("a b c d", 2)
+    /// expect(m).toEqual({ value: "c", start: 4, end: 5 })
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {number} n - The zero-based index of the match to return
+    /// @returns {Match|undefined}
+    #[wasm_bindgen(skip_jsdoc, js_name = findNth)]
+    pub fn find_nth(&self, text: &str, n: usize) -> Result<JsValue> {
+        match self.find_substrings(text)?.nth(n) {
+            Some(m) => serde_wasm_bindgen::to_value(&m),
+            None => Ok(JsValue::UNDEFINED),
+        }
+    }
+
+    /// Returns the byte offsets of every successive non-overlapping match in
+    /// `text` as a flat `Int32Array` of `[start0, end0, start1, end1, ...]`.
+    ///
+    /// This is the same as `findAll`, but avoids creating an object for every
+    /// match, which makes it a better fit for hot paths like highlighting.
+    ///
+    /// Like every other position returned by `RRegex`, the offsets are
+    /// **byte offsets** into the UTF-8 encoding of `text`, not UTF-16 code
+    /// unit indices. They can slice an `Uint8Array` created with a
+    /// TextEncoder.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const indices = new RRegex("\\d+").matchIndices("a1 b22 c333")
+    /// expect(Array.from(indices)).toEqual([1, 2, 4, 6, 8, 11])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {Int32Array}
+    #[wasm_bindgen(skip_jsdoc, js_name = matchIndices)]
+    pub fn match_indices(&self, text: &str) -> Result<js_sys::Int32Array> {
+        let indices: Vec<i32> = self
+            .find_substrings(text)?
+            .flat_map(|m| [m.start() as i32, m.end() as i32])
+            .collect();
+        Ok(js_sys::Int32Array::from(indices.as_slice()))
+    }
+
+    /// Returns the start byte offset of every successive non-overlapping
+    /// match in `text`. This is the most compact way to know where matches
+    /// are, e.g., to draw markers on a scrollbar.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const positions = new RRegex("a").startPositions("a.a.a")
+    /// expect(Array.from(positions)).toEqual([0, 2, 4])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {Uint32Array}
+    #[wasm_bindgen(skip_jsdoc, js_name = startPositions)]
+    pub fn start_positions(&self, text: &str) -> Result<js_sys::Uint32Array> {
+        let positions: Vec<u32> = self
+            .find_substrings(text)?
+            .map(|m| m.start() as u32)
+            .collect();
+        Ok(js_sys::Uint32Array::from(positions.as_slice()))
+    }
+
+    /// Returns the same matches as `findAll`, laid out as three parallel
+    /// arrays instead of an array of objects: the start byte offsets, the
+    /// end byte offsets and the values of the matches.
+    ///
+    /// This is much cheaper to build and to load into typed-array-backed
+    /// tables when a regex matches many times.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const columns = new RRegex("\\d+").findAllColumnar("a1 b22")
+    /// expect(Array.from(columns.starts)).toEqual([1, 4])
+    /// expect(Array.from(columns.ends)).toEqual([2, 6])
+    /// expect(columns.values).toEqual(["1", "22"])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {ColumnarMatches}
+    #[wasm_bindgen(skip_jsdoc, js_name = findAllColumnar)]
+    pub fn find_all_columnar(&self, text: &str) -> Result<js_sys::Object> {
+        let mut starts = Vec::new();
+        let mut ends = Vec::new();
+        let values = js_sys::Array::new();
+        for m in self.find_substrings(text)? {
+            starts.push(m.start() as u32);
+            ends.push(m.end() as u32);
+            values.push(&JsValue::from_str(m.as_str()));
+        }
+
+        let columns = js_sys::Object::new();
+        js_sys::Reflect::set(
+            &columns,
+            &JsValue::from_str("starts"),
+            &js_sys::Uint32Array::from(starts.as_slice()),
+        )?;
+        js_sys::Reflect::set(
+            &columns,
+            &JsValue::from_str("ends"),
+            &js_sys::Uint32Array::from(ends.as_slice()),
+        )?;
+        js_sys::Reflect::set(&columns, &JsValue::from_str("values"), &values)?;
+        Ok(columns)
+    }
+
+    /// Returns how many successive non-overlapping matches start on every
+    /// line of `text`, where lines are split on `\n`. Element `i` is the
+    /// count for line `i`, so the array has one element per line.
+    ///
+    /// A match that spans a line break is only counted on the line where it
+    /// starts.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const counts = new RRegex("\\d").matchCountByLine("1 2\nx\n3")
+    /// expect(Array.from(counts)).toEqual([2, 0, 1])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {Uint32Array}
+    #[wasm_bindgen(skip_jsdoc, js_name = matchCountByLine)]
+    pub fn match_count_by_line(&self, text: &str) -> Result<js_sys::Uint32Array> {
+        let mut counts = vec![0u32; text.matches('\n').count() + 1];
+        let mut line = 0;
+        let mut last = 0;
+        for m in self.find_substrings(text)? {
+            line += text[last..m.start()].matches('\n').count();
+            last = m.start();
+            counts[line] += 1;
+        }
+
+        Ok(js_sys::Uint32Array::from(counts.as_slice()))
+    }
+
+    /// Returns every line of `text` that contains at least one match, like
+    /// `grep` does, where lines are split on `\n`. A line with many matches
+    /// is only returned once.
+    ///
+    /// A match that spans a line break only makes the line where it starts
+    /// a matching line.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const lines = new RRegex("\\d").matchingLines("a1\nb\nc2 3")
+    /// expect(lines).toEqual([
+    ///   { line: 0, start: 0, text: "a1" },
+    ///   { line: 2, start: 5, text: "c2 3" },
+    /// ])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {MatchingLine[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = matchingLines)]
+    pub fn matching_lines(&self, text: &str) -> Result<JsValue> {
+        let mut lines: Vec<MatchingLine> = Vec::new();
+        let mut line = 0;
+        let mut start = 0;
+        for m in self.find_substrings(text)? {
+            while let Some(position) = text[start..m.start()].find('\n') {
+                start += position + 1;
+                line += 1;
+            }
+            if lines.last().is_some_and(|last| last.line == line) {
+                continue;
+            }
+
+            let end = text[start..].find('\n').map_or(text.len(), |end| start + end);
+            lines.push(MatchingLine {
+                line,
+                start,
+                text: &text[start..end],
+            });
+        }
+
+        serde_wasm_bindgen::to_value(&lines)
+    }
+
+    /// Returns the successive non-overlapping matches in `text`, each along
+    /// with the line it's on, where lines are split on `\n`, e.g., to show
+    /// matches with their line like `grep` does.
+    ///
+    /// The line never includes its line break. A match that spans several
+    /// lines comes with all of them, from the start of the line where it
+    /// starts to the end of the line where it ends, and a match that ends
+    /// with a line break comes with the line that break ends.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const [m] = new RRegex("\\d+").findAllWithLine("a\nb 42 c\nd")
+    /// expect(m).toEqual({ start: 4, end: 6, value: "42", lineStart: 2, lineEnd: 8, lineText: "b 42 c" })
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {LineMatch[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = findAllWithLine)]
+    pub fn find_all_with_line(&self, text: &str) -> Result<JsValue> {
+        let mut matches: Vec<LineMatch> = Vec::new();
+        for m in self.find_substrings(text)? {
+            let line_start = text[..m.start()].rfind('\n').map_or(0, |position| position + 1);
+            let from = if m.as_str().ends_with('\n') { m.end() - 1 } else { m.end() };
+            let line_end = text[from..].find('\n').map_or(text.len(), |position| from + position);
+            matches.push(LineMatch {
+                start: m.start(),
+                end: m.end(),
+                value: m.as_str(),
+                line_start,
+                line_end,
+                line_text: &text[line_start..line_end],
+            });
+        }
+
+        serde_wasm_bindgen::to_value(&matches)
+    }
+
+    /// Returns how many times each distinct substring was matched in `text`,
+    /// counting every successive non-overlapping match.
+    ///
+    /// The result is a `Map` from the matched string to its count, in the
+    /// order each string was first matched.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const occurrences = new RRegex("\\w").matchOccurrences("a b a c a")
+    /// expect(Object.fromEntries(occurrences)).toEqual({ a: 3, b: 1, c: 1 })
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {Map<string, number>}
+    #[wasm_bindgen(skip_jsdoc, js_name = matchOccurrences)]
+    pub fn match_occurrences(&self, text: &str) -> Result<js_sys::Map> {
+        let mut positions: HashMap<&str, usize> = HashMap::new();
+        let mut counts: Vec<(&str, u32)> = Vec::new();
+        for m in self.find_substrings(text)? {
+            let position = *positions.entry(m.as_str()).or_insert_with(|| {
+                counts.push((m.as_str(), 0));
+                counts.len() - 1
+            });
+            counts[position].1 += 1;
+        }
+
+        let occurrences = js_sys::Map::new();
+        for (value, count) in counts {
+            occurrences.set(&JsValue::from(value), &JsValue::from(count));
+        }
+        Ok(occurrences)
+    }
+
+    /// Returns the number of successive non-overlapping matches in `text`,
+    /// the total number of bytes they cover, and the fraction of `text` they
+    /// cover, which is `0` when `text` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const stats = new RRegex("\\d+").matchStats("12 ab 34")
+    /// expect(stats).toEqual({ count: 2, totalMatchedBytes: 4, coverage: 0.5 })
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {MatchStats}
+    #[wasm_bindgen(skip_jsdoc, js_name = matchStats)]
+    pub fn match_stats(&self, text: &str) -> Result<JsValue> {
+        let mut count = 0;
+        let mut total_matched_bytes = 0;
+        for m in self.find_substrings(text)? {
+            count += 1;
+            total_matched_bytes += m.end() - m.start();
+        }
+        let coverage = if text.is_empty() {
+            0.0
+        } else {
+            total_matched_bytes as f64 / text.len() as f64
+        };
+
+        serde_wasm_bindgen::to_value(&MatchStats {
+            count,
+            total_matched_bytes,
+            coverage,
+        })
+    }
+
+    /// Returns the distinct substrings matched by the successive
+    /// non-overlapping matches in `text`, in the order each one was first
+    /// matched.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const values = new RRegex("\\w").distinctMatches("a b a c")
+    /// expect(values).toEqual(["a", "b", "c"])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {string[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = distinctMatches)]
+    pub fn distinct_matches(&self, text: &str) -> Result<Vec<String>> {
+        let mut seen: HashSet<&str> = HashSet::new();
+        Ok(self
+            .find_substrings(text)?
+            .map(|m| m.as_str())
+            .filter(|value| seen.insert(value))
+            .map(String::from)
+            .collect())
+    }
+
+    /// Returns a list of the capture names in this regex.
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.capture_names
+    #[wasm_bindgen(skip_jsdoc, js_name = captureNames)]
+    pub fn capture_names(&self) -> Result<Vec<JsValue>> {
+        Ok(self
+            .regex()?
+            .capture_names()
+            .map(|item| match item {
+                Some(name) => JsValue::from(name),
+                None => JsValue::NULL,
+            })
+            .collect())
+    }
+
+    /// Returns the index of the capture group with the given name, or
+    /// `undefined` if there is no group with that name.
+    ///
+    /// The index returned can be used with the `get` list of a `Captures`
+    /// object, and is always less than `capturesLength()`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("(?P<y>\\d{4})-(?P<m>\\d{2})")
+    /// expect(re.captureNameToIndex("m")).toBe(2)
+    /// expect(re.captureNameToIndex("d")).toBe(undefined)
+    /// ```
+    ///
+    /// @param {string} name - The name of the capture group
+    /// @returns {number|undefined}
+    #[wasm_bindgen(skip_jsdoc, js_name = captureNameToIndex)]
+    pub fn capture_name_to_index(&self, name: &str) -> Result<Option<usize>> {
+        let regex = self.regex()?;
+        if name.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(regex.capture_names().position(|item| item == Some(name)))
+    }
+
+    /// Returns an object mapping the name of each named capture group to its
+    /// index, i.e., the same index `captureNameToIndex` returns. Unnamed
+    /// groups are left out.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("(?P<y>\\d{4})-(\\d{2})-(?P<d>\\d{2})")
+    /// expect(re.captureNameIndex()).toEqual({ y: 1, d: 3 })
+    /// ```
+    ///
+    /// @returns {Record<string, number>}
+    #[wasm_bindgen(skip_jsdoc, js_name = captureNameIndex)]
+    pub fn capture_name_index(&self) -> Result<JsValue> {
+        let names = js_sys::Object::new();
+        for (index, name) in self.regex()?.capture_names().enumerate() {
+            if let Some(name) = name {
+                js_sys::Reflect::set(&names, &JsValue::from(name), &JsValue::from(index))?;
+            }
+        }
+
+        Ok(names.into())
+    }
+
+    /// Returns true if and only if this regex has a capture group with the
+    /// given name.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("(?P<y>\\d{4})-(?P<m>\\d{2})")
+    /// expect(re.hasGroupName("m")).toBe(true)
+    /// expect(re.hasGroupName("d")).toBe(false)
+    /// ```
+    ///
+    /// @param {string} name - The name of the capture group
+    /// @returns {boolean}
+    #[wasm_bindgen(skip_jsdoc, js_name = hasGroupName)]
+    pub fn has_group_name(&self, name: &str) -> Result<bool> {
+        Ok(self.capture_name_to_index(name)?.is_some())
+    }
+
+
+    /// Returns the capture groups corresponding to the leftmost-first
+    /// match in `text`. Capture group `0` always corresponds to the entire
+    /// match. If no match is found, then `undefined` is returned.
+    ///
+    /// You should only use `captures` if you need access to the location of
+    /// capturing group matches. Otherwise, `find` is faster for discovering
+    /// the location of the overall match.
+    ///
+    /// # Examples
+    ///
+    /// Say you have some text with movie names and their release years,
+    /// like "'Citizen Kane' (1941)". It'd be nice if we could search for text
+    /// looking like that, while also extracting the movie name and its release
+    /// year separately.
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("'([^']+)'\\s+\\((\\d{4})\)")
+    /// const text = "Not my favorite movie: 'Citizen Kane' (1941)."
+    /// const caps = re.captures(text)
+    /// expect(caps.get[1].value).toBe("Citizen Kane")
+    /// expect(caps.get[2].value).toBe("1941")
+    /// expect(caps.get[0].value).toBe("'Citizen Kane' (1941)")
+    /// ```
+    ///
+    /// Note that the full match is at capture group `0`. Each subsequent
+    /// capture group is indexed by the order of its opening `(`.
+    ///
+    /// We can make this example a bit clearer by using *named* capture groups:
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("'(?P<title>[^']+)'\\s+\\((?P<year>\\d{4})\)")
+    /// const text = "Not my favorite movie: 'Citizen Kane' (1941)."
+    /// const caps = re.captures(text)
+    /// expect(caps.name["title"].value).toBe("Citizen Kane")
+    /// expect(caps.name["year"].value).toBe("1941")
+    /// expect(caps.get[0].value).toBe("'Citizen Kane' (1941)")
+    /// ```
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.captures
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {Captures|undefined}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn captures(&self, text: &str) -> Result<JsValue> {
+        if let Some(captures) = self.regex()?.captures(text) {
+            Match::captures(captures, self.regex()?.capture_names())
+        } else {
+            Ok(JsValue::undefined())
+        }
+    }
+
+    /// Returns the same as `captures`, but starts the search at the given
+    /// offset.
+    ///
+    /// The significance of the starting point is that it takes the surrounding
+    /// context into consideration. For example, the `\A` anchor can only
+    /// match when `start == 0`.
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.captures_at
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {number} start - Zero-based index at which to start matching
+    /// @returns {Captures|undefined}
+    #[wasm_bindgen(skip_jsdoc, js_name = capturesAt)]
+    pub fn captures_at(&self, text: &str, start: usize) -> Result<JsValue> {
+        if start > text.len() {
+            return Ok(JsValue::UNDEFINED);
+        };
+
+        if let Some(captures) = self.regex()?.captures_at(text, start) {
+            Match::captures(captures, self.regex()?.capture_names())
+        } else {
+            Ok(JsValue::undefined())
+        }
+    }
+
+    /// Returns the byte offsets of the capture groups of the leftmost-first
+    /// match in `text` as a flat `Int32Array` of
+    /// `[start0, end0, start1, end1, ...]`, where group `0` is the whole
+    /// match.
+    ///
+    /// This is the same as `captures`, but avoids creating any object besides
+    /// the array. Groups that didn't participate in the match are encoded as
+    /// `-1, -1`, and an empty array is returned when there is no match.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const indices = new RRegex("(\\w+)@(\\w+)?").capturesIndices("joe@")
+    /// expect(Array.from(indices)).toEqual([0, 4, 0, 3, -1, -1])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {Int32Array}
+    #[wasm_bindgen(skip_jsdoc, js_name = capturesIndices)]
+    pub fn captures_indices(&self, text: &str) -> Result<js_sys::Int32Array> {
+        let regex = self.regex()?;
+        let mut locations = regex.capture_locations();
+        if regex.captures_read(&mut locations, text).is_none() {
+            return Ok(js_sys::Int32Array::new_with_length(0));
+        }
+
+        let indices: Vec<i32> = (0..locations.len())
+            .flat_map(|index| match locations.get(index) {
+                Some((start, end)) => [start as i32, end as i32],
+                None => [-1, -1],
+            })
+            .collect();
+        Ok(js_sys::Int32Array::from(indices.as_slice()))
+    }
+
+    /// Expands `template` with the capture groups of the leftmost-first match
+    /// in `text`, or returns `undefined` if there is no match.
+    ///
+    /// The template uses the same syntax as `replace`: `$name` and `${name}`
+    /// are replaced with the matching named group, `$1` with the group at that
+    /// index, and `$$` with a literal `$`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("(?P<last>[^,\\s]+),\\s+(?P<first>\\S+)")
+    /// expect(re.expand("Springsteen, Bruce", "$first $last")).toBe("Bruce Springsteen")
+    /// expect(re.expand("Springsteen, Bruce", "${last}!")).toBe("Springsteen!")
+    /// ```
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.Captures.html#method.expand
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {string} template - The template to expand
+    /// @returns {string|undefined}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn expand(&self, text: &str, template: &str) -> Result<Option<String>> {
+        let captures = match self.regex()?.captures(text) {
+            Some(captures) => captures,
+            None => return Ok(None),
+        };
+        let mut dst = String::new();
+        captures.expand(template, &mut dst);
+        Ok(Some(dst))
+    }
+
+    /// Returns a list with all the non-overlapping capture groups matched
+    /// in `text`. This is operationally the same as `findAll`, except it
+    /// returns information about capturing group matches. The first element
+    /// of every entry's `get` list is the overall match, so there is no need
+    /// to also call `findAll`.
+    ///
+    /// # Example
+    ///
+    /// We can use this to find all movie titles and their release years in
+    /// some text, where the movie is formatted like "'Title' (xxxx)":
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("'(?P<title>[^']+)'\\s+\\((?P<year>\\d{4})\\)")
+    /// const text = "'Citizen Kane' (1941), 'The Wizard of Oz' (1939), 'M' (1931)."
+    /// for caps of re.captures_iter(text) {
+    ///     console.log(
+    ///         "Movie:", caps.name["title"].value, ","
+    ///         "Released:", caps.name["year"].value
+    ///     );
+    /// }
+    /// // Output:
+    /// // Movie: Citizen Kane, Released: 1941
+    /// // Movie: The Wizard of Oz, Released: 1939
+    /// // Movie: M, Released: 1931
+    /// ```
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.captures_iter
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {Captures[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = capturesAll)]
+    pub fn captures_all(&self, text: &str) -> Result<JsValue> {
+        let names = self.regex()?.capture_names();
+        let result = js_sys::Array::new();
+        for captures in self.regex()?.captures_iter(text) {
+            let c = Match::captures(captures, names.clone())?;
+            result.push(&c);
+        }
+
+        Ok(JsValue::from(result))
+    }
+
+    /// Returns every successive non-overlapping match in `text`, shaped like
+    /// the results of JavaScript's `String.prototype.matchAll`, so code
+    /// written against a native `RegExp` works unchanged.
+    ///
+    /// Each result is an array holding the whole match at `[0]` and the
+    /// substring of capture group `n` at `[n]`, or `undefined` if it didn't
+    /// participate in the match. It also has an `index` property with the
+    /// offset of the match in UTF-16 code units, like native indices, an
+    /// `input` property with `text`, and a `groups` object mapping the name
+    /// of every named group to its substring, which is `undefined` when
+    /// there are no named groups.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const [m] = new RRegex("(?P<word>\\w+)").matchAllCompat("😀 hi")
+    /// expect([...m]).toEqual(["hi", "hi"])
+    /// expect(m.index).toBe(3)
+    /// expect(m.groups.word).toBe("hi")
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {RegExpMatchArray[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = matchAllCompat)]
+    pub fn match_all_compat(&self, text: &str) -> Result<JsValue> {
+        let input = JsValue::from(text);
+        let result = js_sys::Array::new();
+        let mut last = 0;
+        let mut units = 0;
+        for captures in self.regex()?.captures_iter(text) {
+            let m = captures.get(0).unwrap();
+            units += text[last..m.start()].encode_utf16().count();
+            let groups = captures.iter().map(|m| m.map(|m| m.as_str()));
+            let array = self.match_array(groups, units, &input)?;
+            result.push(&array);
+            units += m.as_str().encode_utf16().count();
+            last = m.end();
+        }
+
+        Ok(JsValue::from(result))
+    }
+
+    /// Returns the capture groups of the leftmost-first match in `text` as a
+    /// tree that follows the nesting of the groups in the pattern. The root
+    /// is group `0`, the overall match, and the children of every node are
+    /// the groups written directly inside it.
+    ///
+    /// Groups that didn't participate in the match are left out, along with
+    /// the groups nested in them. If no match is found, then `undefined` is
+    /// returned.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const tree = new RRegex("((?P<y>\\d+)-(\\d+))").capturesTree("2020-01")
+    /// expect(tree.children[0].children.map((c) => c.name)).toEqual(["y", undefined])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {CaptureNode|undefined}
+    #[wasm_bindgen(skip_jsdoc, js_name = capturesTree)]
+    pub fn captures_tree(&self, text: &str) -> Result<JsValue> {
+        let captures = match self.regex()?.captures(text) {
+            Some(captures) => captures,
+            None => return Ok(JsValue::UNDEFINED),
+        };
+        let names: Vec<Option<&str>> = self.regex()?.capture_names().collect();
+        let m = captures.get(0).unwrap();
+        let tree = CaptureNode {
+            index: 0,
+            name: None,
+            start: m.start(),
+            end: m.end(),
+            children: capture_nodes(self.hir()?, &captures, &names),
+        };
+
+        serde_wasm_bindgen::to_value(&tree)
+    }
+
+    /// Returns the match of capture group `group` for every successive
+    /// non-overlapping match in `text`, as a flat list. Matches where the
+    /// group didn't participate are skipped.
+    ///
+    /// An error is returned if `group` is not less than `capturesLength()`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const matches = new RRegex("(\\w+)=(\\d+)?").findAllGroup("a=1 b= c=3", 2)
+    /// expect(matches.map((m) => m.value)).toEqual(["1", "3"])
+    /// ```
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.captures_iter
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {number} group - The index of the capture group
+    /// @returns {Match[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = findAllGroup)]
+    pub fn find_all_group(&self, text: &str, group: usize) -> Result<JsValue> {
+        if group >= self.regex()?.captures_len() {
+            return Err(serde_wasm_bindgen::Error::new(format!(
+                "capture group {} does not exist, the regex has {} groups",
+                group,
+                self.regex()?.captures_len()
+            )));
+        }
+
+        serde_wasm_bindgen::to_value(&self.group_matches(text, group)?)
+    }
+
+    /// Returns the match of the capture group named `name` for every
+    /// successive non-overlapping match in `text`, as a flat list. Matches
+    /// where the group didn't participate are skipped.
+    ///
+    /// An error is returned if there is no group with that name.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("id=(?P<id>\\d+)")
+    /// const matches = re.findAllNamedGroup("id=1, id=22", "id")
+    /// expect(matches.map((m) => m.value)).toEqual(["1", "22"])
+    /// ```
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.captures_iter
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {string} name - The name of the capture group
+    /// @returns {Match[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = findAllNamedGroup)]
+    pub fn find_all_named_group(&self, text: &str, name: &str) -> Result<JsValue> {
+        let group = self.capture_name_to_index(name)?.ok_or_else(|| {
+            serde_wasm_bindgen::Error::new(format!("capture group {} does not exist", name))
+        })?;
+
+        serde_wasm_bindgen::to_value(&self.group_matches(text, group)?)
+    }
+
+    /// Returns a lazy iterator over the captures of every successive
+    /// non-overlapping match in `text`. Each call to `next` searches for the
+    /// next match, so nothing is collected upfront like `capturesAll` does.
+    ///
+    /// The iterator keeps its own copy of `text`, and of the regex, so it
+    /// stays valid even if this regex is freed.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const iter = new RRegex("(\\w)(\\d)").capturesIter("a1 b2")
+    /// expect(iter.next().get[1].value).toBe("a")
+    /// expect(iter.next().get[1].value).toBe("b")
+    /// expect(iter.next()).toBe(undefined)
+    /// ```
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.captures_iter
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {RRegexCapturesIter}
+    #[wasm_bindgen(skip_jsdoc, js_name = capturesIter)]
+    pub fn captures_iter(&self, text: &str) -> Result<RRegexCapturesIter> {
+        Ok(RRegexCapturesIter {
+            regex: self.regex()?.clone(),
+            text: text.to_owned(),
+            start: 0,
+            last_end: None,
+        })
+    }
+
+    /// Returns a scanner that finds the matches of this regex in a text that
+    /// arrives in chunks, holding back the last `maxLookBack` bytes where a
+    /// match could still be incomplete.
+    ///
+    /// `maxLookBack` should be at least the length in bytes of the longest
+    /// match expected, see `RRegexScanner` for the details.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const scanner = new RRegex("ERROR \\d+").scanner(64)
+    /// for (const chunk of ["ok\nERROR 4", "2\nok\n"]) {
+    ///   scanner.push(chunk)
+    ///   scanner.drain().forEach((m) => console.log(m.value))
+    /// }
+    /// scanner.finish().forEach((m) => console.log(m.value))
+    /// ```
+    ///
+    /// @param {number} maxLookBack - The number of bytes where a match may still be incomplete
+    /// @returns {RRegexScanner}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn scanner(&self, max_look_back: usize) -> Result<RRegexScanner> {
+        self.regex()?;
+        Ok(RRegexScanner {
+            regex: self.clone(),
+            max_look_back,
+            buffer: String::new(),
+            offset: 0,
+            start: 0,
+            last_end: None,
+        })
+    }
+
+    /// Returns a navigator over the byte ranges of the successive
+    /// non-overlapping matches in `text`, found in a single search, to jump
+    /// between them without searching again. See `RRegexNavigator` for the
+    /// details.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const nav = new RRegex("\\d+").matchNavigator("1 22 333")
+    /// expect(nav.next(1)).toEqual({ start: 2, end: 4 })
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {RRegexNavigator}
+    #[wasm_bindgen(skip_jsdoc, js_name = matchNavigator)]
+    pub fn match_navigator(&self, text: &str) -> Result<RRegexNavigator> {
+        Ok(RRegexNavigator {
+            ranges: self
+                .find_substrings(text)?
+                .map(|m| (m.start(), m.end()))
+                .collect(),
+        })
+    }
+
+    /// Returns a matcher with a mutable `lastIndex`, for code written
+    /// against `RegExp.prototype.exec` with the `g` flag. See
+    /// `RRegexStateful` for the details.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("\\w+").stateful()
+    /// expect(re.exec("ab cd")[0]).toBe("ab")
+    /// expect(re.exec("ab cd").index).toBe(3)
+    /// expect(re.exec("ab cd")).toBe(null)
+    /// ```
+    ///
+    /// @returns {RRegexStateful}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn stateful(&self) -> Result<RRegexStateful> {
+        self.regex()?;
+        Ok(RRegexStateful {
+            regex: self.clone(),
+            last_index: 0,
+        })
+    }
+
+    /// Creates a reusable buffer for the locations of this regex's capture
+    /// groups, to be filled in by `capturesRead`.
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.capture_locations
+    /// @returns {RRegexLocations}
+    #[wasm_bindgen(skip_jsdoc, js_name = createLocations)]
+    pub fn create_locations(&self) -> Result<RRegexLocations> {
+        Ok(RRegexLocations {
+            locations: self.regex()?.capture_locations(),
+        })
+    }
+
+    /// Searches `text` and writes the locations of the capture groups of the
+    /// leftmost-first match into `locations`, returning the overall match or
+    /// `undefined` if there is no match.
+    ///
+    /// This is like `captures`, but reuses the given buffer instead of
+    /// allocating a new `Captures` object on every call.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("(\\d{4})-(\\d{2})")
+    /// const locs = re.createLocations()
+    /// const m = re.capturesRead("on 2010-03", locs)
+    /// expect(m.start).toBe(3)
+    /// expect(locs.start(2)).toBe(8)
+    /// expect(locs.end(2)).toBe(10)
+    /// ```
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.captures_read
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {RRegexLocations} locations - The buffer to write the capture locations to
+    /// @returns {Match|undefined}
+    #[wasm_bindgen(skip_jsdoc, js_name = capturesRead)]
+    pub fn captures_read(&self, text: &str, locations: &mut RRegexLocations) -> Result<JsValue> {
+        match self.regex()?.captures_read(&mut locations.locations, text) {
+            Some(m) => Match::from(m).try_into(),
+            None => Ok(JsValue::UNDEFINED),
+        }
+    }
+
+    /// Searches `text` and writes the strings matched by the capture groups
+    /// of the leftmost-first match into `target`, returning whether there
+    /// was a match.
+    ///
+    /// Every group is written under its index, and named groups under their
+    /// name too, with groups that didn't participate in the match set to
+    /// `undefined`. The other properties of `target` are left untouched, and
+    /// so is `target` itself if there is no match. This is like `captures`,
+    /// but reuses the given object instead of allocating a new one on every
+    /// call.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("(?<key>\\w+)=(?<value>\\w+)?")
+    /// const target = {}
+    /// expect(re.capturesInto("a=1", target)).toBe(true)
+    /// expect(target).toEqual({ 0: "a=1", 1: "a", 2: "1", key: "a", value: "1" })
+    /// expect(re.capturesInto("b=", target)).toBe(true)
+    /// expect(target.value).toBe(undefined)
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {object} target - The object to write the capture groups to
+    /// @returns {boolean}
+    #[wasm_bindgen(skip_jsdoc, js_name = capturesInto)]
+    pub fn captures_into(&self, text: &str, target: &js_sys::Object) -> Result<bool> {
+        let regex = self.regex()?;
+        let captures = match regex.captures(text) {
+            Some(captures) => captures,
+            None => return Ok(false),
+        };
+
+        for (index, name) in regex.capture_names().enumerate() {
+            let value = captures
+                .get(index)
+                .map_or(JsValue::UNDEFINED, |m| JsValue::from_str(m.as_str()));
+            js_sys::Reflect::set(target, &JsValue::from(index as u32), &value)?;
+            if let Some(name) = name {
+                js_sys::Reflect::set(target, &JsValue::from_str(name), &value)?;
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Returns the capture groups of the leftmost-first match in `text` like
+    /// `captures`, but with the value of every group coerced according to
+    /// `kinds`, or `undefined` if there is no match.
+    ///
+    /// `kinds[i]` is the kind of group `i`, one of `"string"`, `"number"` and
+    /// `"boolean"`, and groups without a kind are strings. A number is parsed
+    /// like a Rust `f64`, and a boolean must be `true` or `false`. A value
+    /// that can't be coerced is `null`, and a group that didn't participate
+    /// in the match is `undefined`. Unlike `captures`, `get` has an element
+    /// for every group, so indices always match group numbers. If a kind
+    /// isn't valid, then an error is thrown.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("(?<name>\\w+)=(?<age>\\w+),(?<admin>\\w+)")
+    /// const caps = re.capturesTyped("ann=42,true", ["string", "string", "number", "boolean"])
+    /// expect(caps.name).toEqual({ name: "ann", age: 42, admin: true })
+    /// expect(re.capturesTyped("bob=old,no", [, , "number", "boolean"]).get).toEqual(["bob=old,no", "bob", null, null])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {string[]} kinds - The kind of every group, by index
+    /// @returns {TypedCaptures|undefined}
+    #[wasm_bindgen(skip_jsdoc, js_name = capturesTyped)]
+    pub fn captures_typed(&self, text: &str, kinds: &js_sys::Array) -> Result<JsValue> {
+        let kinds = kinds
+            .iter()
+            .map(|kind| match kind.as_string() {
+                Some(kind) => CaptureKind::parse(&kind),
+                None if kind.is_undefined() => Ok(CaptureKind::String),
+                None => Err(serde_wasm_bindgen::Error::new("capture kind must be a string")),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let regex = self.regex()?;
+        let captures = match regex.captures(text) {
+            Some(captures) => captures,
+            None => return Ok(JsValue::UNDEFINED),
+        };
+
+        let values = js_sys::Array::new();
+        let names = js_sys::Object::new();
+        for (index, name) in regex.capture_names().enumerate() {
+            let kind = kinds.get(index).copied().unwrap_or(CaptureKind::String);
+            let value = captures
+                .get(index)
+                .map_or(JsValue::UNDEFINED, |m| kind.coerce(m.as_str()));
+            values.push(&value);
+            if let Some(name) = name {
+                js_sys::Reflect::set(&names, &JsValue::from_str(name), &value)?;
+            }
+        }
+
+        let result = js_sys::Object::new();
+        js_sys::Reflect::set(&result, &JsValue::from_str("get"), &values)?;
+        js_sys::Reflect::set(&result, &JsValue::from_str("name"), &names)?;
+        Ok(result.into())
+    }
+
+    /// Returns the number of captures.
+    ///
+    /// This includes all named and unnamed groups, including the implicit
+    /// unnamed group that is always present and corresponds to the entire match.
+    ///
+    /// Since the implicit unnamed group is always included in this length, the
+    /// length returned is guaranteed to be greater than zero.
+    ///
+    /// ## Example
+    ///
+    /// ```typescript
+    ///  import { RRegex } from "rregex"
+    ///
+    ///  const re1 = new RRegex("(?P<y>\\d{4})-(?P<m>\\d{2})-(?P<d>\\d{2})")
+    ///  expect(re1.capturesLength()).toEqual(4)
+    ///
+    ///  const re2 = new RRegex("foo")
+    ///  expect(re2.capturesLength()).toEqual(1)
+    ///
+    ///  const re3 = new RRegex("(foo)")
+    ///  expect(re3.capturesLength()).toEqual(2)
+    ///
+    ///  const re4 = new RRegex("(?<a>.(?<b>.))(.)(?:.)(?<c>.)")
+    ///  expect(re4.capturesLength()).toEqual(5)
+    ///
+    ///  const re5 = new RRegex("[a&&b]")
+    ///  expect(re5.capturesLength()).toEqual(1)
+    /// ```
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.captures_len
+    #[wasm_bindgen(js_name = capturesLength)]
+    pub fn captures_len(&self) -> Result<usize> {
+        Ok(self.regex()?.captures_len())
+    }
+
+    /// Replaces the leftmost-first match with the replacement provided.
+    /// The replacement can be a regular string (where `$N` and `$name` are
+    /// expanded to match capture groups) or a function that takes the matches'
+    /// `Captures` and returns the replaced string.
+    ///
+    /// If no match is found, then a copy of the string is returned unchanged.
+    ///
+    /// # Replacement string syntax
+    ///
+    /// All instances of `$name` in the replacement text is replaced with the
+    /// corresponding capture group `name`.
+    ///
+    /// `name` may be an integer corresponding to the index of the
+    /// capture group (counted by order of opening parenthesis where `0` is the
+    /// entire match) or it can be a name (consisting of letters, digits or
+    /// underscores) corresponding to a named capture group.
+    ///
+    /// If `name` isn't a valid capture group (whether the name doesn't exist
+    /// or isn't a valid index), then it is replaced with the empty string.
+    ///
+    /// The longest possible name is used. e.g., `$1a` looks up the capture
+    /// group named `1a` and not the capture group at index `1`. To exert more
+    /// precise control over the name, use braces, e.g., `${1}a`.
+    ///
+    /// To write a literal `$` use `$$`.
+    ///
+    /// # Examples
+    ///
+    /// Note that this function is polymorphic with respect to the replacement.
+    /// In typical usage, this can just be a normal string:
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("[^01]+")
+    /// expect(re.replace("1078910", "").toBe("1010")
+    /// ```
+    ///
+    /// Here's the example using this expansion technique with named capture
+    /// groups:
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("(?P<last>[^,\\s]+),\\s+(?P<first>\\S+)")
+    /// const result = re.replace("Springsteen, Bruce", "$first $last")
+    /// expect(result).toBe("Bruce Springsteen")
+    /// ```
+    ///
+    /// Note that using `$2` instead of `$first` or `$1` instead of `$last`
+    /// would produce the same result. To write a literal `$` use `$$`.
+    ///
+    /// Sometimes the replacement string requires use of curly braces to
+    /// delineate a capture group replacement and surrounding literal text.
+    /// For example, if we wanted to join two words together with an
+    /// underscore:
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("(?P<first>\\w+)\\s+(?P<second>\\w+)")
+    /// const result = re.replace("deep fried", "${first}_$second")
+    /// expect(result).toBe("deep_fried")
+    /// ```
+    ///
+    /// Without the curly braces, the capture group name `first_` would be
+    /// used, and since it doesn't exist, it would be replaced with the empty
+    /// string.
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.replace
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {string} rep - It's a string, it will replace the substring matched by `pattern`.
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn replace(&self, text: &str, rep: &str) -> Result<String> {
+        Ok(self.regex()?.replace(text, rep).into_owned())
+    }
+
+    /// Replaces the leftmost-first match like `replace`, but first checks
+    /// that every `$n`, `$name` and `${name}` in `rep` refers to a capture
+    /// group of this regex. Instead of silently expanding to the empty
+    /// string, a reference to a group that doesn't exist throws an error.
+    ///
+    /// Groups that exist but didn't participate in the match still expand to
+    /// the empty string.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("(\\w+) (\\w+)")
+    /// expect(re.replaceStrict("hello world", "$2 $1")).toBe("world hello")
+    /// expect(() => re.replaceStrict("hello world", "$10")).toThrow()
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {string} rep - It's a string, it will replace the substring matched by `pattern`.
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc, js_name = replaceStrict)]
+    pub fn replace_strict(&self, text: &str, rep: &str) -> Result<String> {
+        self.check_replacement(rep)?;
+
+        self.replace(text, rep)
+    }
+
+    /// Replaces at most `limit` non-overlapping matches in `text` with the
+    /// replacement provided. If `limit` is 0, then all non-overlapping matches
+    /// are replaced.
+    ///
+    /// See the documentation for `replace` for details on how to access
+    /// capturing group matches in the replacement string.
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.replacen
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {number} limit - Max number of replacement
+    /// @param {string} rep - It's a string, it will replace the substring matched by `pattern`.
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn replacen(&self, text: &str, limit: usize, rep: &str) -> Result<String> {
+        Ok(self.regex()?.replacen(text, limit, rep).into_owned())
+    }
+
+    /// Replaces the non-overlapping matches in `text` whose zero-based
+    /// occurrence index is in the range `from..to`, e.g., to skip the first
+    /// match. The other matches are left untouched.
+    ///
+    /// See the documentation for `replace` for details on how to access
+    /// capturing group matches in the replacement string.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("\\d")
+    /// expect(re.replaceRange("1 2 3 4 5", 1, 3, "#")).toBe("1 # # 4 5")
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {number} from - The index of the first match to replace
+    /// @param {number} to - The index of the match to stop replacing at, not included
+    /// @param {string} rep - It's a string, it will replace the substring matched by `pattern`.
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc, js_name = replaceRange)]
+    pub fn replace_range(&self, text: &str, from: usize, to: usize, rep: &str) -> Result<String> {
+        let mut result = String::with_capacity(text.len());
+        let mut last = 0;
+        for captures in self.regex()?.captures_iter(text).take(to).skip(from) {
+            let m = captures.get(0).unwrap();
+            result.push_str(&text[last..m.start()]);
+            captures.expand(rep, &mut result);
+            last = m.end();
+        }
+        result.push_str(&text[last..]);
+
+        Ok(result)
+    }
+
+    /// Replaces all non-overlapping matches in `text` with the replacement
+    /// provided. This is the same as calling `replacen` with `limit` set to
+    /// `0`.
+    ///
+    /// See the documentation for `replace` for details on how to access
+    /// capturing group matches in the replacement string.
+    ///
+    /// Zero-width matches, e.g., of patterns like `\b` used to insert
+    /// markers, follow these rules:
+    ///
+    /// - An empty match inserts `rep` at its position and keeps all of the
+    ///   text around it, so no character is ever dropped.
+    /// - `rep` is inserted at most once at any position. An empty match right
+    ///   where a non-empty match ended isn't replaced, since that position was
+    ///   just replaced. This differs from `String.prototype.replaceAll`, which
+    ///   turns `"baaac"` into `"-b--c-"` for the pattern `a*` where this
+    ///   returns `"-b-c-"`.
+    /// - Empty matches never fall inside a UTF-8 character.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// expect(new RRegex("\\b").replaceAll("ab cd", "|")).toBe("|ab| |cd|")
+    /// expect(new RRegex("a*").replaceAll("baaac", "-")).toBe("-b-c-")
+    /// ```
+    ///
+    /// See the documentation for `replace` for details on how to access capturing group matches in the replacement string.
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.replace_all
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {string} rep - It's a string, it will replace the substring matched by `pattern`.
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc, js_name = replaceAll)]
+    pub fn replace_all(&self, text: &str, rep: &str) -> Result<String> {
+        Ok(self.regex()?.replace_all(text, rep).into_owned())
+    }
+
+    /// Replaces all non-overlapping matches in `text` with a replacement
+    /// compiled with `compileReplacement`, so its template isn't parsed
+    /// again on every call.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex, compileReplacement } from "rregex"
+    ///
+    /// const re = new RRegex("(\\d+)-(\\d+)")
+    /// const rep = compileReplacement("$2-$1")
+    /// expect(re.replaceAllWith("1-2 3-4", rep)).toBe("2-1 4-3")
+    /// ```
+    ///
+    /// @see RRegexReplacement
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {RRegexReplacement} replacement - The compiled replacement
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc, js_name = replaceAllWith)]
+    pub fn replace_all_with(&self, text: &str, replacement: &RRegexReplacement) -> Result<String> {
+        Ok(self.regex()?.replace_all(text, replacement).into_owned())
+    }
+
+    /// Replaces all non-overlapping matches in `text` with the replacement
+    /// provided, which is interpreted like the replacement string of
+    /// JavaScript's `String.prototype.replaceAll` rather than with the
+    /// syntax of `replace`. So code written against a `RegExp` can be ported
+    /// without rewriting its replacements.
+    ///
+    /// The supported tokens are:
+    ///
+    /// * `$$` inserts a `$`.
+    /// * `$&` inserts the matched substring.
+    /// * `` $` `` inserts the portion of `text` before the match.
+    /// * `$'` inserts the portion of `text` after the match.
+    /// * `$n` and `$nn` insert the capture group with the given one or two
+    ///   digit index, from `1` to `99`. Two digits are used if that group
+    ///   exists, e.g., `$10` is group `1` followed by a `0` when there are
+    ///   fewer than ten groups.
+    /// * `$<name>` inserts the named capture group `name`.
+    ///
+    /// Groups that didn't participate in the match, and names that aren't
+    /// groups, insert an empty string. Like in JavaScript, a `$` that doesn't
+    /// start one of these tokens is kept as is, which includes `$0`, a
+    /// `$n` without a group `n`, and every `$<` when the regex has no named
+    /// groups.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("(?P<y>\\d{4})-(\\d{2})")
+    /// expect(re.replaceAllJs("2024-05", "$2/$<y> ($&, $$1)")).toBe("05/2024 (2024-05, $1)")
+    /// ```
+    ///
+    /// @see https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/replace#specifying_a_string_as_the_replacement
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {string} rep - The replacement, using the JavaScript replacement tokens
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc, js_name = replaceAllJs)]
+    pub fn replace_all_js(&self, text: &str, rep: &str) -> Result<String> {
+        let mut result = String::with_capacity(text.len());
+        let mut last = 0;
+        for captures in self.regex()?.captures_iter(text) {
+            let m = captures.get(0).unwrap();
+            result.push_str(&text[last..m.start()]);
+            self.expand_js(&captures, text, rep, &mut result)?;
+            last = m.end();
+        }
+        result.push_str(&text[last..]);
+
+        Ok(result)
+    }
+
+    /// Replaces all non-overlapping matches in `text` with the replacement
+    /// provided, like `replaceAll`, but hands the result to `f` piece by
+    /// piece instead of returning it, so the whole output is never held in
+    /// memory at once.
+    ///
+    /// `f` is called in order with every non-empty run of text between
+    /// matches and with every non-empty replacement, so concatenating the
+    /// chunks gives the string `replaceAll` returns. If `f` throws, then the
+    /// replacement stops and the error is thrown.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const chunks = []
+    /// new RRegex("\\d+").replaceAllChunked("a1b22", "<$0>", (chunk) => chunks.push(chunk))
+    /// expect(chunks).toEqual(["a", "<1>", "b", "<22>"])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {string} rep - It's a string, it will replace the substring matched by `pattern`.
+    /// @param {(chunk: string) => void} f - Called with every piece of the result
+    #[wasm_bindgen(skip_jsdoc, js_name = replaceAllChunked)]
+    pub fn replace_all_chunked(&self, text: &str, rep: &str, f: &js_sys::Function) -> Result<()> {
+        let emit = |chunk: &str| -> Result<()> {
+            if !chunk.is_empty() {
+                f.call1(&JsValue::NULL, &JsValue::from(chunk))
+                    .map_err(serde_wasm_bindgen::Error::from)?;
+            }
+            Ok(())
+        };
+
+        let mut replacement = String::new();
+        let mut last = 0;
+        for captures in self.regex()?.captures_iter(text) {
+            let m = captures.get(0).unwrap();
+            emit(&text[last..m.start()])?;
+            replacement.clear();
+            captures.expand(rep, &mut replacement);
+            emit(&replacement)?;
+            last = m.end();
+        }
+
+        emit(&text[last..])
+    }
+
+    /// Returns the same as `replaceAll`, for a text prepared with `prepare`.
+    ///
+    /// The prepared text is left untouched, the result is a new string. So
+    /// a sequence of independent replacements can all run on the same
+    /// prepared text.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex, prepare } from "rregex"
+    ///
+    /// const text = prepare("a1 b2")
+    /// expect(new RRegex("\\d").replaceAllOn(text, "#")).toBe("a# b#")
+    /// expect(new RRegex("[a-z]").replaceAllOn(text, "_")).toBe("_1 _2")
+    /// ```
+    ///
+    /// @see RRegexText
+    /// @param {RRegexText} text - The prepared text against which to match the regular expression
+    /// @param {string} rep - It's a string, it will replace the substring matched by `pattern`.
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc, js_name = replaceAllOn)]
+    pub fn replace_all_on(&self, text: &RRegexText, rep: &str) -> Result<String> {
+        self.replace_all(&text.text, rep)
+    }
+
+    /// Replaces all non-overlapping matches in `text` with the replacement
+    /// provided, like `replaceAll`, but leaves `result` out when nothing
+    /// matched.
+    ///
+    /// When `changed` is `false` no copy of `text` is made or sent back, so
+    /// the original string can be kept as is. This saves copying a large text
+    /// that usually has no matches just to learn it didn't change.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("\\d")
+    /// expect(re.replaceAllMaybe("a1", "#")).toEqual({ changed: true, result: "a#" })
+    /// expect(re.replaceAllMaybe("ab", "#")).toEqual({ changed: false })
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {string} rep - It's a string, it will replace the substring matched by `pattern`.
+    /// @returns {MaybeReplacement}
+    #[wasm_bindgen(skip_jsdoc, js_name = replaceAllMaybe)]
+    pub fn replace_all_maybe(&self, text: &str, rep: &str) -> Result<JsValue> {
+        let replacement = match self.regex()?.replace_all(text, rep) {
+            Cow::Borrowed(_) => MaybeReplacement {
+                changed: false,
+                result: None,
+            },
+            Cow::Owned(result) => MaybeReplacement {
+                changed: true,
+                result: Some(result),
+            },
+        };
+
+        serde_wasm_bindgen::to_value(&replacement)
+    }
+
+    /// Replaces all non-overlapping matches in `text` with the replacement
+    /// provided, like `replaceAll`, and also returns how many matches were
+    /// replaced.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("\\d{3}-\\d{4}")
+    /// const { result, count } = re.replaceAllCounted("555-1234 or 555-9876", "[redacted]")
+    /// expect(result).toBe("[redacted] or [redacted]")
+    /// expect(count).toBe(2)
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {string} rep - It's a string, it will replace the substring matched by `pattern`.
+    /// @returns {CountedReplacement}
+    #[wasm_bindgen(skip_jsdoc, js_name = replaceAllCounted)]
+    pub fn replace_all_counted(&self, text: &str, rep: &str) -> Result<JsValue> {
+        let mut result = String::with_capacity(text.len());
+        let mut count = 0;
+        let mut last = 0;
+        for captures in self.regex()?.captures_iter(text) {
+            let m = captures.get(0).unwrap();
+            result.push_str(&text[last..m.start()]);
+            captures.expand(rep, &mut result);
+            last = m.end();
+            count += 1;
+        }
+        result.push_str(&text[last..]);
+
+        serde_wasm_bindgen::to_value(&CountedReplacement { result, count })
+    }
+
+    /// Replaces all non-overlapping matches in `text` with the replacement
+    /// provided, like `replaceAll`, and returns where every match was in
+    /// `text` along with where its replacement is in the result, e.g., to
+    /// build a source map between the two strings.
+    ///
+    /// Offsets are byte offsets into `text` for `originalStart` and
+    /// `originalEnd`, and into the result for `outputStart` and `outputEnd`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const { result, spans } = new RRegex("\\d+").replaceAllWithSpans("a1 b22", "#")
+    /// expect(result).toBe("a# b#")
+    /// expect(spans).toEqual([
+    ///   { originalStart: 1, originalEnd: 2, outputStart: 1, outputEnd: 2 },
+    ///   { originalStart: 4, originalEnd: 6, outputStart: 4, outputEnd: 5 },
+    /// ])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {string} rep - It's a string, it will replace the substring matched by `pattern`.
+    /// @returns {SpannedReplacement}
+    #[wasm_bindgen(skip_jsdoc, js_name = replaceAllWithSpans)]
+    pub fn replace_all_with_spans(&self, text: &str, rep: &str) -> Result<JsValue> {
+        let mut result = String::with_capacity(text.len());
+        let mut spans = Vec::new();
+        let mut last = 0;
+        for captures in self.regex()?.captures_iter(text) {
+            let m = captures.get(0).unwrap();
+            result.push_str(&text[last..m.start()]);
+            let output_start = result.len();
+            captures.expand(rep, &mut result);
+            spans.push(ReplacementSpan {
+                original_start: m.start(),
+                original_end: m.end(),
+                output_start,
+                output_end: result.len(),
+            });
+            last = m.end();
+        }
+        result.push_str(&text[last..]);
+
+        serde_wasm_bindgen::to_value(&SpannedReplacement { result, spans })
+    }
+
+    /// Returns the changes `replaceAll` would make to `text` without making
+    /// them, e.g., to show them as a diff to approve. There is one change
+    /// for every match whose replacement differs from the matched text,
+    /// matches that would be replaced by the same text are left out.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const changes = new RRegex("colou?r").previewReplaceAll("color, colour", "colour")
+    /// expect(changes).toEqual([{ start: 0, end: 5, before: "color", after: "colour" }])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {string} rep - It's a string, it will replace the substring matched by `pattern`.
+    /// @returns {ReplacementChange[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = previewReplaceAll)]
+    pub fn preview_replace_all(&self, text: &str, rep: &str) -> Result<JsValue> {
+        let mut changes = Vec::new();
+        for captures in self.regex()?.captures_iter(text) {
+            let m = captures.get(0).unwrap();
+            let mut after = String::new();
+            captures.expand(rep, &mut after);
+            if after != m.as_str() {
+                changes.push(ReplacementChange {
+                    start: m.start(),
+                    end: m.end(),
+                    before: m.as_str(),
+                    after,
+                });
+            }
+        }
+
+        serde_wasm_bindgen::to_value(&changes)
+    }
+
+    /// Replaces all non-overlapping matches in `text` with the string
+    /// returned by `f`, which is called with the `Captures` of every match,
+    /// and calls `progress` with the number of matches replaced so far after
+    /// every `chunkMatches` replacements.
+    ///
+    /// The progress callback lets a long replacement report back to the UI.
+    /// The string returned doesn't depend on it. A `chunkMatches` of `0`
+    /// never calls `progress`. If `f` doesn't return a string, or any of the
+    /// callbacks throws, then an error is thrown.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("\\d+")
+    /// const counts = []
+    /// const result = re.replaceAllFnChunked(
+    ///   "1 2 3 4 5",
+    ///   (caps) => String(Number(caps.get[0].value) * 2),
+    ///   2,
+    ///   (count) => counts.push(count),
+    /// )
+    /// expect(result).toBe("2 4 6 8 10")
+    /// expect(counts).toEqual([2, 4])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {(captures: Captures) => string} f - Returns the replacement for a match
+    /// @param {number} chunkMatches - How many replacements to make between progress calls
+    /// @param {(count: number) => void} [progress] - Called with the number of replacements so far
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc, js_name = replaceAllFnChunked)]
+    pub fn replace_all_fn_chunked(
+        &self,
+        text: &str,
+        f: &js_sys::Function,
+        chunk_matches: usize,
+        progress: Option<js_sys::Function>,
+    ) -> Result<String> {
+        let names = self.regex()?.capture_names();
+        let mut count = 0;
+        self.replace_with(text, |captures| {
+            let replacement = f
+                .call1(&JsValue::NULL, &Match::captures(captures, names.clone())?)
+                .map_err(serde_wasm_bindgen::Error::from)?;
+
+            count += 1;
+            if let Some(progress) = &progress {
+                if chunk_matches > 0 && count % chunk_matches == 0 {
+                    progress
+                        .call1(&JsValue::NULL, &JsValue::from(count))
+                        .map_err(serde_wasm_bindgen::Error::from)?;
+                }
+            }
+            Ok(replacement)
+        })
+    }
+
+    /// Replaces all non-overlapping matches in `text` with the string
+    /// returned by `f`, which is called with a plain object mapping the index
+    /// and the name of every capture group to the substring it matched.
+    ///
+    /// Groups that didn't participate in the match map to `undefined`. If `f`
+    /// doesn't return a string, or throws, then an error is thrown.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("(?P<key>\\w+)=(?P<value>\\w+)")
+    /// const result = re.replaceAllFnNamed("a=1 b=2", (groups) => `${groups.value}=${groups.key}`)
+    /// expect(result).toBe("1=a 2=b")
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {(groups: Record<string, string | undefined>) => string} f - Returns the replacement for a match
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc, js_name = replaceAllFnNamed)]
+    pub fn replace_all_fn_named(&self, text: &str, f: &js_sys::Function) -> Result<String> {
+        self.replace_with(text, |captures| {
+            let groups = js_sys::Object::new();
+            for (index, name) in self.regex()?.capture_names().enumerate() {
+                let value = captures
+                    .get(index)
+                    .map_or(JsValue::UNDEFINED, |m| JsValue::from(m.as_str()));
+                js_sys::Reflect::set(&groups, &JsValue::from(index), &value)?;
+                if let Some(name) = name {
+                    js_sys::Reflect::set(&groups, &JsValue::from(name), &value)?;
+                }
+            }
+
+            f.call1(&JsValue::NULL, &groups).map_err(serde_wasm_bindgen::Error::from)
+        })
+    }
+
+    /// Replaces all non-overlapping matches in `text` with the string
+    /// returned by `f`, which is called with the `Captures` of every match
+    /// along with the byte offsets where the match starts and ends in `text`.
+    ///
+    /// The offsets let the replacement depend on where the match is, e.g.,
+    /// to format matches on the first line differently. If `f` doesn't return
+    /// a string, or throws, then an error is thrown.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("\\d+")
+    /// const result = re.replaceAllFnPos("1 2 3", (caps, start, end) => `${start}..${end}`)
+    /// expect(result).toBe("0..1 2..3 4..5")
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {(captures: Captures, start: number, end: number) => string} f - Returns the replacement for a match
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc, js_name = replaceAllFnPos)]
+    pub fn replace_all_fn_pos(&self, text: &str, f: &js_sys::Function) -> Result<String> {
+        let names = self.regex()?.capture_names();
+        self.replace_with(text, |captures| {
+            let m = captures.get(0).unwrap();
+            f.call3(
+                &JsValue::NULL,
+                &Match::captures(captures, names.clone())?,
+                &JsValue::from(m.start()),
+                &JsValue::from(m.end()),
+            )
+            .map_err(serde_wasm_bindgen::Error::from)
+        })
+    }
+
+    /// Replaces the non-overlapping matches in `text` for which `predicate`
+    /// returns a truthy value with `rep`, and keeps the other matches as
+    /// they are.
+    ///
+    /// The predicate is called with the `Captures` of every match, in the
+    /// order they appear in `text`. Unlike `replaceAllFn`, the replacement
+    /// itself isn't computed in JS: `rep` is expanded like the replacement
+    /// string of `replaceAll`. If `predicate` throws, then the error is
+    /// thrown from here.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("(\\d+)")
+    /// const result = re.replaceAllIf("1 20 300", (caps) => caps.get[1].value.length > 1, "<$1>")
+    /// expect(result).toBe("1 <20> <300>")
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {(captures: Captures) => unknown} predicate - Whether to replace a match
+    /// @param {string} rep - It's a string, it will replace the substring matched by `pattern`.
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc, js_name = replaceAllIf)]
+    pub fn replace_all_if(
+        &self,
+        text: &str,
+        predicate: &js_sys::Function,
+        rep: &str,
+    ) -> Result<String> {
+        let names = self.regex()?.capture_names();
+        let mut result = String::with_capacity(text.len());
+        let mut last = 0;
+        for captures in self.regex()?.captures_iter(text) {
+            let m = captures.get(0).unwrap();
+            result.push_str(&text[last..m.start()]);
+            last = m.end();
+
+            // `captures` is handed over to the predicate, so expand first and
+            // take the replacement back if the match is to be kept.
+            let kept = result.len();
+            captures.expand(rep, &mut result);
+            let replace = predicate
+                .call1(&JsValue::NULL, &Match::captures(captures, names.clone())?)
+                .map_err(serde_wasm_bindgen::Error::from)?
+                .is_truthy();
+            if !replace {
+                result.truncate(kept);
+                result.push_str(m.as_str());
+            }
+        }
+        result.push_str(&text[last..]);
+
+        Ok(result)
+    }
+
+    /// Replaces every occurrence of the keys of `mapping` in `text` with the
+    /// corresponding value, in a single pass.
+    ///
+    /// The keys are matched literally, and when several keys match at the
+    /// same position the longest one wins, so `"category"` is preferred over
+    /// `"cat"`. Replacement values are inserted as they are, i.e., `$` has no
+    /// special meaning. Empty keys are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const result = RRegex.replaceMap("a cat category", { cat: "dog", category: "group" })
+    /// expect(result).toBe("a dog group")
+    /// ```
+    ///
+    /// @param {string} text - The string in which to replace the keys
+    /// @param {Record<string, string>} mapping - The replacement for each literal key
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc, js_name = replaceMap)]
+    pub fn replace_map(text: &str, mapping: JsValue) -> Result<String> {
+        let mapping: HashMap<String, String> = serde_wasm_bindgen::from_value(mapping)?;
+        let mut keys: Vec<&String> = mapping.keys().filter(|key| !key.is_empty()).collect();
+        if keys.is_empty() {
+            return Ok(text.to_owned());
+        }
+
+        // Leftmost-first semantics try the alternatives in order, so the
+        // longest keys have to come first.
+        keys.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        let pattern = keys
+            .iter()
+            .map(|key| regex::escape(key))
+            .collect::<Vec<_>>()
+            .join("|");
+        let regex = regex::Regex::new(&pattern).map_err(serde_wasm_bindgen::Error::new)?;
+
+        Ok(regex
+            .replace_all(text, |captures: &regex::Captures| {
+                mapping[&captures[0]].clone()
+            })
+            .into_owned())
+    }
+
+    /// Replaces the given byte ranges of `text` with the given replacements,
+    /// without running the regular expression again.
+    ///
+    /// `ranges` is a flat array of `[start0, end0, start1, end1, ...]` byte
+    /// offsets, like the one returned by `matchIndices`, and `replacements[i]`
+    /// replaces the i-th range. The replacements are inserted as is, so `$`
+    /// has no special meaning here.
+    ///
+    /// An error is thrown if the ranges aren't sorted and non-overlapping, if
+    /// any offset doesn't fall on a UTF-8 character boundary of `text`, or if
+    /// there isn't exactly one replacement per range.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("\\d+")
+    /// const text = "a1 b22"
+    /// const ranges = re.matchIndices(text)
+    /// expect(re.replaceByRanges(text, ranges, ["one", "two"])).toBe("aone btwo")
+    /// ```
+    ///
+    /// @param {string} text - The string to replace the ranges of
+    /// @param {Int32Array} ranges - The flat list of byte ranges to replace
+    /// @param {string[]} replacements - The replacement for each range
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc, js_name = replaceByRanges)]
+    pub fn replace_by_ranges(
+        &self,
+        text: &str,
+        ranges: &[i32],
+        replacements: Vec<String>,
+    ) -> Result<String> {
+        if !ranges.len().is_multiple_of(2) {
+            return Err(serde_wasm_bindgen::Error::new(
+                "ranges must contain pairs of start and end offsets",
+            ));
+        }
+        if ranges.len() / 2 != replacements.len() {
+            return Err(serde_wasm_bindgen::Error::new(format!(
+                "expected {} replacements but got {}",
+                ranges.len() / 2,
+                replacements.len()
+            )));
+        }
+
+        let mut result = String::with_capacity(text.len());
+        let mut last = 0;
+        for (range, replacement) in ranges.chunks(2).zip(replacements.iter()) {
+            let (start, end) = (range[0], range[1]);
+            if start < 0 || end < start || (start as usize) < last {
+                return Err(serde_wasm_bindgen::Error::new(format!(
+                    "range {}..{} is not sorted or overlaps the previous range",
+                    start, end
+                )));
+            }
+
+            let (start, end) = (start as usize, end as usize);
+            if !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+                return Err(serde_wasm_bindgen::Error::new(format!(
+                    "range {}..{} is not on a character boundary",
+                    start, end
+                )));
+            }
+
+            result.push_str(&text[last..start]);
+            result.push_str(replacement);
+            last = end;
+        }
+        result.push_str(&text[last..]);
+
+        Ok(result)
+    }
+
+    /// Wraps every non-overlapping match in `text` with `prefix` and
+    /// `suffix`, e.g., to render search results with `<mark>` tags.
+    ///
+    /// Unlike `replaceAll`, the delimiters are inserted as is, so any `$` or
+    /// `\` they contain is kept verbatim.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("\\d+")
+    /// expect(re.highlight("a1 b22", "<mark>", "</mark>")).toBe("a<mark>1</mark> b<mark>22</mark>")
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {string} prefix - The string to insert before every match
+    /// @param {string} suffix - The string to insert after every match
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn highlight(&self, text: &str, prefix: &str, suffix: &str) -> Result<String> {
+        let mut result = String::with_capacity(text.len());
+        let mut last = 0;
+        for m in self.find_substrings(text)? {
+            result.push_str(&text[last..m.start()]);
+            result.push_str(prefix);
+            result.push_str(m.as_str());
+            result.push_str(suffix);
+            last = m.end();
+        }
+        result.push_str(&text[last..]);
+
+        Ok(result)
+    }
+
+    /// Returns `text` with a match at its very start removed. If the regex
+    /// doesn't match at the start of `text`, then it's returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("[-_]+")
+    /// expect(re.trimStartMatches("--a-b--")).toBe("a-b--")
+    /// ```
+    ///
+    /// @param {string} text - The string to trim
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc, js_name = trimStartMatches)]
+    pub fn trim_start_matches(&self, text: &str) -> Result<String> {
+        Ok(text[self.trim_start(text)?..].to_owned())
+    }
+
+    /// Returns `text` with a match at its very end removed. If the regex
+    /// doesn't match at the end of `text`, then it's returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("[-_]+")
+    /// expect(re.trimEndMatches("--a-b--")).toBe("--a-b")
+    /// ```
+    ///
+    /// @param {string} text - The string to trim
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc, js_name = trimEndMatches)]
+    pub fn trim_end_matches(&self, text: &str) -> Result<String> {
+        Ok(text[..self.trim_end(text, 0)?].to_owned())
+    }
+
+    /// Returns `text` with the matches at its very start and end removed, as
+    /// if calling both `trimStartMatches` and `trimEndMatches`. Matches
+    /// anywhere else in `text` are kept.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("[-_]+")
+    /// expect(re.trimMatches("--a-b--")).toBe("a-b")
+    /// ```
+    ///
+    /// @param {string} text - The string to trim
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc, js_name = trimMatches)]
+    pub fn trim_matches(&self, text: &str) -> Result<String> {
+        let start = self.trim_start(text)?;
+        Ok(text[start..self.trim_end(text, start)?].to_owned())
+    }
+
+    /// Breaks `text` into an ordered list of segments that are either a match
+    /// of the regex or the text between two matches. Together, the segments
+    /// cover the entire input without gaps.
+    ///
+    /// Empty matches are included as matched segments whose `start` equals
+    /// their `end`, while the text between two matches is only included when
+    /// it isn't empty. The text after the last match is always included as
+    /// the last segment, even when it's empty.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const segments = new RRegex("\\d+").partition("a1b")
+    /// expect(segments).toEqual([
+    ///   { start: 0, end: 1, value: "a", matched: false },
+    ///   { start: 1, end: 2, value: "1", matched: true },
+    ///   { start: 2, end: 3, value: "b", matched: false },
+    /// ])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {Segment[]}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn partition(&self, text: &str) -> Result<JsValue> {
+        let mut segments: Vec<Segment> = Vec::new();
+        let mut last = 0;
+        for m in self.find_substrings(text)? {
+            if m.start() > last {
+                segments.push(Segment::new(text, last, m.start(), false));
+            }
+            segments.push(Segment::new(text, m.start(), m.end(), true));
+            last = m.end();
+        }
+        segments.push(Segment::new(text, last, text.len(), false));
+
+        serde_wasm_bindgen::to_value(&segments)
+    }
+
+    /// Returns an iterator of substrings of `text` delimited by a match of the
+    /// regular expression. Namely, each element of the iterator corresponds to
+    /// text that *isn't* matched by the regular expression.
+    ///
+    /// This method will *not* copy the text given.
+    ///
+    /// # Example
+    ///
+    /// To split a string delimited by arbitrary amounts of spaces or tabs:
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex(r"[ \\t]+")
+    /// const fields = re.split("a b \t  c\td    e")
+    /// expect(fields).toEqual(["a", "b", "c", "d", "e"])
+    /// ```
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.split
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {string[]}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn split(&self, text: &str) -> Result<Vec<String>> {
+        Ok(self.split_pieces(text)?.map(|s| s.to_string()).collect())
+    }
+
+    /// Splits `text` like `split` and returns the results of calling `f`
+    /// with every segment and its index, in order, without building the
+    /// array of segments first.
+    ///
+    /// If `f` throws, then the error is thrown from here.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const numbers = new RRegex(",").splitMap(" 1, 2 ,3", (segment) => Number(segment.trim()))
+    /// expect(numbers).toEqual([1, 2, 3])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {(segment: string, index: number) => T} f - Called with every segment
+    /// @returns {T[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = splitMap)]
+    pub fn split_map(&self, text: &str, f: &js_sys::Function) -> Result<js_sys::Array> {
+        let results = js_sys::Array::new();
+        for (index, segment) in self.split_pieces(text)?.enumerate() {
+            let result = f
+                .call2(&JsValue::NULL, &JsValue::from_str(segment), &JsValue::from(index))
+                .map_err(serde_wasm_bindgen::Error::from)?;
+            results.push(&result);
+        }
+
+        Ok(results)
+    }
+
+    /// Splits `text` like `split`, except every match is kept at the end of
+    /// the segment that precedes it, e.g., to split sentences while keeping
+    /// their punctuation.
+    ///
+    /// The text after the last match is the last segment, unless it's empty.
+    /// Empty segments, which only an empty match can produce, are omitted.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("[.!?]")
+    /// expect(re.splitInclusive("Hi! Bye.")).toEqual(["Hi!", " Bye."])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {string[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = splitInclusive)]
+    pub fn split_inclusive(&self, text: &str) -> Result<Vec<String>> {
+        let mut segments = Vec::new();
+        let mut last = 0;
+        for m in self.find_substrings(text)? {
+            if m.end() > last {
+                segments.push(text[last..m.end()].to_owned());
+                last = m.end();
+            }
+        }
+        if last < text.len() {
+            segments.push(text[last..].to_owned());
+        }
+
+        Ok(segments)
+    }
+
+    /// Splits `text` in two around the last non-overlapping match, the one
+    /// `findLast` returns. The match itself is not included.
+    ///
+    /// If there is no match, then `text` is returned as the only element.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("/")
+    /// expect(re.splitOnLast("a/b/c")).toEqual(["a/b", "c"])
+    /// expect(re.splitOnLast("abc")).toEqual(["abc"])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {string[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = splitOnLast)]
+    pub fn split_on_last(&self, text: &str) -> Result<Vec<String>> {
+        Ok(match self.find_substrings(text)?.last() {
+            Some(m) => vec![text[..m.start()].to_owned(), text[m.end()..].to_owned()],
+            None => vec![text.to_owned()],
+        })
+    }
+
+    /// Returns the same substrings as `split`, along with their byte offsets
+    /// in `text`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const pieces = new RRegex("[ \\t]+").splitIndices("a b \tc")
+    /// expect(pieces).toEqual([
+    ///   { start: 0, end: 1, value: "a" },
+    ///   { start: 2, end: 3, value: "b" },
+    ///   { start: 5, end: 6, value: "c" },
+    /// ])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {Match[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = splitIndices)]
+    pub fn split_indices(&self, text: &str) -> Result<JsValue> {
+        let mut pieces: Vec<Substring> = Vec::new();
+        let mut last = 0;
+        for m in self.find_substrings(text)? {
+            pieces.push(Substring::new(text, last, m.start()));
+            last = m.end();
+        }
+        pieces.push(Substring::new(text, last, text.len()));
+
+        serde_wasm_bindgen::to_value(&pieces)
+    }
+
+    /// Returns the byte offsets of the same substrings as `split`, without
+    /// copying any of them, so they can be sliced out of `text` on demand.
+    /// Like every other offset, these count bytes of the UTF-8 encoding of
+    /// `text`, which are the indices of a JS string only when it's ASCII.
+    ///
+    /// The array is `[0, m0.start, m0.end, m1.start, m1.end, ..., text.length]`
+    /// where `mi` is the `i`-th match, i.e., substring `i` of the split is
+    /// `offsets[2 * i]..offsets[2 * i + 1]`. The array always has an even
+    /// length, two more than twice the number of matches. Adjacent matches
+    /// appear as two equal offsets in a row, which is an empty substring, and
+    /// so does a match at the start or the end of `text`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const offsets = new RRegex(",").splitOffsets("a,,b")
+    /// expect(Array.from(offsets)).toEqual([0, 1, 2, 2, 3, 4])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {Uint32Array}
+    #[wasm_bindgen(skip_jsdoc, js_name = splitOffsets)]
+    pub fn split_offsets(&self, text: &str) -> Result<js_sys::Uint32Array> {
+        let mut offsets = vec![0];
+        for m in self.find_substrings(text)? {
+            offsets.push(m.start() as u32);
+            offsets.push(m.end() as u32);
+        }
+        offsets.push(text.len() as u32);
+
+        Ok(js_sys::Uint32Array::from(offsets.as_slice()))
+    }
+
+    /// Returns the same fields as `splitIndices`, flagging the ones that are
+    /// empty.
+    ///
+    /// Adjacent matches produce an empty field between them, and a match at
+    /// the start or the end of `text` produces an empty field there, so the
+    /// number of fields is always one more than the number of matches.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const fields = new RRegex(",").splitFields("a,,b")
+    /// expect(fields).toEqual([
+    ///   { value: "a", start: 0, end: 1, empty: false },
+    ///   { value: "", start: 2, end: 2, empty: true },
+    ///   { value: "b", start: 3, end: 4, empty: false },
+    /// ])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {Field[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = splitFields)]
+    pub fn split_fields(&self, text: &str) -> Result<JsValue> {
+        let mut fields: Vec<Field> = Vec::new();
+        let mut last = 0;
+        for m in self.find_substrings(text)? {
+            fields.push(Field::new(text, last, m.start()));
+            last = m.end();
+        }
+        fields.push(Field::new(text, last, text.len()));
+
+        serde_wasm_bindgen::to_value(&fields)
+    }
+
+    /// Returns an iterator of at most `limit` substrings of `text` delimited
+    /// by a match of the regular expression. (A `limit` of `0` will return no
+    /// substrings.) Namely, each element of the iterator corresponds to text
+    /// that *isn't* matched by the regular expression. The remainder of the
+    /// string that is not split will be the last element in the iterator.
+    ///
+    /// This method will *not* copy the text given.
+    ///
+    /// # Example
+    ///
+    /// Get the first two words in some text:
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex(r"\\W+")
+    /// const fields = re.splitn("Hey! How are you?", 3)
+    /// expect(fields).toEqual(["Hey", "How", "are you?"])
+    /// ```
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.splitn
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {number} limit - Max number result elements
+    /// @returns {string[]}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn splitn(&self, text: &str, limit: usize) -> Result<Vec<String>> {
+        Ok(self
+            .splitn_pieces(text, limit)?
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect())
+    }
+
+    /// Returns the same parts as `splitn`, and whether the split was
+    /// truncated by `limit`, i.e., whether the last part is a remainder that
+    /// still contains matches of the regular expression.
+    ///
+    /// A `limit` of `0` returns no parts, which is only truncated if `text`
+    /// is not empty.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex(",")
+    /// expect(re.splitnInfo("a,b,c", 2)).toEqual({ parts: ["a", "b,c"], truncated: true })
+    /// expect(re.splitnInfo("a,b,c", 3)).toEqual({ parts: ["a", "b", "c"], truncated: false })
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {number} limit - Max number result elements
+    /// @returns {SplitInfo}
+    #[wasm_bindgen(skip_jsdoc, js_name = splitnInfo)]
+    pub fn splitn_info(&self, text: &str, limit: usize) -> Result<JsValue> {
+        let parts = self.splitn_pieces(text, limit)?;
+        let truncated = if limit == 0 {
+            !text.is_empty()
+        } else {
+            self.split_pieces(text)?.nth(limit).is_some()
+        };
+
+        serde_wasm_bindgen::to_value(&SplitInfo { parts, truncated })
+    }
+
+    /// Returns at most `maxParts` of the substrings returned by `split`, and
+    /// whether any were left out.
+    ///
+    /// Unlike `splitn`, the text after the last part returned is discarded
+    /// instead of being returned as a trailing part, so the number of parts
+    /// never exceeds `maxParts` however many delimiters `text` contains. A
+    /// `maxParts` of `0` returns no parts, which is only truncated if `text`
+    /// is not empty.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex(",")
+    /// expect(re.splitBounded("a,b,c", 2)).toEqual({ parts: ["a", "b"], truncated: true })
+    /// expect(re.splitBounded("a,b,c", 3)).toEqual({ parts: ["a", "b", "c"], truncated: false })
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {number} maxParts - The maximum number of parts to return
+    /// @returns {SplitInfo}
+    #[wasm_bindgen(skip_jsdoc, js_name = splitBounded)]
+    pub fn split_bounded(&self, text: &str, max_parts: usize) -> Result<JsValue> {
+        let mut split = self.split_pieces(text)?;
+        let parts: Vec<&str> = split.by_ref().take(max_parts).collect();
+        let truncated = if max_parts == 0 {
+            !text.is_empty()
+        } else {
+            split.next().is_some()
+        };
+
+        serde_wasm_bindgen::to_value(&SplitInfo { parts, truncated })
+    }
+
+    /// Returns the end location of a match in the text given.
+    ///
+    /// This method may have the same performance characteristics as
+    /// `isMatch`, except it provides an end location for a match. In
+    /// particular, the location returned *may be shorter* than the proper end
+    /// of the leftmost-first match that you would find via `Regex.find`.
+    ///
+    /// Note that it is not guaranteed that this routine finds the shortest or
+    /// "earliest" possible match. Instead, the main idea of this API is that
+    /// it returns the offset at the point at which the internal regex engine
+    /// has determined that a match has occurred. This may vary depending on
+    /// which internal regex engine is used, and thus, the offset itself may
+    /// change.
+    ///
+    /// # Example
+    ///
+    /// Typically, `a+` would match the entire first sequence of `a` in some
+    /// text, but `shortest_match` can give up as soon as it sees the first
+    /// `a`.
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const text = "aaaaa"
+    /// const = new RRegex("a+").shortest_match(text)
+    /// expect(pos).toBe(1)
+    /// ```
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.shortest_match
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {number|undefined}
+    #[wasm_bindgen(skip_jsdoc, js_name = shortestMatch)]
+    pub fn shortest_match(&self, text: &str) -> Result<Option<usize>> {
+        Ok(self.regex()?.shortest_match(text))
+    }
+
+    /// Returns the same as `shortest_match`, but starts the search at the
+    /// given offset.
+    ///
+    /// The significance of the starting point is that it takes the surrounding
+    /// context into consideration. For example, the `\A` anchor can only match
+    /// when `start == 0`.
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.shortest_match_at
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {number} start - Zero-based index at which to start matching
+    /// @returns {number|undefined}
+    #[wasm_bindgen(skip_jsdoc, js_name = shortestMatchAt)]
+    pub fn shortest_match_at(&self, text: &str, start: usize) -> Result<Option<usize>> {
+        if text.len() < start {
+            Ok(None)
+        } else {
+            Ok(self.regex()?.shortest_match_at(text, start))
+        }
+    }
+
+    /// Returns the end location of a match in the text given, like
+    /// `shortestMatch`, along with a start location.
+    ///
+    /// The shortest match engine only reports where a match ends, so the
+    /// start returned here is the start of the leftmost-first match, as
+    /// returned by `find`. This costs a second search, and is an
+    /// approximation: the start is never after the end, but the match that
+    /// ends at `end` may begin later than `start`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const range = new RRegex("a+").shortestMatchRange("xaaaa")
+    /// expect(range).toEqual({ start: 1, end: 2 })
+    /// ```
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.Regex.html#method.shortest_match
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {ByteRange|undefined}
+    #[wasm_bindgen(skip_jsdoc, js_name = shortestMatchRange)]
+    pub fn shortest_match_range(&self, text: &str) -> Result<JsValue> {
+        let end = match self.regex()?.shortest_match(text) {
+            Some(end) => end,
+            None => return Ok(JsValue::UNDEFINED),
+        };
+        let start = self.regex()?.find(text).map_or(end, |m| m.start().min(end));
+
+        serde_wasm_bindgen::to_value(&ByteRange { start, end })
+    }
+
+    /// Returns the regular expression into a high level intermediate
+    /// representation.
+    pub fn syntax(&self) -> Result<JsValue> {
+        let hir = self.hir()?;
+
+        Hir::from(hir).try_into()
+    }
+
+    /// Returns the high level intermediate representation of the regular
+    /// expression as an indented, human-readable tree, which is handy to
+    /// paste in a bug report.
+    ///
+    /// This is the `Debug` formatting of the parsed `Hir`. Unlike `syntax`,
+    /// its exact output is not stable across versions, so it shouldn't be
+    /// parsed.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// console.log(new RRegex("a|b").syntaxDebug())
+    /// ```
+    ///
+    /// @see https://docs.rs/regex-syntax/latest/regex_syntax/hir/struct.Hir.html
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc, js_name = syntaxDebug)]
+    pub fn syntax_debug(&self) -> Result<String> {
+        Ok(format!("{:#?}", self.hir()?))
+    }
+
+    /// Returns the regular expression into a high level intermediate
+    /// representation, parsed with the given options.
+    ///
+    /// Flags like `caseInsensitive` change the character classes produced by
+    /// the parser, so the tree returned here reflects how the regex behaves
+    /// when compiled with the same options.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("a")
+    /// const hir = re.syntaxWithOptions({ caseInsensitive: true })
+    /// expect(hir.kind["@variant"]).toBe("Class")
+    /// ```
+    ///
+    /// @see https://docs.rs/regex-syntax/latest/regex_syntax/struct.ParserBuilder.html
+    /// @param {SyntaxOptions} options - The parser options
+    /// @returns {Hir}
+    #[wasm_bindgen(skip_jsdoc, js_name = syntaxWithOptions)]
+    pub fn syntax_with_options(&self, options: JsValue) -> Result<JsValue> {
+        let options: SyntaxOptions = serde_wasm_bindgen::from_value(options)?;
+        let hir = options
+            .parser()
+            .parse(self.regex()?.as_str())
+            .map_err(serde_wasm_bindgen::Error::new)?;
+
+        Hir::from(&hir).try_into()
+    }
+
+    /// Returns the abstract syntax tree of the regular expression.
+    ///
+    /// Unlike `syntax`, the AST preserves the concrete syntax of the
+    /// pattern, including the span of every node, so it can be used to map
+    /// pieces of the tree back to the original source. Comments written with
+    /// the `x` flag are not part of the tree, use `astWithComments` for them.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("a|b")
+    /// const ast = re.ast()
+    /// expect(ast["@variant"]).toBe("Alternation")
+    /// ```
+    ///
+    /// @see https://docs.rs/regex-syntax/latest/regex_syntax/ast/enum.Ast.html
+    /// @returns {Ast}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn ast(&self) -> Result<JsValue> {
+        let ast = self.parse_ast()?;
+
+        Ast::from(&ast).try_into()
+    }
+
+    /// Returns the abstract syntax tree of the regular expression like
+    /// `ast`, along with the comments written in the pattern with the `x`
+    /// flag and their spans, e.g., to show the documentation inlined in a
+    /// verbose pattern.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("(?x)\\d+ # digits\n[a-z] # letter")
+    /// const { comments } = re.astWithComments()
+    /// expect(comments.map((c) => c.comment)).toEqual([" digits", " letter"])
+    /// ```
+    ///
+    /// @see https://docs.rs/regex-syntax/latest/regex_syntax/ast/struct.WithComments.html
+    /// @returns {AstWithComments}
+    #[wasm_bindgen(skip_jsdoc, js_name = astWithComments)]
+    pub fn ast_with_comments(&self) -> Result<JsValue> {
+        let ast = self
+            .ast_parser()
+            .parse_with_comments(self.regex()?.as_str())
+            .map_err(serde_wasm_bindgen::Error::new)?;
+
+        Ast::from(&ast).try_into()
+    }
+
+    /// Returns true if every match of the regular expression is anchored to
+    /// the start of the text, e.g., `^abc` or `\Afoo|\Abar`.
+    ///
+    /// When this is true, a search only needs to look at the start of the
+    /// text to find a match.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// expect(new RRegex("^abc").isAnchoredStart()).toBe(true)
+    /// expect(new RRegex("(?m)^abc").isAnchoredStart()).toBe(false)
+    /// ```
+    ///
+    /// @see https://docs.rs/regex-syntax/latest/regex_syntax/hir/struct.Properties.html#method.look_set_prefix
+    /// @returns {boolean}
+    #[wasm_bindgen(skip_jsdoc, js_name = isAnchoredStart)]
+    pub fn is_anchored_start(&self) -> Result<bool> {
+        let hir = self.hir()?;
+        Ok(hir.properties().look_set_prefix().contains(hir::Look::Start))
+    }
+
+    /// Returns true if every match of the regular expression is anchored to
+    /// the end of the text, e.g., `abc$` or `foo\z|bar\z`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// expect(new RRegex("abc$").isAnchoredEnd()).toBe(true)
+    /// expect(new RRegex("abc").isAnchoredEnd()).toBe(false)
+    /// ```
+    ///
+    /// @see https://docs.rs/regex-syntax/latest/regex_syntax/hir/struct.Properties.html#method.look_set_suffix
+    /// @returns {boolean}
+    #[wasm_bindgen(skip_jsdoc, js_name = isAnchoredEnd)]
+    pub fn is_anchored_end(&self) -> Result<bool> {
+        let hir = self.hir()?;
+        Ok(hir.properties().look_set_suffix().contains(hir::Look::End))
+    }
+
+    /// Returns true if the regular expression is a plain literal, i.e., it
+    /// only ever matches one specific string and doesn't use any classes,
+    /// repetitions, alternations or assertions.
+    ///
+    /// A literal regular expression can be replaced with a simple substring
+    /// search.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// expect(new RRegex("foo").isLiteral()).toBe(true)
+    /// expect(new RRegex("fo+").isLiteral()).toBe(false)
+    /// ```
+    ///
+    /// @see https://docs.rs/regex-syntax/latest/regex_syntax/hir/struct.Properties.html#method.is_literal
+    /// @returns {boolean}
+    #[wasm_bindgen(skip_jsdoc, js_name = isLiteral)]
+    pub fn is_literal(&self) -> Result<bool> {
+        let hir = self.hir()?;
+        Ok(hir.properties().is_literal())
+    }
+
+    /// Returns the minimum number of bytes a match of the regular expression
+    /// can be, e.g., `3` for `a{3,}` and `1` for `a|bcd`. Texts shorter than
+    /// this can be rejected without running the regex.
+    ///
+    /// Returns `undefined` for a regular expression that can never match,
+    /// like `[a&&b]`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// expect(new RRegex("a{3,}").minMatchLength()).toBe(3)
+    /// expect(new RRegex("δ|bcd").minMatchLength()).toBe(2)
+    /// ```
+    ///
+    /// @see https://docs.rs/regex-syntax/latest/regex_syntax/hir/struct.Properties.html#method.minimum_len
+    /// @returns {number|undefined}
+    #[wasm_bindgen(skip_jsdoc, js_name = minMatchLength)]
+    pub fn min_match_length(&self) -> Result<Option<usize>> {
+        let hir = self.hir()?;
+        Ok(hir.properties().minimum_len())
+    }
+
+    /// Returns true if the regular expression contains an anchor that matches
+    /// at the start or the end of a line, i.e., a `^` or `$` in multi-line
+    /// mode, with or without CRLF mode.
+    ///
+    /// A pattern that doesn't use line anchors can be run on whole texts
+    /// and on individual lines alike, as long as it can't match a line break
+    /// either.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// expect(new RRegex("(?m)^a").usesLineAnchors()).toBe(true)
+    /// expect(new RRegex("^a").usesLineAnchors()).toBe(false)
+    /// ```
+    ///
+    /// @see https://docs.rs/regex-syntax/latest/regex_syntax/hir/struct.LookSet.html#method.contains_anchor_line
+    /// @returns {boolean}
+    #[wasm_bindgen(skip_jsdoc, js_name = usesLineAnchors)]
+    pub fn uses_line_anchors(&self) -> Result<bool> {
+        let hir = self.hir()?;
+        Ok(hir.properties().look_set().contains_anchor_line())
+    }
+
+    /// Returns true if the regular expression contains an anchor that only
+    /// matches at the start or the end of the text, i.e., `\A` and `\z`, or
+    /// a `^` or `$` outside of multi-line mode.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// expect(new RRegex("\\Aa").usesTextAnchors()).toBe(true)
+    /// expect(new RRegex("(?m)^a").usesTextAnchors()).toBe(false)
+    /// ```
+    ///
+    /// @see https://docs.rs/regex-syntax/latest/regex_syntax/hir/struct.LookSet.html#method.contains_anchor_haystack
+    /// @returns {boolean}
+    #[wasm_bindgen(skip_jsdoc, js_name = usesTextAnchors)]
+    pub fn uses_text_anchors(&self) -> Result<bool> {
+        let hir = self.hir()?;
+        Ok(hir.properties().look_set().contains_anchor_haystack())
+    }
+
+    /// Returns the Unicode properties the pattern refers to with `\p{...}`
+    /// or `\P{...}`, as written and without duplicates, in the order they
+    /// first appear, e.g., to know which Unicode tables have to be shipped.
+    ///
+    /// Properties are read from the syntax tree, since the HIR only keeps the
+    /// ranges they expand to. A property with a value is returned as
+    /// `name=value` and a one-letter property like `\pL` as its letter.
+    /// Classes like `\w` also depend on Unicode tables in Unicode mode, but
+    /// aren't properties, so they aren't listed.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("\\p{Greek}+[\\pL\\P{sc:Latin}]\\p{Greek}")
+    /// expect(re.unicodeProperties()).toEqual(["Greek", "L", "sc=Latin"])
+    /// ```
+    ///
+    /// @returns {string[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = unicodeProperties)]
+    pub fn unicode_properties(&self) -> Result<Vec<String>> {
+        let ast = self.parse_ast()?;
+        match ast::visit(&ast, UnicodeProperties::default()) {
+            Ok(properties) => Ok(properties),
+            Err(never) => match never {},
+        }
+    }
+
+    /// Returns the pattern in a canonical form, rendered from its syntax tree
+    /// like `hirToString` does, so that equivalent spellings of a pattern
+    /// collapse to the same string, e.g., to key a cache of compiled regexes.
+    ///
+    /// The normalization happens on the `Hir`, which means that:
+    ///
+    /// * Redundant syntax disappears: `[a-a]` and `(?:a)` both become `a`,
+    ///   and classes are sorted and merged.
+    /// * Inline flags and the options the regex was built with are applied
+    ///   and dropped, e.g., `(?i)a` becomes `[Aa]`.
+    /// * Classes like `\d` are expanded to their ranges, so the canonical form
+    ///   can be much longer than the pattern.
+    ///
+    /// Two patterns with the same canonical form match the same strings, but
+    /// the converse doesn't hold: `a|b` and `[ab]` are both `[ab]`, while
+    /// `a+` and `aa*` stay different. The line terminator and the leftmost
+    /// longest option aren't part of the canonical form.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// expect(new RRegex("(?:[a-a])").canonicalize()).toBe("a")
+    /// expect(new RRegex("a|b").canonicalize()).toBe(new RRegex("[ba]").canonicalize())
+    /// ```
+    ///
+    /// @see hirToString
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn canonicalize(&self) -> Result<String> {
+        Ok(self.hir()?.to_string())
+    }
+
+    /// Returns the name of the outermost `HirKind` of the regular expression,
+    /// i.e., the `@variant` at the root of `syntax()`, without serializing
+    /// the whole tree. It's one of `"Empty"`, `"Literal"`, `"Class"`,
+    /// `"Look"`, `"Repetition"`, `"Capture"`, `"Concat"` and `"Alternation"`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// expect(new RRegex("ab|cd").rootKind()).toBe("Alternation")
+    /// expect(new RRegex("a+b").rootKind()).toBe("Concat")
+    /// ```
+    ///
+    /// @see https://docs.rs/regex-syntax/latest/regex_syntax/hir/enum.HirKind.html
+    /// @returns {string}
+    #[wasm_bindgen(skip_jsdoc, js_name = rootKind)]
+    pub fn root_kind(&self) -> Result<String> {
+        let kind = match self.hir()?.kind() {
+            hir::HirKind::Empty => "Empty",
+            hir::HirKind::Literal(_) => "Literal",
+            hir::HirKind::Class(_) => "Class",
+            hir::HirKind::Look(_) => "Look",
+            hir::HirKind::Repetition(_) => "Repetition",
+            hir::HirKind::Capture(_) => "Capture",
+            hir::HirKind::Concat(_) => "Concat",
+            hir::HirKind::Alternation(_) => "Alternation",
+        };
+        Ok(kind.to_owned())
+    }
+
+    /// Returns the literal strings the regular expression can match, if it's
+    /// an alternation of plain literals like `foo|bar|baz`, or `undefined` if
+    /// it can match anything else, e.g., because of a repetition, an anchor
+    /// or a large class.
+    ///
+    /// Alternatives are returned in the order they're written, and capture
+    /// groups around literals are ignored. An alternation made only of single
+    /// characters like `a|b` is compiled to the class `[ab]`, so classes of up
+    /// to 16 characters are expanded into one alternative per character, in
+    /// ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// expect(new RRegex("foo|bar|baz").literalAlternatives()).toEqual(["foo", "bar", "baz"])
+    /// expect(new RRegex("foo|ba[a-z]").literalAlternatives()).toBe(undefined)
+    /// ```
+    ///
+    /// @returns {string[]|undefined}
+    #[wasm_bindgen(skip_jsdoc, js_name = literalAlternatives)]
+    pub fn literal_alternatives(&self) -> Result<JsValue> {
+        serde_wasm_bindgen::to_value(&literal_alternatives(self.hir()?))
+    }
+
+    /// Returns a rough estimate of how expensive the regular expression is,
+    /// by counting its repetitions and alternations and how deeply the
+    /// repetitions are nested.
+    ///
+    /// Matching never backtracks catastrophically, but large or nested
+    /// repetitions like `(a{100}){100}` make the compiled program big, so the
+    /// estimate can be used to flag patterns before compiling them with a
+    /// size limit. The counts are taken on the syntax tree, after
+    /// simplifications like turning `a|b` into the class `[ab]`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// expect(new RRegex("(ab|cd+)*x{3}").complexity()).toEqual({
+    ///   repetitions: 3,
+    ///   unboundedRepetitions: 2,
+    ///   alternations: 1,
+    ///   maxDepth: 2,
+    /// })
+    /// ```
+    ///
+    /// @returns {Complexity}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn complexity(&self) -> Result<JsValue> {
+        let mut complexity = Complexity::default();
+        count_complexity(self.hir()?, 0, &mut complexity);
+
+        serde_wasm_bindgen::to_value(&complexity)
+    }
+
+    /// Returns the approximate size in bytes of the compiled program of the
+    /// regular expression, e.g., to budget memory across many regexes.
+    ///
+    /// The `regex` crate doesn't report this directly, so it's measured as
+    /// the smallest `size_limit` the pattern still compiles with, found by
+    /// compiling it again a few times on the first call and cached after
+    /// that. It doesn't include the caches of the lazy DFA, which grow
+    /// during searches up to `dfa_size_limit`, and it can be `0` for tiny
+    /// patterns like a single literal.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const small = new RRegex("a").approximateSize()
+    /// expect(new RRegex("\\w{20}").approximateSize()).toBeGreaterThan(small)
+    /// ```
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.RegexBuilder.html#method.size_limit
+    /// @returns {number}
+    #[wasm_bindgen(skip_jsdoc, js_name = approximateSize)]
+    pub fn approximate_size(&self) -> Result<usize> {
+        if let Some(size) = self.size.get() {
+            return Ok(*size);
+        }
+
+        let pattern = self.regex()?.as_str();
+        let compiles = |limit: usize| self.flags.builder(pattern).size_limit(limit).build().is_ok();
+        // The regex was compiled with the default limit of 10 MiB, so the
+        // smallest limit that works is at most that.
+        let (mut low, mut high) = (0, 10 * (1 << 20));
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if compiles(middle) {
+                high = middle;
+            } else {
+                low = middle + 1;
+            }
+        }
+
+        Ok(*self.size.get_or_init(|| low))
+    }
+
+    /// Returns true if the regular expression can only ever match valid
+    /// UTF-8.
+    ///
+    /// Since a `RRegex` can only be compiled from a pattern that matches
+    /// valid UTF-8, this is always true for patterns that compile. Patterns
+    /// like `(?-u:\xFF)`, which could match arbitrary bytes, are rejected
+    /// by the constructor.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// expect(new RRegex("a.c").isAlwaysUtf8()).toBe(true)
+    /// ```
+    ///
+    /// @see https://docs.rs/regex-syntax/latest/regex_syntax/hir/struct.Properties.html#method.is_utf8
+    /// @returns {boolean}
+    #[wasm_bindgen(skip_jsdoc, js_name = isAlwaysUtf8)]
+    pub fn is_always_utf8(&self) -> Result<bool> {
+        let hir = self.hir()?;
+        Ok(hir.properties().is_utf8())
+    }
+
+    /// Returns the JSON representation of the regular expression, that is its
+    /// pattern and the flags it was compiled with. This is called by
+    /// `JSON.stringify`, and the result can be turned back into an equivalent
+    /// regex with `RRegex.fromJSON`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("a+")
+    /// expect(JSON.stringify(re)).toBe('{"source":"a+","flags":""}')
+    /// ```
+    ///
+    /// @returns {RRegexJSON}
+    #[wasm_bindgen(skip_jsdoc, js_name = toJSON)]
+    pub fn to_json(&self) -> Result<JsValue> {
+        let json = RRegexJson {
+            source: self.regex()?.as_str().to_owned(),
+            flags: self.flags.to_string(),
+            octal: self.flags.octal,
+            line_terminator: self.flags.line_terminator,
+            longest_match: self.flags.longest,
+        };
+        serde_wasm_bindgen::to_value(&json)
+    }
+
+    /// Compiles a regular expression from its JSON representation, as
+    /// returned by `toJSON`. The regex returned matches exactly like the one
+    /// the JSON was created from.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = RRegex.fromJSON({ source: "a+", flags: "i" })
+    /// expect(re.isMatch("AA")).toBe(true)
+    /// ```
+    ///
+    /// @param {RRegexJSON} json - The JSON representation of the regex
+    /// @returns {RRegex}
+    #[wasm_bindgen(skip_jsdoc, js_name = fromJSON)]
+    pub fn from_json(json: JsValue) -> Result<RRegex> {
+        let json: RRegexJson = serde_wasm_bindgen::from_value(json)?;
+        let mut flags = RegexFlags::parse(&json.flags)?;
+        flags.octal = json.octal;
+        flags.line_terminator = json.line_terminator;
+        flags.longest = json.longest_match;
+
+        RRegex::compile(&json.source, flags)
+    }
+
+    /// Returns a compact binary form of the regular expression, which can be
+    /// cached or sent elsewhere and turned back into an equivalent regex with
+    /// `RRegex.deserialize`.
+    ///
+    /// The compiled program itself can't be serialized, so the bytes only
+    /// hold the pattern and the options it was compiled with, and
+    /// `deserialize` compiles the pattern again.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const bytes = new RRegex("a+").serialize()
+    /// expect(RRegex.deserialize(bytes).isMatch("aa")).toBe(true)
+    /// ```
+    ///
+    /// @returns {Uint8Array}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn serialize(&self) -> Result<js_sys::Uint8Array> {
+        let source = self.regex()?.as_str().as_bytes();
+        let mut bytes = Vec::with_capacity(SERIALIZED_HEADER_LEN + source.len());
+        bytes.push(SERIALIZED_VERSION);
+        bytes.extend_from_slice(&self.flags.bits().to_le_bytes());
+        bytes.push(self.flags.line_terminator);
+        bytes.extend_from_slice(source);
+
+        Ok(js_sys::Uint8Array::from(bytes.as_slice()))
+    }
+
+    /// Compiles a regular expression from the bytes returned by `serialize`.
+    ///
+    /// An error is returned if the bytes weren't created by `serialize`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex, RRegexBuilder } from "rregex"
+    ///
+    /// const bytes = new RRegexBuilder("a+").caseInsensitive(true).build().serialize()
+    /// expect(RRegex.deserialize(bytes).isMatch("AA")).toBe(true)
+    /// ```
+    ///
+    /// @param {Uint8Array} bytes - The bytes returned by `serialize`
+    /// @returns {RRegex}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn deserialize(bytes: &[u8]) -> Result<RRegex> {
+        let invalid = || serde_wasm_bindgen::Error::new("invalid serialized regex");
+        if bytes.len() < SERIALIZED_HEADER_LEN || bytes[0] != SERIALIZED_VERSION {
+            return Err(invalid());
+        }
+        let flags = RegexFlags::from_bits(u16::from_le_bytes([bytes[1], bytes[2]]), bytes[3]);
+        let source =
+            std::str::from_utf8(&bytes[SERIALIZED_HEADER_LEN..]).map_err(|_| invalid())?;
+
+        RRegex::compile(source, flags)
+    }
+
+    /// The pattern of the regular expression, like `RegExp.prototype.source`.
+    ///
+    /// This is the same string returned by `toString`.
+    #[wasm_bindgen(getter)]
+    pub fn source(&self) -> Result<String> {
+        Ok(self.regex()?.as_str().to_owned())
+    }
+
+    /// Whether the regex was built with `longestMatch(true)`, in which case
+    /// the methods that report where it matches use leftmost-longest matches
+    /// instead of leftmost-first ones. See `RRegexBuilder.longestMatch` for
+    /// the exact list.
+    #[wasm_bindgen(getter, js_name = longestMatch)]
+    pub fn longest_match(&self) -> Result<bool> {
+        self.regex()?;
+        Ok(self.flags.longest)
+    }
+
+    /// The flags of the regular expression, like `RegExp.prototype.flags`.
+    ///
+    /// This includes both the flags the regex was compiled with and the
+    /// inline flags at the very start of its pattern, e.g. `(?i)`, as a
+    /// string of:
+    ///
+    /// - `i`: case insensitive
+    /// - `m`: multi-line mode, `^` and `$` match at line boundaries
+    /// - `s`: `.` matches `\n`
+    /// - `x`: whitespace and comments in the pattern are ignored
+    /// - `U`: the meaning of `x*` and `x*?` is swapped
+    /// - `R`: `\r\n` is treated as a single line terminator
+    /// - `-u`: Unicode support is disabled, e.g. `\w` only matches ASCII
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// expect(new RRegex("(?is)a.b").flags).toBe("is")
+    /// expect(new RRegex("a(?i)b").flags).toBe("")
+    /// ```
+    #[wasm_bindgen(getter)]
+    pub fn flags(&self) -> Result<String> {
+        let mut flags = self.flags;
+        if let Ok(ast) = self.ast_parser().parse(self.regex()?.as_str()) {
+            let leading = match &ast {
+                ast::Ast::Concat(concat) => concat.asts.as_slice(),
+                ast => std::slice::from_ref(ast),
+            };
+            for item in leading {
+                match item {
+                    ast::Ast::Flags(set) => flags.apply(&set.flags),
+                    _ => break,
+                }
+            }
+        }
+
+        Ok(flags.to_string())
+    }
+
+    /// Returns a string representing the regular expression
+    #[wasm_bindgen(js_name = toString)]
+    pub fn as_string(&self) -> Result<String> {
+        Ok(self.regex()?.as_str().to_owned())
+    }
+
+    /// Returns a copy of this regex that shares the compiled program, so the
+    /// pattern isn't parsed or compiled again.
+    ///
+    /// The copy is independent from the original: freeing one of them
+    /// doesn't affect the other.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("\\d+")
+    /// const copy = re.clone()
+    /// re.free()
+    /// expect(copy.isMatch("42")).toBe(true)
+    /// ```
+    ///
+    /// @returns {RRegex}
+    #[wasm_bindgen(skip_jsdoc, js_name = clone)]
+    pub fn duplicate(&self) -> RRegex {
+        self.clone()
+    }
+
+    /// Returns true if both regexes have the same source and were compiled
+    /// with the same options, i.e., if their `toJSON` representations are
+    /// equal.
+    ///
+    /// This compares the patterns as strings, it doesn't check whether they
+    /// match the same texts. For example, `a|b` and `b|a` are different.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// expect(new RRegex("a|b").equals(new RRegex("a|b"))).toBe(true)
+    /// expect(new RRegex("a|b").equals(new RRegex("b|a"))).toBe(false)
+    /// ```
+    ///
+    /// @param {RRegex} other - The regex to compare with
+    /// @returns {boolean}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn equals(&self, other: &RRegex) -> Result<bool> {
+        Ok(self.regex()?.as_str() == other.regex()?.as_str() && self.flags == other.flags)
+    }
+
+    /// Returns whether this regex and `other` may both match the same text,
+    /// e.g., to warn about ambiguous routing rules.
+    ///
+    /// This is a heuristic, not a proof. It returns true if one of `samples`
+    /// matches both regexes. When one of them only matches a single text,
+    /// because it's a literal anchored at both ends like `^/about$`, the
+    /// answer is exact: true if and only if the other regex matches that
+    /// text. Otherwise, false only means that no overlap was found, not that
+    /// the regexes are disjoint.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const users = new RRegex("^/users/\\w+$")
+    /// expect(users.mayOverlap(new RRegex("^/users/me$"), [])).toBe(true)
+    /// expect(users.mayOverlap(new RRegex("^/about$"), [])).toBe(false)
+    /// expect(users.mayOverlap(new RRegex("/\\w+/ann"), ["/users/ann"])).toBe(true)
+    /// ```
+    ///
+    /// @param {RRegex} other - The regex to compare with
+    /// @param {string[]} samples - Texts to try both regexes on
+    /// @returns {boolean}
+    #[wasm_bindgen(skip_jsdoc, js_name = mayOverlap)]
+    pub fn may_overlap(&self, other: &RRegex, samples: Vec<String>) -> Result<bool> {
+        for sample in &samples {
+            if self.is_match(sample)? && other.is_match(sample)? {
+                return Ok(true);
+            }
+        }
+
+        if let Some(literal) = anchored_literal(self.hir()?) {
+            return other.is_match(&literal);
+        }
+        if let Some(literal) = anchored_literal(other.hir()?) {
+            return self.is_match(&literal);
+        }
+
+        Ok(false)
+    }
+
+    /// Releases the compiled regular expression and everything derived from
+    /// it, while keeping the JS object alive.
+    ///
+    /// Unlike calling a method after `free`, which fails with an opaque
+    /// error, calling a method after `dispose` throws an error saying
+    /// "regex has been disposed". Disposing twice is allowed. Clones made
+    /// before disposing, e.g., with `clone`, are left untouched.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegex } from "rregex"
+    ///
+    /// const re = new RRegex("a+")
+    /// re.dispose()
+    /// expect(re.isDisposed()).toBe(true)
+    /// expect(() => re.isMatch("a")).toThrow("regex has been disposed")
+    /// ```
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn dispose(&mut self) {
+        self.regex = None;
+        self.hir = OnceCell::new();
+        self.full = OnceCell::new();
+        self.suffix = OnceCell::new();
+        self.longest = OnceCell::new();
+        self.literal = OnceCell::new();
+        self.required = OnceCell::new();
+        self.size = OnceCell::new();
+    }
+
+    /// Returns true if `dispose` was called on this regex.
+    ///
+    /// @returns {boolean}
+    #[wasm_bindgen(skip_jsdoc, js_name = isDisposed)]
+    pub fn is_disposed(&self) -> bool {
+        self.regex.is_none()
+    }
+}
+
+/// Returns the literals that any string matched by `hir` must contain.
+fn required_literals(hir: &hir::Hir) -> Vec<String> {
+    match hir.kind() {
+        hir::HirKind::Literal(literal) => std::str::from_utf8(&literal.0)
+            .map(|literal| vec![literal.to_owned()])
+            .unwrap_or_default(),
+        hir::HirKind::Capture(capture) => required_literals(&capture.sub),
+        hir::HirKind::Repetition(repetition) if repetition.min > 0 => {
+            required_literals(&repetition.sub)
+        }
+        hir::HirKind::Concat(subs) => subs.iter().flat_map(required_literals).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the nodes of the outermost capture groups in `hir` that
+/// participated in the match of `captures`, with their nested groups.
+fn capture_nodes<'r>(
+    hir: &hir::Hir,
+    captures: &regex::Captures,
+    names: &[Option<&'r str>],
+) -> Vec<CaptureNode<'r>> {
+    match hir.kind() {
+        hir::HirKind::Capture(capture) => {
+            let index = capture.index as usize;
+            match captures.get(index) {
+                Some(m) => vec![CaptureNode {
+                    index,
+                    name: names[index],
+                    start: m.start(),
+                    end: m.end(),
+                    children: capture_nodes(&capture.sub, captures, names),
+                }],
+                None => Vec::new(),
+            }
+        }
+        hir::HirKind::Repetition(repetition) => capture_nodes(&repetition.sub, captures, names),
+        hir::HirKind::Concat(subs) | hir::HirKind::Alternation(subs) => subs
+            .iter()
+            .flat_map(|sub| capture_nodes(sub, captures, names))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the strings `hir` can match if it's an alternation of literals,
+/// where each branch is a literal, a small class or a concatenation of
+/// literals.
+fn literal_alternatives(hir: &hir::Hir) -> Option<Vec<String>> {
+    match hir.kind() {
+        hir::HirKind::Alternation(subs) => {
+            let mut alternatives = Vec::new();
+            for sub in subs {
+                alternatives.extend(literal_alternatives(sub)?);
+            }
+            Some(alternatives)
+        }
+        hir::HirKind::Capture(capture) => literal_alternatives(&capture.sub),
+        hir::HirKind::Class(hir::Class::Unicode(class)) => {
+            let len: u32 = class
+                .ranges()
+                .iter()
+                .map(|range| u32::from(range.end()) - u32::from(range.start()) + 1)
+                .sum();
+            if len > 16 {
+                return None;
+            }
+            Some(
+                class
+                    .ranges()
+                    .iter()
+                    .flat_map(|range| range.start()..=range.end())
+                    .map(String::from)
+                    .collect(),
+            )
+        }
+        _ => literal_string(hir).map(|literal| vec![literal]),
+    }
+}
+
+/// How `capturesTyped` coerces the value of a group.
+#[derive(Clone, Copy)]
+enum CaptureKind {
+    String,
+    Number,
+    Boolean,
+}
+
+impl CaptureKind {
+    fn parse(kind: &str) -> Result<CaptureKind> {
+        match kind {
+            "string" => Ok(CaptureKind::String),
+            "number" => Ok(CaptureKind::Number),
+            "boolean" => Ok(CaptureKind::Boolean),
+            _ => Err(serde_wasm_bindgen::Error::new(format!(
+                "invalid capture kind \"{}\", expected \"string\", \"number\" or \"boolean\"",
+                kind
+            ))),
+        }
+    }
+
+    fn coerce(self, value: &str) -> JsValue {
+        match self {
+            CaptureKind::String => JsValue::from_str(value),
+            CaptureKind::Number => value.parse::<f64>().map_or(JsValue::NULL, JsValue::from),
+            CaptureKind::Boolean => value.parse::<bool>().map_or(JsValue::NULL, JsValue::from),
+        }
+    }
+}
+
+/// Collects the names of the Unicode classes of an AST, for
+/// `unicodeProperties`.
+#[derive(Default)]
+struct UnicodeProperties(Vec<String>);
+
+impl UnicodeProperties {
+    fn add(&mut self, class: &ast::ClassUnicode) {
+        let name = match &class.kind {
+            ast::ClassUnicodeKind::OneLetter(letter) => letter.to_string(),
+            ast::ClassUnicodeKind::Named(name) => name.clone(),
+            ast::ClassUnicodeKind::NamedValue { name, value, .. } => format!("{}={}", name, value),
+        };
+        if !self.0.contains(&name) {
+            self.0.push(name);
+        }
+    }
+}
+
+impl ast::Visitor for UnicodeProperties {
+    type Output = Vec<String>;
+    type Err = std::convert::Infallible;
+
+    fn finish(self) -> std::result::Result<Self::Output, Self::Err> {
+        Ok(self.0)
+    }
+
+    fn visit_pre(&mut self, ast: &ast::Ast) -> std::result::Result<(), Self::Err> {
+        if let ast::Ast::ClassUnicode(class) = ast {
+            self.add(class);
+        }
+        Ok(())
+    }
+
+    fn visit_class_set_item_pre(
+        &mut self,
+        item: &ast::ClassSetItem,
+    ) -> std::result::Result<(), Self::Err> {
+        if let ast::ClassSetItem::Unicode(class) = item {
+            self.add(class);
+        }
+        Ok(())
+    }
+}
+
+/// Returns the only text `hir` matches, if it's made of literals between a
+/// `\A` and a `\z`.
+fn anchored_literal(hir: &hir::Hir) -> Option<String> {
+    let subs = match hir.kind() {
+        hir::HirKind::Concat(subs) if subs.len() >= 2 => subs,
+        _ => return None,
+    };
+    let is_look = |hir: &hir::Hir, look: hir::Look| {
+        matches!(hir.kind(), hir::HirKind::Look(l) if *l == look)
+    };
+    if !is_look(&subs[0], hir::Look::Start) || !is_look(&subs[subs.len() - 1], hir::Look::End) {
+        return None;
+    }
+
+    subs[1..subs.len() - 1].iter().map(literal_string).collect()
+}
+
+/// Returns the only string `hir` can match, if it's made of literals.
+fn literal_string(hir: &hir::Hir) -> Option<String> {
+    match hir.kind() {
+        hir::HirKind::Empty => Some(String::new()),
+        hir::HirKind::Literal(literal) => String::from_utf8(literal.0.to_vec()).ok(),
+        hir::HirKind::Capture(capture) => literal_string(&capture.sub),
+        hir::HirKind::Concat(subs) => subs.iter().map(literal_string).collect(),
+        _ => None,
+    }
+}
+
+/// Adds the repetitions and alternations of `hir` to `complexity`, where
+/// `depth` is the number of repetitions `hir` is nested in.
+fn count_complexity(hir: &hir::Hir, depth: usize, complexity: &mut Complexity) {
+    match hir.kind() {
+        hir::HirKind::Repetition(repetition) => {
+            complexity.repetitions += 1;
+            if repetition.max.is_none() {
+                complexity.unbounded_repetitions += 1;
+            }
+            complexity.max_depth = complexity.max_depth.max(depth + 1);
+            count_complexity(&repetition.sub, depth + 1, complexity);
+        }
+        hir::HirKind::Capture(capture) => count_complexity(&capture.sub, depth, complexity),
+        hir::HirKind::Concat(subs) => {
+            for sub in subs {
+                count_complexity(sub, depth, complexity);
+            }
+        }
+        hir::HirKind::Alternation(subs) => {
+            complexity.alternations += 1;
+            for sub in subs {
+                count_complexity(sub, depth, complexity);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The version of the format written by `serialize`, bumped whenever the
+/// layout changes.
+const SERIALIZED_VERSION: u8 = 1;
+
+/// The version byte, the flag bits and the line terminator.
+const SERIALIZED_HEADER_LEN: usize = 4;
+
+impl From<regex::Regex> for RRegex {
+    fn from(regex: regex::Regex) -> Self {
+        RRegex::with_flags(regex, RegexFlags::default())
+    }
+}
+
+impl RRegex {
+    /// Compiles `pattern` with the given flags.
+    pub(crate) fn compile(pattern: &str, flags: RegexFlags) -> Result<RRegex> {
+        let regex = flags
+            .builder(pattern)
+            .build()
+            .map_err(serde_wasm_bindgen::Error::new)?;
+
+        Ok(RRegex::with_flags(regex, flags))
+    }
+
+    fn with_flags(regex: regex::Regex, flags: RegexFlags) -> RRegex {
+        RRegex {
+            regex: Some(regex),
+            flags,
+            hir: OnceCell::new(),
+            full: OnceCell::new(),
+            suffix: OnceCell::new(),
+            longest: OnceCell::new(),
+            literal: OnceCell::new(),
+            required: OnceCell::new(),
+            size: OnceCell::new(),
+        }
+    }
+
+    /// Returns the compiled regex, or an error if it was disposed.
+    pub(crate) fn regex(&self) -> Result<&regex::Regex> {
+        self.regex
+            .as_ref()
+            .ok_or_else(|| serde_wasm_bindgen::Error::new("regex has been disposed"))
+    }
+
+    /// Returns the end of the match at the start of `text`, or `0`.
+    fn trim_start(&self, text: &str) -> Result<usize> {
+        Ok(match self.find_substring_at(text, 0)? {
+            Some(m) if m.start() == 0 => m.end(),
+            _ => 0,
+        })
+    }
+
+    /// Returns the start of the last match that ends at the end of `text`
+    /// and doesn't start before `start`, or the length of `text`.
+    fn trim_end(&self, text: &str, start: usize) -> Result<usize> {
+        Ok(match self.find_substrings(text)?.last() {
+            Some(m) if m.end() == text.len() && m.start() >= start => m.start(),
+            _ => text.len(),
+        })
+    }
+
+    fn parse_ast(&self) -> Result<ast::Ast> {
+        self.ast_parser()
+            .parse(self.regex()?.as_str())
+            .map_err(serde_wasm_bindgen::Error::new)
+    }
+
+    fn ast_parser(&self) -> ast::parse::Parser {
+        ast::parse::ParserBuilder::new()
+            .ignore_whitespace(self.flags.ignore_whitespace)
+            .octal(self.flags.octal)
+            .build()
+    }
+
+    /// Returns the HIR of this regex, parsing it the first time it's needed.
+    /// The pattern can't change once compiled, so it's safe to reuse.
+    fn hir(&self) -> Result<&hir::Hir> {
+        if let Some(hir) = self.hir.get() {
+            return Ok(hir);
+        }
+
+        let hir = self
+            .flags
+            .parser()
+            .parse(self.regex()?.as_str())
+            .map_err(serde_wasm_bindgen::Error::new)?;
+
+        Ok(self.hir.get_or_init(|| hir))
+    }
+
+    /// Checks that every capture group referenced by the replacement `rep`
+    /// exists, following the same rules as `regex::Captures::expand`.
+    fn check_replacement(&self, rep: &str) -> Result<()> {
+        let mut rest = rep;
+        while let Some(position) = rest.find('$') {
+            rest = &rest[position + 1..];
+            let name = if let Some(braced) = rest.strip_prefix('{') {
+                match braced.find('}') {
+                    Some(end) => {
+                        rest = &braced[end + 1..];
+                        &braced[..end]
+                    }
+                    None => continue,
+                }
+            } else {
+                let end = rest
+                    .find(|c: char| !(c == '_' || c.is_ascii_alphanumeric()))
+                    .unwrap_or(rest.len());
+                if end == 0 {
+                    // `$$` is an escaped `$`, and a `$` alone is literal.
+                    rest = rest.strip_prefix('$').unwrap_or(rest);
+                    continue;
+                }
+                let name = &rest[..end];
+                rest = &rest[end..];
+                name
+            };
+
+            let exists = match name.parse::<usize>() {
+                Ok(index) => index < self.regex()?.captures_len(),
+                Err(_) => name.is_empty() || self.capture_name_to_index(name)?.is_some(),
+            };
+            if !exists {
+                return Err(serde_wasm_bindgen::Error::new(format!(
+                    "replacement refers to capture group {} which does not exist",
+                    name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Appends the replacement `rep` for a match to `dst`, following the
+    /// rules of JavaScript's `GetSubstitution`.
+    fn expand_js(
+        &self,
+        captures: &regex::Captures,
+        text: &str,
+        rep: &str,
+        dst: &mut String,
+    ) -> Result<()> {
+        let regex = self.regex()?;
+        let groups = regex.captures_len() - 1;
+        let has_names = regex.capture_names().any(|name| name.is_some());
+        let group = |index: usize| captures.get(index).map_or("", |m| m.as_str());
+        let m = captures.get(0).unwrap();
+
+        let mut rest = rep;
+        while let Some(position) = rest.find('$') {
+            dst.push_str(&rest[..position]);
+            rest = &rest[position + 1..];
+
+            let bytes = rest.as_bytes();
+            let digit = |i: usize| {
+                bytes
+                    .get(i)
+                    .filter(|b| b.is_ascii_digit())
+                    .map(|b| usize::from(b - b'0'))
+            };
+            let consumed = match bytes.first() {
+                Some(b'$') => {
+                    dst.push('$');
+                    1
+                }
+                Some(b'&') => {
+                    dst.push_str(m.as_str());
+                    1
+                }
+                Some(b'`') => {
+                    dst.push_str(&text[..m.start()]);
+                    1
+                }
+                Some(b'\'') => {
+                    dst.push_str(&text[m.end()..]);
+                    1
+                }
+                Some(b'<') if has_names => match rest.find('>') {
+                    Some(end) => {
+                        let value = self
+                            .capture_name_to_index(&rest[1..end])?
+                            .map_or("", group);
+                        dst.push_str(value);
+                        end + 1
+                    }
+                    None => {
+                        dst.push('$');
+                        0
+                    }
+                },
+                _ => match (digit(0), digit(1)) {
+                    (Some(tens), Some(ones)) if (1..=groups).contains(&(tens * 10 + ones)) => {
+                        dst.push_str(group(tens * 10 + ones));
+                        2
+                    }
+                    (Some(index), _) if (1..=groups).contains(&index) => {
+                        dst.push_str(group(index));
+                        1
+                    }
+                    _ => {
+                        dst.push('$');
+                        0
+                    }
+                },
+            };
+            rest = &rest[consumed..];
+        }
+        dst.push_str(rest);
+
+        Ok(())
+    }
+
+    /// Builds a result shaped like a native `RegExpMatchArray` from the
+    /// substrings of the capture groups of a match that starts `index` UTF-16
+    /// code units into `input`.
+    pub(crate) fn match_array<'t>(
+        &self,
+        groups: impl Iterator<Item = Option<&'t str>>,
+        index: usize,
+        input: &JsValue,
+    ) -> Result<js_sys::Array> {
+        let array: js_sys::Array = groups
+            .map(|group| group.map_or(JsValue::UNDEFINED, JsValue::from))
+            .collect();
+
+        let mut groups = JsValue::UNDEFINED;
+        for (index, name) in self.regex()?.capture_names().enumerate() {
+            if let Some(name) = name {
+                if groups.is_undefined() {
+                    let object = js_sys::Object::new();
+                    js_sys::Reflect::set_prototype_of(&object, &JsValue::NULL)?;
+                    groups = object.into();
+                }
+                js_sys::Reflect::set(&groups, &JsValue::from(name), &array.get(index as u32))?;
+            }
+        }
+
+        js_sys::Reflect::set(&array, &JsValue::from("index"), &JsValue::from(index))?;
+        js_sys::Reflect::set(&array, &JsValue::from("input"), input)?;
+        js_sys::Reflect::set(&array, &JsValue::from("groups"), &groups)?;
+        Ok(array)
+    }
+
+    /// Collects the match of capture group `group` from every match in
+    /// `text`, skipping the ones where it didn't participate.
+    fn group_matches<'t>(&self, text: &'t str, group: usize) -> Result<Vec<Match<'t>>> {
+        Ok(self
+            .regex()?
+            .captures_iter(text)
+            .filter_map(|captures| captures.get(group))
+            .map(Match::from)
+            .collect())
+    }
+
+    /// Returns the string this regex matches if it's a plain literal,
+    /// extracting it from the HIR the first time it's needed.
+    fn literal(&self) -> Option<&str> {
+        self.literal
+            .get_or_init(|| match self.hir().map(hir::Hir::kind) {
+                Ok(hir::HirKind::Literal(literal)) => {
+                    String::from_utf8(literal.0.to_vec()).ok()
+                }
+                _ => None,
+            })
+            .as_deref()
+    }
+
+    /// Returns the literals every match of this regex contains, extracting
+    /// them from the HIR the first time they're needed.
+    fn required_literals(&self) -> &[String] {
+        self.required
+            .get_or_init(|| match self.hir() {
+                Ok(hir) => required_literals(hir),
+                Err(_) => Vec::new(),
+            })
+            .as_slice()
+    }
+
+    /// Returns this regex anchored at both ends, compiling it the first
+    /// time it's needed. The anchors are added to the HIR rather than to the
+    /// pattern string, so that flags and comments in the pattern can't
+    /// affect them.
+    fn full(&self) -> Result<&regex::Regex> {
+        if let Some(full) = self.full.get() {
+            return Ok(full);
+        }
+
+        let full = self.compile_hir(hir::Hir::concat(vec![
+            hir::Hir::look(hir::Look::Start),
+            self.hir()?.clone(),
+            hir::Hir::look(hir::Look::End),
+        ]))?;
+
+        Ok(self.full.get_or_init(|| full))
+    }
+
+    /// Returns this regex anchored at the end of the text, compiling it the
+    /// first time it's needed, in the same way as `full`.
+    fn suffix(&self) -> Result<&regex::Regex> {
+        if let Some(suffix) = self.suffix.get() {
+            return Ok(suffix);
+        }
+
+        let suffix = self.compile_hir(hir::Hir::concat(vec![
+            self.hir()?.clone(),
+            hir::Hir::look(hir::Look::End),
+        ]))?;
+
+        Ok(self.suffix.get_or_init(|| suffix))
+    }
+
+    /// Compiles a regex derived from the HIR of this one. The HIR already
+    /// reflects every flag, except for the line terminator.
+    fn compile_hir(&self, hir: hir::Hir) -> Result<regex::Regex> {
+        regex::RegexBuilder::new(&hir.to_string())
+            .line_terminator(self.flags.line_terminator)
+            .build()
+            .map_err(serde_wasm_bindgen::Error::new)
+    }
+
+    /// Returns a PikeVM that reports every match of this regex, building it
+    /// the first time it's needed. Searching it anchored at the start of a
+    /// leftmost-first match finds the longest match at that position.
+    fn longest(&self) -> Result<&PikeVM> {
+        if let Some(longest) = self.longest.get() {
+            return Ok(longest);
+        }
+
+        let mut look_matcher = regex_automata::util::look::LookMatcher::new();
+        look_matcher.set_line_terminator(self.flags.line_terminator);
+        let nfa = thompson::Compiler::new()
+            .configure(thompson::Config::new().look_matcher(look_matcher))
+            .build_from_hir(self.hir()?)
+            .map_err(serde_wasm_bindgen::Error::new)?;
+        let longest = PikeVM::builder()
+            .configure(PikeVM::config().match_kind(regex_automata::MatchKind::All))
+            .build_from_nfa(nfa)
+            .map_err(serde_wasm_bindgen::Error::new)?;
+
+        Ok(self.longest.get_or_init(|| longest))
+    }
+
+    /// Replaces every successive non-overlapping match in `text` with the
+    /// value `f` returns for its captures, which must be a string.
+    fn replace_with<'t>(
+        &self,
+        text: &'t str,
+        mut f: impl FnMut(regex::Captures<'t>) -> Result<JsValue>,
+    ) -> Result<String> {
+        let mut result = String::with_capacity(text.len());
+        let mut last = 0;
+        for captures in self.regex()?.captures_iter(text) {
+            let m = captures.get(0).unwrap();
+            result.push_str(&text[last..m.start()]);
+            last = m.end();
+
+            let replacement = f(captures)?.as_string().ok_or_else(|| {
+                serde_wasm_bindgen::Error::new("replacement function must return a string")
+            })?;
+            result.push_str(&replacement);
+        }
+        result.push_str(&text[last..]);
+
+        Ok(result)
+    }
+
+    /// Returns the first match at or after `start`, which is the
+    /// leftmost-longest one if `longestMatch` is enabled and the
+    /// leftmost-first one otherwise. `start` must not be past the end of
+    /// `text`.
+    pub(crate) fn find_substring_at<'t>(
+        &self,
+        text: &'t str,
+        start: usize,
+    ) -> Result<Option<Substring<'t>>> {
+        if self.flags.longest {
+            let longest = self.longest()?;
+            let mut cache = longest.create_cache();
+            let bounds = find_longest_at(self.regex()?, longest, &mut cache, text, start);
+            return Ok(bounds.map(|(start, end)| Substring::new(text, start, end)));
+        }
+
+        let m = self.regex()?.find_at(text, start);
+        Ok(m.map(|m| Substring::new(text, m.start(), m.end())))
+    }
+
+    /// Returns an iterator over the successive non-overlapping matches in
+    /// `text`, with the same semantics as `find_substring_at`. Each match is
+    /// only searched for when the iterator is advanced.
+    fn find_substrings<'r, 't>(&'r self, text: &'t str) -> Result<Substrings<'r, 't>> {
+        if self.flags.longest {
+            let longest = self.longest()?;
+            return Ok(Substrings::Longest {
+                regex: self.regex()?,
+                longest,
+                cache: longest.create_cache(),
+                text,
+                start: 0,
+                last_end: None,
+            });
+        }
+
+        Ok(Substrings::First(text, self.regex()?.find_iter(text)))
+    }
+
+    /// Returns an iterator over the pieces of `text` between the matches of
+    /// `find_substrings`, like `regex::Regex::split`.
+    fn split_pieces<'r, 't>(&'r self, text: &'t str) -> Result<Split<'r, 't>> {
+        Ok(Split {
+            matches: self.find_substrings(text)?,
+            text,
+            last: Some(0),
+        })
+    }
+
+    /// Returns at most `limit` pieces of `text`, like
+    /// `regex::Regex::splitn`: the last one holds the rest of `text`.
+    fn splitn_pieces<'t>(&self, text: &'t str, limit: usize) -> Result<Vec<&'t str>> {
+        let mut pieces = Vec::new();
+        if limit == 0 {
+            return Ok(pieces);
+        }
+
+        let mut last = 0;
+        let mut matches = self.find_substrings(text)?;
+        while pieces.len() + 1 < limit {
+            match matches.next() {
+                Some(m) => {
+                    pieces.push(&text[last..m.start()]);
+                    last = m.end();
+                }
+                None => break,
+            }
+        }
+        pieces.push(&text[last..]);
+        Ok(pieces)
+    }
+
+    /// Returns every capture group of the first match at or after `start`,
+    /// with the same semantics as `find_substring_at`, or `None` for the
+    /// groups that didn't participate.
+    pub(crate) fn capture_substrings_at<'t>(
+        &self,
+        text: &'t str,
+        start: usize,
+    ) -> Result<Option<Vec<Option<Substring<'t>>>>> {
+        if self.flags.longest {
+            let m = match self.regex()?.find_at(text, start) {
+                Some(m) => m,
+                None => return Ok(None),
+            };
+            let longest = self.longest()?;
+            let mut cache = longest.create_cache();
+            let mut captures = longest.create_captures();
+            let input = regex_automata::Input::new(text)
+                .range(m.start()..)
+                .anchored(regex_automata::Anchored::Yes);
+            longest.captures(&mut cache, input, &mut captures);
+            let spans = captures
+                .iter()
+                .map(|span| span.map(|span| Substring::new(text, span.start, span.end)))
+                .collect();
+            return Ok(Some(spans));
+        }
+
+        Ok(self.regex()?.captures_at(text, start).map(|captures| {
+            captures
+                .iter()
+                .map(|m| m.map(|m| Substring::new(text, m.start(), m.end())))
+                .collect()
+        }))
+    }
+}
+
+/// Returns the bounds of the longest match that starts where the
+/// leftmost-first match of `regex` at `start` does. Both semantics agree on
+/// where the leftmost match starts, they only differ on where it ends.
+fn find_longest_at(
+    regex: &regex::Regex,
+    longest: &PikeVM,
+    cache: &mut thompson::pikevm::Cache,
+    text: &str,
+    start: usize,
+) -> Option<(usize, usize)> {
+    let m = regex.find_at(text, start)?;
+    let input = regex_automata::Input::new(text)
+        .range(m.start()..)
+        .anchored(regex_automata::Anchored::Yes);
+    let end = longest
+        .find(cache, input)
+        .map_or(m.end(), |longest| longest.end().max(m.end()));
+
+    Some((m.start(), end))
+}
+
+/// The successive non-overlapping matches of a regex in a text, searched
+/// one at a time, with leftmost-first or leftmost-longest semantics.
+enum Substrings<'r, 't> {
+    First(&'t str, regex::Matches<'r, 't>),
+    Longest {
+        regex: &'r regex::Regex,
+        longest: &'r PikeVM,
+        cache: thompson::pikevm::Cache,
+        text: &'t str,
+        start: usize,
+        last_end: Option<usize>,
+    },
+}
+
+impl<'r, 't> Iterator for Substrings<'r, 't> {
+    type Item = Substring<'t>;
+
+    fn next(&mut self) -> Option<Substring<'t>> {
+        let (regex, longest, cache, text, start, last_end) = match self {
+            Substrings::First(text, matches) => {
+                return matches.next().map(|m| Substring::new(text, m.start(), m.end()));
+            }
+            Substrings::Longest {
+                regex,
+                longest,
+                cache,
+                text,
+                start,
+                last_end,
+            } => (regex, longest, cache, text, start, last_end),
+        };
+
+        // Like `find_iter`, an empty match right after the previous match
+        // is skipped.
+        while *start <= text.len() {
+            let (m_start, m_end) = find_longest_at(regex, longest, cache, text, *start)?;
+            *start = match text[m_end..].chars().next() {
+                Some(c) if m_start == m_end => m_end + c.len_utf8(),
+                None if m_start == m_end => m_end + 1,
+                _ => m_end,
+            };
+            if m_start != m_end || *last_end != Some(m_end) {
+                *last_end = Some(m_end);
+                return Some(Substring::new(text, m_start, m_end));
+            }
+        }
+        None
+    }
+}
+
+/// The pieces of a text between the matches of a `Substrings`.
+struct Split<'r, 't> {
+    matches: Substrings<'r, 't>,
+    text: &'t str,
+    last: Option<usize>,
+}
+
+impl<'r, 't> Iterator for Split<'r, 't> {
+    type Item = &'t str;
+
+    fn next(&mut self) -> Option<&'t str> {
+        let last = self.last?;
+        match self.matches.next() {
+            Some(m) => {
+                self.last = Some(m.end());
+                Some(&self.text[last..m.start()])
+            }
+            None => {
+                self.last = None;
+                Some(&self.text[last..])
+            }
+        }
+    }
+}
+
+/// Returns the largest UTF-8 character boundary of `text` that is at most
+/// `index`, or the length of `text` if `index` is past its end.
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    let mut end = index.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
     }
+    end
 }
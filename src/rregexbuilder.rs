@@ -0,0 +1,307 @@
+use crate::rregex::RRegex;
+use crate::types::RegexFlags;
+use std::convert::TryFrom;
+use wasm_bindgen::prelude::*;
+
+type Result<T> = std::result::Result<T, serde_wasm_bindgen::Error>;
+
+/// A configurable builder for a `RRegex`.
+///
+/// A builder can be used to configure how the regex is built, for example, by
+/// setting the default flags (which can be overridden in the expression
+/// itself). Every setter returns the builder, so calls can be chained.
+///
+/// # Example
+///
+/// ```typescript
+/// import { RRegexBuilder } from "rregex"
+///
+/// const re = new RRegexBuilder("foo").caseInsensitive(true).build()
+/// expect(re.isMatch("FOO")).toBe(true)
+/// expect(re.flags).toBe("i")
+/// ```
+///
+/// @see https://docs.rs/regex/latest/regex/struct.RegexBuilder.html
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct RRegexBuilder {
+    pattern: String,
+    flags: RegexFlags,
+}
+
+#[wasm_bindgen]
+impl RRegexBuilder {
+    /// Create a new builder with a default configuration for the given
+    /// pattern.
+    ///
+    /// If the pattern is invalid, then an error will be returned when
+    /// `build` is called.
+    #[wasm_bindgen(constructor)]
+    pub fn new(pattern: &str) -> RRegexBuilder {
+        RRegexBuilder {
+            pattern: pattern.to_owned(),
+            flags: RegexFlags::default(),
+        }
+    }
+
+    /// Compiles the pattern given to `new` with the configuration set on this
+    /// builder.
+    ///
+    /// If the pattern isn't a valid regex, then an error is returned.
+    ///
+    /// @returns {RRegex}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn build(&self) -> Result<RRegex> {
+        RRegex::compile(&self.pattern, self.flags)
+    }
+
+    /// This configures whether to enable case insensitive matching for the
+    /// entire pattern. This setting can also be configured using the inline
+    /// flag `i` in the pattern.
+    ///
+    /// The default for this is `false`.
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.RegexBuilder.html#method.case_insensitive
+    /// @param {boolean} yes - Whether to enable the flag
+    /// @returns {RRegexBuilder}
+    #[wasm_bindgen(skip_jsdoc, js_name = caseInsensitive)]
+    pub fn case_insensitive(&mut self, yes: bool) -> RRegexBuilder {
+        self.flags.case_insensitive = yes;
+        self.clone()
+    }
+
+    /// This configures multi-line mode for the entire pattern. When enabled,
+    /// `^` and `$` also match at the start and end of every line. This
+    /// setting can also be configured using the inline flag `m` in the
+    /// pattern.
+    ///
+    /// The default for this is `false`.
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.RegexBuilder.html#method.multi_line
+    /// @param {boolean} yes - Whether to enable the flag
+    /// @returns {RRegexBuilder}
+    #[wasm_bindgen(skip_jsdoc, js_name = multiLine)]
+    pub fn multi_line(&mut self, yes: bool) -> RRegexBuilder {
+        self.flags.multi_line = yes;
+        self.clone()
+    }
+
+    /// This configures dot-matches-new-line mode for the entire pattern, in
+    /// which `.` also matches `\n`. This setting can also be configured using
+    /// the inline flag `s` in the pattern.
+    ///
+    /// The default for this is `false`.
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.RegexBuilder.html#method.dot_matches_new_line
+    /// @param {boolean} yes - Whether to enable the flag
+    /// @returns {RRegexBuilder}
+    #[wasm_bindgen(skip_jsdoc, js_name = dotMatchesNewLine)]
+    pub fn dot_matches_new_line(&mut self, yes: bool) -> RRegexBuilder {
+        self.flags.dot_matches_new_line = yes;
+        self.clone()
+    }
+
+    /// This configures verbose mode for the entire pattern, in which
+    /// whitespace is ignored and `#` starts a comment. This setting can also
+    /// be configured using the inline flag `x` in the pattern.
+    ///
+    /// The default for this is `false`.
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.RegexBuilder.html#method.ignore_whitespace
+    /// @param {boolean} yes - Whether to enable the flag
+    /// @returns {RRegexBuilder}
+    #[wasm_bindgen(skip_jsdoc, js_name = ignoreWhitespace)]
+    pub fn ignore_whitespace(&mut self, yes: bool) -> RRegexBuilder {
+        self.flags.ignore_whitespace = yes;
+        self.clone()
+    }
+
+    /// This configures swap-greed mode for the entire pattern, in which the
+    /// meaning of `x*` and `x*?` is swapped. This setting can also be
+    /// configured using the inline flag `U` in the pattern.
+    ///
+    /// The default for this is `false`.
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.RegexBuilder.html#method.swap_greed
+    /// @param {boolean} yes - Whether to enable the flag
+    /// @returns {RRegexBuilder}
+    #[wasm_bindgen(skip_jsdoc, js_name = swapGreed)]
+    pub fn swap_greed(&mut self, yes: bool) -> RRegexBuilder {
+        self.flags.swap_greed = yes;
+        self.clone()
+    }
+
+    /// This configures Unicode mode for the entire pattern.
+    ///
+    /// When disabled, classes like `\w`, `\d` and `\s` and assertions like
+    /// `\b` only consider ASCII characters, which makes the compiled regex
+    /// smaller and faster. Since a `RRegex` can only match valid UTF-8,
+    /// patterns that could match arbitrary bytes without Unicode mode, like
+    /// `.` or `[^a]`, fail to compile, as do Unicode classes like `\pL`.
+    /// This setting can also be configured using the inline flag `u` in the
+    /// pattern.
+    ///
+    /// The default for this is `true`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegexBuilder } from "rregex"
+    ///
+    /// const re = new RRegexBuilder("\\w+").unicode(false).build()
+    /// expect(re.find("δxyz").value).toBe("xyz")
+    /// expect(re.flags).toBe("-u")
+    /// ```
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.RegexBuilder.html#method.unicode
+    /// @param {boolean} yes - Whether to enable the flag
+    /// @returns {RRegexBuilder}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn unicode(&mut self, yes: bool) -> RRegexBuilder {
+        self.flags.unicode = yes;
+        self.clone()
+    }
+
+    /// This configures CRLF mode for the entire pattern. When enabled, `\r\n`
+    /// is treated as a single line terminator: multi-line mode `^` and `$`
+    /// match on either side of it and never between `\r` and `\n`, and `.`
+    /// matches neither `\r` nor `\n`. This setting can also be configured
+    /// using the inline flag `R` in the pattern.
+    ///
+    /// The default for this is `false`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegexBuilder } from "rregex"
+    ///
+    /// const re = new RRegexBuilder("^.+$").multiLine(true).crlf(true).build()
+    /// expect(re.findAll("a\r\nb").map(m => m.value)).toEqual(["a", "b"])
+    /// ```
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.RegexBuilder.html#method.crlf
+    /// @param {boolean} yes - Whether to enable the flag
+    /// @returns {RRegexBuilder}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn crlf(&mut self, yes: bool) -> RRegexBuilder {
+        self.flags.crlf = yes;
+        self.clone()
+    }
+
+    /// This configures whether the methods that report where the regex
+    /// matches use leftmost-longest matches, like POSIX tools do, instead of
+    /// the default leftmost-first matches.
+    ///
+    /// Both semantics agree on where a match starts. With leftmost-first
+    /// semantics the first alternative that matches there wins, while with
+    /// leftmost-longest semantics the longest one does.
+    ///
+    /// The rule is that every method that reports or acts on the spans of
+    /// whole matches honors this, which is exactly:
+    ///
+    /// - `find`, `findOrNull`, `findAt`, `findInRange`, `findWithContext`,
+    ///   `findWithPosition`, `findUtf16`, `findOn`, `findWithin`,
+    ///   `findFirstMany`, `findAfter`, `findBefore`, `findLast`,
+    ///   `findLastIndex` and `findNth`;
+    /// - `findAll`, `findAllOn`, `findAllLimited`, `findAllPaged`,
+    ///   `findAllWhere`, `findAllOverlapping`, `findAllWithContext`,
+    ///   `findAllUtf16`, `findAllCharLen`, `findAllMerged`, `findAllColumnar`
+    ///   and `findAllWithLine`;
+    /// - `forEachMatch`, `matchExactlyAt`, `matchIndices`, `startPositions`,
+    ///   `matchCountByLine`, `matchingLines`, `matchOccurrences`,
+    ///   `matchStats`, `distinctMatches`, `matchNavigator`, `scanner` and
+    ///   `stateful`;
+    /// - `highlight`, `partition`, `trimStartMatches`, `trimEndMatches` and
+    ///   `trimMatches`;
+    /// - `split`, `splitMap`, `splitn`, `splitnInfo`, `splitBounded`,
+    ///   `splitInclusive`, `splitOnLast`, `splitIndices`, `splitOffsets` and
+    ///   `splitFields`.
+    ///
+    /// The methods that report capture groups, i.e., the `captures` methods,
+    /// `expand`, `matchAllCompat`, `findAllGroup` and `findAllNamedGroup`, and
+    /// the `replace` methods keep leftmost-first semantics. Use the
+    /// `longestMatch` getter of the regex to check which mode is active.
+    ///
+    /// The default for this is `false`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegexBuilder } from "rregex"
+    ///
+    /// const re = new RRegexBuilder("sam|samwise").longestMatch(true).build()
+    /// expect(re.find("samwise").value).toBe("samwise")
+    /// expect(re.longestMatch).toBe(true)
+    /// ```
+    ///
+    /// @param {boolean} yes - Whether to report leftmost-longest matches
+    /// @returns {RRegexBuilder}
+    #[wasm_bindgen(skip_jsdoc, js_name = longestMatch)]
+    pub fn longest_match(&mut self, yes: bool) -> RRegexBuilder {
+        self.flags.longest = yes;
+        self.clone()
+    }
+
+    /// This configures whether octal escapes like `\141` are supported.
+    ///
+    /// Octal syntax is disabled by default, since it makes error messages
+    /// for backreferences like `\1`, which aren't supported, confusing.
+    /// Enable it to accept patterns written for engines that support it.
+    ///
+    /// The default for this is `false`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegexBuilder } from "rregex"
+    ///
+    /// const re = new RRegexBuilder("\\141").octal(true).build()
+    /// expect(re.isMatch("a")).toBe(true)
+    /// ```
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.RegexBuilder.html#method.octal
+    /// @param {boolean} yes - Whether to enable octal syntax
+    /// @returns {RRegexBuilder}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn octal(&mut self, yes: bool) -> RRegexBuilder {
+        self.flags.octal = yes;
+        self.clone()
+    }
+
+    /// This configures the line terminator used by multi-line mode `^` and
+    /// `$`, and by `.` when dot-matches-new-line mode is disabled.
+    ///
+    /// The line terminator is given as a char code and must be ASCII, for
+    /// example `0` to split NUL-delimited records. Otherwise an error is
+    /// returned.
+    ///
+    /// The default for this is `\n`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegexBuilder } from "rregex"
+    ///
+    /// const re = new RRegexBuilder("^\\w+$").multiLine(true).lineTerminator(0).build()
+    /// expect(re.findAll("foo\0bar").map(m => m.value)).toEqual(["foo", "bar"])
+    /// ```
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.RegexBuilder.html#method.line_terminator
+    /// @param {number} byte - The char code of the line terminator
+    /// @returns {RRegexBuilder}
+    #[wasm_bindgen(skip_jsdoc, js_name = lineTerminator)]
+    pub fn line_terminator(&mut self, byte: u32) -> Result<RRegexBuilder> {
+        let byte = u8::try_from(byte)
+            .ok()
+            .filter(u8::is_ascii)
+            .ok_or_else(|| {
+                serde_wasm_bindgen::Error::new(format!(
+                    "line terminator {} is not an ASCII byte",
+                    byte
+                ))
+            })?;
+
+        self.flags.line_terminator = byte;
+        Ok(self.clone())
+    }
+}
@@ -0,0 +1,70 @@
+use crate::types::Match;
+use wasm_bindgen::prelude::*;
+
+type Result<T> = std::result::Result<T, serde_wasm_bindgen::Error>;
+
+/// A lazy iterator over the captures of every successive non-overlapping
+/// match in a text, created with `RRegex.capturesIter`.
+///
+/// Unlike `capturesAll`, the matches are searched one at a time when `next`
+/// is called, so a scan over a huge text can stop early without collecting
+/// every match first.
+///
+/// # Example
+///
+/// ```typescript
+/// import { RRegex } from "rregex"
+///
+/// const iter = new RRegex("(\\w)(\\d)").capturesIter("a1 b2 c3")
+/// let caps
+/// while ((caps = iter.next()) !== undefined) {
+///   console.log(caps.get[1].value)
+/// }
+/// ```
+///
+/// @see https://docs.rs/regex/latest/regex/struct.CaptureMatches.html
+#[wasm_bindgen]
+pub struct RRegexCapturesIter {
+    pub(crate) regex: regex::Regex,
+    pub(crate) text: String,
+    pub(crate) start: usize,
+    pub(crate) last_end: Option<usize>,
+}
+
+#[wasm_bindgen]
+impl RRegexCapturesIter {
+    /// Returns the captures of the next match, or `undefined` once there are
+    /// no more matches.
+    ///
+    /// Like `capturesAll`, an empty match is never reported right after the
+    /// end of the previous match, and the search always moves forward, so
+    /// patterns that match the empty string can't loop forever.
+    ///
+    /// @returns {Captures|undefined}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn next(&mut self) -> Result<JsValue> {
+        while self.start <= self.text.len() {
+            let captures = match self.regex.captures_at(&self.text, self.start) {
+                Some(captures) => captures,
+                None => break,
+            };
+
+            let m = captures.get(0).unwrap();
+            let empty = m.start() == m.end();
+            self.start = match self.text[m.end()..].chars().next() {
+                Some(c) if empty => m.end() + c.len_utf8(),
+                None if empty => m.end() + 1,
+                _ => m.end(),
+            };
+            if empty && self.last_end == Some(m.end()) {
+                continue;
+            }
+
+            self.last_end = Some(m.end());
+            return Match::captures(captures, self.regex.capture_names());
+        }
+
+        self.start = self.text.len() + 1;
+        Ok(JsValue::UNDEFINED)
+    }
+}
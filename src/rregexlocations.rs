@@ -0,0 +1,63 @@
+use wasm_bindgen::prelude::*;
+
+/// A reusable buffer for the locations of capture groups, created with
+/// `RRegex.createLocations`.
+///
+/// Filling this buffer with `RRegex.capturesRead` doesn't allocate a new
+/// `Captures` object for every search, which makes it a good fit for running
+/// the same regex over many inputs in a tight loop. Every offset is a byte
+/// offset into the text given to the last `capturesRead` call.
+///
+/// # Example
+///
+/// ```typescript
+/// import { RRegex } from "rregex"
+///
+/// const re = new RRegex("(\\w+)@(\\w+)")
+/// const locs = re.createLocations()
+/// for (const text of ["joe@example", "ann@test"]) {
+///   re.capturesRead(text, locs)
+///   console.log(locs.start(1), locs.end(1))
+/// }
+/// ```
+///
+/// @see https://docs.rs/regex/latest/regex/struct.CaptureLocations.html
+#[wasm_bindgen]
+pub struct RRegexLocations {
+    pub(crate) locations: regex::CaptureLocations,
+}
+
+#[wasm_bindgen]
+impl RRegexLocations {
+    /// Returns the start byte offset of the capture group at the given index,
+    /// or `undefined` if the group didn't participate in the last match.
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.CaptureLocations.html#method.get
+    /// @param {number} index - The index of the capture group
+    /// @returns {number|undefined}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn start(&self, index: usize) -> Option<usize> {
+        self.locations.get(index).map(|(start, _)| start)
+    }
+
+    /// Returns the end byte offset of the capture group at the given index,
+    /// or `undefined` if the group didn't participate in the last match.
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.CaptureLocations.html#method.get
+    /// @param {number} index - The index of the capture group
+    /// @returns {number|undefined}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn end(&self, index: usize) -> Option<usize> {
+        self.locations.get(index).map(|(_, end)| end)
+    }
+
+    /// Returns the total number of capture groups, including the implicit
+    /// group for the entire match. This is always the same as
+    /// `capturesLength()` of the regex that created this buffer.
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.CaptureLocations.html#method.len
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.locations.len()
+    }
+}
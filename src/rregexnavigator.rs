@@ -0,0 +1,83 @@
+use crate::types::ByteRange;
+use wasm_bindgen::prelude::*;
+
+type Result<T> = std::result::Result<T, serde_wasm_bindgen::Error>;
+
+/// An index of the matches of a regex in a text, created with
+/// `RRegex.matchNavigator`, to jump between them like "find next" and "find
+/// previous" do in an editor.
+///
+/// The text is searched once when the navigator is created, and only the
+/// byte offsets of the successive non-overlapping matches are kept, so every
+/// query after that is a binary search. The navigator doesn't see later
+/// changes to the text, create a new one when it's edited.
+///
+/// # Example
+///
+/// ```typescript
+/// import { RRegex } from "rregex"
+///
+/// const nav = new RRegex("\\d+").matchNavigator("a 1 bb 22 c 333")
+/// expect(nav.count()).toBe(3)
+/// expect(nav.at(1)).toEqual({ start: 7, end: 9 })
+/// expect(nav.next(4)).toEqual({ start: 7, end: 9 })
+/// expect(nav.prev(11)).toEqual({ start: 7, end: 9 })
+/// nav.free()
+/// ```
+#[wasm_bindgen]
+pub struct RRegexNavigator {
+    pub(crate) ranges: Vec<(usize, usize)>,
+}
+
+#[wasm_bindgen]
+impl RRegexNavigator {
+    /// Returns the number of matches.
+    ///
+    /// @returns {number}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Returns the byte range of the match at `index`, in the order the
+    /// matches appear in the text, or `undefined` if there are fewer
+    /// matches.
+    ///
+    /// @param {number} index - Zero-based index of the match
+    /// @returns {ByteRange|undefined}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn at(&self, index: usize) -> Result<JsValue> {
+        self.range(self.ranges.get(index))
+    }
+
+    /// Returns the byte range of the first match that starts at or after
+    /// the byte `offset`, or `undefined` if there is none.
+    ///
+    /// @param {number} offset - Zero-based byte offset to search from
+    /// @returns {ByteRange|undefined}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn next(&self, offset: usize) -> Result<JsValue> {
+        let index = self.ranges.partition_point(|(start, _)| *start < offset);
+        self.range(self.ranges.get(index))
+    }
+
+    /// Returns the byte range of the last match that ends at or before the
+    /// byte `offset`, or `undefined` if there is none.
+    ///
+    /// @param {number} offset - Zero-based byte offset to search back from
+    /// @returns {ByteRange|undefined}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn prev(&self, offset: usize) -> Result<JsValue> {
+        let index = self.ranges.partition_point(|(_, end)| *end <= offset);
+        self.range(index.checked_sub(1).and_then(|index| self.ranges.get(index)))
+    }
+}
+
+impl RRegexNavigator {
+    fn range(&self, range: Option<&(usize, usize)>) -> Result<JsValue> {
+        match range {
+            Some(&(start, end)) => serde_wasm_bindgen::to_value(&ByteRange { start, end }),
+            None => Ok(JsValue::UNDEFINED),
+        }
+    }
+}
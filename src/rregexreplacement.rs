@@ -0,0 +1,135 @@
+use std::borrow::Cow;
+use wasm_bindgen::prelude::*;
+
+/// A piece of a parsed replacement template.
+enum Piece {
+    Literal(String),
+    Group(usize),
+    Name(String),
+}
+
+/// A replacement string parsed once, created with `compileReplacement`, that
+/// can be applied many times by `RRegex.replaceAllWith`.
+///
+/// `replaceAll` parses the `$1`, `$name` and `${name}` references of its
+/// replacement string on every call, which adds up when the same template is
+/// reused over many texts. A `RRegexReplacement` holds the template already
+/// parsed, with the same syntax as `replace`. A template without references
+/// is inserted as is.
+///
+/// # Example
+///
+/// ```typescript
+/// import { RRegex, compileReplacement } from "rregex"
+///
+/// const re = new RRegex("(?<last>\\w+), (?<first>\\w+)")
+/// const rep = compileReplacement("$first $last")
+/// expect(re.replaceAllWith("Doe, Jane", rep)).toBe("Jane Doe")
+/// expect(re.replaceAllWith("Roe, Richard", rep)).toBe("Richard Roe")
+/// rep.free()
+/// ```
+#[wasm_bindgen]
+pub struct RRegexReplacement {
+    template: String,
+    pieces: Vec<Piece>,
+}
+
+#[wasm_bindgen]
+impl RRegexReplacement {
+    /// Returns the template this replacement was compiled from.
+    ///
+    /// @returns {string}
+    #[wasm_bindgen(getter)]
+    pub fn template(&self) -> String {
+        self.template.clone()
+    }
+}
+
+impl RRegexReplacement {
+    /// Parses `template` the same way `regex::Captures::expand` does: `$$`
+    /// is a literal `$`, `${name}` and `$name` refer to a group by index if
+    /// `name` is a number and by name otherwise, and a `$` that doesn't start
+    /// a reference is kept as is.
+    fn parse(template: &str) -> RRegexReplacement {
+        let mut pieces = Vec::new();
+        let mut literal = String::new();
+        let mut rest = template;
+        while let Some(position) = rest.find('$') {
+            literal.push_str(&rest[..position]);
+            rest = &rest[position + 1..];
+
+            if let Some(after) = rest.strip_prefix('$') {
+                literal.push('$');
+                rest = after;
+                continue;
+            }
+
+            let (name, consumed) = match rest.strip_prefix('{') {
+                Some(braced) => match braced.find('}') {
+                    Some(end) => (&braced[..end], end + 2),
+                    None => ("", 0),
+                },
+                None => {
+                    let end = rest
+                        .find(|c: char| !(c == '_' || c.is_ascii_alphanumeric()))
+                        .unwrap_or(rest.len());
+                    (&rest[..end], end)
+                }
+            };
+            if consumed == 0 {
+                literal.push('$');
+                continue;
+            }
+
+            if !literal.is_empty() {
+                pieces.push(Piece::Literal(std::mem::take(&mut literal)));
+            }
+            pieces.push(match name.parse() {
+                Ok(index) => Piece::Group(index),
+                Err(_) => Piece::Name(name.to_string()),
+            });
+            rest = &rest[consumed..];
+        }
+        literal.push_str(rest);
+        if !literal.is_empty() {
+            pieces.push(Piece::Literal(literal));
+        }
+
+        RRegexReplacement {
+            template: template.to_string(),
+            pieces,
+        }
+    }
+}
+
+impl regex::Replacer for &RRegexReplacement {
+    fn replace_append(&mut self, captures: &regex::Captures<'_>, dst: &mut String) {
+        for piece in &self.pieces {
+            let value = match piece {
+                Piece::Literal(literal) => Some(literal.as_str()),
+                Piece::Group(index) => captures.get(*index).map(|m| m.as_str()),
+                Piece::Name(name) => captures.name(name).map(|m| m.as_str()),
+            };
+            dst.push_str(value.unwrap_or(""));
+        }
+    }
+
+    fn no_expansion(&mut self) -> Option<Cow<'_, str>> {
+        match self.pieces.as_slice() {
+            [] => Some(Cow::Borrowed("")),
+            [Piece::Literal(literal)] => Some(Cow::Borrowed(literal)),
+            _ => None,
+        }
+    }
+}
+
+/// Parses `template` once, so it can be used as the replacement of many
+/// calls to `RRegex.replaceAllWith` without being parsed again.
+///
+/// @see RRegexReplacement
+/// @param {string} template - The replacement string, with the syntax of `replace`
+/// @returns {RRegexReplacement}
+#[wasm_bindgen(skip_jsdoc, js_name = compileReplacement)]
+pub fn compile_replacement(template: &str) -> RRegexReplacement {
+    RRegexReplacement::parse(template)
+}
@@ -0,0 +1,134 @@
+use crate::rregex::RRegex;
+use crate::types::RebasedMatch;
+use wasm_bindgen::prelude::*;
+
+type Result<T> = std::result::Result<T, serde_wasm_bindgen::Error>;
+
+/// Finds the matches of a regex in a text that arrives in chunks, created
+/// with `RRegex.scanner`.
+///
+/// Chunks are appended to an internal buffer with `push`, and `drain`
+/// returns the matches that more text can no longer change. Every offset is
+/// a byte offset from the start of the stream.
+///
+/// The regex engine needs to see the whole text of a match, so the buffer
+/// keeps everything from the end of the last match reported, or at least the
+/// last `maxLookBack` bytes, plus one character of context for things like
+/// `\b`. A match is only reported once it ends more than `maxLookBack` bytes
+/// before the end of the buffer. So `maxLookBack` must be at least as long as
+/// the longest match, otherwise a match might be reported before all of its
+/// text has arrived. Call `finish` once the stream ends to get the matches
+/// that are still held back.
+///
+/// # Example
+///
+/// ```typescript
+/// import { RRegex } from "rregex"
+///
+/// const scanner = new RRegex("\\d+").scanner(8)
+/// scanner.push("a 12 b 3")
+/// scanner.push("4 c 1234567 d")
+/// expect(scanner.drain().map((m) => m.value)).toEqual(["12", "34"])
+/// expect(scanner.finish().map((m) => m.value)).toEqual(["1234567"])
+/// ```
+#[wasm_bindgen]
+pub struct RRegexScanner {
+    pub(crate) regex: RRegex,
+    pub(crate) max_look_back: usize,
+    pub(crate) buffer: String,
+    pub(crate) offset: usize,
+    pub(crate) start: usize,
+    pub(crate) last_end: Option<usize>,
+}
+
+#[wasm_bindgen]
+impl RRegexScanner {
+    /// Appends `chunk` to the text of the stream.
+    ///
+    /// @param {string} chunk - The next piece of the stream
+    /// @returns {void}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn push(&mut self, chunk: &str) {
+        self.buffer.push_str(chunk);
+    }
+
+    /// Returns the matches found since the last call that the text still to
+    /// come can't change, and drops the text that is no longer needed.
+    ///
+    /// @returns {Match[]}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn drain(&mut self) -> Result<JsValue> {
+        self.scan(false)
+    }
+
+    /// Returns every match that is still held back, treating the end of the
+    /// buffer as the end of the stream, e.g., so that `$` can match there.
+    ///
+    /// The scanner is then reset and can be reused for a new stream, with
+    /// offsets starting back at `0`.
+    ///
+    /// @returns {Match[]}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn finish(&mut self) -> Result<JsValue> {
+        let matches = self.scan(true)?;
+        self.buffer.clear();
+        self.offset = 0;
+        self.start = 0;
+        self.last_end = None;
+        Ok(matches)
+    }
+}
+
+impl RRegexScanner {
+    fn scan(&mut self, end_of_stream: bool) -> Result<JsValue> {
+        let limit = self.buffer.len().saturating_sub(self.max_look_back);
+        let mut matches: Vec<RebasedMatch> = Vec::new();
+        while self.start <= self.buffer.len() {
+            let m = match self.regex.find_substring_at(&self.buffer, self.start)? {
+                Some(m) if end_of_stream || m.end() < limit => m,
+                Some(_) => break,
+                None => {
+                    // A match that starts before `limit` would fit in the
+                    // buffer, so no match can start there anymore.
+                    let mut skip = limit;
+                    while !self.buffer.is_char_boundary(skip) {
+                        skip -= 1;
+                    }
+                    self.start = self.start.max(skip);
+                    break;
+                }
+            };
+
+            let empty = m.start() == m.end();
+            self.start = match self.buffer[m.end()..].chars().next() {
+                Some(c) if empty => m.end() + c.len_utf8(),
+                None if empty => m.end() + 1,
+                _ => m.end(),
+            };
+            if empty && self.last_end == Some(m.end()) {
+                continue;
+            }
+
+            self.last_end = Some(m.end());
+            matches.push(RebasedMatch {
+                start: self.offset + m.start(),
+                end: self.offset + m.end(),
+                value: m.as_str(),
+            });
+        }
+        let matches = serde_wasm_bindgen::to_value(&matches)?;
+
+        // Keep the character before the next search as context.
+        let start = self.start.min(self.buffer.len());
+        let cut = self.buffer[..start]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(index, _)| index);
+        self.buffer.drain(..cut);
+        self.offset += cut;
+        self.start -= cut;
+        self.last_end = self.last_end.and_then(|end| end.checked_sub(cut));
+
+        Ok(matches)
+    }
+}
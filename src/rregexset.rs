@@ -1,3 +1,9 @@
+use std::cell::OnceCell;
+
+use crate::types::GroupNameConflict;
+use crate::types::RegexFlags;
+use crate::types::SizeLimits;
+use regex_syntax::hir;
 use wasm_bindgen::prelude::*;
 
 type Result<T> = std::result::Result<T, serde_wasm_bindgen::Error>;
@@ -15,6 +21,9 @@ type Result<T> = std::result::Result<T, serde_wasm_bindgen::Error>;
 #[wasm_bindgen]
 pub struct RRegexSet {
     regexes: regex::RegexSet,
+    flags: RegexFlags,
+    limits: SizeLimits,
+    each: OnceCell<Vec<regex::Regex>>,
 }
 
 #[wasm_bindgen]
@@ -37,18 +46,10 @@ impl RRegexSet {
     /// ```
     #[wasm_bindgen(constructor)]
     pub fn new(list: &js_sys::Array) -> Result<RRegexSet> {
-        let mut patterns: Vec<String> = Vec::with_capacity(list.length() as usize);
-        for (position, item) in list.iter().enumerate() {
-          let pattern = item.as_string()
-            .ok_or(serde_wasm_bindgen::Error::new(format!("item in position {} is not a string", position)))?;
-
-          patterns.push(pattern)
-        };
-
-        let regexes = regex::RegexSet::new(patterns)
+        let regexes = regex::RegexSet::new(patterns(list)?)
           .map_err(|err| serde_wasm_bindgen::Error::new(err.to_string()))?;
 
-        Ok(RRegexSet { regexes })
+        Ok(RRegexSet::from(regexes))
     }
 
     /// Returns true if and only if one of the regexes in this set matches
@@ -125,4 +126,229 @@ impl RRegexSet {
     pub fn matches(&self, text: &str) -> Vec<JsValue> {
         self.regexes.matches(text).iter().map(JsValue::from).collect()
     }
+
+    /// Returns the same set as `matches`, packed in a bitset where bit `i` is
+    /// set if and only if the pattern at index `i` matched.
+    ///
+    /// Patterns are packed 8 per byte, least significant bit first, i.e.,
+    /// pattern `i` is bit `i % 8` of byte `Math.floor(i / 8)`. The array has
+    /// as many bytes as needed to hold one bit per pattern, so two bitsets of
+    /// the same set can be combined with `&` and `|` byte by byte.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegexSet } from "rregex"
+    ///
+    /// const set = new RRegexSet(["\\w+", "\\d+", "foo"])
+    /// expect(set.matchesBitset("foo")).toEqual(new Uint8Array([0b101]))
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @return {Uint8Array}
+    #[wasm_bindgen(skip_jsdoc, js_name = matchesBitset)]
+    pub fn matches_bitset(&self, text: &str) -> js_sys::Uint8Array {
+        let mut bitset = vec![0u8; self.regexes.len().div_ceil(8)];
+        for index in self.regexes.matches(text).iter() {
+            bitset[index / 8] |= 1 << (index % 8);
+        }
+
+        js_sys::Uint8Array::from(bitset.as_slice())
+    }
+
+    /// Returns the same as `matches`, but starts the search at the given
+    /// offset.
+    ///
+    /// The significance of the starting point is that it takes the surrounding
+    /// context into consideration. For example, the `\A` anchor can only
+    /// match when `start == 0`. If `start` is past the end of `text`, then
+    /// no pattern matches.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegexSet } from "rregex"
+    ///
+    /// const set = new RRegexSet(["\\d+", "[a-z]+", "^a"])
+    /// expect(set.matchesAt("a1", 1)).toEqual([0])
+    /// ```
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.RegexSet.html#method.matches_at
+    /// @param {string} text - The string against which to match the regular expression
+    /// @param {number} start - Zero-based index at which to start matching
+    /// @return {number[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = matchesAt)]
+    pub fn matches_at(&self, text: &str, start: usize) -> Vec<JsValue> {
+        if start > text.len() {
+            return Vec::new();
+        }
+
+        self.regexes
+            .matches_at(text, start)
+            .iter()
+            .map(JsValue::from)
+            .collect()
+    }
+
+    /// Returns the capture group names that are defined by more than one
+    /// pattern of this set, along with the indices of those patterns, in the
+    /// order the names first appear.
+    ///
+    /// A set never reports captures, so it compiles fine with such patterns,
+    /// but results are easy to mix up once the patterns are used on their
+    /// own or merged into a single regex.
+    ///
+    /// The names are read from the parsed patterns, so this never compiles
+    /// them and can't hit the size limits of the set.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegexSet } from "rregex"
+    ///
+    /// const set = new RRegexSet(["(?<id>\\d+)", "(?<name>\\w+)", "#(?<id>\\w+)"])
+    /// expect(set.conflictingGroupNames()).toEqual([{ name: "id", patterns: [0, 2] }])
+    /// ```
+    ///
+    /// @returns {GroupNameConflict[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = conflictingGroupNames)]
+    pub fn conflicting_group_names(&self) -> Result<JsValue> {
+        let mut names: Vec<GroupNameConflict> = Vec::new();
+        for (index, pattern) in self.regexes.patterns().iter().enumerate() {
+            let hir = self
+                .flags
+                .parser()
+                .parse(pattern)
+                .map_err(serde_wasm_bindgen::Error::new)?;
+            let pattern_names = match hir::visit(&hir, CaptureNames::default()) {
+                Ok(pattern_names) => pattern_names,
+                Err(never) => match never {},
+            };
+            for name in pattern_names {
+                match names.iter_mut().find(|conflict| conflict.name == name) {
+                    Some(conflict) => conflict.patterns.push(index),
+                    None => names.push(GroupNameConflict {
+                        name,
+                        patterns: vec![index],
+                    }),
+                }
+            }
+        }
+        names.retain(|conflict| conflict.patterns.len() > 1);
+
+        serde_wasm_bindgen::to_value(&names)
+    }
+
+    /// Returns the same indices as `matches`, ordered by where each pattern
+    /// first matches in `text` instead of by pattern index. Patterns whose
+    /// first matches start at the same offset are ordered by index.
+    ///
+    /// This runs one more search for every pattern that matched, so it's
+    /// slower than `matches`.
+    ///
+    /// # Example
+    ///
+    /// ```typescript
+    /// import { RRegexSet } from "rregex"
+    ///
+    /// const set = new RRegexSet(["[a-z]+", "\\d+", "\\w+"])
+    /// expect(set.matchesOrderedByPosition("42 apples")).toEqual([1, 2, 0])
+    /// ```
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @return {number[]}
+    #[wasm_bindgen(skip_jsdoc, js_name = matchesOrderedByPosition)]
+    pub fn matches_ordered_by_position(&self, text: &str) -> Result<Vec<JsValue>> {
+        let each = self.each()?;
+        let mut matches: Vec<(usize, usize)> = self
+            .regexes
+            .matches(text)
+            .iter()
+            .filter_map(|index| each[index].find(text).map(|m| (m.start(), index)))
+            .collect();
+        matches.sort_unstable();
+
+        Ok(matches
+            .into_iter()
+            .map(|(_, index)| JsValue::from(index))
+            .collect())
+    }
+}
+
+impl From<regex::RegexSet> for RRegexSet {
+    fn from(regexes: regex::RegexSet) -> Self {
+        RRegexSet::with_options(regexes, RegexFlags::default(), SizeLimits::default())
+    }
+}
+
+impl RRegexSet {
+    pub(crate) fn with_options(
+        regexes: regex::RegexSet,
+        flags: RegexFlags,
+        limits: SizeLimits,
+    ) -> RRegexSet {
+        RRegexSet {
+            regexes,
+            flags,
+            limits,
+            each: OnceCell::new(),
+        }
+    }
+
+    /// Returns every pattern of the set compiled on its own with the options
+    /// and size limits of the set, compiling them the first time they're needed.
+    fn each(&self) -> Result<&[regex::Regex]> {
+        if let Some(each) = self.each.get() {
+            return Ok(each);
+        }
+
+        let each = self
+            .regexes
+            .patterns()
+            .iter()
+            .map(|pattern| {
+                let mut builder = self.flags.builder(pattern);
+                self.limits.apply(&mut builder);
+                builder.build()
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|err| serde_wasm_bindgen::Error::new(err.to_string()))?;
+
+        Ok(self.each.get_or_init(|| each))
+    }
+}
+
+/// Collects the names of the capture groups of a HIR, in the order they
+/// appear, for `conflictingGroupNames`.
+#[derive(Default)]
+struct CaptureNames(Vec<String>);
+
+impl hir::Visitor for CaptureNames {
+    type Output = Vec<String>;
+    type Err = std::convert::Infallible;
+
+    fn finish(self) -> std::result::Result<Self::Output, Self::Err> {
+        Ok(self.0)
+    }
+
+    fn visit_pre(&mut self, hir: &hir::Hir) -> std::result::Result<(), Self::Err> {
+        if let hir::HirKind::Capture(hir::Capture { name: Some(name), .. }) = hir.kind() {
+            self.0.push(name.to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Collects the patterns of a set from a JS array, which must only contain
+/// strings.
+pub(crate) fn patterns(list: &js_sys::Array) -> Result<Vec<String>> {
+    let mut patterns: Vec<String> = Vec::with_capacity(list.length() as usize);
+    for (position, item) in list.iter().enumerate() {
+      let pattern = item.as_string()
+        .ok_or(serde_wasm_bindgen::Error::new(format!("item in position {} is not a string", position)))?;
+
+      patterns.push(pattern)
+    };
+
+    Ok(patterns)
 }
@@ -0,0 +1,127 @@
+use crate::rregexset::{patterns, RRegexSet};
+use crate::types::RegexFlags;
+use crate::types::SizeLimits;
+use wasm_bindgen::prelude::*;
+
+type Result<T> = std::result::Result<T, serde_wasm_bindgen::Error>;
+
+/// A configurable builder for a `RRegexSet`.
+///
+/// Like `RRegexBuilder`, every setter returns the builder, so calls can be
+/// chained, and the options apply to every pattern in the set.
+///
+/// # Example
+///
+/// ```typescript
+/// import { RRegexSetBuilder } from "rregex"
+///
+/// const set = new RRegexSetBuilder(["foo", "bar"]).caseInsensitive(true).build()
+/// expect(set.matches("FOO")).toEqual([0])
+/// ```
+///
+/// @see https://docs.rs/regex/latest/regex/struct.RegexSetBuilder.html
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct RRegexSetBuilder {
+    builder: regex::RegexSetBuilder,
+    flags: RegexFlags,
+    limits: SizeLimits,
+}
+
+#[wasm_bindgen]
+impl RRegexSetBuilder {
+    /// Create a new builder with a default configuration for the given
+    /// patterns.
+    ///
+    /// If any item in the list is not a string, then an error is returned.
+    /// If a pattern is invalid, then an error will be returned when `build`
+    /// is called.
+    #[wasm_bindgen(constructor)]
+    pub fn new(list: &js_sys::Array) -> Result<RRegexSetBuilder> {
+        Ok(RRegexSetBuilder {
+            builder: regex::RegexSetBuilder::new(patterns(list)?),
+            flags: RegexFlags::default(),
+            limits: SizeLimits::default(),
+        })
+    }
+
+    /// Compiles the patterns given to `new` with the configuration set on
+    /// this builder.
+    ///
+    /// If any pattern isn't a valid regex, or the set exceeds one of the
+    /// size limits, then an error is returned.
+    ///
+    /// @returns {RRegexSet}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn build(&self) -> Result<RRegexSet> {
+        let regexes = self
+            .builder
+            .build()
+            .map_err(|err| serde_wasm_bindgen::Error::new(err.to_string()))?;
+
+        Ok(RRegexSet::with_options(regexes, self.flags, self.limits))
+    }
+
+    /// This configures whether to enable case insensitive matching for every
+    /// pattern in the set. This setting can also be configured using the
+    /// inline flag `i` in a pattern.
+    ///
+    /// The default for this is `false`.
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.RegexSetBuilder.html#method.case_insensitive
+    /// @param {boolean} yes - Whether to enable the flag
+    /// @returns {RRegexSetBuilder}
+    #[wasm_bindgen(skip_jsdoc, js_name = caseInsensitive)]
+    pub fn case_insensitive(&mut self, yes: bool) -> RRegexSetBuilder {
+        self.builder.case_insensitive(yes);
+        self.flags.case_insensitive = yes;
+        self.clone()
+    }
+
+    /// This configures Unicode mode for every pattern in the set. When
+    /// disabled, classes like `\w` only consider ASCII characters. This
+    /// setting can also be configured using the inline flag `u` in a
+    /// pattern.
+    ///
+    /// The default for this is `true`.
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.RegexSetBuilder.html#method.unicode
+    /// @param {boolean} yes - Whether to enable the flag
+    /// @returns {RRegexSetBuilder}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn unicode(&mut self, yes: bool) -> RRegexSetBuilder {
+        self.builder.unicode(yes);
+        self.flags.unicode = yes;
+        self.clone()
+    }
+
+    /// Sets the approximate size limit, in bytes, of the compiled set. If a
+    /// set of patterns exceeds it, then `build` returns an error.
+    ///
+    /// This is a rough guard against the memory used by a set of many or
+    /// large patterns, not a precise bound.
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.RegexSetBuilder.html#method.size_limit
+    /// @param {number} bytes - The size limit in bytes
+    /// @returns {RRegexSetBuilder}
+    #[wasm_bindgen(skip_jsdoc, js_name = sizeLimit)]
+    pub fn size_limit(&mut self, bytes: usize) -> RRegexSetBuilder {
+        self.builder.size_limit(bytes);
+        self.limits.size = Some(bytes);
+        self.clone()
+    }
+
+    /// Sets the approximate capacity, in bytes, of the cache of transitions
+    /// used by the lazy DFA. A bigger cache makes searches faster, at the
+    /// cost of memory.
+    ///
+    /// @see https://docs.rs/regex/latest/regex/struct.RegexSetBuilder.html#method.dfa_size_limit
+    /// @param {number} bytes - The cache capacity in bytes
+    /// @returns {RRegexSetBuilder}
+    #[wasm_bindgen(skip_jsdoc, js_name = dfaSizeLimit)]
+    pub fn dfa_size_limit(&mut self, bytes: usize) -> RRegexSetBuilder {
+        self.builder.dfa_size_limit(bytes);
+        self.limits.dfa_size = Some(bytes);
+        self.clone()
+    }
+}
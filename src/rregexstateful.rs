@@ -0,0 +1,111 @@
+use crate::rregex::RRegex;
+use wasm_bindgen::prelude::*;
+
+type Result<T> = std::result::Result<T, serde_wasm_bindgen::Error>;
+
+/// Matches a regex with a mutable `lastIndex`, like a native `RegExp` with
+/// the `g` flag, created with `RRegex.stateful`.
+///
+/// Each call to `exec` searches from `lastIndex` and moves it past the match
+/// returned, so code that loops on `RegExp.prototype.exec` can be ported as
+/// is. Like native indices, `lastIndex` and the `index` of a match are
+/// counted in UTF-16 code units. When there is no match, `exec` returns
+/// `null` and resets `lastIndex` to `0`.
+///
+/// Unlike a native `RegExp`, an empty match moves `lastIndex` one character
+/// forward, so a loop on `exec` always terminates.
+///
+/// # Example
+///
+/// ```typescript
+/// import { RRegex } from "rregex"
+///
+/// const re = new RRegex("\\d+").stateful()
+/// const values = []
+/// let m
+/// while ((m = re.exec("1 22 333")) !== null) {
+///   values.push([m[0], m.index, re.lastIndex])
+/// }
+/// expect(values).toEqual([["1", 0, 1], ["22", 2, 4], ["333", 5, 8]])
+/// ```
+#[wasm_bindgen]
+pub struct RRegexStateful {
+    pub(crate) regex: RRegex,
+    pub(crate) last_index: usize,
+}
+
+#[wasm_bindgen]
+impl RRegexStateful {
+    /// Returns the next match in `text` starting at `lastIndex`, shaped like
+    /// the result of `RegExp.prototype.exec`, and moves `lastIndex` to the
+    /// end of the match. Returns `null` and resets `lastIndex` to `0` if
+    /// there is no match.
+    ///
+    /// See `RRegex.matchAllCompat` for the shape of the result.
+    ///
+    /// @param {string} text - The string against which to match the regular expression
+    /// @returns {RegExpExecArray|null}
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn exec(&mut self, text: &str) -> Result<JsValue> {
+        let (start, units) = match byte_offset(text, self.last_index) {
+            Some(start) => start,
+            None => {
+                self.last_index = 0;
+                return Ok(JsValue::NULL);
+            }
+        };
+        let spans = match self.regex.capture_substrings_at(text, start)? {
+            Some(spans) => spans,
+            None => {
+                self.last_index = 0;
+                return Ok(JsValue::NULL);
+            }
+        };
+
+        let m = spans[0].as_ref().unwrap();
+        let index = units + text[start..m.start()].encode_utf16().count();
+        let end = index + m.as_str().encode_utf16().count();
+        self.last_index = if m.start() == m.end() {
+            end + text[m.end()..].chars().next().map_or(1, char::len_utf16)
+        } else {
+            end
+        };
+
+        let groups = spans.iter().map(|span| span.as_ref().map(|m| m.as_str()));
+        let array = self
+            .regex
+            .match_array(groups, index, &JsValue::from(text))?;
+        Ok(array.into())
+    }
+
+    /// The offset, in UTF-16 code units, where the next call to `exec`
+    /// starts searching.
+    #[wasm_bindgen(getter, js_name = lastIndex)]
+    pub fn last_index(&self) -> usize {
+        self.last_index
+    }
+
+    #[wasm_bindgen(setter, js_name = lastIndex)]
+    pub fn set_last_index(&mut self, last_index: usize) {
+        self.last_index = last_index;
+    }
+}
+
+/// Returns the byte offset of the first character boundary at or after
+/// `units` UTF-16 code units into `text`, along with the number of code
+/// units before it, or `None` if `text` is shorter than that.
+fn byte_offset(text: &str, units: usize) -> Option<(usize, usize)> {
+    let mut count = 0;
+    for (offset, c) in text.char_indices() {
+        if count >= units {
+            return Some((offset, count));
+        }
+        count += c.len_utf16();
+    }
+
+    if count >= units {
+        Some((text.len(), count))
+    } else {
+        None
+    }
+}
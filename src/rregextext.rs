@@ -0,0 +1,47 @@
+use wasm_bindgen::prelude::*;
+
+/// A text converted to UTF-8 once, created with `prepare`, that can be
+/// searched many times by the `...On` methods of `RRegex`, like `isMatchOn`.
+///
+/// Every call that takes a `string` copies it from JS into UTF-8, which adds
+/// up when the same large text is searched by many regexes. Passing a
+/// `RRegexText` instead skips that copy. The text can't be changed once
+/// prepared, and every offset returned is a byte offset into it.
+///
+/// # Example
+///
+/// ```typescript
+/// import { RRegex, prepare } from "rregex"
+///
+/// const text = prepare("a1 b2 c3")
+/// expect(new RRegex("\\d").findAllOn(text).length).toBe(3)
+/// expect(new RRegex("[a-z]").isMatchOn(text)).toBe(true)
+/// text.free()
+/// ```
+#[wasm_bindgen]
+pub struct RRegexText {
+    pub(crate) text: String,
+}
+
+#[wasm_bindgen]
+impl RRegexText {
+    /// Returns the length of the prepared text in bytes, once encoded as
+    /// UTF-8.
+    ///
+    /// @returns {number}
+    #[wasm_bindgen(getter, js_name = byteLength)]
+    pub fn byte_length(&self) -> usize {
+        self.text.len()
+    }
+}
+
+/// Converts `text` to UTF-8 once, so it can be searched many times without
+/// copying it again.
+///
+/// @see RRegexText
+/// @param {string} text - The text to prepare
+/// @returns {RRegexText}
+#[wasm_bindgen(skip_jsdoc)]
+pub fn prepare(text: String) -> RRegexText {
+    RRegexText { text }
+}
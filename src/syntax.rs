@@ -0,0 +1,250 @@
+use crate::types::Hir;
+use regex_syntax::hir;
+use std::convert::TryInto;
+use wasm_bindgen::prelude::*;
+
+type Result<T> = std::result::Result<T, serde_wasm_bindgen::Error>;
+
+/// Reconstructs a regular expression pattern from an `Hir`, such as the one
+/// returned by `RRegex.syntax`.
+///
+/// The pattern returned is guaranteed to be a valid regular expression that
+/// matches the same strings as the given tree, which makes it possible to
+/// rewrite a regular expression by transforming its syntax tree.
+///
+/// # Example
+///
+/// ```typescript
+/// import { RRegex, hirToString } from "rregex"
+///
+/// const hir = new RRegex("a|bc").syntax()
+/// const re = new RRegex(hirToString(hir))
+/// expect(re.isMatch("bc")).toBe(true)
+/// ```
+///
+/// @param {Hir} hir - The syntax tree to turn back into a pattern
+/// @returns {string}
+#[wasm_bindgen(skip_jsdoc, js_name = hirToString)]
+pub fn hir_to_string(hir: JsValue) -> Result<String> {
+    let hir: Hir<hir::Hir> = serde_wasm_bindgen::from_value(hir)?;
+    Ok(hir.into_inner().to_string())
+}
+
+/// Applies simple case folding to every Unicode character class in an `Hir`,
+/// such as the one returned by `RRegex.syntax`, and returns the transformed
+/// tree in the same shape.
+///
+/// This shows the effective set of characters a class matches under case
+/// insensitive matching, e.g., `[a-c]` becomes `[A-Ca-c]`. Literals and
+/// byte classes are left untouched.
+///
+/// # Example
+///
+/// ```typescript
+/// import { RRegex, classCaseFold, hirToString } from "rregex"
+///
+/// const hir = classCaseFold(new RRegex("[a-c]").syntax())
+/// expect(hirToString(hir)).toBe("[A-Ca-c]")
+/// ```
+///
+/// @param {Hir} hir - The syntax tree to case fold
+/// @returns {Hir}
+#[wasm_bindgen(skip_jsdoc, js_name = classCaseFold)]
+pub fn class_case_fold(hir: JsValue) -> Result<JsValue> {
+    let hir: Hir<hir::Hir> = serde_wasm_bindgen::from_value(hir)?;
+    let hir = map_classes(hir.into_inner(), &|class| match class {
+        hir::Class::Unicode(mut class) => {
+            class
+                .try_case_fold_simple()
+                .map_err(serde_wasm_bindgen::Error::new)?;
+            Ok(hir::Class::Unicode(class))
+        }
+        class => Ok(class),
+    })?;
+
+    Hir::from(&hir).try_into()
+}
+
+/// Negates every character class in an `Hir`, such as the one returned by
+/// `RRegex.syntax`, and returns the transformed tree in the same shape.
+///
+/// Both Unicode and byte classes are negated, so `[abc]` becomes `[^abc]`
+/// and vice versa. Note that the parser turns a class with a single
+/// character, like `[a]`, into a literal, which is left untouched.
+///
+/// # Example
+///
+/// ```typescript
+/// import { RRegex, classNegate, hirToString } from "rregex"
+///
+/// const hir = classNegate(new RRegex("[^abc]").syntax())
+/// expect(hirToString(hir)).toBe(hirToString(new RRegex("[abc]").syntax()))
+/// ```
+///
+/// @param {Hir} hir - The syntax tree to negate the classes of
+/// @returns {Hir}
+#[wasm_bindgen(skip_jsdoc, js_name = classNegate)]
+pub fn class_negate(hir: JsValue) -> Result<JsValue> {
+    let hir: Hir<hir::Hir> = serde_wasm_bindgen::from_value(hir)?;
+    let hir = map_classes(hir.into_inner(), &|class| match class {
+        hir::Class::Unicode(mut class) => {
+            class.negate();
+            Ok(hir::Class::Unicode(class))
+        }
+        hir::Class::Bytes(mut class) => {
+            class.negate();
+            Ok(hir::Class::Bytes(class))
+        }
+    })?;
+
+    Hir::from(&hir).try_into()
+}
+
+/// Returns true if and only if the character `ch` belongs to a Unicode
+/// character class, such as the `ClassUnicode` of a `Class` node in the tree
+/// returned by `RRegex.syntax`.
+///
+/// `ch` must be exactly one Unicode scalar value, e.g., `"δ"` or a surrogate
+/// pair like `"😀"`, otherwise an error is returned.
+///
+/// # Example
+///
+/// ```typescript
+/// import { RRegex, classContains } from "rregex"
+///
+/// const hir = new RRegex("[a-cδ]").syntax()
+/// const [unicode] = hir.kind["@values"][0]["@values"]
+/// expect(classContains(unicode, "δ")).toBe(true)
+/// expect(classContains(unicode, "d")).toBe(false)
+/// ```
+///
+/// @param {ClassUnicode} classUnicode - The class to test
+/// @param {string} ch - The character to look for
+/// @returns {boolean}
+#[wasm_bindgen(skip_jsdoc, js_name = classContains)]
+pub fn class_contains(class_unicode: JsValue, ch: js_sys::JsString) -> Result<bool> {
+    let class: Hir<hir::ClassUnicode> = serde_wasm_bindgen::from_value(class_unicode)?;
+    let ch = scalar(&ch)?;
+
+    Ok(class
+        .into_inner()
+        .ranges()
+        .iter()
+        .any(|range| range.start() <= ch && ch <= range.end()))
+}
+
+/// Returns true if and only if the byte `byte` belongs to a byte class, such
+/// as the `ClassBytes` of a `Class` node in the tree returned by
+/// `RRegex.syntax` for a pattern with Unicode mode disabled.
+///
+/// `byte` must be an integer from `0` to `255`, otherwise an error is
+/// returned.
+///
+/// # Example
+///
+/// ```typescript
+/// import { RRegex, byteClassContains } from "rregex"
+///
+/// const hir = new RRegex("(?-u)[\\x00-\\x1F\\x7F]").syntax()
+/// const [bytes] = hir.kind["@values"][0]["@values"]
+/// expect(byteClassContains(bytes, 0x7f)).toBe(true)
+/// expect(byteClassContains(bytes, 0x20)).toBe(false)
+/// ```
+///
+/// @param {ClassBytes} classBytes - The class to test
+/// @param {number} byte - The byte to look for
+/// @returns {boolean}
+#[wasm_bindgen(skip_jsdoc, js_name = byteClassContains)]
+pub fn byte_class_contains(class_bytes: JsValue, byte: f64) -> Result<bool> {
+    let class: Hir<hir::ClassBytes> = serde_wasm_bindgen::from_value(class_bytes)?;
+    if byte.fract() != 0.0 || !(0.0..=255.0).contains(&byte) {
+        return Err(serde_wasm_bindgen::Error::new(
+            "expected a byte value from 0 to 255",
+        ));
+    }
+    let byte = byte as u8;
+
+    Ok(class
+        .into_inner()
+        .ranges()
+        .iter()
+        .any(|range| range.start() <= byte && byte <= range.end()))
+}
+
+/// Returns the ranges of a byte class, such as the `ClassBytes` of a `Class`
+/// node in the tree returned by `RRegex.syntax`, in normalized form: sorted,
+/// with overlapping and adjacent ranges merged.
+///
+/// The ranges of a class built by changing a tree by hand don't need to be
+/// normalized, so this shows the set of bytes it actually matches.
+///
+/// # Example
+///
+/// ```typescript
+/// import { byteClassRanges } from "rregex"
+///
+/// const ranges = byteClassRanges({
+///   "@type": "struct",
+///   "@name": "regex_syntax::hir::ClassBytes",
+///   ranges: [{ start: 5, end: 9 }, { start: 0, end: 4 }],
+/// })
+/// expect(ranges.map(({ start, end }) => [start, end])).toEqual([[0, 9]])
+/// ```
+///
+/// @param {ClassBytes} classBytes - The class to normalize
+/// @returns {ClassBytesRange[]}
+#[wasm_bindgen(skip_jsdoc, js_name = byteClassRanges)]
+pub fn byte_class_ranges(class_bytes: JsValue) -> Result<JsValue> {
+    let class: Hir<hir::ClassBytes> = serde_wasm_bindgen::from_value(class_bytes)?;
+    let class = class.into_inner();
+    let ranges: Vec<Hir<&hir::ClassBytesRange>> = class.ranges().iter().map(Hir::from).collect();
+
+    serde_wasm_bindgen::to_value(&ranges)
+}
+
+/// Decodes a JS string that holds exactly one Unicode scalar value.
+fn scalar(text: &js_sys::JsString) -> Result<char> {
+    let error = || serde_wasm_bindgen::Error::new("expected a single Unicode scalar value");
+    if !text.is_valid_utf16() {
+        return Err(error());
+    }
+
+    let text = String::from(text);
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) => Ok(ch),
+        _ => Err(error()),
+    }
+}
+
+/// Rebuilds `hir` with every character class replaced by the result of `f`.
+fn map_classes<F>(hir: hir::Hir, f: &F) -> Result<hir::Hir>
+where
+    F: Fn(hir::Class) -> Result<hir::Class>,
+{
+    let hir = match hir.into_kind() {
+        hir::HirKind::Empty => hir::Hir::empty(),
+        hir::HirKind::Literal(literal) => hir::Hir::literal(literal.0),
+        hir::HirKind::Class(class) => hir::Hir::class(f(class)?),
+        hir::HirKind::Look(look) => hir::Hir::look(look),
+        hir::HirKind::Repetition(mut repetition) => {
+            repetition.sub = Box::new(map_classes(*repetition.sub, f)?);
+            hir::Hir::repetition(repetition)
+        }
+        hir::HirKind::Capture(mut capture) => {
+            capture.sub = Box::new(map_classes(*capture.sub, f)?);
+            hir::Hir::capture(capture)
+        }
+        hir::HirKind::Concat(subs) => hir::Hir::concat(
+            subs.into_iter()
+                .map(|sub| map_classes(sub, f))
+                .collect::<Result<_>>()?,
+        ),
+        hir::HirKind::Alternation(subs) => hir::Hir::alternation(
+            subs.into_iter()
+                .map(|sub| map_classes(sub, f))
+                .collect::<Result<_>>()?,
+        ),
+    };
+    Ok(hir)
+}
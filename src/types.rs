@@ -1,5 +1,7 @@
+use regex_syntax::ast;
 use regex_syntax::hir;
 use serde::ser::SerializeStruct;
+use serde::Deserialize;
 use serde::Serialize;
 use std::convert::TryFrom;
 use wasm_bindgen::prelude::*;
@@ -86,6 +88,38 @@ impl<'t> Serialize for Match<'t> {
     }
 }
 
+#[wasm_bindgen(typescript_custom_section)]
+const CHAR_LEN_MATCH_TYPE: &'static str = r#"/**
+ * A match along with its length in Unicode scalar values, as returned by
+ * `findAllCharLen`. Unlike `end - start`, which counts bytes, `charLen`
+ * counts every character once, whatever its size.
+ */
+export type CharLenMatch = Match & {
+  charLen: number
+}"#;
+
+pub struct CharLenMatch<'t>(Substring<'t>);
+
+impl<'t> From<Substring<'t>> for CharLenMatch<'t> {
+    fn from(value: Substring<'t>) -> Self {
+        CharLenMatch(value)
+    }
+}
+
+impl<'t> Serialize for CharLenMatch<'t> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut m = serializer.serialize_struct("CharLenMatch", 4)?;
+        m.serialize_field("start", &self.0.start())?;
+        m.serialize_field("end", &self.0.end())?;
+        m.serialize_field("value", &self.0.as_str())?;
+        m.serialize_field("charLen", &self.0.as_str().chars().count())?;
+        m.end()
+    }
+}
+
 #[wasm_bindgen(typescript_custom_section)]
 const CAPTURES_TYPE: &'static str = r#"/**
  * Represents the capture groups for a single match.
@@ -135,6 +169,17 @@ export type Captures = {
   name: Record<string, Match>
 }"#;
 
+#[wasm_bindgen(typescript_custom_section)]
+const TYPED_CAPTURES_TYPE: &'static str = r#"/**
+ * The capture groups of a match with their values coerced, as returned by
+ * `capturesTyped`. Groups that didn't participate in the match are
+ * `undefined`, and values that couldn't be coerced are `null`.
+ */
+export type TypedCaptures = {
+  get: (string | number | boolean | null | undefined)[]
+  name: Record<string, string | number | boolean | null | undefined>
+}"#;
+
 impl<'t> Match<'t> {
     pub fn captures(
         captures: regex::Captures,
@@ -163,850 +208,3783 @@ impl<'t> Match<'t> {
     }
 }
 
-pub struct Hir<T>(T);
+#[wasm_bindgen(typescript_custom_section)]
+const SEGMENT_TYPE: &'static str = r#"/**
+ * A piece of a haystack, as returned by `partition`, that was either matched
+ * by the regex or lies between two matches.
+ *
+ * Like a `Match`, the byte offsets form a half-open interval and always fall
+ * on valid UTF-8 codepoint boundaries.
+ */
+export type Segment = {
+  start: number
+  end: number
+  value: string
 
-impl<T> From<T> for Hir<T> {
-    fn from(value: T) -> Self {
-        Hir(value)
+  /** Whether this segment is a match of the regex. */
+  matched: boolean
+}"#;
+
+pub struct Segment<'t> {
+    haystack: &'t str,
+    start: usize,
+    end: usize,
+    matched: bool,
+}
+
+impl<'t> Segment<'t> {
+    pub fn new(haystack: &'t str, start: usize, end: usize, matched: bool) -> Self {
+        Segment {
+            haystack,
+            start,
+            end,
+            matched,
+        }
     }
 }
 
-impl<T> TryFrom<Hir<T>> for JsValue
-where
-    Hir<T>: Serialize,
-{
-    type Error = serde_wasm_bindgen::Error;
-    fn try_from(value: Hir<T>) -> Result<Self, Self::Error> {
-        serde_wasm_bindgen::to_value(&value)
+impl<'t> Serialize for Segment<'t> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut segment = serializer.serialize_struct("Segment", 4)?;
+        segment.serialize_field("start", &self.start)?;
+        segment.serialize_field("end", &self.end)?;
+        segment.serialize_field("value", &self.haystack[self.start..self.end])?;
+        segment.serialize_field("matched", &self.matched)?;
+        segment.end()
     }
 }
 
 #[wasm_bindgen(typescript_custom_section)]
-const HIR_TYPE: &'static str = r#"/**
- * A high-level intermediate representation (HIR) for a regular expression.
+const FIELD_TYPE: &'static str = r#"/**
+ * A substring of a haystack between two matches, as returned by
+ * `splitFields`.
  */
-export type Hir = {
-  '@type': 'struct'
-  '@name': 'regex_syntax::hir::Hir'
-  kind: HirKind
+export type Field = {
+  start: number
+  end: number
+  value: string
+
+  /** Whether the field is zero-length, e.g., between two adjacent matches. */
+  empty: boolean
 }"#;
 
-impl Serialize for Hir<&hir::Hir> {
+pub struct Field<'t> {
+    haystack: &'t str,
+    start: usize,
+    end: usize,
+}
+
+impl<'t> Field<'t> {
+    pub fn new(haystack: &'t str, start: usize, end: usize) -> Self {
+        Field {
+            haystack,
+            start,
+            end,
+        }
+    }
+}
+
+impl<'t> Serialize for Field<'t> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let mut hir = serializer.serialize_struct("Hir", 3)?;
-        hir.serialize_field("@type", "struct")?;
-        hir.serialize_field("@name", "regex_syntax::hir::Hir")?;
-        hir.serialize_field("kind", &Hir::from(self.0.kind()))?;
-        hir.end()
+        let mut field = serializer.serialize_struct("Field", 4)?;
+        field.serialize_field("value", &self.haystack[self.start..self.end])?;
+        field.serialize_field("start", &self.start)?;
+        field.serialize_field("end", &self.end)?;
+        field.serialize_field("empty", &(self.start == self.end))?;
+        field.end()
     }
 }
 
 #[wasm_bindgen(typescript_custom_section)]
-const HIRKIND_TYPE: &'static str = r#"/**
- * The underlying kind of an arbitrary `Hir` expression.
- *
- * An `HirKind` is principally useful for doing case analysis on the type of a
- * regular expression. If you're looking to build new `Hir` values, then you must
- * use the smart constructors defined on `Hir`, like `Hir::repetition`, to build
- * new `Hir` values. The API intentionally does not expose any way of building an
- * `Hir` directly from an `HirKind`.
+const BYTE_RANGE_TYPE: &'static str = r#"/**
+ * A half-open range of byte offsets in a haystack.
  */
-export type HirKind =
-  | HirKindEmptyVariant
-  | HirKindLiteralVariant
-  | HirKindClassVariant
-  | HirKindLookVariant
-  | HirKindRepetitionVariant
-  | HirKindCaptureVariant
-  | HirKindConcatVariant
-  | HirKindAlternationVariant
+export type ByteRange = {
+  start: number
+  end: number
+}"#;
 
-/**
- * The empty regular expression, which matches everything, including the empty string.
- */
-export type HirKindEmptyVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir::HirKind'
-  '@variant': 'Empty'
+#[derive(Serialize)]
+pub struct ByteRange {
+    pub start: usize,
+    pub end: usize,
 }
 
-/**
- * A literal string that matches exactly these bytes.
+#[wasm_bindgen(typescript_custom_section)]
+const COUNTED_REPLACEMENT_TYPE: &'static str = r#"/**
+ * The result of a replacement along with the number of matches replaced.
  */
-export type HirKindLiteralVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir::HirKind'
-  '@variant': 'Literal'
-  '@values': [Literal]
-}
+export type CountedReplacement = {
+  result: string
+  count: number
+}"#;
 
-/**
- * A single character class that matches any of the characters in the class.
- * A class can either consist of Unicode scalar values as characters, or it can
- * use bytes.
- *
- * A class may be empty. In which case, it matches nothing.
- */
-export type HirKindClassVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir::HirKind'
-  '@variant': 'Class'
-  '@values': [Class]
+#[derive(Serialize)]
+pub struct CountedReplacement {
+    pub result: String,
+    pub count: usize,
 }
 
-/**
- * A look-around assertion. A look-around match always has zero length.
+#[wasm_bindgen(typescript_custom_section)]
+const SPANNED_REPLACEMENT_TYPE: &'static str = r#"/**
+ * The result of a replacement along with where every replacement was made,
+ * as returned by `replaceAllWithSpans`.
  */
-export type HirKindLookVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir::HirKind'
-  '@variant': 'Look'
-  '@values': [Look]
+export type SpannedReplacement = {
+  result: string
+  spans: ReplacementSpan[]
 }
 
 /**
- * A repetition operation applied to a sub-expression.
+ * Where a match was in the original string and where its replacement is in
+ * the result, as byte offsets into each string.
  */
-export type HirKindRepetitionVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir::HirKind'
-  '@variant': 'Repetition'
-  '@values': [Repetition]
+export type ReplacementSpan = {
+  originalStart: number
+  originalEnd: number
+  outputStart: number
+  outputEnd: number
+}"#;
+
+#[derive(Serialize)]
+pub struct SpannedReplacement {
+    pub result: String,
+    pub spans: Vec<ReplacementSpan>,
 }
 
-/**
- * A capturing group, which contains a sub-expression.
- */
-export type HirKindCaptureVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir::HirKind'
-  '@variant': 'Capture'
-  '@values': [Capture]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplacementSpan {
+    pub original_start: usize,
+    pub original_end: usize,
+    pub output_start: usize,
+    pub output_end: usize,
 }
 
-/**
- * A concatenation of expressions.
- *
- * A concatenation matches only if each of its sub-expressions match one after
- * the other.
- *
- * Concatenations are guaranteed by `Hir`'s smart constructors to always have at
- * least two sub-expressions.
+#[wasm_bindgen(typescript_custom_section)]
+const REPLACEMENT_CHANGE_TYPE: &'static str = r#"/**
+ * A replacement that would change a haystack, as returned by
+ * `previewReplaceAll`. `start` and `end` are the byte offsets of the match,
+ * `before` is the matched text and `after` its replacement.
  */
-export type HirKindConcatVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir::HirKind'
-  '@variant': 'Concat'
-  '@values': [Hir[]]
+export type ReplacementChange = {
+  start: number
+  end: number
+  before: string
+  after: string
+}"#;
+
+#[derive(Serialize)]
+pub struct ReplacementChange<'t> {
+    pub start: usize,
+    pub end: usize,
+    pub before: &'t str,
+    pub after: String,
 }
 
-/**
- * An alternation of expressions.
- *
- * An alternation matches only if at least one of its sub-expressions match.
- * If multiple sub-expressions match, then the leftmost is preferred.
- *
- * Alternations are guaranteed by `Hir`'s smart constructors to always have at
- * least two sub-expressions.
+#[wasm_bindgen(typescript_custom_section)]
+const MAYBE_REPLACEMENT_TYPE: &'static str = r#"/**
+ * The result of a replacement that only carries the new string when
+ * something was replaced.
  */
-export type HirKindAlternationVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir::HirKind'
-  '@variant': 'Alternation'
-  '@values': [Hir[]]
+export type MaybeReplacement = {
+  changed: boolean
+
+  /** The string after replacement. Omitted when nothing matched. */
+  result?: string
 }"#;
 
-impl Serialize for Hir<&hir::HirKind> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        let mut hir = serializer.serialize_struct("HirKind", 4)?;
-        hir.serialize_field("@type", "enum")?;
-        hir.serialize_field("@name", "regex_syntax::hir::HirKind")?;
-        match &self.0 {
-            hir::HirKind::Empty => {
-                hir.serialize_field("@variant", "Empty")?;
-            }
-            hir::HirKind::Literal(l) => {
-                hir.serialize_field("@variant", "Literal")?;
-                hir.serialize_field("@values", &vec![Hir::from(l)])?;
-            }
-            hir::HirKind::Class(c) => {
-                hir.serialize_field("@variant", "Class")?;
-                hir.serialize_field("@values", &vec![Hir::from(c)])?;
-            }
-            hir::HirKind::Look(l) => {
-                hir.serialize_field("@variant", "Look")?;
-                hir.serialize_field("@values", &vec![Hir::from(l)])?;
-            }
-            hir::HirKind::Repetition(r) => {
-                hir.serialize_field("@variant", "Repetition")?;
-                hir.serialize_field("@values", &vec![Hir::from(r)])?;
-            }
-            hir::HirKind::Capture(c) => {
-                hir.serialize_field("@variant", "Capture")?;
-                hir.serialize_field("@values", &vec![Hir::from(c)])?;
-            }
-            hir::HirKind::Concat(c) => {
-                let values: Vec<Hir<&hir::Hir>> = c.iter().map(Hir::from).collect();
-                hir.serialize_field("@variant", "Concat")?;
-                hir.serialize_field("@values", &vec![values])?;
-            }
-            hir::HirKind::Alternation(a) => {
-                let values: Vec<Hir<&hir::Hir>> = a.iter().map(Hir::from).collect();
-                hir.serialize_field("@variant", "Alternation")?;
-                hir.serialize_field("@values", &vec![values])?;
-            }
-        };
-        hir.end()
-    }
+#[derive(Serialize)]
+pub struct MaybeReplacement {
+    pub changed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
 }
 
 #[wasm_bindgen(typescript_custom_section)]
-const LITERAL_TYPE: &'static str = r#"/**
- * The high-level intermediate representation of a literal.
- *
- * A literal corresponds to 0 or more bytes that should be matched literally.
- * The smart constructors defined on `Hir` will automatically concatenate adjacent
- * literals into one literal, and will even automatically replace empty literals
- * with `HirKind::Empty`.
+const MATCH_STATS_TYPE: &'static str = r#"/**
+ * How much of a haystack is covered by matches. `coverage` is the fraction
+ * of the bytes of the haystack that are matched, from `0` to `1`.
  */
-export type Literal = {
-  '@type': 'struct'
-  '@name': 'regex_syntax::hir::Literal'
-  '@values': [Uint8Array]
+export type MatchStats = {
+  count: number
+  totalMatchedBytes: number
+  coverage: number
 }"#;
 
-impl Serialize for Hir<&Box<[u8]>> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        serializer.serialize_bytes(self.0.as_ref())
-    }
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchStats {
+    pub count: usize,
+    pub total_matched_bytes: usize,
+    pub coverage: f64,
 }
 
-impl Serialize for Hir<&hir::Literal> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        // let mut bytes = serializer.serialize_bytes(&self.0.0)?;
+#[wasm_bindgen(typescript_custom_section)]
+const COMPLEXITY_TYPE: &'static str = r#"/**
+ * A rough estimate of how expensive a regex is, counted on its syntax tree.
+ */
+export type Complexity = {
+  /** The number of repetitions, bounded or not. */
+  repetitions: number
 
-        // let mut values = serializer.serialize_seq(Some(1))?;
-        // values.serialize_element(&bytes)?;
+  /** The number of repetitions without an upper bound, like `a+`. */
+  unboundedRepetitions: number
 
-        let mut hir = serializer.serialize_struct("Literal", 3)?;
-        hir.serialize_field("@type", "struct")?;
-        hir.serialize_field("@name", "regex_syntax::hir::Literal")?;
-        hir.serialize_field("@values", &vec![&Hir::from(&self.0 .0)])?;
-        hir.end()
-    }
+  /** The number of alternations, like `ab|cd`. */
+  alternations: number
+
+  /** How deeply repetitions are nested, e.g., `2` for `(a+)+`. */
+  maxDepth: number
+}"#;
+
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Complexity {
+    pub repetitions: usize,
+    pub unbounded_repetitions: usize,
+    pub alternations: usize,
+    pub max_depth: usize,
 }
 
 #[wasm_bindgen(typescript_custom_section)]
-const CLASS_TYPE: &'static str = r#"/**
- * The high-level intermediate representation of a character class.
- *
- * A character class corresponds to a set of characters. A character is either
- * defined by a Unicode scalar value or a byte. Unicode characters are used by
- * default, while bytes are used when Unicode mode (via the `u` flag) is disabled.
- *
- * A character class, regardless of its character type, is represented by a
- * sequence of non-overlapping non-adjacent ranges of characters.
- *
- * Note that `Bytes` variant may be produced even when it exclusively matches
- * valid UTF-8. This is because a `Bytes` variant represents an intention by the
- * author of the regular expression to disable Unicode mode, which in turn
- * impacts the semantics of case insensitive matching. For example, `(?i)k` and
- * `(?i-u)k` will not match the same set of strings.
+const COLUMNAR_MATCHES_TYPE: &'static str = r#"/**
+ * Matches laid out as parallel arrays, as returned by `findAllColumnar`, so
+ * that match `i` is `starts[i]..ends[i]` with the value `values[i]`.
  */
-export type Class =
-  | ClassUnicodeVariant
-  | ClassByteVariant
+export type ColumnarMatches = {
+  starts: Uint32Array
+  ends: Uint32Array
+  values: string[]
+}"#;
 
-/**
- * A set of characters represented by Unicode scalar values.
+#[wasm_bindgen(typescript_custom_section)]
+const GROUP_NAME_CONFLICT_TYPE: &'static str = r#"/**
+ * A capture group name defined by more than one pattern of a set, as
+ * returned by `RRegexSet.conflictingGroupNames`.
  */
-export type ClassUnicodeVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir::Class'
-  '@variant': 'Unicode'
-  '@values': [ClassUnicode]
+export type GroupNameConflict = {
+  name: string
+
+  /** The indices of the patterns defining a group with this name. */
+  patterns: number[]
+}"#;
+
+#[derive(Serialize)]
+pub struct GroupNameConflict {
+    pub name: String,
+    pub patterns: Vec<usize>,
 }
 
-/**
- * A set of characters represented by arbitrary bytes (one byte per character).
+#[wasm_bindgen(typescript_custom_section)]
+const SPLIT_INFO_TYPE: &'static str = r#"/**
+ * The result of a bounded split, along with whether it was truncated, i.e.,
+ * whether there is more of the haystack that could have been split further.
  */
-export type ClassByteVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir::Class'
-  '@variant': 'Bytes'
-  '@values': [ClassBytes]
+export type SplitInfo = {
+  parts: string[]
+  truncated: boolean
 }"#;
 
-impl Serialize for Hir<&hir::Class> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        let mut hir = serializer.serialize_struct("Class", 4)?;
-        hir.serialize_field("@type", "enum")?;
-        hir.serialize_field("@name", "regex_syntax::hir::Class")?;
-        match &self.0 {
-            hir::Class::Bytes(b) => {
-                hir.serialize_field("@variant", "Bytes")?;
-                hir.serialize_field("@values", &vec![Hir::from(b)])?;
-            }
-            hir::Class::Unicode(b) => {
-                hir.serialize_field("@variant", "Unicode")?;
-                hir.serialize_field("@values", &vec![Hir::from(b)])?;
-            }
-        }
-        hir.end()
-    }
+#[derive(Serialize)]
+pub struct SplitInfo<'t> {
+    pub parts: Vec<&'t str>,
+    pub truncated: bool,
 }
 
 #[wasm_bindgen(typescript_custom_section)]
-const CLASSUNICODE_TYPE: &'static str = r#"/**
- * A set of characters represented by Unicode scalar values.
+const MATCHING_LINE_TYPE: &'static str = r#"/**
+ * A line of a haystack that contains at least one match, without its line
+ * break. `line` is the zero-based line number and `start` the byte offset
+ * of the line.
  */
-export type ClassUnicode = {
-  '@type': 'struct'
-  '@name': 'regex_syntax::hir::ClassUnicode'
-
-  /** The underlying ranges as a slice. */
-  ranges: ClassUnicodeRange[]
+export type MatchingLine = {
+  line: number
+  start: number
+  text: string
 }"#;
 
-impl Serialize for Hir<&hir::ClassUnicode> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        let ranges: &Vec<Hir<&hir::ClassUnicodeRange>> =
-            &self.0.ranges().iter().map(Hir::from).collect();
-
-        let mut hir = serializer.serialize_struct("ClassUnicode", 3)?;
-        hir.serialize_field("@type", "struct")?;
-        hir.serialize_field("@name", "regex_syntax::hir::ClassUnicode")?;
-        hir.serialize_field("ranges", ranges)?;
-        hir.end()
-    }
+#[derive(Serialize)]
+pub struct MatchingLine<'t> {
+    pub line: usize,
+    pub start: usize,
+    pub text: &'t str,
 }
 
 #[wasm_bindgen(typescript_custom_section)]
-const CLASSUNICODERANGE_TYPE: &'static str = r#"/**
- * A single range of characters represented by Unicode scalar values.
- *
- * The range is closed. That is, the start and end of the range are included in
- * the range.
+const LINE_MATCH_TYPE: &'static str = r#"/**
+ * A match along with the line it's on, as returned by `findAllWithLine`.
+ * `lineStart` and `lineEnd` are the byte offsets of the line, without its
+ * line break, and `lineText` is its text.
  */
-export type ClassUnicodeRange = {
-  '@type': 'struct'
-  '@name': 'regex_syntax::hir::ClassUnicodeRange'
-
-  /**
-   * Return the start of this range.
-   *
-   * The start of a range is always less than or equal to the end of the range.
-   */
-  start: string
+export type LineMatch = Match & {
+  lineStart: number
+  lineEnd: number
+  lineText: string
+}"#;
 
-  /**
-   * Return the end of this range.
-   *
-   * The end of a range is always greater than or equal to the start of the range.
-   */
-  end: string
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LineMatch<'t> {
+    pub start: usize,
+    pub end: usize,
+    pub value: &'t str,
+    pub line_start: usize,
+    pub line_end: usize,
+    pub line_text: &'t str,
+}
 
-  /**
-   * The number of codepoints in this range.
-   */
-  len: number
+#[wasm_bindgen(typescript_custom_section)]
+const CAPTURE_NODE_TYPE: &'static str = r#"/**
+ * The match of a capture group, along with the matches of the groups nested
+ * in it, in the order they appear in the pattern.
+ */
+export type CaptureNode = {
+  index: number
+  name: string | undefined
+  start: number
+  end: number
+  children: CaptureNode[]
 }"#;
 
-impl Serialize for Hir<&hir::ClassUnicodeRange> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        let mut hir = serializer.serialize_struct("ClassUnicodeRange", 5)?;
-        hir.serialize_field("@type", "struct")?;
-        hir.serialize_field("@name", "regex_syntax::hir::ClassUnicodeRange")?;
-        hir.serialize_field("start", &self.0.start())?;
-        hir.serialize_field("end", &self.0.end())?;
-        hir.serialize_field("len", &self.0.len())?;
-        hir.end()
-    }
+#[derive(Serialize)]
+pub struct CaptureNode<'r> {
+    pub index: usize,
+    pub name: Option<&'r str>,
+    pub start: usize,
+    pub end: usize,
+    pub children: Vec<CaptureNode<'r>>,
 }
 
 #[wasm_bindgen(typescript_custom_section)]
-const CLASSBYTES_TYPE: &'static str = r#"/**
- * A set of characters represented by arbitrary bytes (where one byte corresponds
- * to one character).
+const POSITIONED_MATCH_TYPE: &'static str = r#"/**
+ * A match with the line and column of both its ends, for editors.
  */
-export type ClassBytes = {
-  '@type': 'struct'
-  '@name': 'regex_syntax::hir::ClassBytes'
+export type PositionedMatch = {
+  start: TextPosition
+  end: TextPosition
+  value: string
+}
 
-  /** The underlying ranges as a slice. */
-  ranges: ClassBytesRange[]
+/**
+ * A position in a haystack. `offset` is a byte offset, `line` is zero-based,
+ * and `column` is the zero-based number of UTF-16 code units since the start
+ * of the line, the same unit JS string indices use.
+ */
+export type TextPosition = {
+  offset: number
+  line: number
+  column: number
 }"#;
 
-impl Serialize for Hir<&hir::ClassBytes> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        let ranges: &Vec<Hir<&hir::ClassBytesRange>> =
-            &self.0.ranges().iter().map(Hir::from).collect();
+#[derive(Serialize)]
+pub struct PositionedMatch<'t> {
+    pub start: TextPosition,
+    pub end: TextPosition,
+    pub value: &'t str,
+}
 
-        let mut hir = serializer.serialize_struct("ClassBytes", 3)?;
-        hir.serialize_field("@type", "struct")?;
-        hir.serialize_field("@name", "regex_syntax::hir::ClassBytes")?;
-        hir.serialize_field("ranges", ranges)?;
-        hir.end()
+#[derive(Serialize)]
+pub struct TextPosition {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl TextPosition {
+    /// Returns the position of the byte `offset` in `haystack`, where lines
+    /// are split on `\n`.
+    pub fn new(haystack: &str, offset: usize) -> Self {
+        let before = &haystack[..offset];
+        let line_start = before.rfind('\n').map_or(0, |position| position + 1);
+        TextPosition {
+            offset,
+            line: before.matches('\n').count(),
+            column: before[line_start..].encode_utf16().count(),
+        }
     }
 }
 
 #[wasm_bindgen(typescript_custom_section)]
-const CLASSBYTESRANGE_TYPE: &'static str = r#"/**
- * A single range of characters represented by arbitrary bytes.
- *
- * The range is closed. That is, the start and end of the range are included in
- * the range.
+const CONTEXT_MATCH_TYPE: &'static str = r#"/**
+ * A match along with the text around it, for previews. `before` and `after`
+ * hold up to a given number of characters right before and after the match.
  */
-export type ClassBytesRange = {
-  '@type': 'struct'
-  '@name': 'regex_syntax::hir::ClassBytesRange'
-
-  /**
-   * The start of this range.
-   *
-   * The start of a range is always less than or equal to the end of the range.
-   */
+export type ContextMatch = {
   start: number
-
-  /**
-   * The end of this range.
-   *
-   * The end of a range is always greater than or equal to the start of the range.
-   */
   end: number
-
-  /**
-   * The number of bytes in this range.
-   */
-  len: number
+  value: string
+  before: string
+  after: string
 }"#;
 
-impl Serialize for Hir<&hir::ClassBytesRange> {
+#[derive(Serialize)]
+pub struct ContextMatch<'t> {
+    pub start: usize,
+    pub end: usize,
+    pub value: &'t str,
+    pub before: &'t str,
+    pub after: &'t str,
+}
+
+impl<'t> ContextMatch<'t> {
+    /// Returns the match from `start` to `end` in `haystack`, with up to
+    /// `context` Unicode scalar values on each side.
+    pub fn new(haystack: &'t str, start: usize, end: usize, context: usize) -> Self {
+        let before = &haystack[..start];
+        let before_start = match context.checked_sub(1) {
+            Some(n) => before.char_indices().rev().nth(n).map_or(0, |(i, _)| i),
+            None => start,
+        };
+        let after = &haystack[end..];
+        let after_end = after.char_indices().nth(context).map_or(after.len(), |(i, _)| i);
+
+        ContextMatch {
+            start,
+            end,
+            value: &haystack[start..end],
+            before: &before[before_start..],
+            after: &after[..after_end],
+        }
+    }
+}
+
+/// A match with offsets translated to some other unit or origin, e.g.,
+/// UTF-16 code units or bytes from the start of a stream, serialized with
+/// the same shape as a `Match`.
+#[derive(Serialize)]
+pub struct RebasedMatch<'t> {
+    pub start: usize,
+    pub end: usize,
+    pub value: &'t str,
+}
+
+/// A piece of a haystack that isn't necessarily a match, serialized with
+/// the same shape as a `Match`.
+pub struct Substring<'t> {
+    haystack: &'t str,
+    start: usize,
+    end: usize,
+}
+
+impl<'t> Substring<'t> {
+    pub fn new(haystack: &'t str, start: usize, end: usize) -> Self {
+        Substring {
+            haystack,
+            start,
+            end,
+        }
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    pub fn as_str(&self) -> &'t str {
+        &self.haystack[self.start..self.end]
+    }
+}
+
+impl<'t> Serialize for Substring<'t> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let mut hir = serializer.serialize_struct("ClassBytesRange", 5)?;
-        hir.serialize_field("@type", "struct")?;
-        hir.serialize_field("@name", "regex_syntax::hir::ClassBytesRange")?;
-        hir.serialize_field("start", &self.0.start())?;
-        hir.serialize_field("end", &self.0.end())?;
-        hir.serialize_field("len", &self.0.len())?;
-        hir.end()
+        let mut substring = serializer.serialize_struct("Substring", 3)?;
+        substring.serialize_field("start", &self.start)?;
+        substring.serialize_field("end", &self.end)?;
+        substring.serialize_field("value", &self.haystack[self.start..self.end])?;
+        substring.end()
     }
 }
 
 #[wasm_bindgen(typescript_custom_section)]
-const LOOK_TYPE: &'static str = r#" /**
- * The high-level intermediate representation for a look-around assertion.
+const SYNTAX_OPTIONS_TYPE: &'static str = r#"/**
+ * The options used to parse a regular expression into an `Hir`.
  *
- * An assertion match is always zero-length. Also called an “empty match.”
+ * Every option is disabled by default, except for `unicode`.
+ *
+ * @see https://docs.rs/regex-syntax/latest/regex_syntax/struct.ParserBuilder.html
  */
-export type Look =
-  | LookStartVariant
-  | LookEndVariant
-  | LookStartLFVariant
-  | LookEndLFVariant
-  | LookStartCRLFVariant
-  | LookEndCRLFVariant
-  | LookWordAsciiVariant
-  | LookWordAsciiNegateVariant
-  | LookWordUnicodeVariant
-  | LookWordUnicodeNegateVariant
-  | LookWordStartAsciiVariant
-  | LookWordEndAsciiVariant
-  | LookWordStartUnicodeVariant
-  | LookWordEndUnicodeVariant
-  | LookWordStartHalfAsciiVariant
-  | LookWordEndHalfAsciiVariant
-  | LookWordStartHalfUnicodeVariant
-  | LookWordEndHalfUnicodeVariant
+export type SyntaxOptions = {
+  /** Enable or disable the case insensitive flag (`i`) by default. */
+  caseInsensitive?: boolean
 
-/**
- * Match the beginning of text. Specifically, this matches at the starting
- * position of the input.
- */
-export type LookStartVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir:Look'
-  '@variant': 'Start'
+  /** Enable or disable the multi-line matching flag (`m`) by default. */
+  multiLine?: boolean
+
+  /** Enable or disable the "dot matches any character" flag (`s`) by default. */
+  dotMatchesNewLine?: boolean
+
+  /** Enable verbose mode (`x`) in the regular expression. */
+  ignoreWhitespace?: boolean
+
+  /** Enable or disable the Unicode flag (`u`) by default. */
+  unicode?: boolean
+
+  /** Whether to support octal syntax or not. */
+  octal?: boolean
+}"#;
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SyntaxOptions {
+    case_insensitive: bool,
+    multi_line: bool,
+    dot_matches_new_line: bool,
+    ignore_whitespace: bool,
+    unicode: Option<bool>,
+    octal: bool,
 }
 
-/**
- * Match the end of text. Specifically, this matches at the ending position of
- * the input.
- */
-export type LookEndVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir:Look'
-  '@variant': 'End'
+impl SyntaxOptions {
+    pub fn parser(&self) -> regex_syntax::Parser {
+        regex_syntax::ParserBuilder::new()
+            .case_insensitive(self.case_insensitive)
+            .multi_line(self.multi_line)
+            .dot_matches_new_line(self.dot_matches_new_line)
+            .ignore_whitespace(self.ignore_whitespace)
+            .unicode(self.unicode.unwrap_or(true))
+            .octal(self.octal)
+            .build()
+    }
 }
 
-/**
- * Match the beginning of a line or the beginning of text. Specifically, this
- * matches at the starting position of the input, or at the position immediately
- * following a `\n` character.
- */
-export type LookStartLFVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir:Look'
-  '@variant': 'StartLF'
+/// The options a `RRegex` was compiled with, in the same notation as the
+/// inline flags of a pattern, e.g. `(?im)`.
+///
+/// Options without an inline flag, like `octal` or `line_terminator`, aren't
+/// part of that notation and are kept separately in a `RRegexJSON`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct RegexFlags {
+    pub case_insensitive: bool,
+    pub multi_line: bool,
+    pub dot_matches_new_line: bool,
+    pub ignore_whitespace: bool,
+    pub swap_greed: bool,
+    pub unicode: bool,
+    pub crlf: bool,
+    pub octal: bool,
+    pub line_terminator: u8,
+    pub longest: bool,
 }
 
-/**
- * Match the end of a line or the end of text. Specifically, this matches at the
- * end position of the input, or at the position immediately preceding a `\n`
- * character.
- */
-export type LookEndLFVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir:Look'
-  '@variant': 'EndLF'
+impl Default for RegexFlags {
+    fn default() -> Self {
+        RegexFlags {
+            case_insensitive: false,
+            multi_line: false,
+            dot_matches_new_line: false,
+            ignore_whitespace: false,
+            swap_greed: false,
+            unicode: true,
+            crlf: false,
+            octal: false,
+            line_terminator: b'\n',
+            longest: false,
+        }
+    }
 }
 
-/**
- * Match the beginning of a line or the beginning of text. Specifically, this
- * matches at the starting position of the input, or at the position immediately
- * following either a `\r` or `\n` character, but never after a `\r` when a `\n`
- * follows.
+impl RegexFlags {
+    /// Parses flags written like the inline flags of a pattern, e.g. `ims`.
+    /// Flags after a `-` are disabled.
+    pub fn parse(flags: &str) -> Result<RegexFlags, serde_wasm_bindgen::Error> {
+        let mut result = RegexFlags::default();
+        let mut enabled = true;
+        for flag in flags.chars() {
+            match flag {
+                '-' if enabled => enabled = false,
+                'i' => result.case_insensitive = enabled,
+                'm' => result.multi_line = enabled,
+                's' => result.dot_matches_new_line = enabled,
+                'x' => result.ignore_whitespace = enabled,
+                'U' => result.swap_greed = enabled,
+                'u' => result.unicode = enabled,
+                'R' => result.crlf = enabled,
+                _ => {
+                    return Err(serde_wasm_bindgen::Error::new(format!(
+                        "invalid flags '{}'",
+                        flags
+                    )))
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Applies the given inline flags, e.g. the `i-s` in `(?i-s)`, on top of
+    /// these flags.
+    pub fn apply(&mut self, flags: &ast::Flags) {
+        let mut enabled = true;
+        for item in flags.items.iter() {
+            match item.kind {
+                ast::FlagsItemKind::Negation => enabled = false,
+                ast::FlagsItemKind::Flag(ast::Flag::CaseInsensitive) => {
+                    self.case_insensitive = enabled
+                }
+                ast::FlagsItemKind::Flag(ast::Flag::MultiLine) => self.multi_line = enabled,
+                ast::FlagsItemKind::Flag(ast::Flag::DotMatchesNewLine) => {
+                    self.dot_matches_new_line = enabled
+                }
+                ast::FlagsItemKind::Flag(ast::Flag::IgnoreWhitespace) => {
+                    self.ignore_whitespace = enabled
+                }
+                ast::FlagsItemKind::Flag(ast::Flag::SwapGreed) => self.swap_greed = enabled,
+                ast::FlagsItemKind::Flag(ast::Flag::Unicode) => self.unicode = enabled,
+                ast::FlagsItemKind::Flag(ast::Flag::CRLF) => self.crlf = enabled,
+            }
+        }
+    }
+
+    /// Packs the boolean options in a bitfield, one bit per option.
+    pub fn bits(&self) -> u16 {
+        [
+            self.case_insensitive,
+            self.multi_line,
+            self.dot_matches_new_line,
+            self.ignore_whitespace,
+            self.swap_greed,
+            self.unicode,
+            self.crlf,
+            self.octal,
+            self.longest,
+        ]
+        .iter()
+        .enumerate()
+        .fold(0, |bits, (bit, enabled)| bits | (u16::from(*enabled) << bit))
+    }
+
+    /// Unpacks the options packed by `bits`.
+    pub fn from_bits(bits: u16, line_terminator: u8) -> RegexFlags {
+        let enabled = |bit: u16| bits & (1 << bit) != 0;
+        RegexFlags {
+            case_insensitive: enabled(0),
+            multi_line: enabled(1),
+            dot_matches_new_line: enabled(2),
+            ignore_whitespace: enabled(3),
+            swap_greed: enabled(4),
+            unicode: enabled(5),
+            crlf: enabled(6),
+            octal: enabled(7),
+            line_terminator,
+            longest: enabled(8),
+        }
+    }
+
+    pub fn builder(&self, pattern: &str) -> regex::RegexBuilder {
+        let mut builder = regex::RegexBuilder::new(pattern);
+        builder
+            .case_insensitive(self.case_insensitive)
+            .multi_line(self.multi_line)
+            .dot_matches_new_line(self.dot_matches_new_line)
+            .ignore_whitespace(self.ignore_whitespace)
+            .swap_greed(self.swap_greed)
+            .unicode(self.unicode)
+            .crlf(self.crlf)
+            .octal(self.octal)
+            .line_terminator(self.line_terminator);
+        builder
+    }
+
+    pub fn parser(&self) -> regex_syntax::Parser {
+        regex_syntax::ParserBuilder::new()
+            .case_insensitive(self.case_insensitive)
+            .multi_line(self.multi_line)
+            .dot_matches_new_line(self.dot_matches_new_line)
+            .ignore_whitespace(self.ignore_whitespace)
+            .swap_greed(self.swap_greed)
+            .unicode(self.unicode)
+            .crlf(self.crlf)
+            .octal(self.octal)
+            .line_terminator(self.line_terminator)
+            .build()
+    }
+}
+
+/// The size limits of a `RRegexSet`, which the patterns of the set are also
+/// compiled with when they're needed on their own. `None` keeps the default
+/// of the `regex` crate.
+#[derive(Clone, Copy, Default)]
+pub struct SizeLimits {
+    pub size: Option<usize>,
+    pub dfa_size: Option<usize>,
+}
+
+impl SizeLimits {
+    pub fn apply(&self, builder: &mut regex::RegexBuilder) {
+        if let Some(bytes) = self.size {
+            builder.size_limit(bytes);
+        }
+        if let Some(bytes) = self.dfa_size {
+            builder.dfa_size_limit(bytes);
+        }
+    }
+}
+
+impl std::fmt::Display for RegexFlags {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let flags = [
+            (self.case_insensitive, 'i'),
+            (self.multi_line, 'm'),
+            (self.dot_matches_new_line, 's'),
+            (self.ignore_whitespace, 'x'),
+            (self.swap_greed, 'U'),
+            (self.crlf, 'R'),
+        ];
+        for (enabled, flag) in flags.iter() {
+            if *enabled {
+                write!(f, "{}", flag)?;
+            }
+        }
+        if !self.unicode {
+            write!(f, "-u")?;
+        }
+        Ok(())
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const RREGEX_JSON_TYPE: &'static str = r#"/**
+ * The JSON representation of a `RRegex`, as returned by `toJSON` and
+ * accepted by `RRegex.fromJSON`.
  */
-export type LookStartCRLFVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir:Look'
-  '@variant': 'StartCRLF'
+export type RRegexJSON = {
+  /** The pattern of the regular expression. */
+  source: string
+
+  /** The options the regular expression was compiled with, e.g. `"im-u"`. */
+  flags: string
+
+  /** Whether octal escapes like `\141` are supported. Omitted when `false`. */
+  octal?: boolean
+
+  /**
+   * The byte `^`, `$` and `.` treat as the end of a line, as a char code.
+   * Omitted when it's `\n`.
+   */
+  lineTerminator?: number
+
+  /** Whether `find` reports leftmost-longest matches. Omitted when `false`. */
+  longestMatch?: boolean
+}"#;
+
+#[derive(Serialize, Deserialize)]
+pub struct RRegexJson {
+    pub source: String,
+    #[serde(default)]
+    pub flags: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub octal: bool,
+    #[serde(
+        rename = "lineTerminator",
+        default = "RRegexJson::default_line_terminator",
+        skip_serializing_if = "RRegexJson::is_default_line_terminator"
+    )]
+    pub line_terminator: u8,
+    #[serde(
+        rename = "longestMatch",
+        default,
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub longest_match: bool,
 }
 
-/**
- * Match the end of a line or the end of text. Specifically, this matches at the
- * end position of the input, or at the position immediately preceding a `\r` or
- * `\n` character, but never before a `\n` when a `\r` precedes it.
+impl RRegexJson {
+    fn default_line_terminator() -> u8 {
+        b'\n'
+    }
+
+    fn is_default_line_terminator(line_terminator: &u8) -> bool {
+        *line_terminator == b'\n'
+    }
+}
+
+pub struct Hir<T>(T);
+
+impl<T> From<T> for Hir<T> {
+    fn from(value: T) -> Self {
+        Hir(value)
+    }
+}
+
+impl<T> TryFrom<Hir<T>> for JsValue
+where
+    Hir<T>: Serialize,
+{
+    type Error = serde_wasm_bindgen::Error;
+    fn try_from(value: Hir<T>) -> Result<Self, Self::Error> {
+        serde_wasm_bindgen::to_value(&value)
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const HIR_TYPE: &'static str = r#"/**
+ * A high-level intermediate representation (HIR) for a regular expression.
  */
-export type LookEndCRLFVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir:Look'
-  '@variant': 'EndCRLF'
+export type Hir = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::hir::Hir'
+  kind: HirKind
+}"#;
+
+impl Serialize for Hir<&hir::Hir> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut hir = serializer.serialize_struct("Hir", 3)?;
+        hir.serialize_field("@type", "struct")?;
+        hir.serialize_field("@name", "regex_syntax::hir::Hir")?;
+        hir.serialize_field("kind", &Hir::from(self.0.kind()))?;
+        hir.end()
+    }
 }
 
+#[wasm_bindgen(typescript_custom_section)]
+const HIRKIND_TYPE: &'static str = r#"/**
+ * The underlying kind of an arbitrary `Hir` expression.
+ *
+ * An `HirKind` is principally useful for doing case analysis on the type of a
+ * regular expression. If you're looking to build new `Hir` values, then you must
+ * use the smart constructors defined on `Hir`, like `Hir::repetition`, to build
+ * new `Hir` values. The API intentionally does not expose any way of building an
+ * `Hir` directly from an `HirKind`.
+ */
+export type HirKind =
+  | HirKindEmptyVariant
+  | HirKindLiteralVariant
+  | HirKindClassVariant
+  | HirKindLookVariant
+  | HirKindRepetitionVariant
+  | HirKindCaptureVariant
+  | HirKindConcatVariant
+  | HirKindAlternationVariant
+
 /**
- * Match an ASCII-only word boundary. That is, this matches a position where the
- * left adjacent character and right adjacent character correspond to a word and
- * non-word or a non-word and word character.
+ * The empty regular expression, which matches everything, including the empty string.
  */
-export type LookWordAsciiVariant = {
+export type HirKindEmptyVariant = {
   '@type': 'enum'
-  '@name': 'regex_syntax::hir:Look'
-  '@variant': 'WordAscii'
+  '@name': 'regex_syntax::hir::HirKind'
+  '@variant': 'Empty'
 }
 
 /**
- * Match an ASCII-only negation of a word boundary.
+ * A literal string that matches exactly these bytes.
  */
-export type LookWordAsciiNegateVariant = {
+export type HirKindLiteralVariant = {
   '@type': 'enum'
-  '@name': 'regex_syntax::hir:Look'
-  '@variant': 'WordAsciiNegate'
+  '@name': 'regex_syntax::hir::HirKind'
+  '@variant': 'Literal'
+  '@values': [Literal]
 }
 
 /**
- * Match a Unicode-aware word boundary. That is, this matches a position where
- * the left adjacent character and right adjacent character correspond to a word
- * and non-word or a non-word and word character.
+ * A single character class that matches any of the characters in the class.
+ * A class can either consist of Unicode scalar values as characters, or it can
+ * use bytes.
+ *
+ * A class may be empty. In which case, it matches nothing.
  */
-export type LookWordUnicodeVariant = {
+export type HirKindClassVariant = {
   '@type': 'enum'
-  '@name': 'regex_syntax::hir:Look'
-  '@variant': 'WordUnicode'
+  '@name': 'regex_syntax::hir::HirKind'
+  '@variant': 'Class'
+  '@values': [Class]
 }
 
 /**
- * Match a Unicode-aware negation of a word boundary.
+ * A look-around assertion. A look-around match always has zero length.
  */
-export type LookWordUnicodeNegateVariant = {
+export type HirKindLookVariant = {
   '@type': 'enum'
-  '@name': 'regex_syntax::hir:Look'
-  '@variant': 'WordUnicodeNegate'
+  '@name': 'regex_syntax::hir::HirKind'
+  '@variant': 'Look'
+  '@values': [Look]
 }
 
 /**
- * Match the start of an ASCII-only word boundary. That is, this matches a
- * position at either the beginning of the haystack or where the previous
- * character is not a word character and the following character is a word
- * character.
+ * A repetition operation applied to a sub-expression.
  */
-export type LookWordStartAsciiVariant = {
+export type HirKindRepetitionVariant = {
   '@type': 'enum'
-  '@name': 'regex_syntax::hir:Look'
-  '@variant': 'WordStartAscii'
+  '@name': 'regex_syntax::hir::HirKind'
+  '@variant': 'Repetition'
+  '@values': [Repetition]
 }
 
 /**
- * Match the end of an ASCII-only word boundary. That is, this matches a
- * position at either the end of the haystack orwhere the previous character is
- * a word character and the following character is not a word character.
+ * A capturing group, which contains a sub-expression.
  */
-export type LookWordEndAsciiVariant = {
+export type HirKindCaptureVariant = {
   '@type': 'enum'
-  '@name': 'regex_syntax::hir:Look'
-  '@variant': 'WordEndAscii'
+  '@name': 'regex_syntax::hir::HirKind'
+  '@variant': 'Capture'
+  '@values': [Capture]
 }
 
 /**
- * Match the start of a Unicode word boundary. That is, this matches a position
- * at either the beginning of the haystack or where the previous character is
- * not a word character and the following character is a word character.
+ * A concatenation of expressions.
+ *
+ * A concatenation matches only if each of its sub-expressions match one after
+ * the other.
+ *
+ * Concatenations are guaranteed by `Hir`'s smart constructors to always have at
+ * least two sub-expressions.
  */
-export type LookWordStartUnicodeVariant = {
+export type HirKindConcatVariant = {
   '@type': 'enum'
-  '@name': 'regex_syntax::hir:Look'
-  '@variant': 'WordStartUnicode'
+  '@name': 'regex_syntax::hir::HirKind'
+  '@variant': 'Concat'
+  '@values': [Hir[]]
 }
 
 /**
- * Match the end of a Unicode word boundary. That is, this matches a position at
- *  either the end of the haystack or where the previous character is a word
- * character and the following character is not a word character.
+ * An alternation of expressions.
+ *
+ * An alternation matches only if at least one of its sub-expressions match.
+ * If multiple sub-expressions match, then the leftmost is preferred.
+ *
+ * Alternations are guaranteed by `Hir`'s smart constructors to always have at
+ * least two sub-expressions.
  */
-export type LookWordEndUnicodeVariant = {
+export type HirKindAlternationVariant = {
   '@type': 'enum'
-  '@name': 'regex_syntax::hir:Look'
-  '@variant': 'WordEndUnicode'
+  '@name': 'regex_syntax::hir::HirKind'
+  '@variant': 'Alternation'
+  '@values': [Hir[]]
+}"#;
+
+impl Serialize for Hir<&hir::HirKind> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut hir = serializer.serialize_struct("HirKind", 4)?;
+        hir.serialize_field("@type", "enum")?;
+        hir.serialize_field("@name", "regex_syntax::hir::HirKind")?;
+        match &self.0 {
+            hir::HirKind::Empty => {
+                hir.serialize_field("@variant", "Empty")?;
+            }
+            hir::HirKind::Literal(l) => {
+                hir.serialize_field("@variant", "Literal")?;
+                hir.serialize_field("@values", &vec![Hir::from(l)])?;
+            }
+            hir::HirKind::Class(c) => {
+                hir.serialize_field("@variant", "Class")?;
+                hir.serialize_field("@values", &vec![Hir::from(c)])?;
+            }
+            hir::HirKind::Look(l) => {
+                hir.serialize_field("@variant", "Look")?;
+                hir.serialize_field("@values", &vec![Hir::from(l)])?;
+            }
+            hir::HirKind::Repetition(r) => {
+                hir.serialize_field("@variant", "Repetition")?;
+                hir.serialize_field("@values", &vec![Hir::from(r)])?;
+            }
+            hir::HirKind::Capture(c) => {
+                hir.serialize_field("@variant", "Capture")?;
+                hir.serialize_field("@values", &vec![Hir::from(c)])?;
+            }
+            hir::HirKind::Concat(c) => {
+                let values: Vec<Hir<&hir::Hir>> = c.iter().map(Hir::from).collect();
+                hir.serialize_field("@variant", "Concat")?;
+                hir.serialize_field("@values", &vec![values])?;
+            }
+            hir::HirKind::Alternation(a) => {
+                let values: Vec<Hir<&hir::Hir>> = a.iter().map(Hir::from).collect();
+                hir.serialize_field("@variant", "Alternation")?;
+                hir.serialize_field("@values", &vec![values])?;
+            }
+        };
+        hir.end()
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const LITERAL_TYPE: &'static str = r#"/**
+ * The high-level intermediate representation of a literal.
+ *
+ * A literal corresponds to 0 or more bytes that should be matched literally.
+ * The smart constructors defined on `Hir` will automatically concatenate adjacent
+ * literals into one literal, and will even automatically replace empty literals
+ * with `HirKind::Empty`.
+ */
+export type Literal = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::hir::Literal'
+  '@values': [Uint8Array]
+}"#;
+
+impl Serialize for Hir<&Box<[u8]>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(self.0.as_ref())
+    }
+}
+
+impl Serialize for Hir<&hir::Literal> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // let mut bytes = serializer.serialize_bytes(&self.0.0)?;
+
+        // let mut values = serializer.serialize_seq(Some(1))?;
+        // values.serialize_element(&bytes)?;
+
+        let mut hir = serializer.serialize_struct("Literal", 3)?;
+        hir.serialize_field("@type", "struct")?;
+        hir.serialize_field("@name", "regex_syntax::hir::Literal")?;
+        hir.serialize_field("@values", &vec![&Hir::from(&self.0 .0)])?;
+        hir.end()
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const CLASS_TYPE: &'static str = r#"/**
+ * The high-level intermediate representation of a character class.
+ *
+ * A character class corresponds to a set of characters. A character is either
+ * defined by a Unicode scalar value or a byte. Unicode characters are used by
+ * default, while bytes are used when Unicode mode (via the `u` flag) is disabled.
+ *
+ * A character class, regardless of its character type, is represented by a
+ * sequence of non-overlapping non-adjacent ranges of characters.
+ *
+ * Note that `Bytes` variant may be produced even when it exclusively matches
+ * valid UTF-8. This is because a `Bytes` variant represents an intention by the
+ * author of the regular expression to disable Unicode mode, which in turn
+ * impacts the semantics of case insensitive matching. For example, `(?i)k` and
+ * `(?i-u)k` will not match the same set of strings.
+ */
+export type Class =
+  | ClassUnicodeVariant
+  | ClassByteVariant
+
+/**
+ * A set of characters represented by Unicode scalar values.
+ */
+export type ClassUnicodeVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir::Class'
+  '@variant': 'Unicode'
+  '@values': [ClassUnicode]
+}
+
+/**
+ * A set of characters represented by arbitrary bytes (one byte per character).
+ */
+export type ClassByteVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir::Class'
+  '@variant': 'Bytes'
+  '@values': [ClassBytes]
+}"#;
+
+impl Serialize for Hir<&hir::Class> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut hir = serializer.serialize_struct("Class", 4)?;
+        hir.serialize_field("@type", "enum")?;
+        hir.serialize_field("@name", "regex_syntax::hir::Class")?;
+        match &self.0 {
+            hir::Class::Bytes(b) => {
+                hir.serialize_field("@variant", "Bytes")?;
+                hir.serialize_field("@values", &vec![Hir::from(b)])?;
+            }
+            hir::Class::Unicode(b) => {
+                hir.serialize_field("@variant", "Unicode")?;
+                hir.serialize_field("@values", &vec![Hir::from(b)])?;
+            }
+        }
+        hir.end()
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const CLASSUNICODE_TYPE: &'static str = r#"/**
+ * A set of characters represented by Unicode scalar values.
+ */
+export type ClassUnicode = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::hir::ClassUnicode'
+
+  /** The underlying ranges as a slice. */
+  ranges: ClassUnicodeRange[]
+}"#;
+
+impl Serialize for Hir<&hir::ClassUnicode> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let ranges: &Vec<Hir<&hir::ClassUnicodeRange>> =
+            &self.0.ranges().iter().map(Hir::from).collect();
+
+        let mut hir = serializer.serialize_struct("ClassUnicode", 3)?;
+        hir.serialize_field("@type", "struct")?;
+        hir.serialize_field("@name", "regex_syntax::hir::ClassUnicode")?;
+        hir.serialize_field("ranges", ranges)?;
+        hir.end()
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const CLASSUNICODERANGE_TYPE: &'static str = r#"/**
+ * A single range of characters represented by Unicode scalar values.
+ *
+ * The range is closed. That is, the start and end of the range are included in
+ * the range.
+ */
+export type ClassUnicodeRange = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::hir::ClassUnicodeRange'
+
+  /**
+   * Return the start of this range.
+   *
+   * The start of a range is always less than or equal to the end of the range.
+   */
+  start: string
+
+  /**
+   * Return the end of this range.
+   *
+   * The end of a range is always greater than or equal to the start of the range.
+   */
+  end: string
+
+  /**
+   * The number of codepoints in this range.
+   */
+  len: number
+}"#;
+
+impl Serialize for Hir<&hir::ClassUnicodeRange> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut hir = serializer.serialize_struct("ClassUnicodeRange", 5)?;
+        hir.serialize_field("@type", "struct")?;
+        hir.serialize_field("@name", "regex_syntax::hir::ClassUnicodeRange")?;
+        hir.serialize_field("start", &self.0.start())?;
+        hir.serialize_field("end", &self.0.end())?;
+        hir.serialize_field("len", &self.0.len())?;
+        hir.end()
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const CLASSBYTES_TYPE: &'static str = r#"/**
+ * A set of characters represented by arbitrary bytes (where one byte corresponds
+ * to one character).
+ */
+export type ClassBytes = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::hir::ClassBytes'
+
+  /** The underlying ranges as a slice. */
+  ranges: ClassBytesRange[]
+}"#;
+
+impl Serialize for Hir<&hir::ClassBytes> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let ranges: &Vec<Hir<&hir::ClassBytesRange>> =
+            &self.0.ranges().iter().map(Hir::from).collect();
+
+        let mut hir = serializer.serialize_struct("ClassBytes", 3)?;
+        hir.serialize_field("@type", "struct")?;
+        hir.serialize_field("@name", "regex_syntax::hir::ClassBytes")?;
+        hir.serialize_field("ranges", ranges)?;
+        hir.end()
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const CLASSBYTESRANGE_TYPE: &'static str = r#"/**
+ * A single range of characters represented by arbitrary bytes.
+ *
+ * The range is closed. That is, the start and end of the range are included in
+ * the range.
+ */
+export type ClassBytesRange = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::hir::ClassBytesRange'
+
+  /**
+   * The start of this range.
+   *
+   * The start of a range is always less than or equal to the end of the range.
+   */
+  start: number
+
+  /**
+   * The end of this range.
+   *
+   * The end of a range is always greater than or equal to the start of the range.
+   */
+  end: number
+
+  /**
+   * The number of bytes in this range.
+   */
+  len: number
+}"#;
+
+impl Serialize for Hir<&hir::ClassBytesRange> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut hir = serializer.serialize_struct("ClassBytesRange", 5)?;
+        hir.serialize_field("@type", "struct")?;
+        hir.serialize_field("@name", "regex_syntax::hir::ClassBytesRange")?;
+        hir.serialize_field("start", &self.0.start())?;
+        hir.serialize_field("end", &self.0.end())?;
+        hir.serialize_field("len", &self.0.len())?;
+        hir.end()
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const LOOK_TYPE: &'static str = r#" /**
+ * The high-level intermediate representation for a look-around assertion.
+ *
+ * An assertion match is always zero-length. Also called an “empty match.”
+ */
+export type Look =
+  | LookStartVariant
+  | LookEndVariant
+  | LookStartLFVariant
+  | LookEndLFVariant
+  | LookStartCRLFVariant
+  | LookEndCRLFVariant
+  | LookWordAsciiVariant
+  | LookWordAsciiNegateVariant
+  | LookWordUnicodeVariant
+  | LookWordUnicodeNegateVariant
+  | LookWordStartAsciiVariant
+  | LookWordEndAsciiVariant
+  | LookWordStartUnicodeVariant
+  | LookWordEndUnicodeVariant
+  | LookWordStartHalfAsciiVariant
+  | LookWordEndHalfAsciiVariant
+  | LookWordStartHalfUnicodeVariant
+  | LookWordEndHalfUnicodeVariant
+
+/**
+ * Match the beginning of text. Specifically, this matches at the starting
+ * position of the input.
+ */
+export type LookStartVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir:Look'
+  '@variant': 'Start'
+}
+
+/**
+ * Match the end of text. Specifically, this matches at the ending position of
+ * the input.
+ */
+export type LookEndVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir:Look'
+  '@variant': 'End'
+}
+
+/**
+ * Match the beginning of a line or the beginning of text. Specifically, this
+ * matches at the starting position of the input, or at the position immediately
+ * following a `\n` character.
+ */
+export type LookStartLFVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir:Look'
+  '@variant': 'StartLF'
+}
+
+/**
+ * Match the end of a line or the end of text. Specifically, this matches at the
+ * end position of the input, or at the position immediately preceding a `\n`
+ * character.
+ */
+export type LookEndLFVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir:Look'
+  '@variant': 'EndLF'
+}
+
+/**
+ * Match the beginning of a line or the beginning of text. Specifically, this
+ * matches at the starting position of the input, or at the position immediately
+ * following either a `\r` or `\n` character, but never after a `\r` when a `\n`
+ * follows.
+ */
+export type LookStartCRLFVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir:Look'
+  '@variant': 'StartCRLF'
+}
+
+/**
+ * Match the end of a line or the end of text. Specifically, this matches at the
+ * end position of the input, or at the position immediately preceding a `\r` or
+ * `\n` character, but never before a `\n` when a `\r` precedes it.
+ */
+export type LookEndCRLFVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir:Look'
+  '@variant': 'EndCRLF'
+}
+
+/**
+ * Match an ASCII-only word boundary. That is, this matches a position where the
+ * left adjacent character and right adjacent character correspond to a word and
+ * non-word or a non-word and word character.
+ */
+export type LookWordAsciiVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir:Look'
+  '@variant': 'WordAscii'
+}
+
+/**
+ * Match an ASCII-only negation of a word boundary.
+ */
+export type LookWordAsciiNegateVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir:Look'
+  '@variant': 'WordAsciiNegate'
+}
+
+/**
+ * Match a Unicode-aware word boundary. That is, this matches a position where
+ * the left adjacent character and right adjacent character correspond to a word
+ * and non-word or a non-word and word character.
+ */
+export type LookWordUnicodeVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir:Look'
+  '@variant': 'WordUnicode'
+}
+
+/**
+ * Match a Unicode-aware negation of a word boundary.
+ */
+export type LookWordUnicodeNegateVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir:Look'
+  '@variant': 'WordUnicodeNegate'
+}
+
+/**
+ * Match the start of an ASCII-only word boundary. That is, this matches a
+ * position at either the beginning of the haystack or where the previous
+ * character is not a word character and the following character is a word
+ * character.
+ */
+export type LookWordStartAsciiVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir:Look'
+  '@variant': 'WordStartAscii'
+}
+
+/**
+ * Match the end of an ASCII-only word boundary. That is, this matches a
+ * position at either the end of the haystack orwhere the previous character is
+ * a word character and the following character is not a word character.
+ */
+export type LookWordEndAsciiVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir:Look'
+  '@variant': 'WordEndAscii'
+}
+
+/**
+ * Match the start of a Unicode word boundary. That is, this matches a position
+ * at either the beginning of the haystack or where the previous character is
+ * not a word character and the following character is a word character.
+ */
+export type LookWordStartUnicodeVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir:Look'
+  '@variant': 'WordStartUnicode'
+}
+
+/**
+ * Match the end of a Unicode word boundary. That is, this matches a position at
+ *  either the end of the haystack or where the previous character is a word
+ * character and the following character is not a word character.
+ */
+export type LookWordEndUnicodeVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir:Look'
+  '@variant': 'WordEndUnicode'
+}
+
+/**
+ * Match the start half of an ASCII-only word boundary. That is, this matches a
+ * position at either the beginning of the haystack or where the previous
+ * character is not a word character.
+ */
+export type LookWordStartHalfAsciiVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir::Look',
+  '@variant': 'WordStartHalfAscii'
+}
+
+/**
+ * Match the end half of an ASCII-only word boundary. That is, this matches a
+ * position at either the end of the haystack or where the following character
+ * is not a word character.
+ */
+export type LookWordEndHalfAsciiVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir::Look',
+  '@variant': 'WordEndHalfAscii'
+}
+
+/**
+ * Match the start half of a Unicode word boundary. That is, this matches a
+ * position at either the beginning of the haystack or where the previous
+ * character is not a word character.
+ */
+export type LookWordStartHalfUnicodeVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir::Look',
+  '@variant': 'WordStartHalfUnicode'
+}
+
+/**
+ * Match the end half of a Unicode word boundary. That is, this matches a
+ * position at either the end of the haystack or where the following character
+ * is not a word character.
+ */
+export type LookWordEndHalfUnicodeVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::hir::Look',
+  '@variant': 'WordEndHalfUnicode'
+}"#;
+
+impl Serialize for Hir<&hir::Look> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut hir = serializer.serialize_struct("Look", 3)?;
+        hir.serialize_field("@type", "enum")?;
+        hir.serialize_field("@name", "regex_syntax::hir::Look")?;
+        match self.0 {
+            hir::Look::Start => {
+                hir.serialize_field("@variant", "Start")?;
+            }
+            hir::Look::End => {
+                hir.serialize_field("@variant", "End")?;
+            }
+            hir::Look::StartLF => {
+                hir.serialize_field("@variant", "StartLF")?;
+            }
+            hir::Look::EndLF => {
+                hir.serialize_field("@variant", "EndLF")?;
+            }
+            hir::Look::StartCRLF => {
+                hir.serialize_field("@variant", "StartCRLF")?;
+            }
+            hir::Look::EndCRLF => {
+                hir.serialize_field("@variant", "EndCRLF")?;
+            }
+            hir::Look::WordAscii => {
+                hir.serialize_field("@variant", "WordAscii")?;
+            }
+            hir::Look::WordAsciiNegate => {
+                hir.serialize_field("@variant", "WordAsciiNegate")?;
+            }
+            hir::Look::WordUnicode => {
+                hir.serialize_field("@variant", "WordUnicode")?;
+            }
+            hir::Look::WordUnicodeNegate => {
+                hir.serialize_field("@variant", "WordUnicodeNegate")?;
+            }
+            hir::Look::WordStartAscii => {
+                hir.serialize_field("@variant", "WordStartAscii")?;
+            }
+            hir::Look::WordEndAscii => {
+                hir.serialize_field("@variant", "WordEndAscii")?;
+            }
+            hir::Look::WordStartUnicode => {
+                hir.serialize_field("@variant", "WordStartUnicode")?;
+            }
+            hir::Look::WordEndUnicode => {
+                hir.serialize_field("@variant", "WordEndUnicode")?;
+            }
+            hir::Look::WordStartHalfAscii => {
+                hir.serialize_field("@variant", "WordStartHalfAscii")?;
+            }
+            hir::Look::WordEndHalfAscii => {
+                hir.serialize_field("@variant", "WordEndHalfAscii")?;
+            }
+            hir::Look::WordStartHalfUnicode => {
+                hir.serialize_field("@variant", "WordStartHalfUnicode")?;
+            }
+            hir::Look::WordEndHalfUnicode => {
+                hir.serialize_field("@variant", "WordEndHalfUnicode")?;
+            }
+        };
+        hir.end()
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const REPETITION_TYPE: &'static str = r#"/**
+ * The high-level intermediate representation of a repetition operator.
+ *
+ * A repetition operator permits the repetition of an arbitrary sub-expression
+ */
+export type Repetition = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::hir::Repetition'
+
+  /**
+   * The minimum range of the repetition.
+   *
+   * Note that special cases like `?`, `+` and `*` all get translated into the
+   * ranges `{0,1}`, `{1,}` and `{0,}`, respectively.
+   *
+   * When `min` is zero, this expression can match the empty string regardless
+   * of what its sub-expression is.
+   */
+  min: number
+
+  /**
+   * The maximum range of the repetition.
+   *
+   * Note that when `max` is `undefined`, `min` acts as a lower bound but where
+   * there is no upper bound. For something like `x{5}` where the min and max
+   * are equivalent, `min` will be set to `5` and `max` will be set to `5`.
+   */
+  max?: number
+
+  /**
+   * Whether this repetition operator is greedy or not. A greedy operator will
+   * match as much as it can. A non-greedy operator will match as little as it
+   * can.
+   *
+   * Typically, operators are greedy by default and are only non-greedy when a
+   * `?` suffix is used, e.g., `(expr)*` is greedy while `(expr)*?` is not.
+   * However, this can be inverted via the `U` “ungreedy” flag.
+   */
+  greedy: boolean
+
+  /**
+   * The expression being repeated.
+   */
+  sub: Hir
+}"#;
+
+impl Serialize for Hir<&hir::Repetition> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut hir = serializer.serialize_struct("Repetition", 6)?;
+        hir.serialize_field("@type", "struct")?;
+        hir.serialize_field("@name", "regex_syntax::hir::Repetition")?;
+        hir.serialize_field("min", &self.0.min)?;
+        hir.serialize_field("max", &self.0.max)?;
+        hir.serialize_field("greedy", &self.0.greedy)?;
+        hir.serialize_field("sub", &Hir::from(self.0.sub.as_ref()))?;
+        hir.end()
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const CAPTURE_TYPE: &'static str = r#"/**
+ * The high-level intermediate representation for a capturing group.
+ *
+ * A capturing group always has an index and a child expression. It may also
+ * have a name associated with it (e.g., `(?P<foo>\w)`), but it's not necessary.
+ *
+ * Note that there is no explicit representation of a non-capturing group in a
+ * `Hir`. Instead, non-capturing grouping is handled automatically by the
+ * recursive structure of the `Hir` itself.
+ */
+export type Capture = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::hir::Capture'
+
+  /** The capture index of the capture. */
+  index: number
+
+  /** The name of the capture, if it exists. */
+  name?: String
+
+  /** The expression inside the capturing group, which may be empty. */
+  sub: Hir
+}"#;
+
+impl Serialize for Hir<&hir::Capture> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut hir = serializer.serialize_struct("Capture", 5)?;
+        hir.serialize_field("@type", "struct")?;
+        hir.serialize_field("@name", "regex_syntax::hir::Capture")?;
+        hir.serialize_field("index", &self.0.index)?;
+        hir.serialize_field("name", &self.0.name)?;
+        hir.serialize_field("sub", &Hir::from(self.0.sub.as_ref()))?;
+        hir.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Hir<hir::Hir> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        HirShape::deserialize(deserializer).map(|shape| Hir(shape.into()))
+    }
+}
+
+impl<'de> Deserialize<'de> for Hir<hir::ClassUnicode> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        ClassRangesShape::<char>::deserialize(deserializer).map(|shape| Hir(shape.into()))
+    }
+}
+
+impl<'de> Deserialize<'de> for Hir<hir::ClassBytes> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        ClassRangesShape::<u8>::deserialize(deserializer).map(|shape| Hir(shape.into()))
+    }
+}
+
+impl<T> Hir<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// The JS shape of an `Hir`, as produced by the serializers above. Only the
+/// fields needed to rebuild the tree are read, so `@type` and `@name` are
+/// ignored.
+#[derive(Deserialize)]
+struct HirShape {
+    kind: HirKindShape,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "@variant", content = "@values")]
+enum HirKindShape {
+    Empty,
+    Literal((LiteralShape,)),
+    Class((ClassShape,)),
+    Look((LookShape,)),
+    Repetition((RepetitionShape,)),
+    Capture((CaptureShape,)),
+    Concat((Vec<HirShape>,)),
+    Alternation((Vec<HirShape>,)),
+}
+
+#[derive(Deserialize)]
+struct LiteralShape {
+    #[serde(rename = "@values")]
+    values: (LiteralBytes,),
+}
+
+struct LiteralBytes(Vec<u8>);
+
+impl<'de> Deserialize<'de> for LiteralBytes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = LiteralBytes;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("an Uint8Array or an array of bytes")
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Ok(LiteralBytes(v.to_vec()))
+            }
+
+            fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+                Ok(LiteralBytes(v))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let mut bytes = Vec::new();
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(LiteralBytes(bytes))
+            }
+        }
+
+        deserializer.deserialize_byte_buf(Visitor)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "@variant", content = "@values")]
+enum ClassShape {
+    Unicode((ClassRangesShape<char>,)),
+    Bytes((ClassRangesShape<u8>,)),
+}
+
+#[derive(Deserialize)]
+struct ClassRangesShape<T> {
+    ranges: Vec<ClassRangeShape<T>>,
+}
+
+#[derive(Deserialize)]
+struct ClassRangeShape<T> {
+    start: T,
+    end: T,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "@variant")]
+enum LookShape {
+    Start,
+    End,
+    StartLF,
+    EndLF,
+    StartCRLF,
+    EndCRLF,
+    WordAscii,
+    WordAsciiNegate,
+    WordUnicode,
+    WordUnicodeNegate,
+    WordStartAscii,
+    WordEndAscii,
+    WordStartUnicode,
+    WordEndUnicode,
+    WordStartHalfAscii,
+    WordEndHalfAscii,
+    WordStartHalfUnicode,
+    WordEndHalfUnicode,
+}
+
+#[derive(Deserialize)]
+struct RepetitionShape {
+    min: u32,
+    max: Option<u32>,
+    greedy: bool,
+    sub: Box<HirShape>,
+}
+
+#[derive(Deserialize)]
+struct CaptureShape {
+    index: u32,
+    name: Option<String>,
+    sub: Box<HirShape>,
+}
+
+impl From<HirShape> for hir::Hir {
+    fn from(shape: HirShape) -> Self {
+        match shape.kind {
+            HirKindShape::Empty => hir::Hir::empty(),
+            HirKindShape::Literal((literal,)) => hir::Hir::literal(literal.values.0 .0),
+            HirKindShape::Class((class,)) => hir::Hir::class(class.into()),
+            HirKindShape::Look((look,)) => hir::Hir::look(look.into()),
+            HirKindShape::Repetition((repetition,)) => hir::Hir::repetition(hir::Repetition {
+                min: repetition.min,
+                max: repetition.max,
+                greedy: repetition.greedy,
+                sub: Box::new((*repetition.sub).into()),
+            }),
+            HirKindShape::Capture((capture,)) => hir::Hir::capture(hir::Capture {
+                index: capture.index,
+                name: capture.name.map(String::into_boxed_str),
+                sub: Box::new((*capture.sub).into()),
+            }),
+            HirKindShape::Concat((subs,)) => {
+                hir::Hir::concat(subs.into_iter().map(hir::Hir::from).collect())
+            }
+            HirKindShape::Alternation((subs,)) => {
+                hir::Hir::alternation(subs.into_iter().map(hir::Hir::from).collect())
+            }
+        }
+    }
+}
+
+impl From<ClassRangesShape<char>> for hir::ClassUnicode {
+    fn from(shape: ClassRangesShape<char>) -> Self {
+        hir::ClassUnicode::new(
+            shape
+                .ranges
+                .into_iter()
+                .map(|r| hir::ClassUnicodeRange::new(r.start, r.end)),
+        )
+    }
+}
+
+impl From<ClassRangesShape<u8>> for hir::ClassBytes {
+    fn from(shape: ClassRangesShape<u8>) -> Self {
+        hir::ClassBytes::new(
+            shape
+                .ranges
+                .into_iter()
+                .map(|r| hir::ClassBytesRange::new(r.start, r.end)),
+        )
+    }
+}
+
+impl From<ClassShape> for hir::Class {
+    fn from(shape: ClassShape) -> Self {
+        match shape {
+            ClassShape::Unicode((class,)) => hir::Class::Unicode(class.into()),
+            ClassShape::Bytes((class,)) => hir::Class::Bytes(class.into()),
+        }
+    }
+}
+
+impl From<LookShape> for hir::Look {
+    fn from(shape: LookShape) -> Self {
+        match shape {
+            LookShape::Start => hir::Look::Start,
+            LookShape::End => hir::Look::End,
+            LookShape::StartLF => hir::Look::StartLF,
+            LookShape::EndLF => hir::Look::EndLF,
+            LookShape::StartCRLF => hir::Look::StartCRLF,
+            LookShape::EndCRLF => hir::Look::EndCRLF,
+            LookShape::WordAscii => hir::Look::WordAscii,
+            LookShape::WordAsciiNegate => hir::Look::WordAsciiNegate,
+            LookShape::WordUnicode => hir::Look::WordUnicode,
+            LookShape::WordUnicodeNegate => hir::Look::WordUnicodeNegate,
+            LookShape::WordStartAscii => hir::Look::WordStartAscii,
+            LookShape::WordEndAscii => hir::Look::WordEndAscii,
+            LookShape::WordStartUnicode => hir::Look::WordStartUnicode,
+            LookShape::WordEndUnicode => hir::Look::WordEndUnicode,
+            LookShape::WordStartHalfAscii => hir::Look::WordStartHalfAscii,
+            LookShape::WordEndHalfAscii => hir::Look::WordEndHalfAscii,
+            LookShape::WordStartHalfUnicode => hir::Look::WordStartHalfUnicode,
+            LookShape::WordEndHalfUnicode => hir::Look::WordEndHalfUnicode,
+        }
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const REGEX_ERROR_TYPE: &'static str = r#"/**
+ * Describes why a regular expression failed to compile.
+ *
+ * When the failure comes from the parser, `span` holds the byte offsets of the
+ * offending part of the pattern, so it can be highlighted in an editor. Errors
+ * that are not tied to a position in the pattern, like a compiled regex that
+ * exceeds the size limit, don't have a `span`.
+ *
+ * # Example
+ *
+ * ```typescript
+ * import { RRegex } from "rregex"
+ *
+ * try {
+ *   RRegex.tryNew("a(b")
+ * } catch (err) {
+ *   expect(err.kind).toBe("GroupUnclosed")
+ *   expect(err.span).toEqual({ start: 1, end: 2 })
+ * }
+ * ```
+ */
+export type RegexError = {
+  /** A short description of the error. */
+  message: string
+
+  /** The name of the error kind, e.g. `GroupUnclosed` */
+  kind: string
+
+  /**
+   * When the pattern uses a feature of other regex engines that isn't
+   * supported, the name of that feature, e.g., `backreference` for `\1` or
+   * `lookaround` for `(?=a)`. This makes it possible to show a more helpful
+   * hint than the message.
+   */
+  feature?: 'backreference' | 'lookaround'
+
+  /** The byte offsets in the pattern where the error occurred. */
+  span?: {
+    start: number
+    end: number
+  }
+
+  /**
+   * Where the error occurred in the pattern, for editors that work with
+   * lines and columns. Lines and columns are 1-based, columns are counted in
+   * Unicode code points.
+   */
+  position?: {
+    start: RegexErrorPosition
+    end: RegexErrorPosition
+  }
+}
+
+/**
+ * A position in the pattern of a `RegexError`.
+ */
+export type RegexErrorPosition = {
+  /** The byte offset in the pattern, starting at 0. */
+  offset: number
+
+  /** The line number, starting at 1. */
+  line: number
+
+  /** The column number, starting at 1. */
+  column: number
+}"#;
+
+pub enum RegexError {
+    Syntax(regex_syntax::Error),
+    Compile(regex::Error),
+}
+
+impl RegexError {
+    /// Builds a structured error for `pattern`. The `regex` crate only
+    /// reports syntax errors as strings, so the pattern is parsed again to
+    /// recover the error kind and span.
+    pub fn new(pattern: &str, error: regex::Error) -> Self {
+        match regex_syntax::Parser::new().parse(pattern) {
+            Err(err) => RegexError::Syntax(err),
+            Ok(_) => RegexError::Compile(error),
+        }
+    }
+}
+
+impl TryFrom<RegexError> for JsValue {
+    type Error = serde_wasm_bindgen::Error;
+    fn try_from(value: RegexError) -> Result<Self, Self::Error> {
+        serde_wasm_bindgen::to_value(&value)
+    }
+}
+
+struct ErrorSpan<'a>(&'a ast::Span);
+
+impl<'a> Serialize for ErrorSpan<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut span = serializer.serialize_struct("Span", 2)?;
+        span.serialize_field("start", &self.0.start.offset)?;
+        span.serialize_field("end", &self.0.end.offset)?;
+        span.end()
+    }
+}
+
+struct ErrorPositions<'a>(&'a ast::Span);
+
+impl<'a> Serialize for ErrorPositions<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut span = serializer.serialize_struct("Positions", 2)?;
+        span.serialize_field("start", &ErrorPosition(&self.0.start))?;
+        span.serialize_field("end", &ErrorPosition(&self.0.end))?;
+        span.end()
+    }
+}
+
+struct ErrorPosition<'a>(&'a ast::Position);
+
+impl<'a> Serialize for ErrorPosition<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut position = serializer.serialize_struct("Position", 3)?;
+        position.serialize_field("offset", &self.0.offset)?;
+        position.serialize_field("line", &self.0.line)?;
+        position.serialize_field("column", &self.0.column)?;
+        position.end()
+    }
+}
+
+impl Serialize for RegexError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let feature = match &self {
+            RegexError::Syntax(regex_syntax::Error::Parse(err)) => unsupported_feature(err.kind()),
+            _ => None,
+        };
+        let (message, kind, span) = match &self {
+            RegexError::Syntax(regex_syntax::Error::Parse(err)) => (
+                err.kind().to_string(),
+                ast_error_kind(err.kind()),
+                Some(err.span()),
+            ),
+            RegexError::Syntax(regex_syntax::Error::Translate(err)) => (
+                err.kind().to_string(),
+                hir_error_kind(err.kind()),
+                Some(err.span()),
+            ),
+            RegexError::Syntax(err) => (err.to_string(), "Syntax", None),
+            RegexError::Compile(err @ regex::Error::CompiledTooBig(_)) => {
+                (err.to_string(), "CompiledTooBig", None)
+            }
+            RegexError::Compile(err) => (err.to_string(), "Syntax", None),
+        };
+
+        let mut error = serializer.serialize_struct("RegexError", 5)?;
+        error.serialize_field("message", &message)?;
+        error.serialize_field("kind", kind)?;
+        match feature {
+            Some(feature) => error.serialize_field("feature", feature)?,
+            None => error.skip_field("feature")?,
+        }
+        error.serialize_field("span", &span.map(ErrorSpan))?;
+        error.serialize_field("position", &span.map(ErrorPositions))?;
+        error.end()
+    }
+}
+
+/// Returns the name of the feature of other regex engines that a parse error
+/// of this kind is about, if any.
+fn unsupported_feature(kind: &regex_syntax::ast::ErrorKind) -> Option<&'static str> {
+    use regex_syntax::ast::ErrorKind;
+    match kind {
+        ErrorKind::UnsupportedBackreference => Some("backreference"),
+        ErrorKind::UnsupportedLookAround => Some("lookaround"),
+        _ => None,
+    }
+}
+
+fn ast_error_kind(kind: &regex_syntax::ast::ErrorKind) -> &'static str {
+    use regex_syntax::ast::ErrorKind;
+    match kind {
+        ErrorKind::CaptureLimitExceeded => "CaptureLimitExceeded",
+        ErrorKind::ClassEscapeInvalid => "ClassEscapeInvalid",
+        ErrorKind::ClassRangeInvalid => "ClassRangeInvalid",
+        ErrorKind::ClassRangeLiteral => "ClassRangeLiteral",
+        ErrorKind::ClassUnclosed => "ClassUnclosed",
+        ErrorKind::DecimalEmpty => "DecimalEmpty",
+        ErrorKind::DecimalInvalid => "DecimalInvalid",
+        ErrorKind::EscapeHexEmpty => "EscapeHexEmpty",
+        ErrorKind::EscapeHexInvalid => "EscapeHexInvalid",
+        ErrorKind::EscapeHexInvalidDigit => "EscapeHexInvalidDigit",
+        ErrorKind::EscapeUnexpectedEof => "EscapeUnexpectedEof",
+        ErrorKind::EscapeUnrecognized => "EscapeUnrecognized",
+        ErrorKind::FlagDanglingNegation => "FlagDanglingNegation",
+        ErrorKind::FlagDuplicate { .. } => "FlagDuplicate",
+        ErrorKind::FlagRepeatedNegation { .. } => "FlagRepeatedNegation",
+        ErrorKind::FlagUnexpectedEof => "FlagUnexpectedEof",
+        ErrorKind::FlagUnrecognized => "FlagUnrecognized",
+        ErrorKind::GroupNameDuplicate { .. } => "GroupNameDuplicate",
+        ErrorKind::GroupNameEmpty => "GroupNameEmpty",
+        ErrorKind::GroupNameInvalid => "GroupNameInvalid",
+        ErrorKind::GroupNameUnexpectedEof => "GroupNameUnexpectedEof",
+        ErrorKind::GroupUnclosed => "GroupUnclosed",
+        ErrorKind::GroupUnopened => "GroupUnopened",
+        ErrorKind::NestLimitExceeded(_) => "NestLimitExceeded",
+        ErrorKind::RepetitionCountInvalid => "RepetitionCountInvalid",
+        ErrorKind::RepetitionCountDecimalEmpty => "RepetitionCountDecimalEmpty",
+        ErrorKind::RepetitionCountUnclosed => "RepetitionCountUnclosed",
+        ErrorKind::RepetitionMissing => "RepetitionMissing",
+        ErrorKind::SpecialWordBoundaryUnclosed => "SpecialWordBoundaryUnclosed",
+        ErrorKind::SpecialWordBoundaryUnrecognized => "SpecialWordBoundaryUnrecognized",
+        ErrorKind::SpecialWordOrRepetitionUnexpectedEof => {
+            "SpecialWordOrRepetitionUnexpectedEof"
+        }
+        ErrorKind::UnicodeClassInvalid => "UnicodeClassInvalid",
+        ErrorKind::UnsupportedBackreference => "UnsupportedBackreference",
+        ErrorKind::UnsupportedLookAround => "UnsupportedLookAround",
+        _ => "Syntax",
+    }
+}
+
+fn hir_error_kind(kind: &hir::ErrorKind) -> &'static str {
+    match kind {
+        hir::ErrorKind::UnicodeNotAllowed => "UnicodeNotAllowed",
+        hir::ErrorKind::InvalidUtf8 => "InvalidUtf8",
+        hir::ErrorKind::InvalidLineTerminator => "InvalidLineTerminator",
+        hir::ErrorKind::UnicodePropertyNotFound => "UnicodePropertyNotFound",
+        hir::ErrorKind::UnicodePropertyValueNotFound => "UnicodePropertyValueNotFound",
+        hir::ErrorKind::UnicodePerlClassNotFound => "UnicodePerlClassNotFound",
+        hir::ErrorKind::UnicodeCaseUnavailable => "UnicodeCaseUnavailable",
+        _ => "Syntax",
+    }
+}
+
+pub struct Ast<T>(T);
+
+impl<T> From<T> for Ast<T> {
+    fn from(value: T) -> Self {
+        Ast(value)
+    }
+}
+
+impl<T> TryFrom<Ast<T>> for JsValue
+where
+    Ast<T>: Serialize,
+{
+    type Error = serde_wasm_bindgen::Error;
+    fn try_from(value: Ast<T>) -> Result<Self, Self::Error> {
+        serde_wasm_bindgen::to_value(&value)
+    }
+}
+
+/// Serializes a variant without values of the enum `name`.
+fn serialize_variant<S>(
+    serializer: S,
+    name: &'static str,
+    variant: &'static str,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let mut ast = serializer.serialize_struct("Variant", 3)?;
+    ast.serialize_field("@type", "enum")?;
+    ast.serialize_field("@name", name)?;
+    ast.serialize_field("@variant", variant)?;
+    ast.end()
+}
+
+/// Serializes a variant of the enum `name` that holds `values`.
+fn serialize_variant_values<S, T>(
+    serializer: S,
+    name: &'static str,
+    variant: &'static str,
+    values: &[T],
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    T: Serialize,
+{
+    let mut ast = serializer.serialize_struct("Variant", 4)?;
+    ast.serialize_field("@type", "enum")?;
+    ast.serialize_field("@name", name)?;
+    ast.serialize_field("@variant", variant)?;
+    ast.serialize_field("@values", values)?;
+    ast.end()
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const AST_SPAN_TYPE: &'static str = r#"/**
+ * Span represents the position information of a single AST item.
+ *
+ * All span positions are absolute byte offsets that can be used on the
+ * original regular expression that was parsed.
+ */
+export type AstSpan = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::Span'
+
+  /** The start byte offset. */
+  start: AstPosition
+
+  /** The end byte offset. */
+  end: AstPosition
+}
+
+/**
+ * A single position in a regular expression.
+ *
+ * A position encodes one half of a span, and include the byte offset, line
+ * number and column number.
+ */
+export type AstPosition = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::Position'
+
+  /**
+   * The absolute offset of this position, starting at `0` from the
+   * beginning of the regular expression pattern string.
+   */
+  offset: number
+
+  /** The line number, starting at `1`. */
+  line: number
+
+  /** The approximate column number, starting at `1`. */
+  column: number
+}"#;
+
+impl Serialize for Ast<&ast::Span> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut ast = serializer.serialize_struct("Span", 4)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::Span")?;
+        ast.serialize_field("start", &Ast::from(&self.0.start))?;
+        ast.serialize_field("end", &Ast::from(&self.0.end))?;
+        ast.end()
+    }
+}
+
+impl Serialize for Ast<&ast::Position> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut ast = serializer.serialize_struct("Position", 5)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::Position")?;
+        ast.serialize_field("offset", &self.0.offset)?;
+        ast.serialize_field("line", &self.0.line)?;
+        ast.serialize_field("column", &self.0.column)?;
+        ast.end()
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const AST_TYPE: &'static str = r#"/**
+ * An abstract syntax tree for a single regular expression.
+ *
+ * Unlike the `Hir`, the `Ast` is a faithful representation of the concrete
+ * syntax, and every node carries the `AstSpan` of the pattern it came from.
+ */
+export type Ast =
+  | AstEmptyVariant
+  | AstFlagsVariant
+  | AstLiteralVariant
+  | AstDotVariant
+  | AstAssertionVariant
+  | AstClassUnicodeVariant
+  | AstClassPerlVariant
+  | AstClassBracketedVariant
+  | AstRepetitionVariant
+  | AstGroupVariant
+  | AstAlternationVariant
+  | AstConcatVariant
+
+/** An empty regex that matches everything. */
+export type AstEmptyVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::ast::Ast'
+  '@variant': 'Empty'
+  '@values': [AstSpan]
+}
+
+/** A set of flags, e.g., `(?is)`. */
+export type AstFlagsVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::ast::Ast'
+  '@variant': 'Flags'
+  '@values': [AstSetFlags]
+}
+
+/** A single character literal, which includes escape sequences. */
+export type AstLiteralVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::ast::Ast'
+  '@variant': 'Literal'
+  '@values': [AstLiteral]
+}
+
+/** The "any character" class. */
+export type AstDotVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::ast::Ast'
+  '@variant': 'Dot'
+  '@values': [AstSpan]
+}
+
+/** A single zero-width assertion. */
+export type AstAssertionVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::ast::Ast'
+  '@variant': 'Assertion'
+  '@values': [AstAssertion]
+}
+
+/** A single Unicode character class, e.g., `\pL` or `\p{Greek}`. */
+export type AstClassUnicodeVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::ast::Ast'
+  '@variant': 'ClassUnicode'
+  '@values': [AstClassUnicode]
+}
+
+/** A single perl character class, e.g., `\d` or `\W`. */
+export type AstClassPerlVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::ast::Ast'
+  '@variant': 'ClassPerl'
+  '@values': [AstClassPerl]
+}
+
+/**
+ * A single bracketed character class set, which may contain zero or more
+ * character ranges and/or zero or more nested classes. e.g., `[a-zA-Z\pL]`.
+ */
+export type AstClassBracketedVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::ast::Ast'
+  '@variant': 'ClassBracketed'
+  '@values': [AstClassBracketed]
+}
+
+/** A repetition operator applied to an arbitrary regular expression. */
+export type AstRepetitionVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::ast::Ast'
+  '@variant': 'Repetition'
+  '@values': [AstRepetition]
+}
+
+/** A grouped regular expression. */
+export type AstGroupVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::ast::Ast'
+  '@variant': 'Group'
+  '@values': [AstGroup]
+}
+
+/** An alternation of regular expressions. */
+export type AstAlternationVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::ast::Ast'
+  '@variant': 'Alternation'
+  '@values': [AstAlternation]
+}
+
+/** A concatenation of regular expressions. */
+export type AstConcatVariant = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::ast::Ast'
+  '@variant': 'Concat'
+  '@values': [AstConcat]
+}"#;
+
+impl Serialize for Ast<&ast::Ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut ast = serializer.serialize_struct("Ast", 4)?;
+        ast.serialize_field("@type", "enum")?;
+        ast.serialize_field("@name", "regex_syntax::ast::Ast")?;
+        match self.0 {
+            ast::Ast::Empty(span) => {
+                ast.serialize_field("@variant", "Empty")?;
+                ast.serialize_field("@values", &vec![Ast::from(span.as_ref())])?;
+            }
+            ast::Ast::Flags(flags) => {
+                ast.serialize_field("@variant", "Flags")?;
+                ast.serialize_field("@values", &vec![Ast::from(flags.as_ref())])?;
+            }
+            ast::Ast::Literal(literal) => {
+                ast.serialize_field("@variant", "Literal")?;
+                ast.serialize_field("@values", &vec![Ast::from(literal.as_ref())])?;
+            }
+            ast::Ast::Dot(span) => {
+                ast.serialize_field("@variant", "Dot")?;
+                ast.serialize_field("@values", &vec![Ast::from(span.as_ref())])?;
+            }
+            ast::Ast::Assertion(assertion) => {
+                ast.serialize_field("@variant", "Assertion")?;
+                ast.serialize_field("@values", &vec![Ast::from(assertion.as_ref())])?;
+            }
+            ast::Ast::ClassUnicode(class) => {
+                ast.serialize_field("@variant", "ClassUnicode")?;
+                ast.serialize_field("@values", &vec![Ast::from(class.as_ref())])?;
+            }
+            ast::Ast::ClassPerl(class) => {
+                ast.serialize_field("@variant", "ClassPerl")?;
+                ast.serialize_field("@values", &vec![Ast::from(class.as_ref())])?;
+            }
+            ast::Ast::ClassBracketed(class) => {
+                ast.serialize_field("@variant", "ClassBracketed")?;
+                ast.serialize_field("@values", &vec![Ast::from(class.as_ref())])?;
+            }
+            ast::Ast::Repetition(repetition) => {
+                ast.serialize_field("@variant", "Repetition")?;
+                ast.serialize_field("@values", &vec![Ast::from(repetition.as_ref())])?;
+            }
+            ast::Ast::Group(group) => {
+                ast.serialize_field("@variant", "Group")?;
+                ast.serialize_field("@values", &vec![Ast::from(group.as_ref())])?;
+            }
+            ast::Ast::Alternation(alternation) => {
+                ast.serialize_field("@variant", "Alternation")?;
+                ast.serialize_field("@values", &vec![Ast::from(alternation.as_ref())])?;
+            }
+            ast::Ast::Concat(concat) => {
+                ast.serialize_field("@variant", "Concat")?;
+                ast.serialize_field("@values", &vec![Ast::from(concat.as_ref())])?;
+            }
+        };
+        ast.end()
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const AST_ALTERNATION_TYPE: &'static str = r#"/**
+ * An alternation of regular expressions.
+ */
+export type AstAlternation = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::Alternation'
+
+  /** The span of this alternation. */
+  span: AstSpan
+
+  /** The alternate regular expressions. */
+  asts: Ast[]
+}
+
+/**
+ * A concatenation of regular expressions.
+ */
+export type AstConcat = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::Concat'
+
+  /** The span of this concatenation. */
+  span: AstSpan
+
+  /** The concatenation regular expressions. */
+  asts: Ast[]
+}"#;
+
+impl Serialize for Ast<&ast::Alternation> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let asts: Vec<Ast<&ast::Ast>> = self.0.asts.iter().map(Ast::from).collect();
+        let mut ast = serializer.serialize_struct("Alternation", 4)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::Alternation")?;
+        ast.serialize_field("span", &Ast::from(&self.0.span))?;
+        ast.serialize_field("asts", &asts)?;
+        ast.end()
+    }
+}
+
+impl Serialize for Ast<&ast::Concat> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let asts: Vec<Ast<&ast::Ast>> = self.0.asts.iter().map(Ast::from).collect();
+        let mut ast = serializer.serialize_struct("Concat", 4)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::Concat")?;
+        ast.serialize_field("span", &Ast::from(&self.0.span))?;
+        ast.serialize_field("asts", &asts)?;
+        ast.end()
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const AST_LITERAL_TYPE: &'static str = r#"/**
+ * A single literal expression.
+ *
+ * A literal corresponds to a single Unicode scalar value. Literals may be
+ * represented in their literal form, e.g., `a` or in their escaped form,
+ * e.g., `\x61`.
+ */
+export type AstLiteral = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::Literal'
+
+  /** The span of this literal. */
+  span: AstSpan
+
+  /** The kind of this literal. */
+  kind: AstLiteralKind
+
+  /** The Unicode scalar value corresponding to this literal. */
+  c: string
+}
+
+/**
+ * The kind of a single literal expression.
+ *
+ * - `Verbatim`: the literal is written verbatim, e.g., `a` or `☃`.
+ * - `Meta`: the literal is written as an escape because it is otherwise a
+ *   special regex meta character, e.g., `\*` or `\[`.
+ * - `Superfluous`: the literal is written as an escape despite the fact that
+ *   the escape is unnecessary, e.g., `\%` or `\/`.
+ * - `Octal`: the literal is written as an octal escape, e.g., `\141`.
+ * - `HexFixed`: the literal is written as a hex code with a fixed number of
+ *   digits depending on the type of the escape, e.g., `\x61` or `a`.
+ * - `HexBrace`: the literal is written as a hex code with a bracketed number
+ *   of digits, e.g., `\x{61}`.
+ * - `Special`: the literal is written as a specially recognized escape,
+ *   e.g., `\f` or `\n`.
+ */
+export type AstLiteralKind =
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::LiteralKind'
+    '@variant': 'Verbatim' | 'Meta' | 'Superfluous' | 'Octal'
+  }
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::LiteralKind'
+    '@variant': 'HexFixed' | 'HexBrace'
+    '@values': [AstHexLiteralKind]
+  }
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::LiteralKind'
+    '@variant': 'Special'
+    '@values': [AstSpecialLiteralKind]
+  }
+
+/**
+ * The type of a Unicode hex literal: `X` (`\x`), `UnicodeShort` (`\u`) or
+ * `UnicodeLong` (`\U`).
+ */
+export type AstHexLiteralKind = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::ast::HexLiteralKind'
+  '@variant': 'X' | 'UnicodeShort' | 'UnicodeLong'
+}
+
+/**
+ * The type of a special literal, e.g., `Tab` (`\t`) or `LineFeed` (`\n`).
+ */
+export type AstSpecialLiteralKind = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::ast::SpecialLiteralKind'
+  '@variant':
+    | 'Bell'
+    | 'FormFeed'
+    | 'Tab'
+    | 'LineFeed'
+    | 'CarriageReturn'
+    | 'VerticalTab'
+    | 'Space'
+}"#;
+
+impl Serialize for Ast<&ast::Literal> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut ast = serializer.serialize_struct("Literal", 5)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::Literal")?;
+        ast.serialize_field("span", &Ast::from(&self.0.span))?;
+        ast.serialize_field("kind", &Ast::from(&self.0.kind))?;
+        ast.serialize_field("c", &self.0.c)?;
+        ast.end()
+    }
+}
+
+impl Serialize for Ast<&ast::LiteralKind> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let name = "regex_syntax::ast::LiteralKind";
+        match self.0 {
+            ast::LiteralKind::Verbatim => serialize_variant(serializer, name, "Verbatim"),
+            ast::LiteralKind::Meta => serialize_variant(serializer, name, "Meta"),
+            ast::LiteralKind::Superfluous => serialize_variant(serializer, name, "Superfluous"),
+            ast::LiteralKind::Octal => serialize_variant(serializer, name, "Octal"),
+            ast::LiteralKind::HexFixed(kind) => {
+                serialize_variant_values(serializer, name, "HexFixed", &[Ast::from(kind)])
+            }
+            ast::LiteralKind::HexBrace(kind) => {
+                serialize_variant_values(serializer, name, "HexBrace", &[Ast::from(kind)])
+            }
+            ast::LiteralKind::Special(kind) => {
+                serialize_variant_values(serializer, name, "Special", &[Ast::from(kind)])
+            }
+        }
+    }
+}
+
+impl Serialize for Ast<&ast::HexLiteralKind> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self.0 {
+            ast::HexLiteralKind::X => "X",
+            ast::HexLiteralKind::UnicodeShort => "UnicodeShort",
+            ast::HexLiteralKind::UnicodeLong => "UnicodeLong",
+        };
+        serialize_variant(serializer, "regex_syntax::ast::HexLiteralKind", variant)
+    }
+}
+
+impl Serialize for Ast<&ast::SpecialLiteralKind> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self.0 {
+            ast::SpecialLiteralKind::Bell => "Bell",
+            ast::SpecialLiteralKind::FormFeed => "FormFeed",
+            ast::SpecialLiteralKind::Tab => "Tab",
+            ast::SpecialLiteralKind::LineFeed => "LineFeed",
+            ast::SpecialLiteralKind::CarriageReturn => "CarriageReturn",
+            ast::SpecialLiteralKind::VerticalTab => "VerticalTab",
+            ast::SpecialLiteralKind::Space => "Space",
+        };
+        serialize_variant(serializer, "regex_syntax::ast::SpecialLiteralKind", variant)
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const AST_ASSERTION_TYPE: &'static str = r#"/**
+ * A single zero-width assertion.
+ */
+export type AstAssertion = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::Assertion'
+
+  /** The span of this assertion. */
+  span: AstSpan
+
+  /** The assertion kind, e.g., `\b` or `^`. */
+  kind: AstAssertionKind
+}
+
+/**
+ * An assertion kind.
+ *
+ * - `StartLine`: `^`
+ * - `EndLine`: `$`
+ * - `StartText`: `\A`
+ * - `EndText`: `\z`
+ * - `WordBoundary`: `\b`
+ * - `NotWordBoundary`: `\B`
+ * - `WordBoundaryStart`: `\b{start}`
+ * - `WordBoundaryEnd`: `\b{end}`
+ * - `WordBoundaryStartAngle`: `\<`
+ * - `WordBoundaryEndAngle`: `\>`
+ * - `WordBoundaryStartHalf`: `\b{start-half}`
+ * - `WordBoundaryEndHalf`: `\b{end-half}`
+ */
+export type AstAssertionKind = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::ast::AssertionKind'
+  '@variant':
+    | 'StartLine'
+    | 'EndLine'
+    | 'StartText'
+    | 'EndText'
+    | 'WordBoundary'
+    | 'NotWordBoundary'
+    | 'WordBoundaryStart'
+    | 'WordBoundaryEnd'
+    | 'WordBoundaryStartAngle'
+    | 'WordBoundaryEndAngle'
+    | 'WordBoundaryStartHalf'
+    | 'WordBoundaryEndHalf'
+}"#;
+
+impl Serialize for Ast<&ast::Assertion> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut ast = serializer.serialize_struct("Assertion", 4)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::Assertion")?;
+        ast.serialize_field("span", &Ast::from(&self.0.span))?;
+        ast.serialize_field("kind", &Ast::from(&self.0.kind))?;
+        ast.end()
+    }
+}
+
+impl Serialize for Ast<&ast::AssertionKind> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self.0 {
+            ast::AssertionKind::StartLine => "StartLine",
+            ast::AssertionKind::EndLine => "EndLine",
+            ast::AssertionKind::StartText => "StartText",
+            ast::AssertionKind::EndText => "EndText",
+            ast::AssertionKind::WordBoundary => "WordBoundary",
+            ast::AssertionKind::NotWordBoundary => "NotWordBoundary",
+            ast::AssertionKind::WordBoundaryStart => "WordBoundaryStart",
+            ast::AssertionKind::WordBoundaryEnd => "WordBoundaryEnd",
+            ast::AssertionKind::WordBoundaryStartAngle => "WordBoundaryStartAngle",
+            ast::AssertionKind::WordBoundaryEndAngle => "WordBoundaryEndAngle",
+            ast::AssertionKind::WordBoundaryStartHalf => "WordBoundaryStartHalf",
+            ast::AssertionKind::WordBoundaryEndHalf => "WordBoundaryEndHalf",
+        };
+        serialize_variant(serializer, "regex_syntax::ast::AssertionKind", variant)
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const AST_CLASS_PERL_TYPE: &'static str = r#"/**
+ * A Perl character class, e.g., `\d` or `\W`.
+ */
+export type AstClassPerl = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::ClassPerl'
+
+  /** The span of this class. */
+  span: AstSpan
+
+  /** The kind of Perl class. */
+  kind: {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::ClassPerlKind'
+    '@variant': 'Digit' | 'Space' | 'Word'
+  }
+
+  /** Whether the class is negated or not. e.g., `\d` is not negated but `\D` is. */
+  negated: boolean
+}
+
+/**
+ * An ASCII character class, e.g., `[:alnum:]` or `[:punct:]`.
+ */
+export type AstClassAscii = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::ClassAscii'
+
+  /** The span of this class. */
+  span: AstSpan
+
+  /** The kind of ASCII class. */
+  kind: {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::ClassAsciiKind'
+    '@variant':
+      | 'Alnum'
+      | 'Alpha'
+      | 'Ascii'
+      | 'Blank'
+      | 'Cntrl'
+      | 'Digit'
+      | 'Graph'
+      | 'Lower'
+      | 'Print'
+      | 'Punct'
+      | 'Space'
+      | 'Upper'
+      | 'Word'
+      | 'Xdigit'
+  }
+
+  /** Whether the class is negated or not. e.g., `[[:alpha:]]` is not negated but `[[:^alpha:]]` is. */
+  negated: boolean
+}"#;
+
+impl Serialize for Ast<&ast::ClassPerl> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut ast = serializer.serialize_struct("ClassPerl", 5)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::ClassPerl")?;
+        ast.serialize_field("span", &Ast::from(&self.0.span))?;
+        ast.serialize_field("kind", &Ast::from(&self.0.kind))?;
+        ast.serialize_field("negated", &self.0.negated)?;
+        ast.end()
+    }
+}
+
+impl Serialize for Ast<&ast::ClassPerlKind> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self.0 {
+            ast::ClassPerlKind::Digit => "Digit",
+            ast::ClassPerlKind::Space => "Space",
+            ast::ClassPerlKind::Word => "Word",
+        };
+        serialize_variant(serializer, "regex_syntax::ast::ClassPerlKind", variant)
+    }
+}
+
+impl Serialize for Ast<&ast::ClassAscii> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut ast = serializer.serialize_struct("ClassAscii", 5)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::ClassAscii")?;
+        ast.serialize_field("span", &Ast::from(&self.0.span))?;
+        ast.serialize_field("kind", &Ast::from(&self.0.kind))?;
+        ast.serialize_field("negated", &self.0.negated)?;
+        ast.end()
+    }
+}
+
+impl Serialize for Ast<&ast::ClassAsciiKind> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self.0 {
+            ast::ClassAsciiKind::Alnum => "Alnum",
+            ast::ClassAsciiKind::Alpha => "Alpha",
+            ast::ClassAsciiKind::Ascii => "Ascii",
+            ast::ClassAsciiKind::Blank => "Blank",
+            ast::ClassAsciiKind::Cntrl => "Cntrl",
+            ast::ClassAsciiKind::Digit => "Digit",
+            ast::ClassAsciiKind::Graph => "Graph",
+            ast::ClassAsciiKind::Lower => "Lower",
+            ast::ClassAsciiKind::Print => "Print",
+            ast::ClassAsciiKind::Punct => "Punct",
+            ast::ClassAsciiKind::Space => "Space",
+            ast::ClassAsciiKind::Upper => "Upper",
+            ast::ClassAsciiKind::Word => "Word",
+            ast::ClassAsciiKind::Xdigit => "Xdigit",
+        };
+        serialize_variant(serializer, "regex_syntax::ast::ClassAsciiKind", variant)
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const AST_CLASS_UNICODE_TYPE: &'static str = r#"/**
+ * A Unicode character class, e.g., `\pL` or `\p{Greek}`.
+ */
+export type AstClassUnicode = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::ClassUnicode'
+
+  /** The span of this class. */
+  span: AstSpan
+
+  /**
+   * Whether this class is negated or not.
+   *
+   * Note: be careful when using this attribute. This specifically refers
+   * to whether the class is written as `\p` or `\P`, where the latter
+   * is `negated = true`. However, it also possible to write something like
+   * `\P{scx!=Katakana}` which is actually equivalent to
+   * `\p{scx=Katakana}` and is therefore not actually negated even though
+   * `negated = true` here.
+   */
+  negated: boolean
+
+  /** The kind of Unicode class. */
+  kind: AstClassUnicodeKind
+}
+
+/**
+ * The available forms of Unicode character classes.
+ */
+export type AstClassUnicodeKind =
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::ClassUnicodeKind'
+    /** A one letter abbreviated class, e.g., `\pN`. */
+    '@variant': 'OneLetter'
+    '@values': [string]
+  }
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::ClassUnicodeKind'
+    /** A binary property, general category or script. The string may be empty. */
+    '@variant': 'Named'
+    '@values': [string]
+  }
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::ClassUnicodeKind'
+    /** A property name and an associated value. */
+    '@variant': 'NamedValue'
+
+    /**
+     * The type of Unicode op used to associate `name` with `value`:
+     * `Equal` (`=`), `Colon` (`:`) or `NotEqual` (`!=`).
+     */
+    op: {
+      '@type': 'enum'
+      '@name': 'regex_syntax::ast::ClassUnicodeOpKind'
+      '@variant': 'Equal' | 'Colon' | 'NotEqual'
+    }
+
+    /** The property name (which may be empty). */
+    name: string
+
+    /** The property value (which may be empty). */
+    value: string
+  }"#;
+
+impl Serialize for Ast<&ast::ClassUnicode> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut ast = serializer.serialize_struct("ClassUnicode", 5)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::ClassUnicode")?;
+        ast.serialize_field("span", &Ast::from(&self.0.span))?;
+        ast.serialize_field("negated", &self.0.negated)?;
+        ast.serialize_field("kind", &Ast::from(&self.0.kind))?;
+        ast.end()
+    }
+}
+
+impl Serialize for Ast<&ast::ClassUnicodeKind> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let name = "regex_syntax::ast::ClassUnicodeKind";
+        match self.0 {
+            ast::ClassUnicodeKind::OneLetter(c) => {
+                serialize_variant_values(serializer, name, "OneLetter", &[c])
+            }
+            ast::ClassUnicodeKind::Named(n) => {
+                serialize_variant_values(serializer, name, "Named", &[n])
+            }
+            ast::ClassUnicodeKind::NamedValue {
+                op,
+                name: property,
+                value,
+            } => {
+                let mut ast = serializer.serialize_struct("ClassUnicodeKind", 6)?;
+                ast.serialize_field("@type", "enum")?;
+                ast.serialize_field("@name", name)?;
+                ast.serialize_field("@variant", "NamedValue")?;
+                ast.serialize_field("op", &Ast::from(op))?;
+                ast.serialize_field("name", property)?;
+                ast.serialize_field("value", value)?;
+                ast.end()
+            }
+        }
+    }
+}
+
+impl Serialize for Ast<&ast::ClassUnicodeOpKind> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self.0 {
+            ast::ClassUnicodeOpKind::Equal => "Equal",
+            ast::ClassUnicodeOpKind::Colon => "Colon",
+            ast::ClassUnicodeOpKind::NotEqual => "NotEqual",
+        };
+        serialize_variant(serializer, "regex_syntax::ast::ClassUnicodeOpKind", variant)
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const AST_CLASS_BRACKETED_TYPE: &'static str = r#"/**
+ * A bracketed character class, e.g., `[a-z0-9]`.
+ */
+export type AstClassBracketed = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::ClassBracketed'
+
+  /** The span of this class. */
+  span: AstSpan
+
+  /** Whether this class is negated or not. e.g., `[a]` is not negated but `[^a]` is. */
+  negated: boolean
+
+  /**
+   * The type of this set. A set is either a normal union of things, e.g.,
+   * `[abc]` or a result of applying set operations, e.g., `[\pL--c]`.
+   */
+  kind: AstClassSet
+}
+
+/**
+ * A character class set.
+ *
+ * This type corresponds to the internal structure of a bracketed character
+ * class. That is, every bracketed character is one of two types: a union of
+ * items (literals, ranges, other bracketed classes) or a tree of binary set
+ * operations.
+ */
+export type AstClassSet =
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::ClassSet'
+    /** An item, which can be a single literal, range, nested character class or a union of items. */
+    '@variant': 'Item'
+    '@values': [AstClassSetItem]
+  }
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::ClassSet'
+    /** A single binary operation (i.e., &&, -- or ~~). */
+    '@variant': 'BinaryOp'
+    '@values': [AstClassSetBinaryOp]
+  }
+
+/**
+ * A single component of a character class set.
+ */
+export type AstClassSetItem =
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::ClassSetItem'
+    /** An empty item. */
+    '@variant': 'Empty'
+    '@values': [AstSpan]
+  }
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::ClassSetItem'
+    /** A single literal. */
+    '@variant': 'Literal'
+    '@values': [AstLiteral]
+  }
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::ClassSetItem'
+    /** A range between two literals. */
+    '@variant': 'Range'
+    '@values': [AstClassSetRange]
+  }
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::ClassSetItem'
+    /** An ASCII character class, e.g., `[:alnum:]` or `[:punct:]`. */
+    '@variant': 'Ascii'
+    '@values': [AstClassAscii]
+  }
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::ClassSetItem'
+    /** A Unicode character class, e.g., `\pL` or `\p{Greek}`. */
+    '@variant': 'Unicode'
+    '@values': [AstClassUnicode]
+  }
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::ClassSetItem'
+    /** A perl character class, e.g., `\d` or `\W`. */
+    '@variant': 'Perl'
+    '@values': [AstClassPerl]
+  }
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::ClassSetItem'
+    /** A bracketed character class set, which may contain zero or more character ranges and/or zero or more nested classes. e.g., `[a-zA-Z\pL]`. */
+    '@variant': 'Bracketed'
+    '@values': [AstClassBracketed]
+  }
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::ClassSetItem'
+    /** A union of items. */
+    '@variant': 'Union'
+    '@values': [AstClassSetUnion]
+  }
+
+/**
+ * A single character class range in a set.
+ */
+export type AstClassSetRange = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::ClassSetRange'
+
+  /** The span of this range. */
+  span: AstSpan
+
+  /** The start of this range. */
+  start: AstLiteral
+
+  /** The end of this range. */
+  end: AstLiteral
+}
+
+/**
+ * A union of items inside a character class set.
+ */
+export type AstClassSetUnion = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::ClassSetUnion'
+
+  /** The span of the items in this operation. e.g., the `a-z0-9` in `[^a-z0-9]` */
+  span: AstSpan
+
+  /** The sequence of items that make up this union. */
+  items: AstClassSetItem[]
+}
+
+/**
+ * A Unicode character class set operation.
+ */
+export type AstClassSetBinaryOp = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::ClassSetBinaryOp'
+
+  /** The span of this operation. e.g., the `a-z--[h-p]` in `[a-z--h-p]`. */
+  span: AstSpan
+
+  /**
+   * The type of this set operation: `Intersection` (`&&`), `Difference`
+   * (`--`) or `SymmetricDifference` (`~~`).
+   */
+  kind: {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::ClassSetBinaryOpKind'
+    '@variant': 'Intersection' | 'Difference' | 'SymmetricDifference'
+  }
+
+  /** The left hand side of the operation. */
+  lhs: AstClassSet
+
+  /** The right hand side of the operation. */
+  rhs: AstClassSet
+}"#;
+
+impl Serialize for Ast<&ast::ClassBracketed> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut ast = serializer.serialize_struct("ClassBracketed", 5)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::ClassBracketed")?;
+        ast.serialize_field("span", &Ast::from(&self.0.span))?;
+        ast.serialize_field("negated", &self.0.negated)?;
+        ast.serialize_field("kind", &Ast::from(&self.0.kind))?;
+        ast.end()
+    }
+}
+
+impl Serialize for Ast<&ast::ClassSet> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let name = "regex_syntax::ast::ClassSet";
+        match self.0 {
+            ast::ClassSet::Item(item) => {
+                serialize_variant_values(serializer, name, "Item", &[Ast::from(item)])
+            }
+            ast::ClassSet::BinaryOp(op) => {
+                serialize_variant_values(serializer, name, "BinaryOp", &[Ast::from(op)])
+            }
+        }
+    }
+}
+
+impl Serialize for Ast<&ast::ClassSetItem> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let name = "regex_syntax::ast::ClassSetItem";
+        match self.0 {
+            ast::ClassSetItem::Empty(span) => {
+                serialize_variant_values(serializer, name, "Empty", &[Ast::from(span)])
+            }
+            ast::ClassSetItem::Literal(literal) => {
+                serialize_variant_values(serializer, name, "Literal", &[Ast::from(literal)])
+            }
+            ast::ClassSetItem::Range(range) => {
+                serialize_variant_values(serializer, name, "Range", &[Ast::from(range)])
+            }
+            ast::ClassSetItem::Ascii(class) => {
+                serialize_variant_values(serializer, name, "Ascii", &[Ast::from(class)])
+            }
+            ast::ClassSetItem::Unicode(class) => {
+                serialize_variant_values(serializer, name, "Unicode", &[Ast::from(class)])
+            }
+            ast::ClassSetItem::Perl(class) => {
+                serialize_variant_values(serializer, name, "Perl", &[Ast::from(class)])
+            }
+            ast::ClassSetItem::Bracketed(class) => serialize_variant_values(
+                serializer,
+                name,
+                "Bracketed",
+                &[Ast::from(class.as_ref())],
+            ),
+            ast::ClassSetItem::Union(union) => {
+                serialize_variant_values(serializer, name, "Union", &[Ast::from(union)])
+            }
+        }
+    }
+}
+
+impl Serialize for Ast<&ast::ClassSetRange> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut ast = serializer.serialize_struct("ClassSetRange", 5)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::ClassSetRange")?;
+        ast.serialize_field("span", &Ast::from(&self.0.span))?;
+        ast.serialize_field("start", &Ast::from(&self.0.start))?;
+        ast.serialize_field("end", &Ast::from(&self.0.end))?;
+        ast.end()
+    }
+}
+
+impl Serialize for Ast<&ast::ClassSetUnion> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let items: Vec<Ast<&ast::ClassSetItem>> = self.0.items.iter().map(Ast::from).collect();
+        let mut ast = serializer.serialize_struct("ClassSetUnion", 4)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::ClassSetUnion")?;
+        ast.serialize_field("span", &Ast::from(&self.0.span))?;
+        ast.serialize_field("items", &items)?;
+        ast.end()
+    }
+}
+
+impl Serialize for Ast<&ast::ClassSetBinaryOp> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut ast = serializer.serialize_struct("ClassSetBinaryOp", 6)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::ClassSetBinaryOp")?;
+        ast.serialize_field("span", &Ast::from(&self.0.span))?;
+        ast.serialize_field("kind", &Ast::from(&self.0.kind))?;
+        ast.serialize_field("lhs", &Ast::from(self.0.lhs.as_ref()))?;
+        ast.serialize_field("rhs", &Ast::from(self.0.rhs.as_ref()))?;
+        ast.end()
+    }
+}
+
+impl Serialize for Ast<&ast::ClassSetBinaryOpKind> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self.0 {
+            ast::ClassSetBinaryOpKind::Intersection => "Intersection",
+            ast::ClassSetBinaryOpKind::Difference => "Difference",
+            ast::ClassSetBinaryOpKind::SymmetricDifference => "SymmetricDifference",
+        };
+        serialize_variant(serializer, "regex_syntax::ast::ClassSetBinaryOpKind", variant)
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const AST_REPETITION_TYPE: &'static str = r#"/**
+ * A repetition operation applied to a regular expression.
+ */
+export type AstRepetition = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::Repetition'
+
+  /** The span of this operation. */
+  span: AstSpan
+
+  /** The actual operation. */
+  op: AstRepetitionOp
+
+  /** Whether this operation was applied greedily or not. */
+  greedy: boolean
+
+  /** The regular expression under repetition. */
+  ast: Ast
+}
+
+/**
+ * The repetition operator itself.
+ */
+export type AstRepetitionOp = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::RepetitionOp'
+
+  /** The span of this operator. This includes things like `+`, `*?` and `{m,n}`. */
+  span: AstSpan
+
+  /** The type of operation. */
+  kind: AstRepetitionKind
+}
+
+/**
+ * The kind of a repetition operator: `ZeroOrOne` (`?`), `ZeroOrMore` (`*`),
+ * `OneOrMore` (`+`) or a `Range` (`{m,n}`).
+ */
+export type AstRepetitionKind =
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::RepetitionKind'
+    '@variant': 'ZeroOrOne' | 'ZeroOrMore' | 'OneOrMore'
+  }
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::RepetitionKind'
+    '@variant': 'Range'
+    '@values': [AstRepetitionRange]
+  }
+
+/**
+ * A range repetition operator: `Exactly` (`{m}`), `AtLeast` (`{m,}`) or
+ * `Bounded` (`{m,n}`).
+ */
+export type AstRepetitionRange =
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::RepetitionRange'
+    '@variant': 'Exactly' | 'AtLeast'
+    '@values': [number]
+  }
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::RepetitionRange'
+    '@variant': 'Bounded'
+    '@values': [number, number]
+  }"#;
+
+impl Serialize for Ast<&ast::Repetition> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut ast = serializer.serialize_struct("Repetition", 6)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::Repetition")?;
+        ast.serialize_field("span", &Ast::from(&self.0.span))?;
+        ast.serialize_field("op", &Ast::from(&self.0.op))?;
+        ast.serialize_field("greedy", &self.0.greedy)?;
+        ast.serialize_field("ast", &Ast::from(self.0.ast.as_ref()))?;
+        ast.end()
+    }
+}
+
+impl Serialize for Ast<&ast::RepetitionOp> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut ast = serializer.serialize_struct("RepetitionOp", 4)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::RepetitionOp")?;
+        ast.serialize_field("span", &Ast::from(&self.0.span))?;
+        ast.serialize_field("kind", &Ast::from(&self.0.kind))?;
+        ast.end()
+    }
+}
+
+impl Serialize for Ast<&ast::RepetitionKind> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let name = "regex_syntax::ast::RepetitionKind";
+        match self.0 {
+            ast::RepetitionKind::ZeroOrOne => serialize_variant(serializer, name, "ZeroOrOne"),
+            ast::RepetitionKind::ZeroOrMore => serialize_variant(serializer, name, "ZeroOrMore"),
+            ast::RepetitionKind::OneOrMore => serialize_variant(serializer, name, "OneOrMore"),
+            ast::RepetitionKind::Range(range) => {
+                serialize_variant_values(serializer, name, "Range", &[Ast::from(range)])
+            }
+        }
+    }
+}
+
+impl Serialize for Ast<&ast::RepetitionRange> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let name = "regex_syntax::ast::RepetitionRange";
+        match self.0 {
+            ast::RepetitionRange::Exactly(n) => {
+                serialize_variant_values(serializer, name, "Exactly", &[n])
+            }
+            ast::RepetitionRange::AtLeast(n) => {
+                serialize_variant_values(serializer, name, "AtLeast", &[n])
+            }
+            ast::RepetitionRange::Bounded(m, n) => {
+                serialize_variant_values(serializer, name, "Bounded", &[m, n])
+            }
+        }
+    }
 }
 
-/**
- * Match the start half of an ASCII-only word boundary. That is, this matches a
- * position at either the beginning of the haystack or where the previous
- * character is not a word character.
+#[wasm_bindgen(typescript_custom_section)]
+const AST_GROUP_TYPE: &'static str = r#"/**
+ * A grouped regular expression.
+ *
+ * This includes both capturing and non-capturing groups. This does **not**
+ * include flag-only groups like `(?is)`, but does contain any group that
+ * contains a sub-expression, e.g., `(a)`, `(?P<name>a)`, `(?:a)` and
+ * `(?is:a)`.
  */
-export type LookWordStartHalfAsciiVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir::Look',
-  '@variant': 'WordStartHalfAscii'
-}
+export type AstGroup = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::Group'
 
-/**
- * Match the end half of an ASCII-only word boundary. That is, this matches a
- * position at either the end of the haystack or where the following character
- * is not a word character.
- */
-export type LookWordEndHalfAsciiVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir::Look',
-  '@variant': 'WordEndHalfAscii'
+  /** The span of this group. */
+  span: AstSpan
+
+  /** The kind of this group. */
+  kind: AstGroupKind
+
+  /** The regular expression in this group. */
+  ast: Ast
 }
 
 /**
- * Match the start half of a Unicode word boundary. That is, this matches a
- * position at either the beginning of the haystack or where the previous
- * character is not a word character.
+ * The kind of a group.
  */
-export type LookWordStartHalfUnicodeVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir::Look',
-  '@variant': 'WordStartHalfUnicode'
-}
+export type AstGroupKind =
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::GroupKind'
+    /** `(a)` */
+    '@variant': 'CaptureIndex'
+    '@values': [number]
+  }
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::GroupKind'
+    /** `(?<name>a)` or `(?P<name>a)` */
+    '@variant': 'CaptureName'
+
+    /** True if the `?P<` syntax is used and false if the `?<` syntax is used. */
+    startsWithP: boolean
+
+    /** The capture name. */
+    name: AstCaptureName
+  }
+  | {
+    '@type': 'enum'
+    '@name': 'regex_syntax::ast::GroupKind'
+    /** `(?:a)` and `(?i:a)` */
+    '@variant': 'NonCapturing'
+    '@values': [AstFlags]
+  }
 
 /**
- * Match the end half of a Unicode word boundary. That is, this matches a
- * position at either the end of the haystack or where the following character
- * is not a word character.
+ * A capture name.
+ *
+ * This corresponds to the name itself between the angle brackets in, e.g.,
+ * `(?P<foo>expr)`.
  */
-export type LookWordEndHalfUnicodeVariant = {
-  '@type': 'enum'
-  '@name': 'regex_syntax::hir::Look',
-  '@variant': 'WordEndHalfUnicode'
+export type AstCaptureName = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::CaptureName'
+
+  /** The span of this capture name. */
+  span: AstSpan
+
+  /** The capture name. */
+  name: string
+
+  /** The capture index. */
+  index: number
 }"#;
 
-impl Serialize for Hir<&hir::Look> {
+impl Serialize for Ast<&ast::Group> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let mut hir = serializer.serialize_struct("Look", 3)?;
-        hir.serialize_field("@type", "enum")?;
-        hir.serialize_field("@name", "regex_syntax::hir::Look")?;
+        let mut ast = serializer.serialize_struct("Group", 5)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::Group")?;
+        ast.serialize_field("span", &Ast::from(&self.0.span))?;
+        ast.serialize_field("kind", &Ast::from(&self.0.kind))?;
+        ast.serialize_field("ast", &Ast::from(self.0.ast.as_ref()))?;
+        ast.end()
+    }
+}
+
+impl Serialize for Ast<&ast::GroupKind> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let name = "regex_syntax::ast::GroupKind";
         match self.0 {
-            hir::Look::Start => {
-                hir.serialize_field("@variant", "Start")?;
-            }
-            hir::Look::End => {
-                hir.serialize_field("@variant", "End")?;
+            ast::GroupKind::CaptureIndex(index) => {
+                serialize_variant_values(serializer, name, "CaptureIndex", &[index])
             }
-            hir::Look::StartLF => {
-                hir.serialize_field("@variant", "StartLF")?;
-            }
-            hir::Look::EndLF => {
-                hir.serialize_field("@variant", "EndLF")?;
-            }
-            hir::Look::StartCRLF => {
-                hir.serialize_field("@variant", "StartCRLF")?;
-            }
-            hir::Look::EndCRLF => {
-                hir.serialize_field("@variant", "EndCRLF")?;
-            }
-            hir::Look::WordAscii => {
-                hir.serialize_field("@variant", "WordAscii")?;
-            }
-            hir::Look::WordAsciiNegate => {
-                hir.serialize_field("@variant", "WordAsciiNegate")?;
-            }
-            hir::Look::WordUnicode => {
-                hir.serialize_field("@variant", "WordUnicode")?;
-            }
-            hir::Look::WordUnicodeNegate => {
-                hir.serialize_field("@variant", "WordUnicodeNegate")?;
-            }
-            hir::Look::WordStartAscii => {
-                hir.serialize_field("@variant", "WordStartAscii")?;
-            }
-            hir::Look::WordEndAscii => {
-                hir.serialize_field("@variant", "WordEndAscii")?;
-            }
-            hir::Look::WordStartUnicode => {
-                hir.serialize_field("@variant", "WordStartUnicode")?;
+            ast::GroupKind::CaptureName {
+                starts_with_p,
+                name: capture,
+            } => {
+                let mut ast = serializer.serialize_struct("GroupKind", 5)?;
+                ast.serialize_field("@type", "enum")?;
+                ast.serialize_field("@name", name)?;
+                ast.serialize_field("@variant", "CaptureName")?;
+                ast.serialize_field("startsWithP", starts_with_p)?;
+                ast.serialize_field("name", &Ast::from(capture))?;
+                ast.end()
             }
-            hir::Look::WordEndUnicode => {
-                hir.serialize_field("@variant", "WordEndUnicode")?;
-            }
-            hir::Look::WordStartHalfAscii => {
-                hir.serialize_field("@variant", "WordStartHalfAscii")?;
-            }
-            hir::Look::WordEndHalfAscii => {
-                hir.serialize_field("@variant", "WordEndHalfAscii")?;
+            ast::GroupKind::NonCapturing(flags) => {
+                serialize_variant_values(serializer, name, "NonCapturing", &[Ast::from(flags)])
             }
-            hir::Look::WordStartHalfUnicode => {
-                hir.serialize_field("@variant", "WordStartHalfUnicode")?;
-            }
-            hir::Look::WordEndHalfUnicode => {
-                hir.serialize_field("@variant", "WordEndHalfUnicode")?;
-            }
-        };
-        hir.end()
+        }
+    }
+}
+
+impl Serialize for Ast<&ast::CaptureName> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut ast = serializer.serialize_struct("CaptureName", 5)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::CaptureName")?;
+        ast.serialize_field("span", &Ast::from(&self.0.span))?;
+        ast.serialize_field("name", &self.0.name)?;
+        ast.serialize_field("index", &self.0.index)?;
+        ast.end()
     }
 }
 
 #[wasm_bindgen(typescript_custom_section)]
-const REPETITION_TYPE: &'static str = r#"/**
- * The high-level intermediate representation of a repetition operator.
+const AST_FLAGS_TYPE: &'static str = r#"/**
+ * A group of flags that is not applied to a particular regular expression.
+ */
+export type AstSetFlags = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::SetFlags'
+
+  /** The span of these flags, including the grouping parentheses. */
+  span: AstSpan
+
+  /** The actual sequence of flags. */
+  flags: AstFlags
+}
+
+/**
+ * A group of flags.
  *
- * A repetition operator permits the repetition of an arbitrary sub-expression
+ * This corresponds only to the sequence of flags themselves, e.g., `is-u`.
  */
-export type Repetition = {
+export type AstFlags = {
   '@type': 'struct'
-  '@name': 'regex_syntax::hir::Repetition'
+  '@name': 'regex_syntax::ast::Flags'
 
-  /**
-   * The minimum range of the repetition.
-   *
-   * Note that special cases like `?`, `+` and `*` all get translated into the
-   * ranges `{0,1}`, `{1,}` and `{0,}`, respectively.
-   *
-   * When `min` is zero, this expression can match the empty string regardless
-   * of what its sub-expression is.
-   */
-  min: number
+  /** The span of this group of flags. */
+  span: AstSpan
 
   /**
-   * The maximum range of the repetition.
-   *
-   * Note that when `max` is `undefined`, `min` acts as a lower bound but where
-   * there is no upper bound. For something like `x{5}` where the min and max
-   * are equivalent, `min` will be set to `5` and `max` will be set to `5`.
+   * A sequence of flag items. Each item is either a flag or a negation
+   * operator. Every flag after a negation, like the `u` in `is-u`, is
+   * disabled rather than enabled.
    */
-  max?: number
+  items: AstFlagsItem[]
+}
 
-  /**
-   * Whether this repetition operator is greedy or not. A greedy operator will
-   * match as much as it can. A non-greedy operator will match as little as it
-   * can.
-   *
-   * Typically, operators are greedy by default and are only non-greedy when a
-   * `?` suffix is used, e.g., `(expr)*` is greedy while `(expr)*?` is not.
-   * However, this can be inverted via the `U` “ungreedy” flag.
-   */
-  greedy: boolean
+/**
+ * A single item in a group of flags.
+ */
+export type AstFlagsItem = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::FlagsItem'
 
-  /**
-   * The expression being repeated.
-   */
-  sub: Hir
+  /** The span of this item. */
+  span: AstSpan
+
+  /** The kind of this item. */
+  kind: AstFlagsItemKind
+}
+
+/**
+ * The kind of an item in a group of flags, either the negation operator `-`
+ * or a flag.
+ */
+export type AstFlagsItemKind =
+  | {
+      '@type': 'enum'
+      '@name': 'regex_syntax::ast::FlagsItemKind'
+      '@variant': 'Negation'
+    }
+  | {
+      '@type': 'enum'
+      '@name': 'regex_syntax::ast::FlagsItemKind'
+      '@variant': 'Flag'
+      '@values': [AstFlag]
+    }
+
+/**
+ * A single flag, with the letter it's written with in a pattern:
+ *
+ * - `CaseInsensitive`: `i`
+ * - `MultiLine`: `m`
+ * - `DotMatchesNewLine`: `s`
+ * - `SwapGreed`: `U`
+ * - `Unicode`: `u`
+ * - `CRLF`: `R`
+ * - `IgnoreWhitespace`: `x`
+ */
+export type AstFlag = {
+  '@type': 'enum'
+  '@name': 'regex_syntax::ast::Flag'
+  '@variant':
+    | 'CaseInsensitive'
+    | 'MultiLine'
+    | 'DotMatchesNewLine'
+    | 'SwapGreed'
+    | 'Unicode'
+    | 'CRLF'
+    | 'IgnoreWhitespace'
 }"#;
 
-impl Serialize for Hir<&hir::Repetition> {
+impl Serialize for Ast<&ast::SetFlags> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let mut hir = serializer.serialize_struct("Repetition", 6)?;
-        hir.serialize_field("@type", "struct")?;
-        hir.serialize_field("@name", "regex_syntax::hir::Repetition")?;
-        hir.serialize_field("min", &self.0.min)?;
-        hir.serialize_field("max", &self.0.max)?;
-        hir.serialize_field("greedy", &self.0.greedy)?;
-        hir.serialize_field("sub", &Hir::from(self.0.sub.as_ref()))?;
-        hir.end()
+        let mut ast = serializer.serialize_struct("SetFlags", 4)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::SetFlags")?;
+        ast.serialize_field("span", &Ast::from(&self.0.span))?;
+        ast.serialize_field("flags", &Ast::from(&self.0.flags))?;
+        ast.end()
+    }
+}
+
+impl Serialize for Ast<&ast::Flags> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let items: Vec<Ast<&ast::FlagsItem>> = self.0.items.iter().map(Ast::from).collect();
+
+        let mut ast = serializer.serialize_struct("Flags", 4)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::Flags")?;
+        ast.serialize_field("span", &Ast::from(&self.0.span))?;
+        ast.serialize_field("items", &items)?;
+        ast.end()
+    }
+}
+
+impl Serialize for Ast<&ast::FlagsItem> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut ast = serializer.serialize_struct("FlagsItem", 4)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::FlagsItem")?;
+        ast.serialize_field("span", &Ast::from(&self.0.span))?;
+        ast.serialize_field("kind", &Ast::from(&self.0.kind))?;
+        ast.end()
+    }
+}
+
+impl Serialize for Ast<&ast::FlagsItemKind> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self.0 {
+            ast::FlagsItemKind::Negation => {
+                serialize_variant(serializer, "regex_syntax::ast::FlagsItemKind", "Negation")
+            }
+            ast::FlagsItemKind::Flag(flag) => serialize_variant_values(
+                serializer,
+                "regex_syntax::ast::FlagsItemKind",
+                "Flag",
+                &[Ast::from(flag)],
+            ),
+        }
+    }
+}
+
+impl Serialize for Ast<&ast::Flag> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self.0 {
+            ast::Flag::CaseInsensitive => "CaseInsensitive",
+            ast::Flag::MultiLine => "MultiLine",
+            ast::Flag::DotMatchesNewLine => "DotMatchesNewLine",
+            ast::Flag::SwapGreed => "SwapGreed",
+            ast::Flag::Unicode => "Unicode",
+            ast::Flag::CRLF => "CRLF",
+            ast::Flag::IgnoreWhitespace => "IgnoreWhitespace",
+        };
+        serialize_variant(serializer, "regex_syntax::ast::Flag", variant)
     }
 }
 
 #[wasm_bindgen(typescript_custom_section)]
-const CAPTURE_TYPE: &'static str = r#"/**
- * The high-level intermediate representation for a capturing group.
- *
- * A capturing group always has an index and a child expression. It may also
- * have a name associated with it (e.g., `(?P<foo>\w)`), but it's not necessary.
+const AST_WITH_COMMENTS_TYPE: &'static str = r#"/**
+ * The abstract syntax tree of a regular expression, along with the comments
+ * written in it with the `x` flag, in the order they appear.
  *
- * Note that there is no explicit representation of a non-capturing group in a
- * `Hir`. Instead, non-capturing grouping is handled automatically by the
- * recursive structure of the `Hir` itself.
+ * Whitespace ignored by the `x` flag is not part of the tree, it's whatever
+ * lies between the spans of the nodes and comments.
  */
-export type Capture = {
+export type AstWithComments = {
   '@type': 'struct'
-  '@name': 'regex_syntax::hir::Capture'
-
-  /** The capture index of the capture. */
-  index: number
-
-  /** The name of the capture, if it exists. */
-  name?: String
+  '@name': 'regex_syntax::ast::WithComments'
+  ast: Ast
+  comments: AstComment[]
+}
 
-  /** The expression inside the capturing group, which may be empty. */
-  sub: Hir
+/**
+ * A comment from a regular expression with an associated span, e.g., the
+ * ` digits` in `\d+ # digits`, without the leading `#` and the trailing line
+ * break.
+ */
+export type AstComment = {
+  '@type': 'struct'
+  '@name': 'regex_syntax::ast::Comment'
+  span: AstSpan
+  comment: string
 }"#;
 
-impl Serialize for Hir<&hir::Capture> {
+impl Serialize for Ast<&ast::WithComments> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let mut hir = serializer.serialize_struct("Capture", 5)?;
-        hir.serialize_field("@type", "struct")?;
-        hir.serialize_field("@name", "regex_syntax::hir::Capture")?;
-        hir.serialize_field("index", &self.0.index)?;
-        hir.serialize_field("name", &self.0.name)?;
-        hir.serialize_field("sub", &Hir::from(self.0.sub.as_ref()))?;
-        hir.end()
+        let comments: Vec<Ast<&ast::Comment>> = self.0.comments.iter().map(Ast::from).collect();
+        let mut ast = serializer.serialize_struct("WithComments", 4)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::WithComments")?;
+        ast.serialize_field("ast", &Ast::from(&self.0.ast))?;
+        ast.serialize_field("comments", &comments)?;
+        ast.end()
+    }
+}
+
+impl Serialize for Ast<&ast::Comment> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut ast = serializer.serialize_struct("Comment", 4)?;
+        ast.serialize_field("@type", "struct")?;
+        ast.serialize_field("@name", "regex_syntax::ast::Comment")?;
+        ast.serialize_field("span", &Ast::from(&self.0.span))?;
+        ast.serialize_field("comment", &self.0.comment)?;
+        ast.end()
     }
 }
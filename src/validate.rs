@@ -0,0 +1,30 @@
+use crate::types::RegexError;
+use std::convert::TryInto;
+use wasm_bindgen::prelude::*;
+
+type Result<T> = std::result::Result<T, serde_wasm_bindgen::Error>;
+
+/// Checks whether `pattern` is a valid regular expression without keeping
+/// the compiled regex around, so there is no `RRegex` to `free`.
+///
+/// Returns `null` when the pattern compiles, or the same `RegexError` object
+/// `RRegex.tryNew` throws when it doesn't.
+///
+/// # Example
+///
+/// ```typescript
+/// import { validate } from "rregex"
+///
+/// expect(validate("a+")).toBe(null)
+/// expect(validate("a(b").span).toEqual({ start: 1, end: 2 })
+/// ```
+///
+/// @param {string} pattern - The regular expression to check
+/// @returns {RegexError | null}
+#[wasm_bindgen(skip_jsdoc)]
+pub fn validate(pattern: &str) -> Result<JsValue> {
+    match regex::Regex::new(pattern) {
+        Ok(_) => Ok(JsValue::NULL),
+        Err(err) => RegexError::new(pattern, err).try_into(),
+    }
+}
@@ -0,0 +1,32 @@
+use crate::types::Match;
+use std::sync::OnceLock;
+use wasm_bindgen::prelude::*;
+
+type Result<T> = std::result::Result<T, serde_wasm_bindgen::Error>;
+
+static WORD: OnceLock<regex::Regex> = OnceLock::new();
+
+/// Returns every run of Unicode word characters in `text`, i.e., every
+/// match of `\w+`, with its byte offsets.
+///
+/// The `\w+` regex is compiled once and reused by every call, so there's no
+/// `RRegex` to create or `free`.
+///
+/// # Example
+///
+/// ```typescript
+/// import { words } from "rregex"
+///
+/// const tokens = words("Hello, wörld!")
+/// expect(tokens.map((m) => m.value)).toEqual(["Hello", "wörld"])
+/// expect(tokens[1]).toEqual({ value: "wörld", start: 7, end: 13 })
+/// ```
+///
+/// @param {string} text - The string to split into words
+/// @returns {Match[]}
+#[wasm_bindgen(skip_jsdoc)]
+pub fn words(text: &str) -> Result<JsValue> {
+    let word = WORD.get_or_init(|| regex::Regex::new(r"\w+").unwrap());
+    let matches: Vec<Match> = word.find_iter(text).map(Match::from).collect();
+    serde_wasm_bindgen::to_value(&matches)
+}
@@ -1,11 +1,451 @@
 import { deepEqual } from "node:assert"
 import { test } from "bun:test"
-import { RRegex, RRegexSet } from '../lib/esm.mjs'
+import { RRegex, RRegexBuilder, RRegexSet, RRegexSetBuilder, byteClassContains, byteClassRanges, classCaseFold, classContains, classNegate, compileReplacement, hirToString, prepare, validate, words } from '../lib/esm.mjs'
 
 test(`RRegex::toString`, () => {
   deepEqual(new RRegex('a').toString(), 'a')
 })
 
+test(`RRegex::source`, () => {
+  const regex = new RRegex('a\\d+')
+  deepEqual(regex.source, 'a\\d+')
+  deepEqual(`/${regex.source}/`, '/a\\d+/')
+})
+
+test(`RRegex::flags`, () => {
+  deepEqual(new RRegex('a').flags, '')
+  deepEqual(new RRegex('(?is)a.b').flags, 'is')
+  deepEqual(new RRegex('(?x)(?U-x) a').flags, 'U')
+  deepEqual(new RRegex('(?i)').flags, 'i')
+  deepEqual(new RRegex('a(?i)b').flags, '')
+  deepEqual(new RRegex('(?i:a)').flags, '')
+  deepEqual(RRegex.fromJSON({ source: '(?-i)a', flags: 'im' }).flags, 'm')
+})
+
+test(`RRegex::tryNew`, () => {
+  const position = ([offset, line, column], [endOffset, endLine, endColumn]) => ({
+    start: { offset, line, column },
+    end: { offset: endOffset, line: endLine, column: endColumn },
+  })
+
+  deepEqual(RRegex.tryNew('a').toString(), 'a')
+
+  const errors = [
+    ['a(b', { message: 'unclosed group', kind: 'GroupUnclosed', span: { start: 1, end: 2 }, position: position([1, 1, 2], [2, 1, 3]) }],
+    ['a{2', { message: 'unclosed counted repetition', kind: 'RepetitionCountUnclosed', span: { start: 1, end: 3 }, position: position([1, 1, 2], [3, 1, 4]) }],
+    ['\\p{Foo}', { message: 'Unicode property not found', kind: 'UnicodePropertyNotFound', span: { start: 0, end: 7 }, position: position([0, 1, 1], [7, 1, 8]) }],
+    ['(?x)\n  a\n  (b', { message: 'unclosed group', kind: 'GroupUnclosed', span: { start: 11, end: 12 }, position: position([11, 3, 3], [12, 3, 4]) }],
+    ['δ(', { message: 'unclosed group', kind: 'GroupUnclosed', span: { start: 2, end: 3 }, position: position([2, 1, 2], [3, 1, 3]) }],
+    ['(a)\\1', { message: 'backreferences are not supported', kind: 'UnsupportedBackreference', feature: 'backreference', span: { start: 3, end: 5 }, position: position([3, 1, 4], [5, 1, 6]) }],
+  ]
+
+  for (const [pattern, expected] of errors) {
+    let error
+    try {
+      RRegex.tryNew(pattern)
+    } catch (err) {
+      error = err
+    }
+    deepEqual(error, expected)
+  }
+})
+
+test(`RRegex::tryNew -> unsupported features`, () => {
+  const error = (pattern) => {
+    try {
+      RRegex.tryNew(pattern)
+    } catch (err) {
+      return err
+    }
+  }
+
+  deepEqual(error('(a)\\1').feature, 'backreference')
+  deepEqual(error('a(?=b)').feature, 'lookaround')
+  deepEqual(error('a(?=b)').span, { start: 1, end: 4 })
+  deepEqual(error('x(?<!a)').span, { start: 1, end: 5 })
+  deepEqual('feature' in error('a(b'), false)
+})
+
+test(`RRegex::getOrCompile`, () => {
+  const first = RRegex.getOrCompile('\\d+')
+  const second = RRegex.getOrCompile('\\d+')
+  deepEqual(first === second, false)
+  deepEqual(first.equals(second), true)
+  deepEqual(second.find('a42'), { start: 1, end: 3, value: '42' })
+
+  first.free()
+  deepEqual(second.isMatch('7'), true)
+  deepEqual(RRegex.getOrCompile('\\d+').isMatch('7'), true)
+
+  try {
+    RRegex.getOrCompile('(')
+    deepEqual(true, false)
+  } catch (error) {
+    deepEqual(error instanceof Error, true)
+  }
+
+  RRegex.clearCache()
+  deepEqual(second.isMatch('7'), true)
+  deepEqual(RRegex.getOrCompile('\\d+').isMatch('x'), false)
+})
+
+test(`validate`, () => {
+  deepEqual(validate('a+'), null)
+  deepEqual(validate('(?<name>a)\\d'), null)
+  deepEqual(validate('a(b').span, { start: 1, end: 2 })
+  deepEqual(validate('a(b').kind, 'GroupUnclosed')
+  deepEqual(validate('a(b').position.start, { offset: 1, line: 1, column: 2 })
+  deepEqual(validate('\\p{Foo}').kind, 'UnicodePropertyNotFound')
+  deepEqual(validate('a{1000}{1000}').kind, 'CompiledTooBig')
+  deepEqual(validate('(a)\\1').feature, 'backreference')
+  deepEqual(validate('(a)\\1').span, { start: 3, end: 5 })
+  deepEqual(validate('a(b').feature, undefined)
+  for (const pattern of ['a(?=b)', 'a(?!b)', '(?<=a)b', '(?<!a)b']) {
+    deepEqual(validate(pattern).feature, 'lookaround')
+    deepEqual(validate(pattern).kind, 'UnsupportedLookAround')
+  }
+})
+
+test(`words`, () => {
+  deepEqual(words('Hello, wörld!'), [
+    { value: 'Hello', start: 0, end: 5 },
+    { value: 'wörld', start: 7, end: 13 },
+  ])
+  deepEqual(words('snake_case 42 x'), new RRegex('\\w+').findAll('snake_case 42 x'))
+  deepEqual(words(''), [])
+  deepEqual(words('  ...  '), [])
+})
+
+test(`prepare`, () => {
+  const text = prepare('a1 δ2 c3')
+  deepEqual(text.byteLength, 9)
+
+  const digits = new RRegex('\\d')
+  deepEqual(digits.isMatchOn(text), true)
+  deepEqual(digits.findOn(text), digits.find('a1 δ2 c3'))
+  deepEqual(digits.findAllOn(text), digits.findAll('a1 δ2 c3'))
+
+  const missing = new RRegex('x')
+  deepEqual(missing.isMatchOn(text), false)
+  deepEqual(missing.findOn(text), undefined)
+  deepEqual(missing.findAllOn(text), [])
+
+  deepEqual(new RRegex('^$').isMatchOn(prepare('')), true)
+})
+
+test(`RRegex::replaceAllOn`, () => {
+  const text = prepare('a1 b2')
+  deepEqual(new RRegex('\\d').replaceAllOn(text, '#'), 'a# b#')
+  deepEqual(new RRegex('(?P<l>[a-z])').replaceAllOn(text, '<$l>'), '<a>1 <b>2')
+  deepEqual(new RRegex('x').replaceAllOn(text, '#'), 'a1 b2')
+  deepEqual(new RRegex('\\d').findAllOn(text).length, 2)
+})
+
+test(`RRegex::toJSON`, () => {
+  const regex = new RRegex('a+')
+  deepEqual(regex.toJSON(), { source: 'a+', flags: '' })
+  deepEqual(JSON.stringify({ regex }), '{"regex":{"source":"a+","flags":""}}')
+})
+
+test(`RRegex::fromJSON`, () => {
+  const regex = RRegex.fromJSON({ source: 'a+', flags: 'i' })
+  deepEqual(regex.isMatch('AA'), true)
+  deepEqual(regex.toJSON(), { source: 'a+', flags: 'i' })
+
+  const copy = RRegex.fromJSON(JSON.parse(JSON.stringify(RRegex.fromJSON({ source: '^a . b$', flags: 'mxsU' }))))
+  deepEqual(copy.toJSON(), { source: '^a . b$', flags: 'msxU' })
+  deepEqual(copy.find('x\na\nb'), { value: 'a\nb', start: 2, end: 5 })
+
+  deepEqual(RRegex.fromJSON({ source: 'a' }).toJSON(), { source: 'a', flags: '' })
+  deepEqual(RRegex.fromJSON({ source: 'a', flags: 'i-i' }).isMatch('A'), false)
+
+  for (const json of [{ source: 'a', flags: 'g' }, { source: '(' }, {}]) {
+    let error
+    try {
+      RRegex.fromJSON(json)
+    } catch (err) {
+      error = err
+    }
+    deepEqual(error instanceof Error, true)
+  }
+})
+
+test(`RRegex::serialize`, () => {
+  const bytes = new RRegex('a+').serialize()
+  deepEqual(bytes instanceof Uint8Array, true)
+  deepEqual(RRegex.deserialize(bytes).source, 'a+')
+  deepEqual(RRegex.deserialize(bytes).isMatch('aa'), true)
+
+  const regex = new RRegexBuilder('^a.c$').caseInsensitive(true).multiLine(true).octal(true).lineTerminator(0).longestMatch(true).build()
+  const copy = RRegex.deserialize(regex.serialize())
+  deepEqual(copy.toJSON(), regex.toJSON())
+  deepEqual(copy.isMatch('x\0A\nC\0'), true)
+  deepEqual(new RRegex('☃|é').serialize().length, 4 + new TextEncoder().encode('☃|é').length)
+
+  for (const bytes of [new Uint8Array([]), new Uint8Array([2, 0, 0, 10]), new Uint8Array([1, 0, 0, 10, 0xff]), new Uint8Array([1, 0, 0, 10, 40])]) {
+    try {
+      RRegex.deserialize(bytes)
+      deepEqual(true, false)
+    } catch (error) {
+      deepEqual(error instanceof Error, true)
+    }
+  }
+})
+
+test(`RRegex::clone`, () => {
+  const regex = new RRegexBuilder('(?<year>\\d{4})-(?<month>\\d{2})').caseInsensitive(true).build()
+  const copy = regex.clone()
+  deepEqual(copy.toJSON(), regex.toJSON())
+  deepEqual(copy.flags, regex.flags)
+  deepEqual(copy.captures('on 2024-05'), regex.captures('on 2024-05'))
+
+  regex.free()
+  deepEqual(copy.find('on 2024-05').value, '2024-05')
+  deepEqual(copy.clone().isMatch('1999-12'), true)
+})
+
+test(`RRegex::equals`, () => {
+  const regex = new RRegex('a|b')
+  deepEqual(regex.equals(new RRegex('a|b')), true)
+  deepEqual(regex.equals(regex), true)
+  deepEqual(regex.equals(regex.clone()), true)
+  deepEqual(regex.equals(new RRegex('b|a')), false)
+  deepEqual(regex.equals(new RRegexBuilder('a|b').caseInsensitive(true).build()), false)
+  deepEqual(new RRegexBuilder('a|b').build().equals(regex), true)
+})
+
+test(`RRegex::mayOverlap`, () => {
+  const users = new RRegex('^/users/\\w+$')
+  deepEqual(users.mayOverlap(new RRegex('^/users/me$'), []), true)
+  deepEqual(new RRegex('^/users/me$').mayOverlap(users, []), true)
+  deepEqual(users.mayOverlap(new RRegex('^/about$'), []), false)
+  deepEqual(new RRegex('^a$').mayOverlap(new RRegex('^b$'), ['a', 'b']), false)
+  deepEqual(new RRegex('^$').mayOverlap(new RRegex('x*'), []), true)
+  deepEqual(users.mayOverlap(new RRegex('/\\w+/ann'), ['/users/ann']), true)
+  deepEqual(users.mayOverlap(new RRegex('/\\w+/ann'), ['/users/bob', '/x/ann']), false)
+  deepEqual(new RRegex('(?m)^a$').mayOverlap(new RRegex('b'), []), false)
+})
+
+test(`RRegex::dispose`, () => {
+  const regex = new RRegex('a+')
+  const copy = regex.clone()
+  deepEqual(regex.isDisposed(), false)
+  deepEqual(regex.syntax().kind['@variant'], 'Repetition')
+  regex.dispose()
+  regex.dispose()
+  deepEqual(regex.isDisposed(), true)
+  for (const call of [() => regex.isMatch('a'), () => regex.find('a'), () => regex.syntax(), () => regex.source, () => regex.flags, () => regex.longestMatch, () => regex.equals(copy), () => regex.stateful()]) {
+    try {
+      call()
+      deepEqual(true, false)
+    } catch (error) {
+      deepEqual(error instanceof Error, true)
+      deepEqual(error.message, 'regex has been disposed')
+    }
+  }
+  deepEqual(copy.isDisposed(), false)
+  deepEqual(copy.isMatch('a'), true)
+  regex.free()
+})
+
+test(`RRegexBuilder::build`, () => {
+  const regex = new RRegexBuilder('^a.b$').caseInsensitive(true).multiLine(true).dotMatchesNewLine(true).build()
+  deepEqual(regex.find('x\nA\nB'), { value: 'A\nB', start: 2, end: 5 })
+  deepEqual(regex.flags, 'ims')
+  deepEqual(new RRegexBuilder('a').build().flags, '')
+  deepEqual(new RRegexBuilder('a b # comment').ignoreWhitespace(true).build().isMatch('ab'), true)
+  deepEqual(new RRegexBuilder('a+').swapGreed(true).build().find('aaa').value, 'a')
+
+  const builder = new RRegexBuilder('a')
+  builder.caseInsensitive(true)
+  deepEqual(builder.build().isMatch('A'), true)
+
+  let error
+  try {
+    new RRegexBuilder('(').build()
+  } catch (err) {
+    error = err
+  }
+  deepEqual(error instanceof Error, true)
+})
+
+test(`RRegexBuilder::unicode`, () => {
+  const regex = new RRegexBuilder('\\w+').unicode(false).build()
+  deepEqual(regex.find('δxyz').value, 'xyz')
+  deepEqual(regex.flags, '-u')
+  deepEqual(regex.toJSON(), { source: '\\w+', flags: '-u' })
+  deepEqual(RRegex.fromJSON(regex.toJSON()).find('δxyz').value, 'xyz')
+  deepEqual(new RRegexBuilder('\\w+').unicode(true).build().find('δxyz').value, 'δxyz')
+  deepEqual(new RRegex('(?-u)\\w').flags, '-u')
+
+  for (const pattern of ['\\pL', '.']) {
+    let error
+    try {
+      new RRegexBuilder(pattern).unicode(false).build()
+    } catch (err) {
+      error = err
+    }
+    deepEqual(error instanceof Error, true)
+  }
+})
+
+test(`RRegexBuilder::octal`, () => {
+  const regex = new RRegexBuilder('\\141').octal(true).build()
+  deepEqual(regex.isMatch('a'), true)
+  deepEqual(regex.find('xa'), { value: 'a', start: 1, end: 2 })
+  deepEqual(regex.toJSON(), { source: '\\141', flags: '', octal: true })
+  deepEqual(RRegex.fromJSON(regex.toJSON()).isMatch('a'), true)
+  deepEqual(regex.ast()['@variant'], 'Literal')
+
+  for (const build of [() => new RRegexBuilder('\\141').octal(false).build(), () => RRegex.fromJSON({ source: '\\141' })]) {
+    let error
+    try {
+      build()
+    } catch (err) {
+      error = err
+    }
+    deepEqual(error instanceof Error, true)
+  }
+})
+
+test(`RRegexBuilder::lineTerminator`, () => {
+  const regex = new RRegexBuilder('^\\w+$').multiLine(true).lineTerminator(0).build()
+  deepEqual(regex.findAll('foo\0bar').map((m) => m.value), ['foo', 'bar'])
+  deepEqual(regex.isMatch('foo\nbar'), false)
+  deepEqual(regex.toJSON(), { source: '^\\w+$', flags: 'm', lineTerminator: 0 })
+  deepEqual(RRegex.fromJSON(regex.toJSON()).findAll('foo\0bar').length, 2)
+  deepEqual(new RRegexBuilder('.+').lineTerminator(13).build().find('ab\rc').value, 'ab')
+  deepEqual(new RRegexBuilder('(?m)a$').lineTerminator(0).build().isFullMatch('a'), true)
+
+  for (const byte of [128, 255, 256]) {
+    let error
+    try {
+      new RRegexBuilder('a').lineTerminator(byte)
+    } catch (err) {
+      error = err
+    }
+    deepEqual(error instanceof Error, true)
+  }
+})
+
+test(`RRegexBuilder::crlf`, () => {
+  const regex = new RRegexBuilder('^.+$').multiLine(true).crlf(true).build()
+  deepEqual(regex.findAll('a\r\nb').map((m) => m.value), ['a', 'b'])
+  deepEqual(regex.flags, 'mR')
+  deepEqual(RRegex.fromJSON(regex.toJSON()).findAll('a\r\nb').map((m) => m.value), ['a', 'b'])
+  deepEqual(new RRegexBuilder('^.+$').multiLine(true).build().findAll('a\r\nb').map((m) => m.value), ['a\r', 'b'])
+  deepEqual(new RRegex('(?mR)^.+$').flags, 'mR')
+})
+
+test(`RRegexBuilder::longestMatch`, () => {
+  const first = new RRegexBuilder('sam|samwise').build()
+  deepEqual(first.find('samwise').value, 'sam')
+  deepEqual(first.longestMatch, false)
+
+  const longest = new RRegexBuilder('sam|samwise').longestMatch(true).build()
+  deepEqual(longest.find('samwise'), { value: 'samwise', start: 0, end: 7 })
+  deepEqual(longest.findAt('a samwise', 1), { value: 'samwise', start: 2, end: 9 })
+  deepEqual(longest.findAll('samwise sam samwise').map((m) => m.value), ['samwise', 'sam', 'samwise'])
+  deepEqual(longest.longestMatch, true)
+  deepEqual(longest.toJSON(), { source: 'sam|samwise', flags: '', longestMatch: true })
+  deepEqual(RRegex.fromJSON(longest.toJSON()).find('samwise').value, 'samwise')
+
+  const lazy = new RRegexBuilder('a+?').longestMatch(true).build()
+  deepEqual(lazy.find('aaa').value, 'aaa')
+
+  const empty = new RRegexBuilder('a*').longestMatch(true).build()
+  deepEqual(empty.findAll('baab'), new RRegex('a*').findAll('baab'))
+  deepEqual(empty.findAll('δa').map((m) => [m.start, m.end]), [[0, 0], [2, 3]])
+})
+
+test(`RRegexBuilder::longestMatch find family`, () => {
+  const longest = new RRegexBuilder('sam|samwise').longestMatch(true).build()
+  const text = 'sam samwise'
+  deepEqual(longest.findFirstMany(['samwise', 'no']), [{ value: 'samwise', start: 0, end: 7 }, undefined])
+  deepEqual(longest.findLast(text), { value: 'samwise', start: 4, end: 11 })
+  deepEqual(longest.findLastIndex(text), { start: 4, end: 11 })
+  deepEqual(longest.findNth(text, 1), { value: 'samwise', start: 4, end: 11 })
+  deepEqual(longest.findAllMerged(text, 0), [{ start: 0, end: 3 }, { start: 4, end: 11 }])
+  deepEqual(longest.findAllWhere(text, (m) => m.start > 0), [{ value: 'samwise', start: 4, end: 11 }])
+  deepEqual(longest.findAllOverlapping('samwise').map((m) => m.value), ['samwise'])
+  deepEqual(longest.findAllWithLine(text)[1].value, 'samwise')
+  deepEqual(longest.matchIndices('samwise'), new Int32Array([0, 7]))
+})
+
+test(`RRegexBuilder::longestMatch match methods`, () => {
+  const longest = new RRegexBuilder('sam|samwise').longestMatch(true).build()
+  const text = 'sam samwise'
+  const values = []
+  longest.forEachMatch(text, (m) => values.push(m.value))
+  deepEqual(values, ['sam', 'samwise'])
+  deepEqual(longest.matchExactlyAt(text, 4), { value: 'samwise', start: 4, end: 11 })
+  deepEqual(longest.matchStats(text), { count: 2, totalMatchedBytes: 10, coverage: 10 / 11 })
+  deepEqual(longest.matchNavigator(text).at(1), { start: 4, end: 11 })
+  deepEqual(longest.highlight(text, '[', ']'), '[sam] [samwise]')
+  deepEqual(longest.trimMatches('samwise x samwise'), ' x ')
+  deepEqual(longest.split('xsamwisey'), ['x', 'y'])
+  deepEqual(longest.splitn('xsamwiseysamwisez', 2), ['x', 'ysamwisez'])
+  deepEqual(longest.splitInclusive('xsamwisey'), ['xsamwise', 'y'])
+  deepEqual(longest.splitOnLast('xsamwisey'), ['x', 'y'])
+  deepEqual(Array.from(longest.splitOffsets('xsamwisey')), [0, 1, 8, 9])
+  deepEqual(longest.partition('samwise!').map((segment) => segment.value), ['samwise', '!'])
+
+  const scanner = longest.scanner(8)
+  scanner.push('samwise and sam')
+  deepEqual(scanner.finish().map((m) => m.value), ['samwise', 'sam'])
+
+  const groups = new RRegexBuilder('(sam)|(samwise)').longestMatch(true).build().stateful()
+  deepEqual([...groups.exec('samwise')], ['samwise', undefined, 'samwise'])
+
+  const split = new RRegexBuilder('a|ab|b').longestMatch(true).build()
+  deepEqual(Array.from(split.startPositions('ab')), [0])
+  deepEqual(Array.from(split.matchCountByLine('ab\nb')), [1, 1])
+  deepEqual(Object.fromEntries(split.matchOccurrences('ab b')), { ab: 1, b: 1 })
+  deepEqual(split.distinctMatches('ab b'), ['ab', 'b'])
+})
+
+test(`RRegex::unionOf`, () => {
+  const regex = RRegex.unionOf(['a.b', 'c*', 'a.b'])
+  deepEqual(regex.toString(), 'a\\.b|c\\*')
+  deepEqual(regex.isMatch('xa.by'), true)
+  deepEqual(regex.isMatch('axb'), false)
+
+  const words = RRegex.unionOf(['cat', 'dog'], true)
+  deepEqual(words.isMatch('concatenate'), false)
+  deepEqual(words.find('hot dog'), { value: 'dog', start: 4, end: 7 })
+
+  const empty = RRegex.unionOf([])
+  deepEqual(empty.isMatch(''), false)
+  deepEqual(empty.isMatch('abc'), false)
+})
+
+test(`RRegex::fromHir`, () => {
+  const regex = RRegex.fromHir(new RRegex('^(?P<x>a|bc)+$').syntax())
+  deepEqual(regex.toString(), '(?:\\A(?P<x>(?:a|(?:bc)))+\\z)')
+  deepEqual(regex.isMatch('abca'), true)
+  deepEqual(regex.captureNames(), [null, 'x'])
+
+  const hir = new RRegex('a').syntax()
+  hir.kind['@values'][0]['@values'][0] = new TextEncoder().encode('$z')
+  deepEqual(RRegex.fromHir(hir).isMatch('x$z'), true)
+
+  const invalid = [
+    {},
+    { kind: { '@variant': 'Unknown' } },
+    { kind: { '@variant': 'Literal', '@values': [] } },
+    { kind: { '@variant': 'Literal', '@values': [new Uint8Array([0xff])] } },
+  ]
+  for (const hir of invalid) {
+    let error
+    try {
+      RRegex.fromHir(hir)
+    } catch (err) {
+      error = err
+    }
+    deepEqual(error instanceof Error, true)
+  }
+})
+
 test(`RRegex::isMatch`, () => {
   const regex = new RRegex('a')
   deepEqual(regex.isMatch('abc'), true)
@@ -16,6 +456,31 @@ test(`RRegex::isMatch`, () => {
   deepEqual(re.isMatch(text), true);
 })
 
+test(`RRegex::isMatch literal patterns`, () => {
+  const texts = ['', 'foo', 'a foo b', 'FOO', 'δfooδ', 'fo']
+  for (const pattern of ['foo', '', 'δ', '(?i)foo', 'f(o)o', 'foo|bar', 'fo\\.']) {
+    const regex = new RRegex(pattern)
+    for (const text of texts) {
+      deepEqual(regex.isMatch(text), regex.find(text) !== undefined)
+    }
+  }
+  deepEqual(new RRegex('foo').isMatch('a foo b'), true)
+  deepEqual(new RRegex('(?i)foo').isMatch('FOO'), true)
+})
+
+test(`RRegex::isMatch required literals`, () => {
+  const texts = ['', 'foo1bar', 'foo bar', 'foobar', 'bar1foo', 'xfoo12barx', 'FOO1BAR', 'δfoo9barδ']
+  const patterns = ['foo\\d+bar', '(foo)\\d*(?:bar)+', '(?i)foo\\d+bar', 'foo\\d+bar|x', '(?:foo)?\\d', 'f(o)o\\d{2}bar', '^foo', 'δfoo']
+  for (const pattern of patterns) {
+    const regex = new RRegex(pattern)
+    for (const text of texts) {
+      deepEqual(regex.isMatch(text), regex.find(text) !== undefined, `${pattern} on ${text}`)
+    }
+  }
+  deepEqual(new RRegex('foo\\d+bar').isMatch('foo1bar'), true)
+  deepEqual(new RRegex('foo\\d+bar').isMatch('foo1baz'), false)
+})
+
 test(`RRegex::isMatchAt`, () => {
   const regex = new RRegex('a')
   deepEqual(regex.isMatchAt('ab', 0), true)
@@ -35,6 +500,88 @@ test(`RRegex::isMatchAt`, () => {
   deepEqual(re.isMatchAt(text, 5), false)
 })
 
+test(`RRegex::isMatchWithin`, () => {
+  const regex = new RRegex('\\d+')
+  deepEqual(regex.isMatchWithin('ab 12 cd', 0, 3), false)
+  deepEqual(regex.isMatchWithin('ab 12 cd', 0, 4), true)
+  deepEqual(regex.isMatchWithin('ab 12 cd', 5, 8), false)
+  deepEqual(regex.isMatchWithin('ab 12 cd', 4, 3), false)
+  deepEqual(regex.isMatchWithin('ab 12 cd', 0, 9), false)
+  deepEqual(regex.isMatchWithin('ä1', 1, 3), false)
+  deepEqual(new RRegex('1$').isMatchWithin('ab 12 cd', 0, 4), true)
+  deepEqual(new RRegex('\\b2').isMatchWithin('ab 12 cd', 4, 5), false)
+})
+
+test(`RRegex::isMatchBudgeted`, () => {
+  const re = new RRegex('\\d+')
+  deepEqual(re.isMatchBudgeted('abc 123', 4), false)
+  deepEqual(re.isMatchBudgeted('abc 123', 5), true)
+  deepEqual(re.isMatchBudgeted('abc 123', 100), true)
+  deepEqual(re.isMatchBudgeted('abc 123', 0), false)
+  deepEqual(new RRegex('é$').isMatchBudgeted('aé b', 2), false)
+  deepEqual(new RRegex('é$').isMatchBudgeted('aé b', 3), true)
+})
+
+test(`RRegex::isFullMatch`, () => {
+  const regex = new RRegex('\\d+')
+  deepEqual(regex.isFullMatch('123'), true)
+  deepEqual(regex.isFullMatch('12abc'), false)
+  deepEqual(regex.isFullMatch('abc12'), false)
+  deepEqual(regex.isFullMatch(''), false)
+  deepEqual(new RRegex('a|ab').isFullMatch('ab'), true)
+  deepEqual(new RRegex('(?x) a b # comment').isFullMatch('ab'), true)
+  deepEqual(new RRegex('(?m)^a$').isFullMatch('a\na'), false)
+})
+
+test(`RRegex::startsWithMatch`, () => {
+  const regex = new RRegex('\\d+')
+  deepEqual(regex.startsWithMatch('12abc'), true)
+  deepEqual(regex.startsWithMatch('abc12'), false)
+  deepEqual(regex.startsWithMatch(''), false)
+  deepEqual(new RRegex('b|ab').startsWithMatch('ab'), true)
+  deepEqual(new RRegex('x*').startsWithMatch('abc'), true)
+})
+
+test(`RRegex::endsWithMatch`, () => {
+  const regex = new RRegex('\\d+')
+  deepEqual(regex.endsWithMatch('abc12'), true)
+  deepEqual(regex.endsWithMatch('12abc'), false)
+  deepEqual(new RRegex('aa').endsWithMatch('aaa'), true)
+  deepEqual(new RRegex('a$').endsWithMatch('ba'), true)
+  deepEqual(new RRegex('(?m)a$').endsWithMatch('a\nb'), false)
+  deepEqual(new RRegex('(?x) a # comment').endsWithMatch('ba'), true)
+  deepEqual(new RRegex('x*').endsWithMatch('abc'), true)
+})
+
+test(`RRegex::isMatchMany`, () => {
+  const regex = new RRegex('^\\d+$')
+  deepEqual(regex.isMatchMany(['123', 'abc', '4', '']), [true, false, true, false])
+  deepEqual(regex.isMatchMany([]), [])
+})
+
+test(`RRegex::isMatchChunks`, () => {
+  const re = new RRegex('hello world')
+  deepEqual(re.isMatchChunks(['say hel', 'lo wor', 'ld!']), true)
+  deepEqual(re.isMatchChunks(['hello', 'world']), false)
+  deepEqual(re.isMatchChunks([]), false)
+  deepEqual(new RRegex('^b').isMatchChunks(['a', 'b']), false)
+  deepEqual(new RRegex('^$').isMatchChunks([]), true)
+})
+
+test(`RRegex::isNoMatch`, () => {
+  const re = new RRegex('\\d')
+  deepEqual(re.isNoMatch('abc'), true)
+  deepEqual(re.isNoMatch('a1c'), false)
+  deepEqual(new RRegex('foo').isNoMatch('a foo'), false)
+})
+
+test(`RRegex::filterNonMatching`, () => {
+  const re = new RRegex('\\d')
+  deepEqual(re.filterNonMatching(['a', 'b1', 'c', '2']), ['a', 'c'])
+  deepEqual(re.filterNonMatching([]), [])
+  deepEqual(new RRegex('').filterNonMatching(['a', '']), [])
+})
+
 test(`RRegex::find`, () => {
   const regex = new RRegex('a')
   deepEqual(regex.find('abc'), { value: 'a', start: 0, end: 1 })
@@ -49,6 +596,13 @@ test(`RRegex::find`, () => {
   })
 })
 
+test(`RRegex::findOrNull`, () => {
+  const re = new RRegex('\\d+')
+  deepEqual(re.findOrNull('ab 12'), { start: 3, end: 5, value: '12' })
+  deepEqual(re.findOrNull('ab'), null)
+  deepEqual(new RRegexBuilder('a+?').longestMatch(true).build().findOrNull('xaa'), { start: 1, end: 3, value: 'aa' })
+})
+
 test(`RRegex::findAt`, () => {
   const regex = new RRegex('a')
   deepEqual(regex.find('abc'), { value: 'a', start: 0, end: 1 })
@@ -67,6 +621,119 @@ test(`RRegex::findAt`, () => {
   deepEqual(re.findAt(text, 5), undefined)
 })
 
+test(`RRegex::findInRange`, () => {
+  const re = new RRegex('\\d+')
+  deepEqual(re.findInRange('1 22 333', 2, 6), { start: 2, end: 4, value: '22' })
+  deepEqual(re.findInRange('1 22 333', 6, 7), { start: 6, end: 7, value: '3' })
+  deepEqual(re.findInRange('1 22 333', 1, 2), undefined)
+  deepEqual(re.findInRange('1 22 333', 6, 5), undefined)
+  deepEqual(re.findInRange('1 22 333', 0, 100), undefined)
+  deepEqual(re.findInRange('é1', 1, 3), undefined)
+  deepEqual(re.findInRange('é1', 2, 3), { start: 2, end: 3, value: '1' })
+  deepEqual(new RRegex('\\bb').findInRange('ab b', 1, 4), { start: 3, end: 4, value: 'b' })
+  deepEqual(new RRegex('b$').findInRange('abc', 0, 2), { start: 1, end: 2, value: 'b' })
+})
+
+test(`RRegex::findWithPosition`, () => {
+  const digits = new RRegex('\\d+')
+  const text = 'ab\n😀 12'
+  const m = digits.findWithPosition(text)
+  deepEqual(m, {
+    start: { offset: 8, line: 1, column: 3 },
+    end: { offset: 10, line: 1, column: 5 },
+    value: '12',
+  })
+  deepEqual(text.split('\n')[m.start.line].slice(m.start.column, m.end.column), '12')
+  deepEqual(digits.findWithPosition('none'), undefined)
+  deepEqual(new RRegex('b\\nc').findWithPosition('ab\ncd'), {
+    start: { offset: 1, line: 0, column: 1 },
+    end: { offset: 4, line: 1, column: 1 },
+    value: 'b\nc',
+  })
+  const longest = new RRegexBuilder('a|ab').longestMatch(true).build()
+  deepEqual(longest.findWithPosition('x\nab').end, { offset: 4, line: 1, column: 2 })
+})
+
+test(`RRegex::findWithContext`, () => {
+  const fox = new RRegex('fox')
+  deepEqual(fox.findWithContext('the quick brown fox jumps', 6), { start: 16, end: 19, value: 'fox', before: 'brown ', after: ' jumps' })
+  deepEqual(fox.findWithContext('fox', 3), { start: 0, end: 3, value: 'fox', before: '', after: '' })
+  deepEqual(fox.findWithContext('ab fox cd', 0), { start: 3, end: 6, value: 'fox', before: '', after: '' })
+  deepEqual(fox.findWithContext('😀éfoxδ😀x', 2), { start: 6, end: 9, value: 'fox', before: '😀é', after: 'δ😀' })
+  deepEqual(fox.findWithContext('none', 2), undefined)
+  const longest = new RRegexBuilder('a|ab').longestMatch(true).build()
+  deepEqual(longest.findWithContext('xabx', 1), { start: 1, end: 3, value: 'ab', before: 'x', after: 'x' })
+})
+
+test(`RRegex::findAllWithContext`, () => {
+  const digit = new RRegex('\\d')
+  deepEqual(digit.findAllWithContext('a1b2c', 1), [
+    { start: 1, end: 2, value: '1', before: 'a', after: 'b' },
+    { start: 3, end: 4, value: '2', before: 'b', after: 'c' },
+  ])
+  deepEqual(digit.findAllWithContext('12', 5).map(({ before, after }) => [before, after]), [['', '2'], ['1', '']])
+  deepEqual(digit.findAllWithContext('δ1😀', 1).map(({ before, after }) => [before, after]), [['δ', '😀']])
+  deepEqual(digit.findAllWithContext('none', 1), [])
+  const longest = new RRegexBuilder('a|ab').longestMatch(true).build()
+  deepEqual(longest.findAllWithContext('ab ab', 1).map((m) => m.before + m.value + m.after), ['ab ', ' ab'])
+})
+
+test(`RRegex::findUtf16`, () => {
+  const digits = new RRegex('\\d+')
+  const text = '😀 12'
+  const m = digits.findUtf16(text)
+  deepEqual(m, { start: 3, end: 5, value: '12' })
+  deepEqual(text.slice(m.start, m.end), '12')
+  deepEqual(new RRegex('δ😀').findUtf16('aδ😀b'), { start: 1, end: 4, value: 'δ😀' })
+  deepEqual(digits.findUtf16('none'), undefined)
+  deepEqual(new RRegexBuilder('a|ab').longestMatch(true).build().findUtf16('😀ab'), { start: 2, end: 4, value: 'ab' })
+})
+
+test(`RRegex::findAllUtf16`, () => {
+  const text = '😀1 δ22 😀😀3'
+  const matches = new RRegex('\\d+').findAllUtf16(text)
+  deepEqual(matches, [
+    { start: 2, end: 3, value: '1' },
+    { start: 5, end: 7, value: '22' },
+    { start: 12, end: 13, value: '3' },
+  ])
+  for (const m of matches) {
+    deepEqual(text.slice(m.start, m.end), m.value)
+  }
+  deepEqual(new RRegex('\\d').findAllUtf16('none'), [])
+  const longest = new RRegexBuilder('a|ab').longestMatch(true).build()
+  deepEqual(longest.findAllUtf16('😀ab ab').map((m) => [m.start, m.end]), [[2, 4], [5, 7]])
+})
+
+test(`RRegex::findWithin`, () => {
+  const regex = new RRegex('\\d+')
+  deepEqual(regex.findWithin('ab 12 cd', 3), undefined)
+  deepEqual(regex.findWithin('ab 12 cd', 4), { start: 3, end: 4, value: '1' })
+  deepEqual(regex.findWithin('ab 12 cd', 100), { start: 3, end: 5, value: '12' })
+  deepEqual(new RRegex('δ$').findWithin('δδ', 3), { start: 0, end: 2, value: 'δ' })
+  deepEqual(new RRegex('.').findWithin('δ', 1), undefined)
+})
+
+test(`RRegex::matchExactlyAt`, () => {
+  const regex = new RRegex('\\d+')
+  deepEqual(regex.matchExactlyAt('ab12', 2), { start: 2, end: 4, value: '12' })
+  deepEqual(regex.matchExactlyAt('ab12', 3), { start: 3, end: 4, value: '2' })
+  deepEqual(regex.matchExactlyAt('ab12', 1), undefined)
+  deepEqual(regex.matchExactlyAt('ab12', 10), undefined)
+  deepEqual(new RRegex('\\bx').matchExactlyAt('ax', 1), undefined)
+  deepEqual(new RRegex('').matchExactlyAt('ab', 2), { start: 2, end: 2, value: '' })
+})
+
+test(`RRegex::findFirstMany`, () => {
+  const regex = new RRegex('\\d+')
+  deepEqual(regex.findFirstMany(['id 42', 'none', '7 8']), [
+    { value: '42', start: 3, end: 5 },
+    undefined,
+    { value: '7', start: 0, end: 1 },
+  ])
+  deepEqual(regex.findFirstMany([]), [])
+})
+
 test(`RRegex::findAll`, () => {
   const regex = new RRegex('a')
   deepEqual(regex.findAll('abcabcabc'), [
@@ -102,6 +769,247 @@ test(`RRegex::findAll`, () => {
   ])
 })
 
+test(`RRegex::findAllCharLen`, () => {
+  const re = new RRegex('\\S+')
+  deepEqual(re.findAllCharLen('héllo 👋🏽 ab'), [
+    { start: 0, end: 6, value: 'héllo', charLen: 5 },
+    { start: 7, end: 15, value: '👋🏽', charLen: 2 },
+    { start: 16, end: 18, value: 'ab', charLen: 2 },
+  ])
+  deepEqual(re.findAllCharLen('   '), [])
+  const longest = new RRegexBuilder('é|éa').longestMatch(true).build()
+  deepEqual(longest.findAllCharLen('éa'), [{ start: 0, end: 3, value: 'éa', charLen: 2 }])
+})
+
+test(`RRegex::findAllMerged`, () => {
+  const re = new RRegex('\\d')
+  deepEqual(re.findAllMerged('12 3  4', 0), [{ start: 0, end: 2 }, { start: 3, end: 4 }, { start: 6, end: 7 }])
+  deepEqual(re.findAllMerged('12 3  4', 1), [{ start: 0, end: 4 }, { start: 6, end: 7 }])
+  deepEqual(re.findAllMerged('12 3  4', 2), [{ start: 0, end: 7 }])
+  deepEqual(re.findAllMerged('abc', 5), [])
+  deepEqual(new RRegex('a|').findAllMerged('aba', 0), [{ start: 0, end: 1 }, { start: 2, end: 3 }])
+})
+
+test(`RRegex::findLast`, () => {
+  const regex = new RRegex('\\d')
+  deepEqual(regex.findLast('a1 b2 c3'), { value: '3', start: 7, end: 8 })
+  deepEqual(regex.findLast('abc'), undefined)
+})
+
+test(`RRegex::findAfter`, () => {
+  const re = new RRegex('\\d+')
+  deepEqual(re.findAfter('1 22 333', 0), { start: 0, end: 1, value: '1' })
+  deepEqual(re.findAfter('1 22 333', 2), { start: 2, end: 4, value: '22' })
+  deepEqual(re.findAfter('1 22 333', 3), { start: 3, end: 4, value: '2' })
+  deepEqual(re.findAfter('1 22 333', 8), undefined)
+  deepEqual(re.findAfter('1 22 333', 9), undefined)
+  deepEqual(re.findAfter('é1', 1), undefined)
+  deepEqual(re.findAfter('é1', 2), { start: 2, end: 3, value: '1' })
+})
+
+test(`RRegex::findBefore`, () => {
+  const re = new RRegex('\\d+')
+  deepEqual(re.findBefore('1 22 333', 6), { start: 2, end: 4, value: '22' })
+  deepEqual(re.findBefore('1 22 333', 8), { start: 5, end: 8, value: '333' })
+  deepEqual(re.findBefore('1 22 333', 1), { start: 0, end: 1, value: '1' })
+  deepEqual(re.findBefore('1 22 333', 0), undefined)
+  deepEqual(re.findBefore('1 22 333', 100), undefined)
+  deepEqual(re.findBefore('1é', 2), undefined)
+  const longest = new RRegexBuilder('a|ab').longestMatch(true).build()
+  deepEqual(longest.findBefore('ab ab', 4), { start: 0, end: 2, value: 'ab' })
+})
+
+test(`RRegex::findLastIndex`, () => {
+  const regex = new RRegex('/')
+  deepEqual(regex.findLastIndex('a/b/c'), { start: 3, end: 4 })
+  deepEqual(regex.findLastIndex('abc'), undefined)
+  deepEqual(new RRegex('a*').findLastIndex('baa'), { start: 1, end: 3 })
+})
+
+test(`RRegex::findNth`, () => {
+  const regex = new RRegex('\\w')
+  deepEqual(regex.findNth('a b c d', 2), { value: 'c', start: 4, end: 5 })
+  deepEqual(regex.findNth('a b c d', 0), regex.find('a b c d'))
+  deepEqual(regex.findNth('a b c d', 3).value, 'd')
+  deepEqual(regex.findNth('a b c d', 4), undefined)
+  deepEqual(new RRegex('a*').findNth('baab', 2), new RRegex('a*').findAll('baab')[2])
+})
+
+test(`RRegex::findAllLimited`, () => {
+  const regex = new RRegex('\\d')
+  deepEqual(regex.findAllLimited('1 2 3 4', 2), [
+    { value: '1', start: 0, end: 1 },
+    { value: '2', start: 2, end: 3 },
+  ])
+  deepEqual(regex.findAllLimited('1 2 3 4', 0), regex.findAll('1 2 3 4'))
+  deepEqual(regex.findAllLimited('1 2', 10).length, 2)
+  deepEqual(regex.findAllLimited('abc', 1), [])
+  deepEqual(new RRegex('').findAllLimited('x'.repeat(100000), 3).length, 3)
+
+  const longest = new RRegexBuilder('a|ab').longestMatch(true).build()
+  deepEqual(longest.findAllLimited('ab ab ab', 2).map((m) => m.value), ['ab', 'ab'])
+})
+
+test(`RRegex::findAllPaged`, () => {
+  const digit = new RRegex('\\d')
+  deepEqual(digit.findAllPaged('1 2 3', 2), [[{ start: 0, end: 1, value: '1' }, { start: 2, end: 3, value: '2' }], [{ start: 4, end: 5, value: '3' }]])
+  deepEqual(digit.findAllPaged('1 2 3 4', 2).map((page) => page.length), [2, 2])
+  deepEqual(digit.findAllPaged('none', 3), [])
+  deepEqual(digit.findAllPaged('123', 10).flat(), digit.findAll('123'))
+  deepEqual(new RRegexBuilder('a+?').longestMatch(true).build().findAllPaged('aa a', 1), [[{ start: 0, end: 2, value: 'aa' }], [{ start: 3, end: 4, value: 'a' }]])
+  try {
+    digit.findAllPaged('1', 0)
+    deepEqual(true, false)
+  } catch (error) {
+    deepEqual(error instanceof Error, true)
+  }
+})
+
+test(`RRegex::forEachMatch`, () => {
+  const regex = new RRegex('\\d')
+  const matches = []
+  deepEqual(regex.forEachMatch('1 2 3', (m) => { matches.push(m) }), undefined)
+  deepEqual(matches, [
+    { start: 0, end: 1, value: '1' },
+    { start: 2, end: 3, value: '2' },
+    { start: 4, end: 5, value: '3' },
+  ])
+
+  const values = []
+  regex.forEachMatch('1 2 3', (m) => {
+    values.push(m.value)
+    return m.value !== '2'
+  })
+  deepEqual(values, ['1', '2'])
+
+  let calls = 0
+  regex.forEachMatch('1 2 3', () => { calls++; return 0 })
+  deepEqual(calls, 3)
+
+  try {
+    regex.forEachMatch('1', () => { throw new Error('boom') })
+    deepEqual(true, false)
+  } catch (error) {
+    deepEqual(error.message, 'boom')
+  }
+})
+
+test(`RRegex::findAllWhere`, () => {
+  const re = new RRegex('\\d+')
+  deepEqual(re.findAllWhere('1 22 333', (m) => m.value.length > 1), [{ start: 2, end: 4, value: '22' }, { start: 5, end: 8, value: '333' }])
+  const seen = []
+  deepEqual(re.findAllWhere('1 2 3', (m) => { seen.push(m.start); return m.start % 4 === 0 ? 1 : '' }), [{ start: 0, end: 1, value: '1' }, { start: 4, end: 5, value: '3' }])
+  deepEqual(seen, [0, 2, 4])
+  deepEqual(re.findAllWhere('none', () => true), [])
+  try {
+    re.findAllWhere('1', () => { throw new Error('nope') })
+    deepEqual(true, false)
+  } catch (error) {
+    deepEqual(error instanceof Error, true)
+  }
+})
+
+test(`RRegex::findAllOverlapping`, () => {
+  const regex = new RRegex('\\d\\d')
+  deepEqual(regex.findAllOverlapping('1234'), [
+    { value: '12', start: 0, end: 2 },
+    { value: '23', start: 1, end: 3 },
+    { value: '34', start: 2, end: 4 },
+  ])
+  deepEqual(regex.findAllOverlapping('a1'), [])
+  deepEqual(new RRegex('ä.').findAllOverlapping('ääa').map((m) => m.value), ['ää', 'äa'])
+  deepEqual(new RRegex('x*').findAllOverlapping('ab').map((m) => m.start), [0, 1, 2])
+})
+
+test(`RRegex::matchIndices`, () => {
+  const regex = new RRegex('\\d+')
+  deepEqual(regex.matchIndices('a1 b22 c333'), new Int32Array([1, 2, 4, 6, 8, 11]))
+  deepEqual(new RRegex('ö+').matchIndices('äöö'), new Int32Array([2, 6]))
+  deepEqual(regex.matchIndices('abc'), new Int32Array([]))
+})
+
+test(`RRegex::startPositions`, () => {
+  const positions = new RRegex('a').startPositions('a.a.a')
+  deepEqual(positions instanceof Uint32Array, true)
+  deepEqual(Array.from(positions), [0, 2, 4])
+  deepEqual(Array.from(new RRegex('\\d+').startPositions('δ12 3')), [2, 5])
+  deepEqual(new RRegex('x').startPositions('abc').length, 0)
+})
+
+test(`RRegex::findAllColumnar`, () => {
+  const columns = new RRegex('\\d+').findAllColumnar('a1 b22 é333')
+  deepEqual(columns.starts instanceof Uint32Array, true)
+  deepEqual(Array.from(columns.starts), [1, 4, 9])
+  deepEqual(Array.from(columns.ends), [2, 6, 12])
+  deepEqual(columns.values, ['1', '22', '333'])
+  const empty = new RRegex('x').findAllColumnar('abc')
+  deepEqual([empty.starts.length, empty.ends.length, empty.values], [0, 0, []])
+  const longest = new RRegexBuilder('a|ab').longestMatch(true).build()
+  deepEqual(longest.findAllColumnar('ab ab').values, ['ab', 'ab'])
+})
+
+test(`RRegex::matchCountByLine`, () => {
+  const digits = new RRegex('\\d')
+  deepEqual(Array.from(digits.matchCountByLine('1 2\nx\n3')), [2, 0, 1])
+  deepEqual(Array.from(digits.matchCountByLine('')), [0])
+  deepEqual(Array.from(digits.matchCountByLine('1\n')), [1, 0])
+  deepEqual(Array.from(new RRegex('a\\nb').matchCountByLine('a\nb a\nb')), [1, 1, 0])
+})
+
+test(`RRegex::matchingLines`, () => {
+  const digits = new RRegex('\\d')
+  deepEqual(digits.matchingLines('a1\nb\nc2 3'), [
+    { line: 0, start: 0, text: 'a1' },
+    { line: 2, start: 5, text: 'c2 3' },
+  ])
+  deepEqual(digits.matchingLines('none\nhere'), [])
+  deepEqual(digits.matchingLines('δ\n1\n'), [{ line: 1, start: 3, text: '1' }])
+  deepEqual(new RRegex('a\\nb').matchingLines('x\na\nb'), [{ line: 1, start: 2, text: 'a' }])
+  deepEqual(new RRegex('$').matchingLines('a\n'), [{ line: 1, start: 2, text: '' }])
+})
+
+test(`RRegex::findAllWithLine`, () => {
+  const re = new RRegex('\\d+')
+  deepEqual(re.findAllWithLine('a\nb 42 c\nd 7'), [
+    { start: 4, end: 6, value: '42', lineStart: 2, lineEnd: 8, lineText: 'b 42 c' },
+    { start: 11, end: 12, value: '7', lineStart: 9, lineEnd: 12, lineText: 'd 7' },
+  ])
+  deepEqual(new RRegex('b\\nc').findAllWithLine('ab\ncd\ne'), [
+    { start: 1, end: 4, value: 'b\nc', lineStart: 0, lineEnd: 5, lineText: 'ab\ncd' },
+  ])
+  deepEqual(new RRegex('b\\n').findAllWithLine('ab\ncd'), [
+    { start: 1, end: 3, value: 'b\n', lineStart: 0, lineEnd: 2, lineText: 'ab' },
+  ])
+  deepEqual(new RRegex('(?m)^$').findAllWithLine('a\n\nb'), [
+    { start: 2, end: 2, value: '', lineStart: 2, lineEnd: 2, lineText: '' },
+  ])
+  deepEqual(re.findAllWithLine('none'), [])
+})
+
+test(`RRegex::matchOccurrences`, () => {
+  const occurrences = new RRegex('\\w').matchOccurrences('a b a c a')
+  deepEqual(occurrences instanceof Map, true)
+  deepEqual([...occurrences], [['a', 3], ['b', 1], ['c', 1]])
+  deepEqual(new RRegex('\\d+').matchOccurrences('no digits').size, 0)
+  deepEqual(Object.fromEntries(new RRegex('δ|x*').matchOccurrences('δxδ')), { 'δ': 2, 'x': 1 })
+})
+
+test(`RRegex::matchStats`, () => {
+  const digits = new RRegex('\\d+')
+  deepEqual(digits.matchStats('12 ab 34'), { count: 2, totalMatchedBytes: 4, coverage: 0.5 })
+  deepEqual(digits.matchStats('abc'), { count: 0, totalMatchedBytes: 0, coverage: 0 })
+  deepEqual(digits.matchStats(''), { count: 0, totalMatchedBytes: 0, coverage: 0 })
+  deepEqual(new RRegex('δ').matchStats('δa'), { count: 1, totalMatchedBytes: 2, coverage: 2 / 3 })
+  deepEqual(new RRegex('').matchStats('ab'), { count: 3, totalMatchedBytes: 0, coverage: 0 })
+})
+
+test(`RRegex::distinctMatches`, () => {
+  deepEqual(new RRegex('\\w').distinctMatches('a b a c'), ['a', 'b', 'c'])
+  deepEqual(new RRegex('\\d+').distinctMatches('3 12 3 12 δ 7'), ['3', '12', '7'])
+  deepEqual(new RRegex('\\d').distinctMatches('none'), [])
+  deepEqual(new RRegex('(?i)a').distinctMatches('aAa'), ['a', 'A'])
+})
+
 test(`RRegex::capturesLength`, () => {
   const re1 = new RRegex("(?P<y>\\d{4})-(?P<m>\\d{2})-(?P<d>\\d{2})")
   deepEqual(re1.capturesLength(), 4)
@@ -129,6 +1037,28 @@ test(`RRegex::captureNames`, () => {
   ])
 })
 
+test(`RRegex::captureNameToIndex`, () => {
+  const regex = new RRegex('(?P<y>\\d{4})-(?P<m>\\d{2})-(\\d{2})')
+  deepEqual(regex.captureNameToIndex('y'), 1)
+  deepEqual(regex.captureNameToIndex('m'), 2)
+  deepEqual(regex.captureNameToIndex('d'), undefined)
+  deepEqual(regex.captureNameToIndex(''), undefined)
+})
+
+test(`RRegex::captureNameIndex`, () => {
+  deepEqual(new RRegex('(?P<y>\\d{4})-(\\d{2})-(?P<d>\\d{2})').captureNameIndex(), { y: 1, d: 3 })
+  deepEqual(new RRegex('(a)(b)').captureNameIndex(), {})
+})
+
+test(`RRegex::hasGroupName`, () => {
+  const regex = new RRegex('(?P<y>\\d{4})-(?P<m>\\d{2})-(\\d{2})')
+  deepEqual(regex.hasGroupName('y'), true)
+  deepEqual(regex.hasGroupName('m'), true)
+  deepEqual(regex.hasGroupName('d'), false)
+  deepEqual(regex.hasGroupName(''), false)
+  deepEqual(new RRegex('a').hasGroupName('a'), false)
+})
+
 test(`RRegex::captures`, () => {
   const regex = new RRegex('(?P<y>\\d{4})-(?P<m>\\d{2})-(?P<d>\\d{2})')
   deepEqual(regex.captures(''), undefined)
@@ -147,6 +1077,87 @@ test(`RRegex::captures`, () => {
   })
 })
 
+test(`RRegex::capturesAt`, () => {
+  const regex = new RRegex('(?P<word>\\w+)')
+  deepEqual(regex.capturesAt('foo bar', 3), {
+    get: [
+      { value: 'bar', start: 4, end: 7 },
+      { value: 'bar', start: 4, end: 7 },
+    ],
+    name: {
+      word: { value: 'bar', start: 4, end: 7 },
+    },
+  })
+  deepEqual(regex.capturesAt('foo bar', 7), undefined)
+  deepEqual(regex.capturesAt('foo bar', 8), undefined)
+  deepEqual(new RRegex('\\Afoo').capturesAt('foofoo', 3), undefined)
+})
+
+test(`RRegex::capturesIndices`, () => {
+  const regex = new RRegex('(\\w+)@(\\w+)?')
+  const indices = regex.capturesIndices('to joe@')
+  deepEqual(indices instanceof Int32Array, true)
+  deepEqual(Array.from(indices), [3, 7, 3, 6, -1, -1])
+  deepEqual(Array.from(regex.capturesIndices('a@b')), [0, 3, 0, 1, 2, 3])
+  deepEqual(Array.from(regex.capturesIndices('no match')), [])
+  deepEqual(Array.from(new RRegex('δ(x)').capturesIndices('aδx')), [1, 4, 3, 4])
+})
+
+test(`RRegex::expand`, () => {
+  const regex = new RRegex('(?P<last>[^,\\s]+),\\s+(?P<first>\\S+)')
+  deepEqual(regex.expand('Springsteen, Bruce', '$first $last'), 'Bruce Springsteen')
+  deepEqual(regex.expand('Springsteen, Bruce', '${last}! $2 $$'), 'Springsteen! Bruce $')
+  deepEqual(regex.expand('Springsteen', '$first'), undefined)
+})
+
+test(`RRegex::capturesRead`, () => {
+  const regex = new RRegex('(\\d{4})-(\\d{2})(-\\d{2})?')
+  const locations = regex.createLocations()
+  deepEqual(locations.length, 4)
+
+  deepEqual(regex.capturesRead('on 2010-03', locations), { value: '2010-03', start: 3, end: 10 })
+  deepEqual(locations.start(0), 3)
+  deepEqual(locations.end(0), 10)
+  deepEqual(locations.start(2), 8)
+  deepEqual(locations.end(2), 10)
+  deepEqual(locations.start(3), undefined)
+  deepEqual(locations.start(4), undefined)
+
+  deepEqual(regex.capturesRead('1999-12-31', locations), { value: '1999-12-31', start: 0, end: 10 })
+  deepEqual(locations.start(3), 7)
+  deepEqual(regex.capturesRead('none', locations), undefined)
+})
+
+test(`RRegex::capturesInto`, () => {
+  const re = new RRegex('(?<key>\\w+)=(?<value>\\w+)?')
+  const target = { other: true }
+  deepEqual(re.capturesInto('a=1', target), true)
+  deepEqual(target, { 0: 'a=1', 1: 'a', 2: '1', key: 'a', value: '1', other: true })
+  deepEqual(re.capturesInto('b=', target), true)
+  deepEqual(target, { 0: 'b=', 1: 'b', 2: undefined, key: 'b', value: undefined, other: true })
+  deepEqual(re.capturesInto('nothing', target), false)
+  deepEqual(target.key, 'b')
+})
+
+test(`RRegex::capturesTyped`, () => {
+  const re = new RRegex('(?<name>\\w+)=(?<age>[\\w.]+),(?<admin>\\w+)(x)?')
+  deepEqual(re.capturesTyped('ann=42.5,true', ['string', 'string', 'number', 'boolean']), {
+    get: ['ann=42.5,true', 'ann', 42.5, true, undefined],
+    name: { name: 'ann', age: 42.5, admin: true },
+  })
+  deepEqual(re.capturesTyped('bob=old,no', [, , 'number', 'boolean']).get, ['bob=old,no', 'bob', null, null, undefined])
+  deepEqual(re.capturesTyped('bob=1,false', []).get, ['bob=1,false', 'bob', '1', 'false', undefined])
+  deepEqual(re.capturesTyped('nothing', ['number']), undefined)
+  for (const kinds of [['date'], [1]]) {
+    try {
+      re.capturesTyped('nothing', kinds)
+      deepEqual(true, false)
+    } catch (error) {
+      deepEqual(error instanceof Error, true)
+    }
+  }
+})
+
 test(`RRegex::capturesAll`, () => {
   const regex = new RRegex('(?P<y>\\d{4})-(?P<m>\\d{2})-(?P<d>\\d{2})')
   deepEqual(regex.capturesAll(''), [])
@@ -205,6 +1216,205 @@ test(`RRegex::capturesAll`, () => {
       }
     },
   ])
+
+  const [entry] = new RRegex('(\\d+)-(\\d+)').capturesAll('2020-01')
+  deepEqual(entry.get[0], { value: '2020-01', start: 0, end: 7 })
+  deepEqual(entry.get[1], { value: '2020', start: 0, end: 4 })
+  deepEqual(entry.get[2], { value: '01', start: 5, end: 7 })
+})
+
+test(`RRegex::matchAllCompat`, () => {
+  const [m] = new RRegex('(?P<word>\\w+)').matchAllCompat('😀 hi')
+  deepEqual([...m], ['hi', 'hi'])
+  deepEqual(m.index, 3)
+  deepEqual(m.input, '😀 hi')
+  deepEqual(m.groups.word, 'hi')
+
+  const cases = [['(?<y>\\d{4})-(?<m>\\d{2})?(x)?', '😀2024-05 é1999-'], ['(a)|(b)', 'ab'], ['\\d', 'a1b2'], ['(?<n>z)', 'none']]
+  for (const [pattern, text] of cases) {
+    const native = [...text.matchAll(new RegExp(pattern, 'gu'))]
+    deepEqual(new RRegex(pattern).matchAllCompat(text), native)
+  }
+})
+
+test(`RRegex::capturesTree`, () => {
+  const regex = new RRegex('((?P<y>\\d+)-(\\d+))(x)?|(z)')
+  deepEqual(regex.capturesTree('on 2020-01'), {
+    index: 0,
+    name: undefined,
+    start: 3,
+    end: 10,
+    children: [
+      {
+        index: 1,
+        name: undefined,
+        start: 3,
+        end: 10,
+        children: [
+          { index: 2, name: 'y', start: 3, end: 7, children: [] },
+          { index: 3, name: undefined, start: 8, end: 10, children: [] },
+        ],
+      },
+    ],
+  })
+  deepEqual(regex.capturesTree('z').children, [{ index: 5, name: undefined, start: 0, end: 1, children: [] }])
+  deepEqual(regex.capturesTree('none'), undefined)
+  deepEqual(new RRegex('(a(b))+').capturesTree('abab').children[0], {
+    index: 1,
+    name: undefined,
+    start: 2,
+    end: 4,
+    children: [{ index: 2, name: undefined, start: 3, end: 4, children: [] }],
+  })
+})
+
+test(`RRegex::findAllGroup`, () => {
+  const regex = new RRegex('(\\w+)=(\\d+)?')
+  deepEqual(regex.findAllGroup('a=1 b= c=3', 2), [
+    { start: 2, end: 3, value: '1' },
+    { start: 9, end: 10, value: '3' },
+  ])
+  deepEqual(regex.findAllGroup('a=1 b= c=3', 0).map((m) => m.value), ['a=1', 'b=', 'c=3'])
+  deepEqual(regex.findAllGroup('', 1), [])
+
+  try {
+    regex.findAllGroup('a=1', 3)
+    deepEqual(true, false)
+  } catch (error) {
+    deepEqual(error instanceof Error, true)
+  }
+})
+
+test(`RRegex::findAllNamedGroup`, () => {
+  const regex = new RRegex('id=(?P<id>\\d+)(?P<suffix>[a-z])?')
+  deepEqual(regex.findAllNamedGroup('id=1, id=22x', 'id'), [
+    { start: 3, end: 4, value: '1' },
+    { start: 9, end: 11, value: '22' },
+  ])
+  deepEqual(regex.findAllNamedGroup('id=1, id=22x', 'suffix'), [{ start: 11, end: 12, value: 'x' }])
+
+  for (const name of ['missing', '']) {
+    try {
+      regex.findAllNamedGroup('id=1', name)
+      deepEqual(true, false)
+    } catch (error) {
+      deepEqual(error instanceof Error, true)
+    }
+  }
+})
+
+test(`RRegex::capturesIter`, () => {
+  const regex = new RRegex('(\\w)(?<digit>\\d)?')
+  const iter = regex.capturesIter('a1 b c3')
+  deepEqual(iter.next(), regex.capturesAt('a1 b c3', 0))
+  deepEqual(iter.next().get[1].value, 'b')
+  const last = iter.next()
+  deepEqual(last.name.digit, { value: '3', start: 6, end: 7 })
+  deepEqual(iter.next(), undefined)
+  deepEqual(iter.next(), undefined)
+
+  const collect = (regex, text) => {
+    const iter = regex.capturesIter(text)
+    const all = []
+    for (let caps = iter.next(); caps !== undefined; caps = iter.next()) {
+      all.push(caps)
+    }
+    return all
+  }
+  for (const [pattern, text] of [['a*', 'baab'], ['', 'δx'], ['\\b', 'ab cd'], ['(a)|b', 'abab']]) {
+    const regex = new RRegex(pattern)
+    deepEqual(collect(regex, text), regex.capturesAll(text))
+  }
+
+  const early = new RRegex('x').capturesIter('x'.repeat(100000))
+  deepEqual(early.next().get[0].start, 0)
+
+  const owned = new RRegex('\\d')
+  const rest = owned.capturesIter('1 2')
+  owned.free()
+  deepEqual(rest.next().get[0].value, '1')
+})
+
+test(`RRegex::scanner`, () => {
+  const scanner = new RRegex('\\d+').scanner(8)
+  scanner.push('a 12 b 3')
+  scanner.push('4 c 1234567 d')
+  deepEqual(scanner.drain(), [
+    { start: 2, end: 4, value: '12' },
+    { start: 7, end: 9, value: '34' },
+  ])
+  deepEqual(scanner.drain(), [])
+  deepEqual(scanner.finish(), [{ start: 12, end: 19, value: '1234567' }])
+  deepEqual(scanner.finish(), [])
+
+  const text = 'δ1 a22 \nb333 x\n4444 δδ 5 end\n'
+  for (const pattern of ['\\d+', '\\b\\w', '^\\w+', '\\w*$', 'x*']) {
+    const regex = new RRegex(`(?m)${pattern}`)
+    for (const size of [1, 2, 3, 7]) {
+      const scanner = regex.scanner(8)
+      const matches = []
+      const chars = [...text]
+      for (let i = 0; i < chars.length; i += size) {
+        scanner.push(chars.slice(i, i + size).join(''))
+        matches.push(...scanner.drain())
+      }
+      matches.push(...scanner.finish())
+      deepEqual(matches, regex.findAll(text), `${pattern} in chunks of ${size}`)
+    }
+  }
+})
+
+test(`RRegex::stateful`, () => {
+  const re = new RRegex('\\d+').stateful()
+  const values = []
+  let m
+  while ((m = re.exec('1 22 333')) !== null) {
+    values.push([m[0], m.index, re.lastIndex])
+  }
+  deepEqual(values, [['1', 0, 1], ['22', 2, 4], ['333', 5, 8]])
+  deepEqual(re.lastIndex, 0)
+
+  for (const [pattern, text] of [['(?<w>[a-z])(\\d)?', '😀a1 b é2'], ['\\p{L}+', 'δx 😀 yz']]) {
+    const ours = new RRegex(pattern).stateful()
+    const native = new RegExp(pattern, 'gu')
+    for (let i = 0; i < 10; i++) {
+      deepEqual(ours.exec(text), native.exec(text))
+      deepEqual(ours.lastIndex, native.lastIndex)
+    }
+  }
+
+  const empty = new RRegex('').stateful()
+  const indices = []
+  while ((m = empty.exec('a😀')) !== null) {
+    indices.push([m.index, empty.lastIndex])
+  }
+  deepEqual(indices, [[0, 1], [1, 3], [3, 4]])
+
+  const word = new RRegex('\\w+').stateful()
+  word.lastIndex = 3
+  deepEqual(word.exec('ab cd')[0], 'cd')
+  word.lastIndex = 50
+  deepEqual(word.exec('ab cd'), null)
+  deepEqual(word.lastIndex, 0)
+})
+
+test(`RRegex::matchNavigator`, () => {
+  const nav = new RRegex('\\d+').matchNavigator('a 1 bb 22 c 333')
+  deepEqual(nav.count(), 3)
+  deepEqual(nav.at(0), { start: 2, end: 3 })
+  deepEqual(nav.at(2), { start: 12, end: 15 })
+  deepEqual(nav.at(3), undefined)
+  deepEqual(nav.next(0), { start: 2, end: 3 })
+  deepEqual(nav.next(3), { start: 7, end: 9 })
+  deepEqual(nav.next(7), { start: 7, end: 9 })
+  deepEqual(nav.next(13), undefined)
+  deepEqual(nav.prev(2), undefined)
+  deepEqual(nav.prev(3), { start: 2, end: 3 })
+  deepEqual(nav.prev(11), { start: 7, end: 9 })
+  deepEqual(nav.prev(100), { start: 12, end: 15 })
+  nav.free()
+  const empty = new RRegex('x').matchNavigator('abc')
+  deepEqual([empty.count(), empty.at(0), empty.next(0), empty.prev(3)], [0, undefined, undefined, undefined])
 })
 
 test(`RRegex::replace`, () => {
@@ -230,10 +1440,256 @@ test(`RRegex::replace`, () => {
   }
 })
 
+test(`RRegex::replaceStrict`, () => {
+  const regex = new RRegex('(?P<first>\\w+)\\s+(\\w+)(x)?')
+  deepEqual(regex.replaceStrict('deep fried', '$2 $first'), 'fried deep')
+  deepEqual(regex.replaceStrict('deep fried', '${first}_$2'), 'deep_fried')
+  deepEqual(regex.replaceStrict('deep fried', '[$3]'), '[]')
+  deepEqual(regex.replaceStrict('deep fried', '$$10 $ ${ $0'), '$10 $ ${ deep fried')
+
+  for (const rep of ['$10', '$1a', '$first_', '${second}', '$4']) {
+    try {
+      regex.replaceStrict('deep fried', rep)
+      deepEqual(rep, false)
+    } catch (error) {
+      deepEqual(error instanceof Error, true)
+    }
+  }
+})
+
 test(`RRegex::replaceAll`, () => {
   const regex = new RRegex('a')
   deepEqual(regex.replaceAll('abcabcabc', 'z'), 'zbczbczbc')
   deepEqual(regex.replaceAll('defdefdef', 'z'), 'defdefdef')
+  deepEqual(new RRegex('\\b').replaceAll('ab cd', '|'), '|ab| |cd|')
+  deepEqual(new RRegex('\\b').replaceAll('', '|'), '')
+  deepEqual(new RRegex('\\b').replaceAll(' ', '|'), ' ')
+  deepEqual(new RRegex('(\\b)').replaceAll('ab cd', '[$1]'), '[]ab[] []cd[]')
+  deepEqual(new RRegex('a*').replaceAll('baaac', '-'), '-b-c-')
+  deepEqual(new RRegex('').replaceAll('aé', '|'), '|a|é|')
+  deepEqual(new RRegex('x*|b').replaceAll('abc', '|'), '|a|b|c|')
+})
+
+test(`RRegex::replaceAllWith`, () => {
+  const re = new RRegex('(?<last>\\w+), (?<first>\\w+)')
+  const rep = compileReplacement('$first $last')
+  deepEqual(rep.template, '$first $last')
+  deepEqual(re.replaceAllWith('Doe, Jane', rep), 'Jane Doe')
+  deepEqual(re.replaceAllWith('Roe, Richard', rep), 'Richard Roe')
+  deepEqual(re.replaceAllWith('nothing', rep), 'nothing')
+  for (const template of ['$2-$1', '${2}x', '$$1', '$', '${1', '$1a', '${}', '$9', 'plain', '']) {
+    const other = new RRegex('(\\d)-(\\d)')
+    deepEqual(other.replaceAllWith('1-2 3-4', compileReplacement(template)), other.replaceAll('1-2 3-4', template))
+  }
+})
+
+test(`RRegex::replaceAllJs`, () => {
+  const date = new RRegex('(?P<y>\\d{4})-(\\d{2})')
+  deepEqual(date.replaceAllJs('2024-05', '$2/$<y> ($&, $$1)'), '05/2024 (2024-05, $1)')
+  deepEqual(date.replaceAllJs('a 2024-05 b', "[$`|$']"), 'a [a | b] b')
+  deepEqual(date.replaceAllJs('2024-05', '$0 $3 $<m> $<y $'), '$0 $3  $<y $')
+  deepEqual(date.replaceAllJs('2024-05', '$10 $01 $02'), '20240 2024 05')
+
+  const unnamed = new RRegex('(a)(b)?')
+  deepEqual(unnamed.replaceAllJs('a ab', '[$1$2$<x>]'), '[a$<x>] [ab$<x>]')
+
+  const many = new RRegex('(a)(b)(c)(d)(e)(f)(g)(h)(i)(j)')
+  deepEqual(many.replaceAllJs('abcdefghij', '$10-$11'), 'j-a1')
+
+  for (const [pattern, text, rep] of [['(?<y>\\d{4})-(\\d{2})', '2024-05 1999-12', "$2/$<y> $$ $& $` $' $0 $3 $<z> $<y"], ['(a)|(b)', 'ab', '[$1|$2|$3]'], ['', 'ab', '-$&-']]) {
+    deepEqual(new RRegex(pattern).replaceAllJs(text, rep), text.replaceAll(new RegExp(pattern, 'g'), rep))
+  }
+})
+
+test(`RRegex::replaceAllChunked`, () => {
+  const re = new RRegex('\\d+')
+  const chunks = []
+  re.replaceAllChunked('a1b22', '<$0>', (chunk) => chunks.push(chunk))
+  deepEqual(chunks, ['a', '<1>', 'b', '<22>'])
+
+  for (const [text, rep] of [['1 22 333 x', '[$0]'], ['12', ''], ['', 'x'], ['no digits', '#']]) {
+    const pieces = []
+    re.replaceAllChunked(text, rep, (chunk) => pieces.push(chunk))
+    deepEqual(pieces.join(''), re.replaceAll(text, rep))
+    deepEqual(pieces.includes(''), false)
+  }
+
+  try {
+    re.replaceAllChunked('1', '#', () => { throw new Error('stop') })
+    deepEqual(true, false)
+  } catch (error) {
+    deepEqual(error instanceof Error, true)
+  }
+})
+
+test(`RRegex::replaceAllMaybe`, () => {
+  const re = new RRegex('\\d')
+  deepEqual(re.replaceAllMaybe('a1', '#'), { changed: true, result: 'a#' })
+  deepEqual(re.replaceAllMaybe('ab', '#'), { changed: false })
+  deepEqual('result' in re.replaceAllMaybe('ab', '#'), false)
+  deepEqual(re.replaceAllMaybe('1', '$0'), { changed: true, result: '1' })
+  deepEqual(re.replaceAllMaybe('a1b2', ''), { changed: true, result: 'ab' })
+})
+
+test(`RRegex::replaceAllCounted`, () => {
+  const regex = new RRegex('(?P<area>\\d{3})-\\d{4}')
+  const text = '555-1234 or 666-9876'
+  deepEqual(regex.replaceAllCounted(text, '[redacted]'), { result: '[redacted] or [redacted]', count: 2 })
+  deepEqual(regex.replaceAllCounted(text, '$area-XXXX $$').result, regex.replaceAll(text, '$area-XXXX $$'))
+  deepEqual(regex.replaceAllCounted(text, 'x').count, regex.findAll(text).length)
+  deepEqual(regex.replaceAllCounted('none', 'x'), { result: 'none', count: 0 })
+})
+
+test(`RRegex::replaceAllWithSpans`, () => {
+  const re = new RRegex('(\\d+)')
+  deepEqual(re.replaceAllWithSpans('a1 b22', '<$1>'), {
+    result: 'a<1> b<22>',
+    spans: [
+      { originalStart: 1, originalEnd: 2, outputStart: 1, outputEnd: 4 },
+      { originalStart: 4, originalEnd: 6, outputStart: 6, outputEnd: 10 },
+    ],
+  })
+  deepEqual(re.replaceAllWithSpans('é1é', ''), {
+    result: 'éé',
+    spans: [{ originalStart: 2, originalEnd: 3, outputStart: 2, outputEnd: 2 }],
+  })
+  deepEqual(re.replaceAllWithSpans('none', 'x'), { result: 'none', spans: [] })
+})
+
+test(`RRegex::previewReplaceAll`, () => {
+  const re = new RRegex('colou?r')
+  deepEqual(re.previewReplaceAll('color, colour', 'colour'), [{ start: 0, end: 5, before: 'color', after: 'colour' }])
+  deepEqual(re.previewReplaceAll('colour', 'colour'), [])
+  deepEqual(new RRegex('(\\w)(\\w)').previewReplaceAll('ab aa é', '$2$1'), [{ start: 0, end: 2, before: 'ab', after: 'ba' }])
+  deepEqual(new RRegex('\\b').previewReplaceAll('ab', '|'), [
+    { start: 0, end: 0, before: '', after: '|' },
+    { start: 2, end: 2, before: '', after: '|' },
+  ])
+})
+
+test(`RRegex::replaceAllFnChunked`, () => {
+  const regex = new RRegex('(?<n>\\d+)')
+  const double = (caps) => String(Number(caps.name.n.value) * 2)
+  const counts = []
+  deepEqual(regex.replaceAllFnChunked('1 2 3 4 5', double, 2, (count) => counts.push(count)), '2 4 6 8 10')
+  deepEqual(counts, [2, 4])
+  deepEqual(regex.replaceAllFnChunked('1 2 3', double, 0, () => { throw new Error('unexpected') }), '2 4 6')
+  deepEqual(regex.replaceAllFnChunked('1 2 3', double, 1), '2 4 6')
+  deepEqual(regex.replaceAllFnChunked('no digits', double, 1), 'no digits')
+  deepEqual(new RRegex('').replaceAllFnChunked('ab', () => '-', 1), '-a-b-')
+
+  for (const [f, progress] of [[() => 1, undefined], [() => { throw new Error('f') }, undefined], [double, () => { throw new Error('progress') }]]) {
+    let error
+    try {
+      regex.replaceAllFnChunked('1 2', f, 1, progress)
+    } catch (err) {
+      error = err
+    }
+    deepEqual(error instanceof Error, true)
+  }
+})
+
+test(`RRegex::replaceAllFnNamed`, () => {
+  const regex = new RRegex('(?P<key>\\w+)=(?P<value>\\d+)?')
+  const seen = []
+  const result = regex.replaceAllFnNamed('a=1 b=', (groups) => {
+    seen.push(groups)
+    return `${groups.value}:${groups.key}`
+  })
+  deepEqual(result, '1:a undefined:b')
+  deepEqual(seen[0], { 0: 'a=1', 1: 'a', 2: '1', key: 'a', value: '1' })
+  deepEqual(Object.keys(seen[1]).includes('value'), true)
+  deepEqual(seen[1].value, undefined)
+
+  try {
+    regex.replaceAllFnNamed('a=1', () => 1)
+    deepEqual(true, false)
+  } catch (error) {
+    deepEqual(error instanceof Error, true)
+  }
+})
+
+test(`RRegex::replaceAllFnPos`, () => {
+  const re = new RRegex('\\d+')
+  deepEqual(re.replaceAllFnPos('1 22 3', (caps, start, end) => `${caps.get[0].value}@${start}..${end}`), '1@0..1 22@2..4 3@5..6')
+  deepEqual(new RRegex('b').replaceAllFnPos('☃b', (_, start) => String(start)), '☃3')
+  deepEqual(re.replaceAllFnPos('none', () => 'x'), 'none')
+  try {
+    re.replaceAllFnPos('1', () => 1)
+    deepEqual(true, false)
+  } catch (error) {
+    deepEqual(error instanceof Error, true)
+  }
+})
+
+test(`RRegex::replaceAllIf`, () => {
+  const re = new RRegex('(?<n>\\d+)')
+  const seen = []
+  const result = re.replaceAllIf('1 20 300', (caps) => {
+    seen.push(caps.name.n.value)
+    return caps.get[1].value.length > 1
+  }, '<$n>')
+  deepEqual(result, '1 <20> <300>')
+  deepEqual(seen, ['1', '20', '300'])
+  deepEqual(re.replaceAllIf('1 2', () => 0, 'x'), '1 2')
+  deepEqual(re.replaceAllIf('1 2', () => 'yes', '$$'), '$ $')
+  try {
+    re.replaceAllIf('1', () => { throw new Error('boom') }, 'x')
+    deepEqual(true, false)
+  } catch (error) {
+    deepEqual(error.message, 'boom')
+  }
+})
+
+test(`RRegex::replaceMap`, () => {
+  deepEqual(RRegex.replaceMap('a cat category', { cat: 'dog', category: 'group' }), 'a dog group')
+  deepEqual(RRegex.replaceMap('a.b a*b', { 'a.b': 'x', 'a*b': '$1' }), 'x $1')
+  deepEqual(RRegex.replaceMap('über ü', { 'ü': 'ue', 'über': 'over' }), 'over ue')
+  deepEqual(RRegex.replaceMap('abc', {}), 'abc')
+  deepEqual(RRegex.replaceMap('abc', { '': 'x' }), 'abc')
+
+  let error
+  try {
+    RRegex.replaceMap('abc', { a: 1 })
+  } catch (err) {
+    error = err
+  }
+  deepEqual(error instanceof Error, true)
+})
+
+test(`RRegex::replaceByRanges`, () => {
+  const regex = new RRegex('\\d+')
+  const text = 'a1 b22 ä333'
+  deepEqual(regex.replaceByRanges(text, regex.matchIndices(text), ['one', 'two', 'three']), 'aone btwo äthree')
+  deepEqual(regex.replaceByRanges(text, [], []), text)
+  deepEqual(regex.replaceByRanges('abc', [0, 0, 3, 3], ['<', '>']), '<abc>')
+
+  const invalid = [
+    [[0, 1, 2], ['x']],
+    [[0, 1], ['x', 'y']],
+    [[2, 3, 0, 1], ['x', 'y']],
+    [[1, 0], ['x']],
+    [[8, 9], ['x']],
+    [[0, 100], ['x']],
+  ]
+
+  for (const [ranges, replacements] of invalid) {
+    let error
+    try {
+      regex.replaceByRanges(text, ranges, replacements)
+    } catch (err) {
+      error = err
+    }
+    deepEqual(error instanceof Error, true)
+  }
+})
+
+test(`RRegex::highlight`, () => {
+  const regex = new RRegex('\\d+')
+  deepEqual(regex.highlight('a1 b22', '<mark>', '</mark>'), 'a<mark>1</mark> b<mark>22</mark>')
+  deepEqual(regex.highlight('a1', '$0${1}', '\\$$'), 'a$0${1}1\\$$')
+  deepEqual(regex.highlight('abc', '[', ']'), 'abc')
+  deepEqual(new RRegex('x*').highlight('ab', '[', ']'), '[]a[]b[]')
 })
 
 test(`RRegex::replacen`, () => {
@@ -242,6 +1698,60 @@ test(`RRegex::replacen`, () => {
   deepEqual(regex.replacen('defdefdef', 2, 'z'), 'defdefdef')
 })
 
+test(`RRegex::replaceRange`, () => {
+  const digits = new RRegex('\\d')
+  deepEqual(digits.replaceRange('1 2 3 4 5', 1, 3, '#'), '1 # # 4 5')
+  deepEqual(digits.replaceRange('1 2 3 4 5', 0, 100, '#'), '# # # # #')
+  deepEqual(digits.replaceRange('1 2 3 4 5', 3, 3, '#'), '1 2 3 4 5')
+  deepEqual(digits.replaceRange('1 2 3 4 5', 4, 2, '#'), '1 2 3 4 5')
+  deepEqual(digits.replaceRange('1 2 3', 7, 9, '#'), '1 2 3')
+  deepEqual(new RRegex('(\\w)(\\d)').replaceRange('a1 b2 c3', 2, 3, '$2$1'), 'a1 b2 3c')
+})
+
+test(`RRegex::trimStartMatches`, () => {
+  const regex = new RRegex('[-_]+')
+  deepEqual(regex.trimStartMatches('--a-b--'), 'a-b--')
+  deepEqual(regex.trimStartMatches('a-b--'), 'a-b--')
+  deepEqual(regex.trimStartMatches('---'), '')
+})
+
+test(`RRegex::trimEndMatches`, () => {
+  const regex = new RRegex('[-_]+')
+  deepEqual(regex.trimEndMatches('--a-b--'), '--a-b')
+  deepEqual(regex.trimEndMatches('--a-b'), '--a-b')
+  deepEqual(regex.trimEndMatches('---'), '')
+})
+
+test(`RRegex::trimMatches`, () => {
+  const regex = new RRegex('[-_]+')
+  deepEqual(regex.trimMatches('--a-b__'), 'a-b')
+  deepEqual(regex.trimMatches('a-b'), 'a-b')
+  deepEqual(regex.trimMatches('---'), '')
+  deepEqual(regex.trimMatches(''), '')
+})
+
+test(`RRegex::partition`, () => {
+  const regex = new RRegex('\\d+')
+  deepEqual(regex.partition('a1b22'), [
+    { start: 0, end: 1, value: 'a', matched: false },
+    { start: 1, end: 2, value: '1', matched: true },
+    { start: 2, end: 3, value: 'b', matched: false },
+    { start: 3, end: 5, value: '22', matched: true },
+    { start: 5, end: 5, value: '', matched: false },
+  ])
+  deepEqual(regex.partition(''), [
+    { start: 0, end: 0, value: '', matched: false },
+  ])
+  deepEqual(new RRegex('x*').partition('ab'), [
+    { start: 0, end: 0, value: '', matched: true },
+    { start: 0, end: 1, value: 'a', matched: false },
+    { start: 1, end: 1, value: '', matched: true },
+    { start: 1, end: 2, value: 'b', matched: false },
+    { start: 2, end: 2, value: '', matched: true },
+    { start: 2, end: 2, value: '', matched: false },
+  ])
+})
+
 test(`RRegex::split`, () => {
   const regex = new RRegex('a')
   deepEqual(regex.split('abcabcabc'), ['', 'bc', 'bc', 'bc'])
@@ -252,6 +1762,78 @@ test(`RRegex::split`, () => {
   deepEqual(fields, ['a', 'b', 'c', 'd', 'e']);
 })
 
+test(`RRegex::splitMap`, () => {
+  const re = new RRegex(',')
+  deepEqual(re.splitMap(' 1, 2 ,3', (segment) => Number(segment.trim())), [1, 2, 3])
+  deepEqual(re.splitMap('a,,b', (segment, index) => [segment, index]), [['a', 0], ['', 1], ['b', 2]])
+  deepEqual(re.splitMap('a,b', (segment) => segment), re.split('a,b'))
+  try {
+    re.splitMap('a,b', () => { throw new Error('boom') })
+    deepEqual(true, false)
+  } catch (error) {
+    deepEqual(error.message, 'boom')
+  }
+})
+
+test(`RRegex::splitInclusive`, () => {
+  const regex = new RRegex('[.!?]')
+  deepEqual(regex.splitInclusive('Hi! Bye.'), ['Hi!', ' Bye.'])
+  deepEqual(regex.splitInclusive('Hi! Bye'), ['Hi!', ' Bye'])
+  deepEqual(regex.splitInclusive('a..b'), ['a.', '.', 'b'])
+  deepEqual(regex.splitInclusive('no punctuation'), ['no punctuation'])
+  deepEqual(regex.splitInclusive(''), [])
+  deepEqual(new RRegex('x*').splitInclusive('δx'), ['δx'])
+})
+
+test(`RRegex::splitOnLast`, () => {
+  const regex = new RRegex('/')
+  deepEqual(regex.splitOnLast('a/b/c'), ['a/b', 'c'])
+  deepEqual(regex.splitOnLast('a/'), ['a', ''])
+  deepEqual(regex.splitOnLast('abc'), ['abc'])
+  deepEqual(new RRegex('\\s+').splitOnLast('δ  x   ü'), ['δ  x', 'ü'])
+})
+
+test(`RRegex::splitIndices`, () => {
+  const regex = new RRegex('[ \\t]+')
+  deepEqual(regex.splitIndices('a b \tc'), [
+    { start: 0, end: 1, value: 'a' },
+    { start: 2, end: 3, value: 'b' },
+    { start: 5, end: 6, value: 'c' },
+  ])
+  deepEqual(regex.splitIndices(' a ').map((m) => m.value), regex.split(' a '))
+  deepEqual(new RRegex('x*').splitIndices('ab').map((m) => m.value), new RRegex('x*').split('ab'))
+})
+
+test(`RRegex::splitOffsets`, () => {
+  const re = new RRegex(',')
+  deepEqual(Array.from(re.splitOffsets('a,,b')), [0, 1, 2, 2, 3, 4])
+  deepEqual(Array.from(re.splitOffsets(',a,')), [0, 0, 1, 2, 3, 3])
+  deepEqual(Array.from(re.splitOffsets('abc')), [0, 3])
+  deepEqual(Array.from(re.splitOffsets('')), [0, 0])
+  const text = 'x, y,z'
+  const offsets = new RRegex(',\\s*').splitOffsets(text)
+  const pieces = []
+  for (let i = 0; i < offsets.length; i += 2) {
+    pieces.push(text.slice(offsets[i], offsets[i + 1]))
+  }
+  deepEqual(pieces, new RRegex(',\\s*').split(text))
+})
+
+test(`RRegex::splitFields`, () => {
+  const comma = new RRegex(',')
+  deepEqual(comma.splitFields('a,,b'), [
+    { value: 'a', start: 0, end: 1, empty: false },
+    { value: '', start: 2, end: 2, empty: true },
+    { value: 'b', start: 3, end: 4, empty: false },
+  ])
+  deepEqual(comma.splitFields(', ,'), [
+    { value: '', start: 0, end: 0, empty: true },
+    { value: ' ', start: 1, end: 2, empty: false },
+    { value: '', start: 3, end: 3, empty: true },
+  ])
+  deepEqual(comma.splitFields(''), [{ value: '', start: 0, end: 0, empty: true }])
+})
+
 test(`RRegex::splitn`, () => {
   const regex = new RRegex(',')
   deepEqual(regex.splitn('a,b,c', 0), [])
@@ -270,6 +1852,30 @@ test(`RRegex::splitn`, () => {
   deepEqual(fields, ['Hey', 'How', 'are you?'])
 })
 
+test(`RRegex::splitnInfo`, () => {
+  const regex = new RRegex(',')
+  deepEqual(regex.splitnInfo('a,b,c', 2), { parts: ['a', 'b,c'], truncated: true })
+  deepEqual(regex.splitnInfo('a,b,c', 3), { parts: ['a', 'b', 'c'], truncated: false })
+  deepEqual(regex.splitnInfo('a,b,c', 10), { parts: ['a', 'b', 'c'], truncated: false })
+  deepEqual(regex.splitnInfo('a,b,c', 1), { parts: ['a,b,c'], truncated: true })
+  deepEqual(regex.splitnInfo('abc', 1), { parts: ['abc'], truncated: false })
+  deepEqual(regex.splitnInfo('a,b', 0), { parts: [], truncated: true })
+  deepEqual(regex.splitnInfo('', 0), { parts: [], truncated: false })
+  deepEqual(regex.splitnInfo('a,', 2), { parts: ['a', ''], truncated: false })
+})
+
+test(`RRegex::splitBounded`, () => {
+  const comma = new RRegex(',')
+  deepEqual(comma.splitBounded('a,b,c', 2), { parts: ['a', 'b'], truncated: true })
+  deepEqual(comma.splitBounded('a,b,c', 3), { parts: ['a', 'b', 'c'], truncated: false })
+  deepEqual(comma.splitBounded('a,b,c', 10), { parts: ['a', 'b', 'c'], truncated: false })
+  deepEqual(comma.splitBounded('a,b,', 2), { parts: ['a', 'b'], truncated: true })
+  deepEqual(comma.splitBounded(',,,,,,', 1), { parts: [''], truncated: true })
+  deepEqual(comma.splitBounded('a', 0), { parts: [], truncated: true })
+  deepEqual(comma.splitBounded('', 0), { parts: [], truncated: false })
+  deepEqual(comma.splitBounded('', 1), { parts: [''], truncated: false })
+})
+
 test(`RRegex::shortestMatch`, () => {
   const regex = new RRegex('a')
   deepEqual(regex.shortestMatch('abcabcabc'), 1)
@@ -298,6 +1904,13 @@ test(`RRegex::shortestMatchAt`, () => {
   deepEqual(regex.shortestMatchAt('defdefdef', 2), undefined)
 })
 
+test(`RRegex::shortestMatchRange`, () => {
+  const regex = new RRegex('a+')
+  deepEqual(regex.shortestMatchRange('xaaaa'), { start: 1, end: 2 })
+  deepEqual(regex.shortestMatchRange('xyz'), undefined)
+  deepEqual(new RRegex('x*').shortestMatchRange('ab'), { start: 0, end: 0 })
+})
+
 test('regex_syntax::hir::Empty', () => {
   const regex = new RRegex('')
   deepEqual(regex.syntax(), {
@@ -1008,6 +2621,436 @@ test('regex_syntax::hir::Capture', () => {
 })
 
 
+test('RRegex::syntax', () => {
+  const regex = new RRegex('(?i)a|b')
+  const first = regex.syntax()
+  deepEqual(regex.syntax(), first)
+  deepEqual(regex.syntax(), new RRegex('(?i)a|b').syntax())
+})
+
+test(`RRegex::syntaxDebug`, () => {
+  const debug = new RRegex('a|[0-9]').syntaxDebug()
+  deepEqual(typeof debug, 'string')
+  deepEqual(debug.includes('\n'), true)
+  deepEqual(debug.includes('Alternation'), true)
+  deepEqual(debug.includes("'0'..='9'"), true)
+})
+
+test('RRegex::syntaxWithOptions', () => {
+  const regex = new RRegex('a')
+  deepEqual(regex.syntaxWithOptions({}), regex.syntax())
+  deepEqual(regex.syntaxWithOptions({ caseInsensitive: true }), {
+    "@name": "regex_syntax::hir::Hir",
+    "@type": "struct",
+    "kind": {
+      "@name": "regex_syntax::hir::HirKind",
+      "@type": "enum",
+      "@variant": "Class",
+      "@values": [
+        {
+          "@name": "regex_syntax::hir::Class",
+          "@type": "enum",
+          "@variant": "Unicode",
+          "@values": [
+            {
+              "@name": "regex_syntax::hir::ClassUnicode",
+              "@type": "struct",
+              "ranges": [
+                {
+                  "@name": "regex_syntax::hir::ClassUnicodeRange",
+                  "@type": "struct",
+                  "start": "A",
+                  "end": "A",
+                  "len": 1,
+                },
+                {
+                  "@name": "regex_syntax::hir::ClassUnicodeRange",
+                  "@type": "struct",
+                  "start": "a",
+                  "end": "a",
+                  "len": 1,
+                },
+              ]
+            }
+          ]
+        }
+      ],
+    }
+  })
+
+  const bytes = new RRegex('[a-c]')
+  deepEqual(bytes.syntaxWithOptions({ unicode: false }).kind['@values'][0]['@variant'], 'Bytes')
+})
+
+test(`RRegex::ast`, () => {
+  deepEqual(new RRegex('a|b').ast(), {
+    "@type": "enum",
+    "@name": "regex_syntax::ast::Ast",
+    "@variant": "Alternation",
+    "@values": [
+      {
+        "@type": "struct",
+        "@name": "regex_syntax::ast::Alternation",
+        "span": {
+          "@type": "struct",
+          "@name": "regex_syntax::ast::Span",
+          "start": {
+            "@type": "struct",
+            "@name": "regex_syntax::ast::Position",
+            "offset": 0,
+            "line": 1,
+            "column": 1
+          },
+          "end": {
+            "@type": "struct",
+            "@name": "regex_syntax::ast::Position",
+            "offset": 3,
+            "line": 1,
+            "column": 4
+          }
+        },
+        "asts": [
+          {
+            "@type": "enum",
+            "@name": "regex_syntax::ast::Ast",
+            "@variant": "Literal",
+            "@values": [
+              {
+                "@type": "struct",
+                "@name": "regex_syntax::ast::Literal",
+                "span": {
+                  "@type": "struct",
+                  "@name": "regex_syntax::ast::Span",
+                  "start": {
+                    "@type": "struct",
+                    "@name": "regex_syntax::ast::Position",
+                    "offset": 0,
+                    "line": 1,
+                    "column": 1
+                  },
+                  "end": {
+                    "@type": "struct",
+                    "@name": "regex_syntax::ast::Position",
+                    "offset": 1,
+                    "line": 1,
+                    "column": 2
+                  }
+                },
+                "kind": {
+                  "@type": "enum",
+                  "@name": "regex_syntax::ast::LiteralKind",
+                  "@variant": "Verbatim"
+                },
+                "c": "a"
+              }
+            ]
+          },
+          {
+            "@type": "enum",
+            "@name": "regex_syntax::ast::Ast",
+            "@variant": "Literal",
+            "@values": [
+              {
+                "@type": "struct",
+                "@name": "regex_syntax::ast::Literal",
+                "span": {
+                  "@type": "struct",
+                  "@name": "regex_syntax::ast::Span",
+                  "start": {
+                    "@type": "struct",
+                    "@name": "regex_syntax::ast::Position",
+                    "offset": 2,
+                    "line": 1,
+                    "column": 3
+                  },
+                  "end": {
+                    "@type": "struct",
+                    "@name": "regex_syntax::ast::Position",
+                    "offset": 3,
+                    "line": 1,
+                    "column": 4
+                  }
+                },
+                "kind": {
+                  "@type": "enum",
+                  "@name": "regex_syntax::ast::LiteralKind",
+                  "@variant": "Verbatim"
+                },
+                "c": "b"
+              }
+            ]
+          }
+        ]
+      }
+    ]
+  })
+
+  const group = new RRegex('(?P<x>[^b-d])+').ast()
+  deepEqual(group['@variant'], 'Repetition')
+  deepEqual(group['@values'][0].greedy, true)
+  const kind = group['@values'][0].ast['@values'][0].kind
+  deepEqual(kind['@variant'], 'CaptureName')
+  deepEqual(kind.startsWithP, true)
+  deepEqual(kind.name.name, 'x')
+  deepEqual(kind.name.index, 1)
+})
+
+test(`RRegex::ast -> Flags`, () => {
+  const span = (start, end) => ({
+    '@type': 'struct',
+    '@name': 'regex_syntax::ast::Span',
+    start: { '@type': 'struct', '@name': 'regex_syntax::ast::Position', offset: start, line: 1, column: start + 1 },
+    end: { '@type': 'struct', '@name': 'regex_syntax::ast::Position', offset: end, line: 1, column: end + 1 },
+  })
+  const flag = (variant, start) => ({
+    '@type': 'struct',
+    '@name': 'regex_syntax::ast::FlagsItem',
+    span: span(start, start + 1),
+    kind: {
+      '@type': 'enum',
+      '@name': 'regex_syntax::ast::FlagsItemKind',
+      '@variant': 'Flag',
+      '@values': [{ '@type': 'enum', '@name': 'regex_syntax::ast::Flag', '@variant': variant }],
+    },
+  })
+
+  const setFlags = new RRegex('(?i-s)a').ast()['@values'][0].asts[0]['@values'][0]
+  deepEqual(setFlags.flags, {
+    '@type': 'struct',
+    '@name': 'regex_syntax::ast::Flags',
+    span: span(2, 5),
+    items: [
+      flag('CaseInsensitive', 2),
+      {
+        '@type': 'struct',
+        '@name': 'regex_syntax::ast::FlagsItem',
+        span: span(3, 4),
+        kind: { '@type': 'enum', '@name': 'regex_syntax::ast::FlagsItemKind', '@variant': 'Negation' },
+      },
+      flag('DotMatchesNewLine', 4),
+    ],
+  })
+
+  const group = new RRegex('(?mxURu:a)').ast()['@values'][0]
+  deepEqual(
+    group.kind['@values'][0].items.map((item) => item.kind['@values'][0]['@variant']),
+    ['MultiLine', 'IgnoreWhitespace', 'SwapGreed', 'CRLF', 'Unicode'],
+  )
+})
+
+test(`RRegex::astWithComments`, () => {
+  const position = (offset, line, column) => ({ '@type': 'struct', '@name': 'regex_syntax::ast::Position', offset, line, column })
+  const regex = new RRegex('(?x)\\d+ # digits\n[a-z] # letter')
+  const { ast, comments, ...rest } = regex.astWithComments()
+  deepEqual(rest, { '@type': 'struct', '@name': 'regex_syntax::ast::WithComments' })
+  deepEqual(ast, regex.ast())
+  deepEqual(comments, [
+    {
+      '@type': 'struct',
+      '@name': 'regex_syntax::ast::Comment',
+      span: { '@type': 'struct', '@name': 'regex_syntax::ast::Span', start: position(8, 1, 9), end: position(17, 2, 1) },
+      comment: ' digits',
+    },
+    {
+      '@type': 'struct',
+      '@name': 'regex_syntax::ast::Comment',
+      span: { '@type': 'struct', '@name': 'regex_syntax::ast::Span', start: position(23, 2, 7), end: position(31, 2, 15) },
+      comment: ' letter',
+    },
+  ])
+
+  deepEqual(new RRegex('a#b').astWithComments().comments, [])
+})
+
+test(`RRegex::isAnchoredStart`, () => {
+  deepEqual(new RRegex('^abc').isAnchoredStart(), true)
+  deepEqual(new RRegex('\\Afoo|\\Abar').isAnchoredStart(), true)
+  deepEqual(new RRegex('^foo|bar').isAnchoredStart(), false)
+  deepEqual(new RRegex('(?m)^abc').isAnchoredStart(), false)
+})
+
+test(`RRegex::isAnchoredEnd`, () => {
+  deepEqual(new RRegex('abc$').isAnchoredEnd(), true)
+  deepEqual(new RRegex('foo\\z|bar\\z').isAnchoredEnd(), true)
+  deepEqual(new RRegex('abc').isAnchoredEnd(), false)
+})
+
+test(`RRegex::isLiteral`, () => {
+  deepEqual(new RRegex('foo').isLiteral(), true)
+  deepEqual(new RRegex('fo+').isLiteral(), false)
+  deepEqual(new RRegex('foo|bar').isLiteral(), false)
+  deepEqual(new RRegex('^foo').isLiteral(), false)
+})
+
+test(`RRegex::minMatchLength`, () => {
+  deepEqual(new RRegex('a{3,}').minMatchLength(), 3)
+  deepEqual(new RRegex('a|bcd').minMatchLength(), 1)
+  deepEqual(new RRegex('δ|bcd').minMatchLength(), 2)
+  deepEqual(new RRegex('foo\\d+bar').minMatchLength(), 7)
+  deepEqual(new RRegex('a*').minMatchLength(), 0)
+  deepEqual(new RRegex('^$').minMatchLength(), 0)
+  deepEqual(new RRegex('[a&&b]').minMatchLength(), undefined)
+})
+
+test(`RRegex::usesLineAnchors`, () => {
+  deepEqual(new RRegex('(?m)^a').usesLineAnchors(), true)
+  deepEqual(new RRegex('(?mR)a$').usesLineAnchors(), true)
+  deepEqual(new RRegexBuilder('b|a$').multiLine(true).build().usesLineAnchors(), true)
+  deepEqual(new RRegex('^a$').usesLineAnchors(), false)
+  deepEqual(new RRegex('a\\b').usesLineAnchors(), false)
+})
+
+test(`RRegex::usesTextAnchors`, () => {
+  deepEqual(new RRegex('\\Aa').usesTextAnchors(), true)
+  deepEqual(new RRegex('a\\z').usesTextAnchors(), true)
+  deepEqual(new RRegex('^a').usesTextAnchors(), true)
+  deepEqual(new RRegex('(?m)^a$').usesTextAnchors(), false)
+  deepEqual(new RRegex('(?m)\\A^a').usesTextAnchors(), true)
+  deepEqual(new RRegex('abc').usesTextAnchors(), false)
+})
+
+test(`RRegex::unicodeProperties`, () => {
+  const re = new RRegex('\\p{Greek}+[\\pL\\P{sc:Latin}]\\p{Greek}')
+  deepEqual(re.unicodeProperties(), ['Greek', 'L', 'sc=Latin'])
+  deepEqual(new RRegex('[[\\p{N}--\\p{Nd}]&&\\p{Script=Arabic}]').unicodeProperties(), ['N', 'Nd', 'Script=Arabic'])
+  deepEqual(new RRegex('\\w+\\d').unicodeProperties(), [])
+})
+
+test(`RRegex::rootKind`, () => {
+  const cases = [['', 'Empty'], ['abc', 'Literal'], ['[a-z]', 'Class'], ['^', 'Look'], ['a+', 'Repetition'], ['(a)', 'Capture'], ['a+b', 'Concat'], ['ab|cd', 'Alternation']]
+  for (const [pattern, kind] of cases) {
+    const regex = new RRegex(pattern)
+    deepEqual(regex.rootKind(), kind)
+    deepEqual(regex.syntax().kind['@variant'], kind)
+  }
+})
+
+test(`RRegex::literalAlternatives`, () => {
+  deepEqual(new RRegex('foo|bar|baz').literalAlternatives(), ['foo', 'bar', 'baz'])
+  deepEqual(new RRegex('foo').literalAlternatives(), ['foo'])
+  deepEqual(new RRegex('(foo)|b(?:a)r|').literalAlternatives(), ['foo', 'bar', ''])
+  deepEqual(new RRegex('a|c|b|long').literalAlternatives(), ['a', 'c', 'b', 'long'])
+  deepEqual(new RRegex('a|c|b').literalAlternatives(), ['a', 'b', 'c'])
+  deepEqual(new RRegex('foo|ba[a-z]').literalAlternatives(), undefined)
+  deepEqual(new RRegex('[a-z]').literalAlternatives(), undefined)
+  deepEqual(new RRegex('foo+|bar').literalAlternatives(), undefined)
+  deepEqual(new RRegex('^foo|bar').literalAlternatives(), undefined)
+  deepEqual(new RRegex('(?:ab|cd)ef').literalAlternatives(), undefined)
+})
+
+test(`RRegex::canonicalize`, () => {
+  deepEqual(new RRegex('(?:[a-a])').canonicalize(), 'a')
+  deepEqual(new RRegex('a').canonicalize(), 'a')
+  deepEqual(new RRegex('a|b').canonicalize(), new RRegex('[ba]').canonicalize())
+  deepEqual(new RRegex('[c-ea-c]').canonicalize(), new RRegex('[a-e]').canonicalize())
+  deepEqual(new RRegex('(?i)a').canonicalize(), new RRegexBuilder('a').caseInsensitive(true).build().canonicalize())
+  deepEqual(new RRegex('a+').canonicalize() === new RRegex('aa*').canonicalize(), false)
+  for (const pattern of ['(?i)\\w+@\\d{2,}', '(?m)^(a|bc)?$', '(?<name>x)\\b']) {
+    const canonical = new RRegex(pattern).canonicalize()
+    deepEqual(new RRegex(canonical).canonicalize(), canonical)
+  }
+})
+
+test(`RRegex::complexity`, () => {
+  deepEqual(new RRegex('(ab|cd+)*x{3}').complexity(), { repetitions: 3, unboundedRepetitions: 2, alternations: 1, maxDepth: 2 })
+  deepEqual(new RRegex('abc').complexity(), { repetitions: 0, unboundedRepetitions: 0, alternations: 0, maxDepth: 0 })
+  deepEqual(new RRegex('((a+)+)+').complexity().maxDepth, 3)
+  deepEqual(new RRegex('a|b').complexity().alternations, 0)
+  deepEqual(new RRegex('(a{1000}){10}').complexity(), { repetitions: 2, unboundedRepetitions: 0, alternations: 0, maxDepth: 2 })
+})
+
+test(`RRegex::approximateSize`, () => {
+  const small = new RRegex('a').approximateSize()
+  deepEqual(Number.isInteger(small), true)
+  deepEqual(new RRegex('a').approximateSize(), small)
+  const large = new RRegex('\\w{20}')
+  deepEqual(large.approximateSize() > small, true)
+  deepEqual(large.approximateSize(), large.approximateSize())
+  deepEqual(new RRegex('(?-u:\\w{20})').approximateSize() < large.approximateSize(), true)
+})
+
+test(`RRegex::isAlwaysUtf8`, () => {
+  deepEqual(new RRegex('a.c').isAlwaysUtf8(), true)
+  deepEqual(new RRegex('(?-u:a)').isAlwaysUtf8(), true)
+})
+
+test(`hirToString`, () => {
+  deepEqual(hirToString(new RRegex('a|bc').syntax()), '(?:a|(?:bc))')
+  deepEqual(hirToString(new RRegex('^(?P<x>[a-c])*$').syntax()), '(?:\\A(?P<x>[a-c])*\\z)')
+  deepEqual(hirToString(new RRegex('').syntax()), '(?:)')
+
+  const regex = new RRegex(hirToString(new RRegex('(?i)k\\d{2,5}?').syntax()))
+  deepEqual(regex.find('xK12345').start, 1)
+  deepEqual(regex.find('xK12345').end, 4)
+})
+
+test(`classContains`, () => {
+  const unicode = (pattern) => new RRegex(pattern).syntax().kind['@values'][0]['@values'][0]
+  const letters = unicode('[a-cδ😀]')
+  deepEqual(classContains(letters, 'a'), true)
+  deepEqual(classContains(letters, 'c'), true)
+  deepEqual(classContains(letters, 'd'), false)
+  deepEqual(classContains(letters, 'δ'), true)
+  deepEqual(classContains(letters, '😀'), true)
+  deepEqual(classContains(unicode('[^a]'), 'a'), false)
+  deepEqual(classContains(unicode('[^a]'), '\u{10FFFF}'), true)
+
+  for (const ch of ['', 'ab', '\uD83D', 'a\u0301']) {
+    try {
+      classContains(letters, ch)
+      deepEqual(ch, false)
+    } catch (error) {
+      deepEqual(error instanceof Error, true)
+    }
+  }
+})
+
+test(`byteClassContains`, () => {
+  const hir = new RRegex('(?-u)[\\x00-\\x1F\\x7F]').syntax()
+  const [bytes] = hir.kind['@values'][0]['@values']
+  deepEqual(byteClassContains(bytes, 0x7f), true)
+  deepEqual(byteClassContains(bytes, 0), true)
+  deepEqual(byteClassContains(bytes, 0x20), false)
+  for (const byte of [-1, 256, 1.5]) {
+    try {
+      byteClassContains(bytes, byte)
+      deepEqual(true, false)
+    } catch (error) {
+      deepEqual(error instanceof Error, true)
+    }
+  }
+})
+
+test(`byteClassRanges`, () => {
+  const ranges = byteClassRanges({
+    '@type': 'struct',
+    '@name': 'regex_syntax::hir::ClassBytes',
+    ranges: [{ start: 20, end: 30 }, { start: 5, end: 9 }, { start: 0, end: 4 }, { start: 25, end: 40 }],
+  })
+  deepEqual(ranges.map(({ start, end, len }) => [start, end, len]), [[0, 9, 10], [20, 40, 21]])
+
+  const [bytes] = new RRegex('(?-u)[a-c]').syntax().kind['@values'][0]['@values']
+  deepEqual(byteClassRanges(bytes), bytes.ranges)
+})
+
+test(`classCaseFold`, () => {
+  deepEqual(hirToString(classCaseFold(new RRegex('[a-c]').syntax())), '[A-Ca-c]')
+  deepEqual(new RRegex(hirToString(classCaseFold(new RRegex('x(?:[k-l]|\\d)+').syntax()))).find('xL\u212A1k').value, 'xL\u212A1k')
+  deepEqual(classCaseFold(new RRegex('(?i)[a-c]').syntax()), new RRegex('(?i)[a-c]').syntax())
+  deepEqual(classCaseFold(new RRegex('ab').syntax()), new RRegex('ab').syntax())
+  deepEqual(new RRegex(hirToString(classCaseFold(new RRegex('^(?<x>[a-c]+)$').syntax()))).isMatch('aBc'), true)
+})
+
+test(`classNegate`, () => {
+  const syntax = (pattern) => new RRegex(pattern).syntax()
+  deepEqual(classNegate(syntax('[^abc]')), syntax('[abc]'))
+  deepEqual(classNegate(syntax('[abc]')), syntax('[^abc]'))
+  deepEqual(hirToString(classNegate(syntax('(?-u:[a-c])'))), '(?-u:[\\x00-`d-\\xFF])')
+  deepEqual(classNegate(classNegate(syntax('x(?<d>\\d+)|[a-z]*'))), syntax('x(?<d>\\d+)|[a-z]*'))
+  deepEqual(new RRegex(hirToString(classNegate(syntax('^[a-z]+$')))).isMatch('ABC'), true)
+  deepEqual(classNegate(syntax('ab')), syntax('ab'))
+})
+
 test('regex_syntax -> Empty character class', () => {
   const regex = new RRegex('[a&&b]')
   deepEqual(regex.syntax(), {
@@ -1054,6 +3097,75 @@ test(`RRegexSet::matches`, () => {
   deepEqual(set.matches('foobar'), [0, 2, 3, 4, 6])
 })
 
+test(`RRegexSet::matchesAt`, () => {
+  const set = new RRegexSet(['\\d+', '[a-z]+', '^a', '\\bb'])
+  deepEqual(set.matchesAt('a1', 0), [0, 1, 2])
+  deepEqual(set.matchesAt('a1', 1), [0])
+  deepEqual(set.matchesAt('ab', 1), [1])
+  deepEqual(set.matchesAt('a1', 2), [])
+  deepEqual(set.matchesAt('a1', 3), [])
+  deepEqual(set.matchesAt('a1', 100), [])
+})
+
+test(`RRegexSet::matchesOrderedByPosition`, () => {
+  const set = new RRegexSet(['[a-z]+', '\\d+', '\\w+', 'zzz'])
+  deepEqual(set.matchesOrderedByPosition('42 apples'), [1, 2, 0])
+  deepEqual(set.matches('42 apples'), [0, 1, 2])
+  deepEqual(set.matchesOrderedByPosition('!!!'), [])
+  const builder = new RRegexSetBuilder(['B', 'a']).caseInsensitive(true).build()
+  deepEqual(builder.matchesOrderedByPosition('xAb'), [1, 0])
+  const large = new RRegexSetBuilder(['\\w{3000}', '\\d']).sizeLimit(1 << 30).build()
+  deepEqual(large.matchesOrderedByPosition(`1 ${'a'.repeat(3000)}`), [1, 0])
+})
+
+test(`RRegexSet::matchesBitset`, () => {
+  const set = new RRegexSet(['\\w+', '\\d+', 'foo'])
+  deepEqual(set.matchesBitset('foo'), new Uint8Array([0b101]))
+  deepEqual(set.matchesBitset('☃'), new Uint8Array([0]))
+
+  const patterns = Array.from({ length: 20 }, (_, i) => `^${i}$`)
+  const bitset = new RRegexSet(patterns).matchesBitset('9')
+  deepEqual(bitset, new Uint8Array([0, 0b10, 0]))
+  deepEqual(new RRegexSet([]).matchesBitset('a'), new Uint8Array([]))
+})
+
+test(`RRegexSet::conflictingGroupNames`, () => {
+  const set = new RRegexSet(['(?<id>\\d+)', '(?<name>\\w+)', '#(?<id>\\w+)', '(?<name>a)|(?<id>b)'])
+  deepEqual(set.conflictingGroupNames(), [
+    { name: 'id', patterns: [0, 2, 3] },
+    { name: 'name', patterns: [1, 3] },
+  ])
+  deepEqual(new RRegexSet(['(?<a>x)', '(?<b>y)', 'z']).conflictingGroupNames(), [])
+  const large = new RRegexSetBuilder(['(?<a>\\w{3000})', '(?<a>b)']).sizeLimit(1 << 30).build()
+  deepEqual(large.conflictingGroupNames(), [{ name: 'a', patterns: [0, 1] }])
+})
+
+test(`RRegexSetBuilder::build`, () => {
+  const set = new RRegexSetBuilder(['foo', 'bar', '\\w+']).caseInsensitive(true).build()
+  deepEqual(set.matches('FOO'), [0, 2])
+  deepEqual(new RRegexSetBuilder(['foo']).build().isMatch('FOO'), false)
+
+  const ascii = new RRegexSetBuilder(['^\\w+$']).unicode(false).build()
+  deepEqual(ascii.isMatch('abc'), true)
+  deepEqual(ascii.isMatch('δ'), false)
+
+  deepEqual(new RRegexSetBuilder(['a{100}']).sizeLimit(1 << 20).dfaSizeLimit(1 << 20).build().isMatch('a'.repeat(100)), true)
+
+  for (const build of [
+    () => new RRegexSetBuilder(['\\w{1000}']).sizeLimit(100).build(),
+    () => new RRegexSetBuilder(['(']).build(),
+    () => new RRegexSetBuilder(['a', 1]),
+  ]) {
+    let error
+    try {
+      build()
+    } catch (err) {
+      error = err
+    }
+    deepEqual(error instanceof Error, true)
+  }
+})
+
 test("Match -> UTF-8 (äöü)", () => {
   const re = new RRegex("ä")
   const m = re.find("äöü") // { start: 0, end: 2 }
@@ -1,10 +1,450 @@
 import { assertEquals } from "https://deno.land/std@0.221.0/assert/mod.ts";
-import { RRegex, RRegexSet } from '../lib/esm.mjs'
+import { RRegex, RRegexBuilder, RRegexSet, RRegexSetBuilder, byteClassContains, byteClassRanges, classCaseFold, classContains, classNegate, compileReplacement, hirToString, prepare, validate, words } from '../lib/esm.mjs'
 
 Deno.test(`RRegex::toString`, () => {
   assertEquals(new RRegex('a').toString(), 'a')
 })
 
+Deno.test(`RRegex::source`, () => {
+  const regex = new RRegex('a\\d+')
+  assertEquals(regex.source, 'a\\d+')
+  assertEquals(`/${regex.source}/`, '/a\\d+/')
+})
+
+Deno.test(`RRegex::flags`, () => {
+  assertEquals(new RRegex('a').flags, '')
+  assertEquals(new RRegex('(?is)a.b').flags, 'is')
+  assertEquals(new RRegex('(?x)(?U-x) a').flags, 'U')
+  assertEquals(new RRegex('(?i)').flags, 'i')
+  assertEquals(new RRegex('a(?i)b').flags, '')
+  assertEquals(new RRegex('(?i:a)').flags, '')
+  assertEquals(RRegex.fromJSON({ source: '(?-i)a', flags: 'im' }).flags, 'm')
+})
+
+Deno.test(`RRegex::tryNew`, () => {
+  const position = ([offset, line, column], [endOffset, endLine, endColumn]) => ({
+    start: { offset, line, column },
+    end: { offset: endOffset, line: endLine, column: endColumn },
+  })
+
+  assertEquals(RRegex.tryNew('a').toString(), 'a')
+
+  const errors = [
+    ['a(b', { message: 'unclosed group', kind: 'GroupUnclosed', span: { start: 1, end: 2 }, position: position([1, 1, 2], [2, 1, 3]) }],
+    ['a{2', { message: 'unclosed counted repetition', kind: 'RepetitionCountUnclosed', span: { start: 1, end: 3 }, position: position([1, 1, 2], [3, 1, 4]) }],
+    ['\\p{Foo}', { message: 'Unicode property not found', kind: 'UnicodePropertyNotFound', span: { start: 0, end: 7 }, position: position([0, 1, 1], [7, 1, 8]) }],
+    ['(?x)\n  a\n  (b', { message: 'unclosed group', kind: 'GroupUnclosed', span: { start: 11, end: 12 }, position: position([11, 3, 3], [12, 3, 4]) }],
+    ['δ(', { message: 'unclosed group', kind: 'GroupUnclosed', span: { start: 2, end: 3 }, position: position([2, 1, 2], [3, 1, 3]) }],
+    ['(a)\\1', { message: 'backreferences are not supported', kind: 'UnsupportedBackreference', feature: 'backreference', span: { start: 3, end: 5 }, position: position([3, 1, 4], [5, 1, 6]) }],
+  ]
+
+  for (const [pattern, expected] of errors) {
+    let error
+    try {
+      RRegex.tryNew(pattern)
+    } catch (err) {
+      error = err
+    }
+    assertEquals(error, expected)
+  }
+})
+
+Deno.test(`RRegex::tryNew -> unsupported features`, () => {
+  const error = (pattern) => {
+    try {
+      RRegex.tryNew(pattern)
+    } catch (err) {
+      return err
+    }
+  }
+
+  assertEquals(error('(a)\\1').feature, 'backreference')
+  assertEquals(error('a(?=b)').feature, 'lookaround')
+  assertEquals(error('a(?=b)').span, { start: 1, end: 4 })
+  assertEquals(error('x(?<!a)').span, { start: 1, end: 5 })
+  assertEquals('feature' in error('a(b'), false)
+})
+
+Deno.test(`RRegex::getOrCompile`, () => {
+  const first = RRegex.getOrCompile('\\d+')
+  const second = RRegex.getOrCompile('\\d+')
+  assertEquals(first === second, false)
+  assertEquals(first.equals(second), true)
+  assertEquals(second.find('a42'), { start: 1, end: 3, value: '42' })
+
+  first.free()
+  assertEquals(second.isMatch('7'), true)
+  assertEquals(RRegex.getOrCompile('\\d+').isMatch('7'), true)
+
+  try {
+    RRegex.getOrCompile('(')
+    assertEquals(true, false)
+  } catch (error) {
+    assertEquals(error instanceof Error, true)
+  }
+
+  RRegex.clearCache()
+  assertEquals(second.isMatch('7'), true)
+  assertEquals(RRegex.getOrCompile('\\d+').isMatch('x'), false)
+})
+
+Deno.test(`validate`, () => {
+  assertEquals(validate('a+'), null)
+  assertEquals(validate('(?<name>a)\\d'), null)
+  assertEquals(validate('a(b').span, { start: 1, end: 2 })
+  assertEquals(validate('a(b').kind, 'GroupUnclosed')
+  assertEquals(validate('a(b').position.start, { offset: 1, line: 1, column: 2 })
+  assertEquals(validate('\\p{Foo}').kind, 'UnicodePropertyNotFound')
+  assertEquals(validate('a{1000}{1000}').kind, 'CompiledTooBig')
+  assertEquals(validate('(a)\\1').feature, 'backreference')
+  assertEquals(validate('(a)\\1').span, { start: 3, end: 5 })
+  assertEquals(validate('a(b').feature, undefined)
+  for (const pattern of ['a(?=b)', 'a(?!b)', '(?<=a)b', '(?<!a)b']) {
+    assertEquals(validate(pattern).feature, 'lookaround')
+    assertEquals(validate(pattern).kind, 'UnsupportedLookAround')
+  }
+})
+
+Deno.test(`words`, () => {
+  assertEquals(words('Hello, wörld!'), [
+    { value: 'Hello', start: 0, end: 5 },
+    { value: 'wörld', start: 7, end: 13 },
+  ])
+  assertEquals(words('snake_case 42 x'), new RRegex('\\w+').findAll('snake_case 42 x'))
+  assertEquals(words(''), [])
+  assertEquals(words('  ...  '), [])
+})
+
+Deno.test(`prepare`, () => {
+  const text = prepare('a1 δ2 c3')
+  assertEquals(text.byteLength, 9)
+
+  const digits = new RRegex('\\d')
+  assertEquals(digits.isMatchOn(text), true)
+  assertEquals(digits.findOn(text), digits.find('a1 δ2 c3'))
+  assertEquals(digits.findAllOn(text), digits.findAll('a1 δ2 c3'))
+
+  const missing = new RRegex('x')
+  assertEquals(missing.isMatchOn(text), false)
+  assertEquals(missing.findOn(text), undefined)
+  assertEquals(missing.findAllOn(text), [])
+
+  assertEquals(new RRegex('^$').isMatchOn(prepare('')), true)
+})
+
+Deno.test(`RRegex::replaceAllOn`, () => {
+  const text = prepare('a1 b2')
+  assertEquals(new RRegex('\\d').replaceAllOn(text, '#'), 'a# b#')
+  assertEquals(new RRegex('(?P<l>[a-z])').replaceAllOn(text, '<$l>'), '<a>1 <b>2')
+  assertEquals(new RRegex('x').replaceAllOn(text, '#'), 'a1 b2')
+  assertEquals(new RRegex('\\d').findAllOn(text).length, 2)
+})
+
+Deno.test(`RRegex::toJSON`, () => {
+  const regex = new RRegex('a+')
+  assertEquals(regex.toJSON(), { source: 'a+', flags: '' })
+  assertEquals(JSON.stringify({ regex }), '{"regex":{"source":"a+","flags":""}}')
+})
+
+Deno.test(`RRegex::fromJSON`, () => {
+  const regex = RRegex.fromJSON({ source: 'a+', flags: 'i' })
+  assertEquals(regex.isMatch('AA'), true)
+  assertEquals(regex.toJSON(), { source: 'a+', flags: 'i' })
+
+  const copy = RRegex.fromJSON(JSON.parse(JSON.stringify(RRegex.fromJSON({ source: '^a . b$', flags: 'mxsU' }))))
+  assertEquals(copy.toJSON(), { source: '^a . b$', flags: 'msxU' })
+  assertEquals(copy.find('x\na\nb'), { value: 'a\nb', start: 2, end: 5 })
+
+  assertEquals(RRegex.fromJSON({ source: 'a' }).toJSON(), { source: 'a', flags: '' })
+  assertEquals(RRegex.fromJSON({ source: 'a', flags: 'i-i' }).isMatch('A'), false)
+
+  for (const json of [{ source: 'a', flags: 'g' }, { source: '(' }, {}]) {
+    let error
+    try {
+      RRegex.fromJSON(json)
+    } catch (err) {
+      error = err
+    }
+    assertEquals(error instanceof Error, true)
+  }
+})
+
+Deno.test(`RRegex::serialize`, () => {
+  const bytes = new RRegex('a+').serialize()
+  assertEquals(bytes instanceof Uint8Array, true)
+  assertEquals(RRegex.deserialize(bytes).source, 'a+')
+  assertEquals(RRegex.deserialize(bytes).isMatch('aa'), true)
+
+  const regex = new RRegexBuilder('^a.c$').caseInsensitive(true).multiLine(true).octal(true).lineTerminator(0).longestMatch(true).build()
+  const copy = RRegex.deserialize(regex.serialize())
+  assertEquals(copy.toJSON(), regex.toJSON())
+  assertEquals(copy.isMatch('x\0A\nC\0'), true)
+  assertEquals(new RRegex('☃|é').serialize().length, 4 + new TextEncoder().encode('☃|é').length)
+
+  for (const bytes of [new Uint8Array([]), new Uint8Array([2, 0, 0, 10]), new Uint8Array([1, 0, 0, 10, 0xff]), new Uint8Array([1, 0, 0, 10, 40])]) {
+    try {
+      RRegex.deserialize(bytes)
+      assertEquals(true, false)
+    } catch (error) {
+      assertEquals(error instanceof Error, true)
+    }
+  }
+})
+
+Deno.test(`RRegex::clone`, () => {
+  const regex = new RRegexBuilder('(?<year>\\d{4})-(?<month>\\d{2})').caseInsensitive(true).build()
+  const copy = regex.clone()
+  assertEquals(copy.toJSON(), regex.toJSON())
+  assertEquals(copy.flags, regex.flags)
+  assertEquals(copy.captures('on 2024-05'), regex.captures('on 2024-05'))
+
+  regex.free()
+  assertEquals(copy.find('on 2024-05').value, '2024-05')
+  assertEquals(copy.clone().isMatch('1999-12'), true)
+})
+
+Deno.test(`RRegex::equals`, () => {
+  const regex = new RRegex('a|b')
+  assertEquals(regex.equals(new RRegex('a|b')), true)
+  assertEquals(regex.equals(regex), true)
+  assertEquals(regex.equals(regex.clone()), true)
+  assertEquals(regex.equals(new RRegex('b|a')), false)
+  assertEquals(regex.equals(new RRegexBuilder('a|b').caseInsensitive(true).build()), false)
+  assertEquals(new RRegexBuilder('a|b').build().equals(regex), true)
+})
+
+Deno.test(`RRegex::mayOverlap`, () => {
+  const users = new RRegex('^/users/\\w+$')
+  assertEquals(users.mayOverlap(new RRegex('^/users/me$'), []), true)
+  assertEquals(new RRegex('^/users/me$').mayOverlap(users, []), true)
+  assertEquals(users.mayOverlap(new RRegex('^/about$'), []), false)
+  assertEquals(new RRegex('^a$').mayOverlap(new RRegex('^b$'), ['a', 'b']), false)
+  assertEquals(new RRegex('^$').mayOverlap(new RRegex('x*'), []), true)
+  assertEquals(users.mayOverlap(new RRegex('/\\w+/ann'), ['/users/ann']), true)
+  assertEquals(users.mayOverlap(new RRegex('/\\w+/ann'), ['/users/bob', '/x/ann']), false)
+  assertEquals(new RRegex('(?m)^a$').mayOverlap(new RRegex('b'), []), false)
+})
+
+Deno.test(`RRegex::dispose`, () => {
+  const regex = new RRegex('a+')
+  const copy = regex.clone()
+  assertEquals(regex.isDisposed(), false)
+  assertEquals(regex.syntax().kind['@variant'], 'Repetition')
+  regex.dispose()
+  regex.dispose()
+  assertEquals(regex.isDisposed(), true)
+  for (const call of [() => regex.isMatch('a'), () => regex.find('a'), () => regex.syntax(), () => regex.source, () => regex.flags, () => regex.longestMatch, () => regex.equals(copy), () => regex.stateful()]) {
+    try {
+      call()
+      assertEquals(true, false)
+    } catch (error) {
+      assertEquals(error instanceof Error, true)
+      assertEquals(error.message, 'regex has been disposed')
+    }
+  }
+  assertEquals(copy.isDisposed(), false)
+  assertEquals(copy.isMatch('a'), true)
+  regex.free()
+})
+
+Deno.test(`RRegexBuilder::build`, () => {
+  const regex = new RRegexBuilder('^a.b$').caseInsensitive(true).multiLine(true).dotMatchesNewLine(true).build()
+  assertEquals(regex.find('x\nA\nB'), { value: 'A\nB', start: 2, end: 5 })
+  assertEquals(regex.flags, 'ims')
+  assertEquals(new RRegexBuilder('a').build().flags, '')
+  assertEquals(new RRegexBuilder('a b # comment').ignoreWhitespace(true).build().isMatch('ab'), true)
+  assertEquals(new RRegexBuilder('a+').swapGreed(true).build().find('aaa').value, 'a')
+
+  const builder = new RRegexBuilder('a')
+  builder.caseInsensitive(true)
+  assertEquals(builder.build().isMatch('A'), true)
+
+  let error
+  try {
+    new RRegexBuilder('(').build()
+  } catch (err) {
+    error = err
+  }
+  assertEquals(error instanceof Error, true)
+})
+
+Deno.test(`RRegexBuilder::unicode`, () => {
+  const regex = new RRegexBuilder('\\w+').unicode(false).build()
+  assertEquals(regex.find('δxyz').value, 'xyz')
+  assertEquals(regex.flags, '-u')
+  assertEquals(regex.toJSON(), { source: '\\w+', flags: '-u' })
+  assertEquals(RRegex.fromJSON(regex.toJSON()).find('δxyz').value, 'xyz')
+  assertEquals(new RRegexBuilder('\\w+').unicode(true).build().find('δxyz').value, 'δxyz')
+  assertEquals(new RRegex('(?-u)\\w').flags, '-u')
+
+  for (const pattern of ['\\pL', '.']) {
+    let error
+    try {
+      new RRegexBuilder(pattern).unicode(false).build()
+    } catch (err) {
+      error = err
+    }
+    assertEquals(error instanceof Error, true)
+  }
+})
+
+Deno.test(`RRegexBuilder::octal`, () => {
+  const regex = new RRegexBuilder('\\141').octal(true).build()
+  assertEquals(regex.isMatch('a'), true)
+  assertEquals(regex.find('xa'), { value: 'a', start: 1, end: 2 })
+  assertEquals(regex.toJSON(), { source: '\\141', flags: '', octal: true })
+  assertEquals(RRegex.fromJSON(regex.toJSON()).isMatch('a'), true)
+  assertEquals(regex.ast()['@variant'], 'Literal')
+
+  for (const build of [() => new RRegexBuilder('\\141').octal(false).build(), () => RRegex.fromJSON({ source: '\\141' })]) {
+    let error
+    try {
+      build()
+    } catch (err) {
+      error = err
+    }
+    assertEquals(error instanceof Error, true)
+  }
+})
+
+Deno.test(`RRegexBuilder::lineTerminator`, () => {
+  const regex = new RRegexBuilder('^\\w+$').multiLine(true).lineTerminator(0).build()
+  assertEquals(regex.findAll('foo\0bar').map((m) => m.value), ['foo', 'bar'])
+  assertEquals(regex.isMatch('foo\nbar'), false)
+  assertEquals(regex.toJSON(), { source: '^\\w+$', flags: 'm', lineTerminator: 0 })
+  assertEquals(RRegex.fromJSON(regex.toJSON()).findAll('foo\0bar').length, 2)
+  assertEquals(new RRegexBuilder('.+').lineTerminator(13).build().find('ab\rc').value, 'ab')
+  assertEquals(new RRegexBuilder('(?m)a$').lineTerminator(0).build().isFullMatch('a'), true)
+
+  for (const byte of [128, 255, 256]) {
+    let error
+    try {
+      new RRegexBuilder('a').lineTerminator(byte)
+    } catch (err) {
+      error = err
+    }
+    assertEquals(error instanceof Error, true)
+  }
+})
+
+Deno.test(`RRegexBuilder::crlf`, () => {
+  const regex = new RRegexBuilder('^.+$').multiLine(true).crlf(true).build()
+  assertEquals(regex.findAll('a\r\nb').map((m) => m.value), ['a', 'b'])
+  assertEquals(regex.flags, 'mR')
+  assertEquals(RRegex.fromJSON(regex.toJSON()).findAll('a\r\nb').map((m) => m.value), ['a', 'b'])
+  assertEquals(new RRegexBuilder('^.+$').multiLine(true).build().findAll('a\r\nb').map((m) => m.value), ['a\r', 'b'])
+  assertEquals(new RRegex('(?mR)^.+$').flags, 'mR')
+})
+
+Deno.test(`RRegexBuilder::longestMatch`, () => {
+  const first = new RRegexBuilder('sam|samwise').build()
+  assertEquals(first.find('samwise').value, 'sam')
+  assertEquals(first.longestMatch, false)
+
+  const longest = new RRegexBuilder('sam|samwise').longestMatch(true).build()
+  assertEquals(longest.find('samwise'), { value: 'samwise', start: 0, end: 7 })
+  assertEquals(longest.findAt('a samwise', 1), { value: 'samwise', start: 2, end: 9 })
+  assertEquals(longest.findAll('samwise sam samwise').map((m) => m.value), ['samwise', 'sam', 'samwise'])
+  assertEquals(longest.longestMatch, true)
+  assertEquals(longest.toJSON(), { source: 'sam|samwise', flags: '', longestMatch: true })
+  assertEquals(RRegex.fromJSON(longest.toJSON()).find('samwise').value, 'samwise')
+
+  const lazy = new RRegexBuilder('a+?').longestMatch(true).build()
+  assertEquals(lazy.find('aaa').value, 'aaa')
+
+  const empty = new RRegexBuilder('a*').longestMatch(true).build()
+  assertEquals(empty.findAll('baab'), new RRegex('a*').findAll('baab'))
+  assertEquals(empty.findAll('δa').map((m) => [m.start, m.end]), [[0, 0], [2, 3]])
+})
+
+Deno.test(`RRegexBuilder::longestMatch find family`, () => {
+  const longest = new RRegexBuilder('sam|samwise').longestMatch(true).build()
+  const text = 'sam samwise'
+  assertEquals(longest.findFirstMany(['samwise', 'no']), [{ value: 'samwise', start: 0, end: 7 }, undefined])
+  assertEquals(longest.findLast(text), { value: 'samwise', start: 4, end: 11 })
+  assertEquals(longest.findLastIndex(text), { start: 4, end: 11 })
+  assertEquals(longest.findNth(text, 1), { value: 'samwise', start: 4, end: 11 })
+  assertEquals(longest.findAllMerged(text, 0), [{ start: 0, end: 3 }, { start: 4, end: 11 }])
+  assertEquals(longest.findAllWhere(text, (m) => m.start > 0), [{ value: 'samwise', start: 4, end: 11 }])
+  assertEquals(longest.findAllOverlapping('samwise').map((m) => m.value), ['samwise'])
+  assertEquals(longest.findAllWithLine(text)[1].value, 'samwise')
+  assertEquals(longest.matchIndices('samwise'), new Int32Array([0, 7]))
+})
+
+Deno.test(`RRegexBuilder::longestMatch match methods`, () => {
+  const longest = new RRegexBuilder('sam|samwise').longestMatch(true).build()
+  const text = 'sam samwise'
+  const values = []
+  longest.forEachMatch(text, (m) => values.push(m.value))
+  assertEquals(values, ['sam', 'samwise'])
+  assertEquals(longest.matchExactlyAt(text, 4), { value: 'samwise', start: 4, end: 11 })
+  assertEquals(longest.matchStats(text), { count: 2, totalMatchedBytes: 10, coverage: 10 / 11 })
+  assertEquals(longest.matchNavigator(text).at(1), { start: 4, end: 11 })
+  assertEquals(longest.highlight(text, '[', ']'), '[sam] [samwise]')
+  assertEquals(longest.trimMatches('samwise x samwise'), ' x ')
+  assertEquals(longest.split('xsamwisey'), ['x', 'y'])
+  assertEquals(longest.splitn('xsamwiseysamwisez', 2), ['x', 'ysamwisez'])
+  assertEquals(longest.splitInclusive('xsamwisey'), ['xsamwise', 'y'])
+  assertEquals(longest.splitOnLast('xsamwisey'), ['x', 'y'])
+  assertEquals(Array.from(longest.splitOffsets('xsamwisey')), [0, 1, 8, 9])
+  assertEquals(longest.partition('samwise!').map((segment) => segment.value), ['samwise', '!'])
+
+  const scanner = longest.scanner(8)
+  scanner.push('samwise and sam')
+  assertEquals(scanner.finish().map((m) => m.value), ['samwise', 'sam'])
+
+  const groups = new RRegexBuilder('(sam)|(samwise)').longestMatch(true).build().stateful()
+  assertEquals([...groups.exec('samwise')], ['samwise', undefined, 'samwise'])
+
+  const split = new RRegexBuilder('a|ab|b').longestMatch(true).build()
+  assertEquals(Array.from(split.startPositions('ab')), [0])
+  assertEquals(Array.from(split.matchCountByLine('ab\nb')), [1, 1])
+  assertEquals(Object.fromEntries(split.matchOccurrences('ab b')), { ab: 1, b: 1 })
+  assertEquals(split.distinctMatches('ab b'), ['ab', 'b'])
+})
+
+Deno.test(`RRegex::unionOf`, () => {
+  const regex = RRegex.unionOf(['a.b', 'c*', 'a.b'])
+  assertEquals(regex.toString(), 'a\\.b|c\\*')
+  assertEquals(regex.isMatch('xa.by'), true)
+  assertEquals(regex.isMatch('axb'), false)
+
+  const words = RRegex.unionOf(['cat', 'dog'], true)
+  assertEquals(words.isMatch('concatenate'), false)
+  assertEquals(words.find('hot dog'), { value: 'dog', start: 4, end: 7 })
+
+  const empty = RRegex.unionOf([])
+  assertEquals(empty.isMatch(''), false)
+  assertEquals(empty.isMatch('abc'), false)
+})
+
+Deno.test(`RRegex::fromHir`, () => {
+  const regex = RRegex.fromHir(new RRegex('^(?P<x>a|bc)+$').syntax())
+  assertEquals(regex.toString(), '(?:\\A(?P<x>(?:a|(?:bc)))+\\z)')
+  assertEquals(regex.isMatch('abca'), true)
+  assertEquals(regex.captureNames(), [null, 'x'])
+
+  const hir = new RRegex('a').syntax()
+  hir.kind['@values'][0]['@values'][0] = new TextEncoder().encode('$z')
+  assertEquals(RRegex.fromHir(hir).isMatch('x$z'), true)
+
+  const invalid = [
+    {},
+    { kind: { '@variant': 'Unknown' } },
+    { kind: { '@variant': 'Literal', '@values': [] } },
+    { kind: { '@variant': 'Literal', '@values': [new Uint8Array([0xff])] } },
+  ]
+  for (const hir of invalid) {
+    let error
+    try {
+      RRegex.fromHir(hir)
+    } catch (err) {
+      error = err
+    }
+    assertEquals(error instanceof Error, true)
+  }
+})
+
 Deno.test(`RRegex::isMatch`, () => {
   const regex = new RRegex('a')
   assertEquals(regex.isMatch('abc'), true)
@@ -15,6 +455,31 @@ Deno.test(`RRegex::isMatch`, () => {
   assertEquals(re.isMatch(text), true);
 })
 
+Deno.test(`RRegex::isMatch literal patterns`, () => {
+  const texts = ['', 'foo', 'a foo b', 'FOO', 'δfooδ', 'fo']
+  for (const pattern of ['foo', '', 'δ', '(?i)foo', 'f(o)o', 'foo|bar', 'fo\\.']) {
+    const regex = new RRegex(pattern)
+    for (const text of texts) {
+      assertEquals(regex.isMatch(text), regex.find(text) !== undefined)
+    }
+  }
+  assertEquals(new RRegex('foo').isMatch('a foo b'), true)
+  assertEquals(new RRegex('(?i)foo').isMatch('FOO'), true)
+})
+
+Deno.test(`RRegex::isMatch required literals`, () => {
+  const texts = ['', 'foo1bar', 'foo bar', 'foobar', 'bar1foo', 'xfoo12barx', 'FOO1BAR', 'δfoo9barδ']
+  const patterns = ['foo\\d+bar', '(foo)\\d*(?:bar)+', '(?i)foo\\d+bar', 'foo\\d+bar|x', '(?:foo)?\\d', 'f(o)o\\d{2}bar', '^foo', 'δfoo']
+  for (const pattern of patterns) {
+    const regex = new RRegex(pattern)
+    for (const text of texts) {
+      assertEquals(regex.isMatch(text), regex.find(text) !== undefined, `${pattern} on ${text}`)
+    }
+  }
+  assertEquals(new RRegex('foo\\d+bar').isMatch('foo1bar'), true)
+  assertEquals(new RRegex('foo\\d+bar').isMatch('foo1baz'), false)
+})
+
 Deno.test(`RRegex::isMatchAt`, () => {
   const regex = new RRegex('a')
   assertEquals(regex.isMatchAt('ab', 0), true)
@@ -34,6 +499,88 @@ Deno.test(`RRegex::isMatchAt`, () => {
   assertEquals(re.isMatchAt(text, 5), false)
 })
 
+Deno.test(`RRegex::isMatchWithin`, () => {
+  const regex = new RRegex('\\d+')
+  assertEquals(regex.isMatchWithin('ab 12 cd', 0, 3), false)
+  assertEquals(regex.isMatchWithin('ab 12 cd', 0, 4), true)
+  assertEquals(regex.isMatchWithin('ab 12 cd', 5, 8), false)
+  assertEquals(regex.isMatchWithin('ab 12 cd', 4, 3), false)
+  assertEquals(regex.isMatchWithin('ab 12 cd', 0, 9), false)
+  assertEquals(regex.isMatchWithin('ä1', 1, 3), false)
+  assertEquals(new RRegex('1$').isMatchWithin('ab 12 cd', 0, 4), true)
+  assertEquals(new RRegex('\\b2').isMatchWithin('ab 12 cd', 4, 5), false)
+})
+
+Deno.test(`RRegex::isMatchBudgeted`, () => {
+  const re = new RRegex('\\d+')
+  assertEquals(re.isMatchBudgeted('abc 123', 4), false)
+  assertEquals(re.isMatchBudgeted('abc 123', 5), true)
+  assertEquals(re.isMatchBudgeted('abc 123', 100), true)
+  assertEquals(re.isMatchBudgeted('abc 123', 0), false)
+  assertEquals(new RRegex('é$').isMatchBudgeted('aé b', 2), false)
+  assertEquals(new RRegex('é$').isMatchBudgeted('aé b', 3), true)
+})
+
+Deno.test(`RRegex::isFullMatch`, () => {
+  const regex = new RRegex('\\d+')
+  assertEquals(regex.isFullMatch('123'), true)
+  assertEquals(regex.isFullMatch('12abc'), false)
+  assertEquals(regex.isFullMatch('abc12'), false)
+  assertEquals(regex.isFullMatch(''), false)
+  assertEquals(new RRegex('a|ab').isFullMatch('ab'), true)
+  assertEquals(new RRegex('(?x) a b # comment').isFullMatch('ab'), true)
+  assertEquals(new RRegex('(?m)^a$').isFullMatch('a\na'), false)
+})
+
+Deno.test(`RRegex::startsWithMatch`, () => {
+  const regex = new RRegex('\\d+')
+  assertEquals(regex.startsWithMatch('12abc'), true)
+  assertEquals(regex.startsWithMatch('abc12'), false)
+  assertEquals(regex.startsWithMatch(''), false)
+  assertEquals(new RRegex('b|ab').startsWithMatch('ab'), true)
+  assertEquals(new RRegex('x*').startsWithMatch('abc'), true)
+})
+
+Deno.test(`RRegex::endsWithMatch`, () => {
+  const regex = new RRegex('\\d+')
+  assertEquals(regex.endsWithMatch('abc12'), true)
+  assertEquals(regex.endsWithMatch('12abc'), false)
+  assertEquals(new RRegex('aa').endsWithMatch('aaa'), true)
+  assertEquals(new RRegex('a$').endsWithMatch('ba'), true)
+  assertEquals(new RRegex('(?m)a$').endsWithMatch('a\nb'), false)
+  assertEquals(new RRegex('(?x) a # comment').endsWithMatch('ba'), true)
+  assertEquals(new RRegex('x*').endsWithMatch('abc'), true)
+})
+
+Deno.test(`RRegex::isMatchMany`, () => {
+  const regex = new RRegex('^\\d+$')
+  assertEquals(regex.isMatchMany(['123', 'abc', '4', '']), [true, false, true, false])
+  assertEquals(regex.isMatchMany([]), [])
+})
+
+Deno.test(`RRegex::isMatchChunks`, () => {
+  const re = new RRegex('hello world')
+  assertEquals(re.isMatchChunks(['say hel', 'lo wor', 'ld!']), true)
+  assertEquals(re.isMatchChunks(['hello', 'world']), false)
+  assertEquals(re.isMatchChunks([]), false)
+  assertEquals(new RRegex('^b').isMatchChunks(['a', 'b']), false)
+  assertEquals(new RRegex('^$').isMatchChunks([]), true)
+})
+
+Deno.test(`RRegex::isNoMatch`, () => {
+  const re = new RRegex('\\d')
+  assertEquals(re.isNoMatch('abc'), true)
+  assertEquals(re.isNoMatch('a1c'), false)
+  assertEquals(new RRegex('foo').isNoMatch('a foo'), false)
+})
+
+Deno.test(`RRegex::filterNonMatching`, () => {
+  const re = new RRegex('\\d')
+  assertEquals(re.filterNonMatching(['a', 'b1', 'c', '2']), ['a', 'c'])
+  assertEquals(re.filterNonMatching([]), [])
+  assertEquals(new RRegex('').filterNonMatching(['a', '']), [])
+})
+
 Deno.test(`RRegex::find`, () => {
   const regex = new RRegex('a')
   assertEquals(regex.find('abc'), { value: 'a', start: 0, end: 1 })
@@ -48,6 +595,13 @@ Deno.test(`RRegex::find`, () => {
   })
 })
 
+Deno.test(`RRegex::findOrNull`, () => {
+  const re = new RRegex('\\d+')
+  assertEquals(re.findOrNull('ab 12'), { start: 3, end: 5, value: '12' })
+  assertEquals(re.findOrNull('ab'), null)
+  assertEquals(new RRegexBuilder('a+?').longestMatch(true).build().findOrNull('xaa'), { start: 1, end: 3, value: 'aa' })
+})
+
 Deno.test(`RRegex::findAt`, () => {
   const regex = new RRegex('a')
   assertEquals(regex.find('abc'), { value: 'a', start: 0, end: 1 })
@@ -66,6 +620,119 @@ Deno.test(`RRegex::findAt`, () => {
   assertEquals(re.findAt(text, 5), undefined)
 })
 
+Deno.test(`RRegex::findInRange`, () => {
+  const re = new RRegex('\\d+')
+  assertEquals(re.findInRange('1 22 333', 2, 6), { start: 2, end: 4, value: '22' })
+  assertEquals(re.findInRange('1 22 333', 6, 7), { start: 6, end: 7, value: '3' })
+  assertEquals(re.findInRange('1 22 333', 1, 2), undefined)
+  assertEquals(re.findInRange('1 22 333', 6, 5), undefined)
+  assertEquals(re.findInRange('1 22 333', 0, 100), undefined)
+  assertEquals(re.findInRange('é1', 1, 3), undefined)
+  assertEquals(re.findInRange('é1', 2, 3), { start: 2, end: 3, value: '1' })
+  assertEquals(new RRegex('\\bb').findInRange('ab b', 1, 4), { start: 3, end: 4, value: 'b' })
+  assertEquals(new RRegex('b$').findInRange('abc', 0, 2), { start: 1, end: 2, value: 'b' })
+})
+
+Deno.test(`RRegex::findWithPosition`, () => {
+  const digits = new RRegex('\\d+')
+  const text = 'ab\n😀 12'
+  const m = digits.findWithPosition(text)
+  assertEquals(m, {
+    start: { offset: 8, line: 1, column: 3 },
+    end: { offset: 10, line: 1, column: 5 },
+    value: '12',
+  })
+  assertEquals(text.split('\n')[m.start.line].slice(m.start.column, m.end.column), '12')
+  assertEquals(digits.findWithPosition('none'), undefined)
+  assertEquals(new RRegex('b\\nc').findWithPosition('ab\ncd'), {
+    start: { offset: 1, line: 0, column: 1 },
+    end: { offset: 4, line: 1, column: 1 },
+    value: 'b\nc',
+  })
+  const longest = new RRegexBuilder('a|ab').longestMatch(true).build()
+  assertEquals(longest.findWithPosition('x\nab').end, { offset: 4, line: 1, column: 2 })
+})
+
+Deno.test(`RRegex::findWithContext`, () => {
+  const fox = new RRegex('fox')
+  assertEquals(fox.findWithContext('the quick brown fox jumps', 6), { start: 16, end: 19, value: 'fox', before: 'brown ', after: ' jumps' })
+  assertEquals(fox.findWithContext('fox', 3), { start: 0, end: 3, value: 'fox', before: '', after: '' })
+  assertEquals(fox.findWithContext('ab fox cd', 0), { start: 3, end: 6, value: 'fox', before: '', after: '' })
+  assertEquals(fox.findWithContext('😀éfoxδ😀x', 2), { start: 6, end: 9, value: 'fox', before: '😀é', after: 'δ😀' })
+  assertEquals(fox.findWithContext('none', 2), undefined)
+  const longest = new RRegexBuilder('a|ab').longestMatch(true).build()
+  assertEquals(longest.findWithContext('xabx', 1), { start: 1, end: 3, value: 'ab', before: 'x', after: 'x' })
+})
+
+Deno.test(`RRegex::findAllWithContext`, () => {
+  const digit = new RRegex('\\d')
+  assertEquals(digit.findAllWithContext('a1b2c', 1), [
+    { start: 1, end: 2, value: '1', before: 'a', after: 'b' },
+    { start: 3, end: 4, value: '2', before: 'b', after: 'c' },
+  ])
+  assertEquals(digit.findAllWithContext('12', 5).map(({ before, after }) => [before, after]), [['', '2'], ['1', '']])
+  assertEquals(digit.findAllWithContext('δ1😀', 1).map(({ before, after }) => [before, after]), [['δ', '😀']])
+  assertEquals(digit.findAllWithContext('none', 1), [])
+  const longest = new RRegexBuilder('a|ab').longestMatch(true).build()
+  assertEquals(longest.findAllWithContext('ab ab', 1).map((m) => m.before + m.value + m.after), ['ab ', ' ab'])
+})
+
+Deno.test(`RRegex::findUtf16`, () => {
+  const digits = new RRegex('\\d+')
+  const text = '😀 12'
+  const m = digits.findUtf16(text)
+  assertEquals(m, { start: 3, end: 5, value: '12' })
+  assertEquals(text.slice(m.start, m.end), '12')
+  assertEquals(new RRegex('δ😀').findUtf16('aδ😀b'), { start: 1, end: 4, value: 'δ😀' })
+  assertEquals(digits.findUtf16('none'), undefined)
+  assertEquals(new RRegexBuilder('a|ab').longestMatch(true).build().findUtf16('😀ab'), { start: 2, end: 4, value: 'ab' })
+})
+
+Deno.test(`RRegex::findAllUtf16`, () => {
+  const text = '😀1 δ22 😀😀3'
+  const matches = new RRegex('\\d+').findAllUtf16(text)
+  assertEquals(matches, [
+    { start: 2, end: 3, value: '1' },
+    { start: 5, end: 7, value: '22' },
+    { start: 12, end: 13, value: '3' },
+  ])
+  for (const m of matches) {
+    assertEquals(text.slice(m.start, m.end), m.value)
+  }
+  assertEquals(new RRegex('\\d').findAllUtf16('none'), [])
+  const longest = new RRegexBuilder('a|ab').longestMatch(true).build()
+  assertEquals(longest.findAllUtf16('😀ab ab').map((m) => [m.start, m.end]), [[2, 4], [5, 7]])
+})
+
+Deno.test(`RRegex::findWithin`, () => {
+  const regex = new RRegex('\\d+')
+  assertEquals(regex.findWithin('ab 12 cd', 3), undefined)
+  assertEquals(regex.findWithin('ab 12 cd', 4), { start: 3, end: 4, value: '1' })
+  assertEquals(regex.findWithin('ab 12 cd', 100), { start: 3, end: 5, value: '12' })
+  assertEquals(new RRegex('δ$').findWithin('δδ', 3), { start: 0, end: 2, value: 'δ' })
+  assertEquals(new RRegex('.').findWithin('δ', 1), undefined)
+})
+
+Deno.test(`RRegex::matchExactlyAt`, () => {
+  const regex = new RRegex('\\d+')
+  assertEquals(regex.matchExactlyAt('ab12', 2), { start: 2, end: 4, value: '12' })
+  assertEquals(regex.matchExactlyAt('ab12', 3), { start: 3, end: 4, value: '2' })
+  assertEquals(regex.matchExactlyAt('ab12', 1), undefined)
+  assertEquals(regex.matchExactlyAt('ab12', 10), undefined)
+  assertEquals(new RRegex('\\bx').matchExactlyAt('ax', 1), undefined)
+  assertEquals(new RRegex('').matchExactlyAt('ab', 2), { start: 2, end: 2, value: '' })
+})
+
+Deno.test(`RRegex::findFirstMany`, () => {
+  const regex = new RRegex('\\d+')
+  assertEquals(regex.findFirstMany(['id 42', 'none', '7 8']), [
+    { value: '42', start: 3, end: 5 },
+    undefined,
+    { value: '7', start: 0, end: 1 },
+  ])
+  assertEquals(regex.findFirstMany([]), [])
+})
+
 Deno.test(`RRegex::findAll`, () => {
   const regex = new RRegex('a')
   assertEquals(regex.findAll('abcabcabc'), [
@@ -101,6 +768,247 @@ Deno.test(`RRegex::findAll`, () => {
   ])
 })
 
+Deno.test(`RRegex::findAllCharLen`, () => {
+  const re = new RRegex('\\S+')
+  assertEquals(re.findAllCharLen('héllo 👋🏽 ab'), [
+    { start: 0, end: 6, value: 'héllo', charLen: 5 },
+    { start: 7, end: 15, value: '👋🏽', charLen: 2 },
+    { start: 16, end: 18, value: 'ab', charLen: 2 },
+  ])
+  assertEquals(re.findAllCharLen('   '), [])
+  const longest = new RRegexBuilder('é|éa').longestMatch(true).build()
+  assertEquals(longest.findAllCharLen('éa'), [{ start: 0, end: 3, value: 'éa', charLen: 2 }])
+})
+
+Deno.test(`RRegex::findAllMerged`, () => {
+  const re = new RRegex('\\d')
+  assertEquals(re.findAllMerged('12 3  4', 0), [{ start: 0, end: 2 }, { start: 3, end: 4 }, { start: 6, end: 7 }])
+  assertEquals(re.findAllMerged('12 3  4', 1), [{ start: 0, end: 4 }, { start: 6, end: 7 }])
+  assertEquals(re.findAllMerged('12 3  4', 2), [{ start: 0, end: 7 }])
+  assertEquals(re.findAllMerged('abc', 5), [])
+  assertEquals(new RRegex('a|').findAllMerged('aba', 0), [{ start: 0, end: 1 }, { start: 2, end: 3 }])
+})
+
+Deno.test(`RRegex::findLast`, () => {
+  const regex = new RRegex('\\d')
+  assertEquals(regex.findLast('a1 b2 c3'), { value: '3', start: 7, end: 8 })
+  assertEquals(regex.findLast('abc'), undefined)
+})
+
+Deno.test(`RRegex::findAfter`, () => {
+  const re = new RRegex('\\d+')
+  assertEquals(re.findAfter('1 22 333', 0), { start: 0, end: 1, value: '1' })
+  assertEquals(re.findAfter('1 22 333', 2), { start: 2, end: 4, value: '22' })
+  assertEquals(re.findAfter('1 22 333', 3), { start: 3, end: 4, value: '2' })
+  assertEquals(re.findAfter('1 22 333', 8), undefined)
+  assertEquals(re.findAfter('1 22 333', 9), undefined)
+  assertEquals(re.findAfter('é1', 1), undefined)
+  assertEquals(re.findAfter('é1', 2), { start: 2, end: 3, value: '1' })
+})
+
+Deno.test(`RRegex::findBefore`, () => {
+  const re = new RRegex('\\d+')
+  assertEquals(re.findBefore('1 22 333', 6), { start: 2, end: 4, value: '22' })
+  assertEquals(re.findBefore('1 22 333', 8), { start: 5, end: 8, value: '333' })
+  assertEquals(re.findBefore('1 22 333', 1), { start: 0, end: 1, value: '1' })
+  assertEquals(re.findBefore('1 22 333', 0), undefined)
+  assertEquals(re.findBefore('1 22 333', 100), undefined)
+  assertEquals(re.findBefore('1é', 2), undefined)
+  const longest = new RRegexBuilder('a|ab').longestMatch(true).build()
+  assertEquals(longest.findBefore('ab ab', 4), { start: 0, end: 2, value: 'ab' })
+})
+
+Deno.test(`RRegex::findLastIndex`, () => {
+  const regex = new RRegex('/')
+  assertEquals(regex.findLastIndex('a/b/c'), { start: 3, end: 4 })
+  assertEquals(regex.findLastIndex('abc'), undefined)
+  assertEquals(new RRegex('a*').findLastIndex('baa'), { start: 1, end: 3 })
+})
+
+Deno.test(`RRegex::findNth`, () => {
+  const regex = new RRegex('\\w')
+  assertEquals(regex.findNth('a b c d', 2), { value: 'c', start: 4, end: 5 })
+  assertEquals(regex.findNth('a b c d', 0), regex.find('a b c d'))
+  assertEquals(regex.findNth('a b c d', 3).value, 'd')
+  assertEquals(regex.findNth('a b c d', 4), undefined)
+  assertEquals(new RRegex('a*').findNth('baab', 2), new RRegex('a*').findAll('baab')[2])
+})
+
+Deno.test(`RRegex::findAllLimited`, () => {
+  const regex = new RRegex('\\d')
+  assertEquals(regex.findAllLimited('1 2 3 4', 2), [
+    { value: '1', start: 0, end: 1 },
+    { value: '2', start: 2, end: 3 },
+  ])
+  assertEquals(regex.findAllLimited('1 2 3 4', 0), regex.findAll('1 2 3 4'))
+  assertEquals(regex.findAllLimited('1 2', 10).length, 2)
+  assertEquals(regex.findAllLimited('abc', 1), [])
+  assertEquals(new RRegex('').findAllLimited('x'.repeat(100000), 3).length, 3)
+
+  const longest = new RRegexBuilder('a|ab').longestMatch(true).build()
+  assertEquals(longest.findAllLimited('ab ab ab', 2).map((m) => m.value), ['ab', 'ab'])
+})
+
+Deno.test(`RRegex::findAllPaged`, () => {
+  const digit = new RRegex('\\d')
+  assertEquals(digit.findAllPaged('1 2 3', 2), [[{ start: 0, end: 1, value: '1' }, { start: 2, end: 3, value: '2' }], [{ start: 4, end: 5, value: '3' }]])
+  assertEquals(digit.findAllPaged('1 2 3 4', 2).map((page) => page.length), [2, 2])
+  assertEquals(digit.findAllPaged('none', 3), [])
+  assertEquals(digit.findAllPaged('123', 10).flat(), digit.findAll('123'))
+  assertEquals(new RRegexBuilder('a+?').longestMatch(true).build().findAllPaged('aa a', 1), [[{ start: 0, end: 2, value: 'aa' }], [{ start: 3, end: 4, value: 'a' }]])
+  try {
+    digit.findAllPaged('1', 0)
+    assertEquals(true, false)
+  } catch (error) {
+    assertEquals(error instanceof Error, true)
+  }
+})
+
+Deno.test(`RRegex::forEachMatch`, () => {
+  const regex = new RRegex('\\d')
+  const matches = []
+  assertEquals(regex.forEachMatch('1 2 3', (m) => { matches.push(m) }), undefined)
+  assertEquals(matches, [
+    { start: 0, end: 1, value: '1' },
+    { start: 2, end: 3, value: '2' },
+    { start: 4, end: 5, value: '3' },
+  ])
+
+  const values = []
+  regex.forEachMatch('1 2 3', (m) => {
+    values.push(m.value)
+    return m.value !== '2'
+  })
+  assertEquals(values, ['1', '2'])
+
+  let calls = 0
+  regex.forEachMatch('1 2 3', () => { calls++; return 0 })
+  assertEquals(calls, 3)
+
+  try {
+    regex.forEachMatch('1', () => { throw new Error('boom') })
+    assertEquals(true, false)
+  } catch (error) {
+    assertEquals(error.message, 'boom')
+  }
+})
+
+Deno.test(`RRegex::findAllWhere`, () => {
+  const re = new RRegex('\\d+')
+  assertEquals(re.findAllWhere('1 22 333', (m) => m.value.length > 1), [{ start: 2, end: 4, value: '22' }, { start: 5, end: 8, value: '333' }])
+  const seen = []
+  assertEquals(re.findAllWhere('1 2 3', (m) => { seen.push(m.start); return m.start % 4 === 0 ? 1 : '' }), [{ start: 0, end: 1, value: '1' }, { start: 4, end: 5, value: '3' }])
+  assertEquals(seen, [0, 2, 4])
+  assertEquals(re.findAllWhere('none', () => true), [])
+  try {
+    re.findAllWhere('1', () => { throw new Error('nope') })
+    assertEquals(true, false)
+  } catch (error) {
+    assertEquals(error instanceof Error, true)
+  }
+})
+
+Deno.test(`RRegex::findAllOverlapping`, () => {
+  const regex = new RRegex('\\d\\d')
+  assertEquals(regex.findAllOverlapping('1234'), [
+    { value: '12', start: 0, end: 2 },
+    { value: '23', start: 1, end: 3 },
+    { value: '34', start: 2, end: 4 },
+  ])
+  assertEquals(regex.findAllOverlapping('a1'), [])
+  assertEquals(new RRegex('ä.').findAllOverlapping('ääa').map((m) => m.value), ['ää', 'äa'])
+  assertEquals(new RRegex('x*').findAllOverlapping('ab').map((m) => m.start), [0, 1, 2])
+})
+
+Deno.test(`RRegex::matchIndices`, () => {
+  const regex = new RRegex('\\d+')
+  assertEquals(regex.matchIndices('a1 b22 c333'), new Int32Array([1, 2, 4, 6, 8, 11]))
+  assertEquals(new RRegex('ö+').matchIndices('äöö'), new Int32Array([2, 6]))
+  assertEquals(regex.matchIndices('abc'), new Int32Array([]))
+})
+
+Deno.test(`RRegex::startPositions`, () => {
+  const positions = new RRegex('a').startPositions('a.a.a')
+  assertEquals(positions instanceof Uint32Array, true)
+  assertEquals(Array.from(positions), [0, 2, 4])
+  assertEquals(Array.from(new RRegex('\\d+').startPositions('δ12 3')), [2, 5])
+  assertEquals(new RRegex('x').startPositions('abc').length, 0)
+})
+
+Deno.test(`RRegex::findAllColumnar`, () => {
+  const columns = new RRegex('\\d+').findAllColumnar('a1 b22 é333')
+  assertEquals(columns.starts instanceof Uint32Array, true)
+  assertEquals(Array.from(columns.starts), [1, 4, 9])
+  assertEquals(Array.from(columns.ends), [2, 6, 12])
+  assertEquals(columns.values, ['1', '22', '333'])
+  const empty = new RRegex('x').findAllColumnar('abc')
+  assertEquals([empty.starts.length, empty.ends.length, empty.values], [0, 0, []])
+  const longest = new RRegexBuilder('a|ab').longestMatch(true).build()
+  assertEquals(longest.findAllColumnar('ab ab').values, ['ab', 'ab'])
+})
+
+Deno.test(`RRegex::matchCountByLine`, () => {
+  const digits = new RRegex('\\d')
+  assertEquals(Array.from(digits.matchCountByLine('1 2\nx\n3')), [2, 0, 1])
+  assertEquals(Array.from(digits.matchCountByLine('')), [0])
+  assertEquals(Array.from(digits.matchCountByLine('1\n')), [1, 0])
+  assertEquals(Array.from(new RRegex('a\\nb').matchCountByLine('a\nb a\nb')), [1, 1, 0])
+})
+
+Deno.test(`RRegex::matchingLines`, () => {
+  const digits = new RRegex('\\d')
+  assertEquals(digits.matchingLines('a1\nb\nc2 3'), [
+    { line: 0, start: 0, text: 'a1' },
+    { line: 2, start: 5, text: 'c2 3' },
+  ])
+  assertEquals(digits.matchingLines('none\nhere'), [])
+  assertEquals(digits.matchingLines('δ\n1\n'), [{ line: 1, start: 3, text: '1' }])
+  assertEquals(new RRegex('a\\nb').matchingLines('x\na\nb'), [{ line: 1, start: 2, text: 'a' }])
+  assertEquals(new RRegex('$').matchingLines('a\n'), [{ line: 1, start: 2, text: '' }])
+})
+
+Deno.test(`RRegex::findAllWithLine`, () => {
+  const re = new RRegex('\\d+')
+  assertEquals(re.findAllWithLine('a\nb 42 c\nd 7'), [
+    { start: 4, end: 6, value: '42', lineStart: 2, lineEnd: 8, lineText: 'b 42 c' },
+    { start: 11, end: 12, value: '7', lineStart: 9, lineEnd: 12, lineText: 'd 7' },
+  ])
+  assertEquals(new RRegex('b\\nc').findAllWithLine('ab\ncd\ne'), [
+    { start: 1, end: 4, value: 'b\nc', lineStart: 0, lineEnd: 5, lineText: 'ab\ncd' },
+  ])
+  assertEquals(new RRegex('b\\n').findAllWithLine('ab\ncd'), [
+    { start: 1, end: 3, value: 'b\n', lineStart: 0, lineEnd: 2, lineText: 'ab' },
+  ])
+  assertEquals(new RRegex('(?m)^$').findAllWithLine('a\n\nb'), [
+    { start: 2, end: 2, value: '', lineStart: 2, lineEnd: 2, lineText: '' },
+  ])
+  assertEquals(re.findAllWithLine('none'), [])
+})
+
+Deno.test(`RRegex::matchOccurrences`, () => {
+  const occurrences = new RRegex('\\w').matchOccurrences('a b a c a')
+  assertEquals(occurrences instanceof Map, true)
+  assertEquals([...occurrences], [['a', 3], ['b', 1], ['c', 1]])
+  assertEquals(new RRegex('\\d+').matchOccurrences('no digits').size, 0)
+  assertEquals(Object.fromEntries(new RRegex('δ|x*').matchOccurrences('δxδ')), { 'δ': 2, 'x': 1 })
+})
+
+Deno.test(`RRegex::matchStats`, () => {
+  const digits = new RRegex('\\d+')
+  assertEquals(digits.matchStats('12 ab 34'), { count: 2, totalMatchedBytes: 4, coverage: 0.5 })
+  assertEquals(digits.matchStats('abc'), { count: 0, totalMatchedBytes: 0, coverage: 0 })
+  assertEquals(digits.matchStats(''), { count: 0, totalMatchedBytes: 0, coverage: 0 })
+  assertEquals(new RRegex('δ').matchStats('δa'), { count: 1, totalMatchedBytes: 2, coverage: 2 / 3 })
+  assertEquals(new RRegex('').matchStats('ab'), { count: 3, totalMatchedBytes: 0, coverage: 0 })
+})
+
+Deno.test(`RRegex::distinctMatches`, () => {
+  assertEquals(new RRegex('\\w').distinctMatches('a b a c'), ['a', 'b', 'c'])
+  assertEquals(new RRegex('\\d+').distinctMatches('3 12 3 12 δ 7'), ['3', '12', '7'])
+  assertEquals(new RRegex('\\d').distinctMatches('none'), [])
+  assertEquals(new RRegex('(?i)a').distinctMatches('aAa'), ['a', 'A'])
+})
+
 Deno.test(`RRegex::capturesLength`, () => {
   const re1 = new RRegex("(?P<y>\\d{4})-(?P<m>\\d{2})-(?P<d>\\d{2})")
   assertEquals(re1.capturesLength(), 4)
@@ -128,6 +1036,28 @@ Deno.test(`RRegex::captureNames`, () => {
   ])
 })
 
+Deno.test(`RRegex::captureNameToIndex`, () => {
+  const regex = new RRegex('(?P<y>\\d{4})-(?P<m>\\d{2})-(\\d{2})')
+  assertEquals(regex.captureNameToIndex('y'), 1)
+  assertEquals(regex.captureNameToIndex('m'), 2)
+  assertEquals(regex.captureNameToIndex('d'), undefined)
+  assertEquals(regex.captureNameToIndex(''), undefined)
+})
+
+Deno.test(`RRegex::captureNameIndex`, () => {
+  assertEquals(new RRegex('(?P<y>\\d{4})-(\\d{2})-(?P<d>\\d{2})').captureNameIndex(), { y: 1, d: 3 })
+  assertEquals(new RRegex('(a)(b)').captureNameIndex(), {})
+})
+
+Deno.test(`RRegex::hasGroupName`, () => {
+  const regex = new RRegex('(?P<y>\\d{4})-(?P<m>\\d{2})-(\\d{2})')
+  assertEquals(regex.hasGroupName('y'), true)
+  assertEquals(regex.hasGroupName('m'), true)
+  assertEquals(regex.hasGroupName('d'), false)
+  assertEquals(regex.hasGroupName(''), false)
+  assertEquals(new RRegex('a').hasGroupName('a'), false)
+})
+
 Deno.test(`RRegex::captures`, () => {
   const regex = new RRegex('(?P<y>\\d{4})-(?P<m>\\d{2})-(?P<d>\\d{2})')
   assertEquals(regex.captures(''), undefined)
@@ -146,6 +1076,87 @@ Deno.test(`RRegex::captures`, () => {
   })
 })
 
+Deno.test(`RRegex::capturesAt`, () => {
+  const regex = new RRegex('(?P<word>\\w+)')
+  assertEquals(regex.capturesAt('foo bar', 3), {
+    get: [
+      { value: 'bar', start: 4, end: 7 },
+      { value: 'bar', start: 4, end: 7 },
+    ],
+    name: {
+      word: { value: 'bar', start: 4, end: 7 },
+    },
+  })
+  assertEquals(regex.capturesAt('foo bar', 7), undefined)
+  assertEquals(regex.capturesAt('foo bar', 8), undefined)
+  assertEquals(new RRegex('\\Afoo').capturesAt('foofoo', 3), undefined)
+})
+
+Deno.test(`RRegex::capturesIndices`, () => {
+  const regex = new RRegex('(\\w+)@(\\w+)?')
+  const indices = regex.capturesIndices('to joe@')
+  assertEquals(indices instanceof Int32Array, true)
+  assertEquals(Array.from(indices), [3, 7, 3, 6, -1, -1])
+  assertEquals(Array.from(regex.capturesIndices('a@b')), [0, 3, 0, 1, 2, 3])
+  assertEquals(Array.from(regex.capturesIndices('no match')), [])
+  assertEquals(Array.from(new RRegex('δ(x)').capturesIndices('aδx')), [1, 4, 3, 4])
+})
+
+Deno.test(`RRegex::expand`, () => {
+  const regex = new RRegex('(?P<last>[^,\\s]+),\\s+(?P<first>\\S+)')
+  assertEquals(regex.expand('Springsteen, Bruce', '$first $last'), 'Bruce Springsteen')
+  assertEquals(regex.expand('Springsteen, Bruce', '${last}! $2 $$'), 'Springsteen! Bruce $')
+  assertEquals(regex.expand('Springsteen', '$first'), undefined)
+})
+
+Deno.test(`RRegex::capturesRead`, () => {
+  const regex = new RRegex('(\\d{4})-(\\d{2})(-\\d{2})?')
+  const locations = regex.createLocations()
+  assertEquals(locations.length, 4)
+
+  assertEquals(regex.capturesRead('on 2010-03', locations), { value: '2010-03', start: 3, end: 10 })
+  assertEquals(locations.start(0), 3)
+  assertEquals(locations.end(0), 10)
+  assertEquals(locations.start(2), 8)
+  assertEquals(locations.end(2), 10)
+  assertEquals(locations.start(3), undefined)
+  assertEquals(locations.start(4), undefined)
+
+  assertEquals(regex.capturesRead('1999-12-31', locations), { value: '1999-12-31', start: 0, end: 10 })
+  assertEquals(locations.start(3), 7)
+  assertEquals(regex.capturesRead('none', locations), undefined)
+})
+
+Deno.test(`RRegex::capturesInto`, () => {
+  const re = new RRegex('(?<key>\\w+)=(?<value>\\w+)?')
+  const target = { other: true }
+  assertEquals(re.capturesInto('a=1', target), true)
+  assertEquals(target, { 0: 'a=1', 1: 'a', 2: '1', key: 'a', value: '1', other: true })
+  assertEquals(re.capturesInto('b=', target), true)
+  assertEquals(target, { 0: 'b=', 1: 'b', 2: undefined, key: 'b', value: undefined, other: true })
+  assertEquals(re.capturesInto('nothing', target), false)
+  assertEquals(target.key, 'b')
+})
+
+Deno.test(`RRegex::capturesTyped`, () => {
+  const re = new RRegex('(?<name>\\w+)=(?<age>[\\w.]+),(?<admin>\\w+)(x)?')
+  assertEquals(re.capturesTyped('ann=42.5,true', ['string', 'string', 'number', 'boolean']), {
+    get: ['ann=42.5,true', 'ann', 42.5, true, undefined],
+    name: { name: 'ann', age: 42.5, admin: true },
+  })
+  assertEquals(re.capturesTyped('bob=old,no', [, , 'number', 'boolean']).get, ['bob=old,no', 'bob', null, null, undefined])
+  assertEquals(re.capturesTyped('bob=1,false', []).get, ['bob=1,false', 'bob', '1', 'false', undefined])
+  assertEquals(re.capturesTyped('nothing', ['number']), undefined)
+  for (const kinds of [['date'], [1]]) {
+    try {
+      re.capturesTyped('nothing', kinds)
+      assertEquals(true, false)
+    } catch (error) {
+      assertEquals(error instanceof Error, true)
+    }
+  }
+})
+
 Deno.test(`RRegex::capturesAll`, () => {
   const regex = new RRegex('(?P<y>\\d{4})-(?P<m>\\d{2})-(?P<d>\\d{2})')
   assertEquals(regex.capturesAll(''), [])
@@ -204,6 +1215,205 @@ Deno.test(`RRegex::capturesAll`, () => {
       }
     },
   ])
+
+  const [entry] = new RRegex('(\\d+)-(\\d+)').capturesAll('2020-01')
+  assertEquals(entry.get[0], { value: '2020-01', start: 0, end: 7 })
+  assertEquals(entry.get[1], { value: '2020', start: 0, end: 4 })
+  assertEquals(entry.get[2], { value: '01', start: 5, end: 7 })
+})
+
+Deno.test(`RRegex::matchAllCompat`, () => {
+  const [m] = new RRegex('(?P<word>\\w+)').matchAllCompat('😀 hi')
+  assertEquals([...m], ['hi', 'hi'])
+  assertEquals(m.index, 3)
+  assertEquals(m.input, '😀 hi')
+  assertEquals(m.groups.word, 'hi')
+
+  const cases = [['(?<y>\\d{4})-(?<m>\\d{2})?(x)?', '😀2024-05 é1999-'], ['(a)|(b)', 'ab'], ['\\d', 'a1b2'], ['(?<n>z)', 'none']]
+  for (const [pattern, text] of cases) {
+    const native = [...text.matchAll(new RegExp(pattern, 'gu'))]
+    assertEquals(new RRegex(pattern).matchAllCompat(text), native)
+  }
+})
+
+Deno.test(`RRegex::capturesTree`, () => {
+  const regex = new RRegex('((?P<y>\\d+)-(\\d+))(x)?|(z)')
+  assertEquals(regex.capturesTree('on 2020-01'), {
+    index: 0,
+    name: undefined,
+    start: 3,
+    end: 10,
+    children: [
+      {
+        index: 1,
+        name: undefined,
+        start: 3,
+        end: 10,
+        children: [
+          { index: 2, name: 'y', start: 3, end: 7, children: [] },
+          { index: 3, name: undefined, start: 8, end: 10, children: [] },
+        ],
+      },
+    ],
+  })
+  assertEquals(regex.capturesTree('z').children, [{ index: 5, name: undefined, start: 0, end: 1, children: [] }])
+  assertEquals(regex.capturesTree('none'), undefined)
+  assertEquals(new RRegex('(a(b))+').capturesTree('abab').children[0], {
+    index: 1,
+    name: undefined,
+    start: 2,
+    end: 4,
+    children: [{ index: 2, name: undefined, start: 3, end: 4, children: [] }],
+  })
+})
+
+Deno.test(`RRegex::findAllGroup`, () => {
+  const regex = new RRegex('(\\w+)=(\\d+)?')
+  assertEquals(regex.findAllGroup('a=1 b= c=3', 2), [
+    { start: 2, end: 3, value: '1' },
+    { start: 9, end: 10, value: '3' },
+  ])
+  assertEquals(regex.findAllGroup('a=1 b= c=3', 0).map((m) => m.value), ['a=1', 'b=', 'c=3'])
+  assertEquals(regex.findAllGroup('', 1), [])
+
+  try {
+    regex.findAllGroup('a=1', 3)
+    assertEquals(true, false)
+  } catch (error) {
+    assertEquals(error instanceof Error, true)
+  }
+})
+
+Deno.test(`RRegex::findAllNamedGroup`, () => {
+  const regex = new RRegex('id=(?P<id>\\d+)(?P<suffix>[a-z])?')
+  assertEquals(regex.findAllNamedGroup('id=1, id=22x', 'id'), [
+    { start: 3, end: 4, value: '1' },
+    { start: 9, end: 11, value: '22' },
+  ])
+  assertEquals(regex.findAllNamedGroup('id=1, id=22x', 'suffix'), [{ start: 11, end: 12, value: 'x' }])
+
+  for (const name of ['missing', '']) {
+    try {
+      regex.findAllNamedGroup('id=1', name)
+      assertEquals(true, false)
+    } catch (error) {
+      assertEquals(error instanceof Error, true)
+    }
+  }
+})
+
+Deno.test(`RRegex::capturesIter`, () => {
+  const regex = new RRegex('(\\w)(?<digit>\\d)?')
+  const iter = regex.capturesIter('a1 b c3')
+  assertEquals(iter.next(), regex.capturesAt('a1 b c3', 0))
+  assertEquals(iter.next().get[1].value, 'b')
+  const last = iter.next()
+  assertEquals(last.name.digit, { value: '3', start: 6, end: 7 })
+  assertEquals(iter.next(), undefined)
+  assertEquals(iter.next(), undefined)
+
+  const collect = (regex, text) => {
+    const iter = regex.capturesIter(text)
+    const all = []
+    for (let caps = iter.next(); caps !== undefined; caps = iter.next()) {
+      all.push(caps)
+    }
+    return all
+  }
+  for (const [pattern, text] of [['a*', 'baab'], ['', 'δx'], ['\\b', 'ab cd'], ['(a)|b', 'abab']]) {
+    const regex = new RRegex(pattern)
+    assertEquals(collect(regex, text), regex.capturesAll(text))
+  }
+
+  const early = new RRegex('x').capturesIter('x'.repeat(100000))
+  assertEquals(early.next().get[0].start, 0)
+
+  const owned = new RRegex('\\d')
+  const rest = owned.capturesIter('1 2')
+  owned.free()
+  assertEquals(rest.next().get[0].value, '1')
+})
+
+Deno.test(`RRegex::scanner`, () => {
+  const scanner = new RRegex('\\d+').scanner(8)
+  scanner.push('a 12 b 3')
+  scanner.push('4 c 1234567 d')
+  assertEquals(scanner.drain(), [
+    { start: 2, end: 4, value: '12' },
+    { start: 7, end: 9, value: '34' },
+  ])
+  assertEquals(scanner.drain(), [])
+  assertEquals(scanner.finish(), [{ start: 12, end: 19, value: '1234567' }])
+  assertEquals(scanner.finish(), [])
+
+  const text = 'δ1 a22 \nb333 x\n4444 δδ 5 end\n'
+  for (const pattern of ['\\d+', '\\b\\w', '^\\w+', '\\w*$', 'x*']) {
+    const regex = new RRegex(`(?m)${pattern}`)
+    for (const size of [1, 2, 3, 7]) {
+      const scanner = regex.scanner(8)
+      const matches = []
+      const chars = [...text]
+      for (let i = 0; i < chars.length; i += size) {
+        scanner.push(chars.slice(i, i + size).join(''))
+        matches.push(...scanner.drain())
+      }
+      matches.push(...scanner.finish())
+      assertEquals(matches, regex.findAll(text), `${pattern} in chunks of ${size}`)
+    }
+  }
+})
+
+Deno.test(`RRegex::stateful`, () => {
+  const re = new RRegex('\\d+').stateful()
+  const values = []
+  let m
+  while ((m = re.exec('1 22 333')) !== null) {
+    values.push([m[0], m.index, re.lastIndex])
+  }
+  assertEquals(values, [['1', 0, 1], ['22', 2, 4], ['333', 5, 8]])
+  assertEquals(re.lastIndex, 0)
+
+  for (const [pattern, text] of [['(?<w>[a-z])(\\d)?', '😀a1 b é2'], ['\\p{L}+', 'δx 😀 yz']]) {
+    const ours = new RRegex(pattern).stateful()
+    const native = new RegExp(pattern, 'gu')
+    for (let i = 0; i < 10; i++) {
+      assertEquals(ours.exec(text), native.exec(text))
+      assertEquals(ours.lastIndex, native.lastIndex)
+    }
+  }
+
+  const empty = new RRegex('').stateful()
+  const indices = []
+  while ((m = empty.exec('a😀')) !== null) {
+    indices.push([m.index, empty.lastIndex])
+  }
+  assertEquals(indices, [[0, 1], [1, 3], [3, 4]])
+
+  const word = new RRegex('\\w+').stateful()
+  word.lastIndex = 3
+  assertEquals(word.exec('ab cd')[0], 'cd')
+  word.lastIndex = 50
+  assertEquals(word.exec('ab cd'), null)
+  assertEquals(word.lastIndex, 0)
+})
+
+Deno.test(`RRegex::matchNavigator`, () => {
+  const nav = new RRegex('\\d+').matchNavigator('a 1 bb 22 c 333')
+  assertEquals(nav.count(), 3)
+  assertEquals(nav.at(0), { start: 2, end: 3 })
+  assertEquals(nav.at(2), { start: 12, end: 15 })
+  assertEquals(nav.at(3), undefined)
+  assertEquals(nav.next(0), { start: 2, end: 3 })
+  assertEquals(nav.next(3), { start: 7, end: 9 })
+  assertEquals(nav.next(7), { start: 7, end: 9 })
+  assertEquals(nav.next(13), undefined)
+  assertEquals(nav.prev(2), undefined)
+  assertEquals(nav.prev(3), { start: 2, end: 3 })
+  assertEquals(nav.prev(11), { start: 7, end: 9 })
+  assertEquals(nav.prev(100), { start: 12, end: 15 })
+  nav.free()
+  const empty = new RRegex('x').matchNavigator('abc')
+  assertEquals([empty.count(), empty.at(0), empty.next(0), empty.prev(3)], [0, undefined, undefined, undefined])
 })
 
 Deno.test(`RRegex::replace`, () => {
@@ -229,10 +1439,256 @@ Deno.test(`RRegex::replace`, () => {
   }
 })
 
+Deno.test(`RRegex::replaceStrict`, () => {
+  const regex = new RRegex('(?P<first>\\w+)\\s+(\\w+)(x)?')
+  assertEquals(regex.replaceStrict('deep fried', '$2 $first'), 'fried deep')
+  assertEquals(regex.replaceStrict('deep fried', '${first}_$2'), 'deep_fried')
+  assertEquals(regex.replaceStrict('deep fried', '[$3]'), '[]')
+  assertEquals(regex.replaceStrict('deep fried', '$$10 $ ${ $0'), '$10 $ ${ deep fried')
+
+  for (const rep of ['$10', '$1a', '$first_', '${second}', '$4']) {
+    try {
+      regex.replaceStrict('deep fried', rep)
+      assertEquals(rep, false)
+    } catch (error) {
+      assertEquals(error instanceof Error, true)
+    }
+  }
+})
+
 Deno.test(`RRegex::replaceAll`, () => {
   const regex = new RRegex('a')
   assertEquals(regex.replaceAll('abcabcabc', 'z'), 'zbczbczbc')
   assertEquals(regex.replaceAll('defdefdef', 'z'), 'defdefdef')
+  assertEquals(new RRegex('\\b').replaceAll('ab cd', '|'), '|ab| |cd|')
+  assertEquals(new RRegex('\\b').replaceAll('', '|'), '')
+  assertEquals(new RRegex('\\b').replaceAll(' ', '|'), ' ')
+  assertEquals(new RRegex('(\\b)').replaceAll('ab cd', '[$1]'), '[]ab[] []cd[]')
+  assertEquals(new RRegex('a*').replaceAll('baaac', '-'), '-b-c-')
+  assertEquals(new RRegex('').replaceAll('aé', '|'), '|a|é|')
+  assertEquals(new RRegex('x*|b').replaceAll('abc', '|'), '|a|b|c|')
+})
+
+Deno.test(`RRegex::replaceAllWith`, () => {
+  const re = new RRegex('(?<last>\\w+), (?<first>\\w+)')
+  const rep = compileReplacement('$first $last')
+  assertEquals(rep.template, '$first $last')
+  assertEquals(re.replaceAllWith('Doe, Jane', rep), 'Jane Doe')
+  assertEquals(re.replaceAllWith('Roe, Richard', rep), 'Richard Roe')
+  assertEquals(re.replaceAllWith('nothing', rep), 'nothing')
+  for (const template of ['$2-$1', '${2}x', '$$1', '$', '${1', '$1a', '${}', '$9', 'plain', '']) {
+    const other = new RRegex('(\\d)-(\\d)')
+    assertEquals(other.replaceAllWith('1-2 3-4', compileReplacement(template)), other.replaceAll('1-2 3-4', template))
+  }
+})
+
+Deno.test(`RRegex::replaceAllJs`, () => {
+  const date = new RRegex('(?P<y>\\d{4})-(\\d{2})')
+  assertEquals(date.replaceAllJs('2024-05', '$2/$<y> ($&, $$1)'), '05/2024 (2024-05, $1)')
+  assertEquals(date.replaceAllJs('a 2024-05 b', "[$`|$']"), 'a [a | b] b')
+  assertEquals(date.replaceAllJs('2024-05', '$0 $3 $<m> $<y $'), '$0 $3  $<y $')
+  assertEquals(date.replaceAllJs('2024-05', '$10 $01 $02'), '20240 2024 05')
+
+  const unnamed = new RRegex('(a)(b)?')
+  assertEquals(unnamed.replaceAllJs('a ab', '[$1$2$<x>]'), '[a$<x>] [ab$<x>]')
+
+  const many = new RRegex('(a)(b)(c)(d)(e)(f)(g)(h)(i)(j)')
+  assertEquals(many.replaceAllJs('abcdefghij', '$10-$11'), 'j-a1')
+
+  for (const [pattern, text, rep] of [['(?<y>\\d{4})-(\\d{2})', '2024-05 1999-12', "$2/$<y> $$ $& $` $' $0 $3 $<z> $<y"], ['(a)|(b)', 'ab', '[$1|$2|$3]'], ['', 'ab', '-$&-']]) {
+    assertEquals(new RRegex(pattern).replaceAllJs(text, rep), text.replaceAll(new RegExp(pattern, 'g'), rep))
+  }
+})
+
+Deno.test(`RRegex::replaceAllChunked`, () => {
+  const re = new RRegex('\\d+')
+  const chunks = []
+  re.replaceAllChunked('a1b22', '<$0>', (chunk) => chunks.push(chunk))
+  assertEquals(chunks, ['a', '<1>', 'b', '<22>'])
+
+  for (const [text, rep] of [['1 22 333 x', '[$0]'], ['12', ''], ['', 'x'], ['no digits', '#']]) {
+    const pieces = []
+    re.replaceAllChunked(text, rep, (chunk) => pieces.push(chunk))
+    assertEquals(pieces.join(''), re.replaceAll(text, rep))
+    assertEquals(pieces.includes(''), false)
+  }
+
+  try {
+    re.replaceAllChunked('1', '#', () => { throw new Error('stop') })
+    assertEquals(true, false)
+  } catch (error) {
+    assertEquals(error instanceof Error, true)
+  }
+})
+
+Deno.test(`RRegex::replaceAllMaybe`, () => {
+  const re = new RRegex('\\d')
+  assertEquals(re.replaceAllMaybe('a1', '#'), { changed: true, result: 'a#' })
+  assertEquals(re.replaceAllMaybe('ab', '#'), { changed: false })
+  assertEquals('result' in re.replaceAllMaybe('ab', '#'), false)
+  assertEquals(re.replaceAllMaybe('1', '$0'), { changed: true, result: '1' })
+  assertEquals(re.replaceAllMaybe('a1b2', ''), { changed: true, result: 'ab' })
+})
+
+Deno.test(`RRegex::replaceAllCounted`, () => {
+  const regex = new RRegex('(?P<area>\\d{3})-\\d{4}')
+  const text = '555-1234 or 666-9876'
+  assertEquals(regex.replaceAllCounted(text, '[redacted]'), { result: '[redacted] or [redacted]', count: 2 })
+  assertEquals(regex.replaceAllCounted(text, '$area-XXXX $$').result, regex.replaceAll(text, '$area-XXXX $$'))
+  assertEquals(regex.replaceAllCounted(text, 'x').count, regex.findAll(text).length)
+  assertEquals(regex.replaceAllCounted('none', 'x'), { result: 'none', count: 0 })
+})
+
+Deno.test(`RRegex::replaceAllWithSpans`, () => {
+  const re = new RRegex('(\\d+)')
+  assertEquals(re.replaceAllWithSpans('a1 b22', '<$1>'), {
+    result: 'a<1> b<22>',
+    spans: [
+      { originalStart: 1, originalEnd: 2, outputStart: 1, outputEnd: 4 },
+      { originalStart: 4, originalEnd: 6, outputStart: 6, outputEnd: 10 },
+    ],
+  })
+  assertEquals(re.replaceAllWithSpans('é1é', ''), {
+    result: 'éé',
+    spans: [{ originalStart: 2, originalEnd: 3, outputStart: 2, outputEnd: 2 }],
+  })
+  assertEquals(re.replaceAllWithSpans('none', 'x'), { result: 'none', spans: [] })
+})
+
+Deno.test(`RRegex::previewReplaceAll`, () => {
+  const re = new RRegex('colou?r')
+  assertEquals(re.previewReplaceAll('color, colour', 'colour'), [{ start: 0, end: 5, before: 'color', after: 'colour' }])
+  assertEquals(re.previewReplaceAll('colour', 'colour'), [])
+  assertEquals(new RRegex('(\\w)(\\w)').previewReplaceAll('ab aa é', '$2$1'), [{ start: 0, end: 2, before: 'ab', after: 'ba' }])
+  assertEquals(new RRegex('\\b').previewReplaceAll('ab', '|'), [
+    { start: 0, end: 0, before: '', after: '|' },
+    { start: 2, end: 2, before: '', after: '|' },
+  ])
+})
+
+Deno.test(`RRegex::replaceAllFnChunked`, () => {
+  const regex = new RRegex('(?<n>\\d+)')
+  const double = (caps) => String(Number(caps.name.n.value) * 2)
+  const counts = []
+  assertEquals(regex.replaceAllFnChunked('1 2 3 4 5', double, 2, (count) => counts.push(count)), '2 4 6 8 10')
+  assertEquals(counts, [2, 4])
+  assertEquals(regex.replaceAllFnChunked('1 2 3', double, 0, () => { throw new Error('unexpected') }), '2 4 6')
+  assertEquals(regex.replaceAllFnChunked('1 2 3', double, 1), '2 4 6')
+  assertEquals(regex.replaceAllFnChunked('no digits', double, 1), 'no digits')
+  assertEquals(new RRegex('').replaceAllFnChunked('ab', () => '-', 1), '-a-b-')
+
+  for (const [f, progress] of [[() => 1, undefined], [() => { throw new Error('f') }, undefined], [double, () => { throw new Error('progress') }]]) {
+    let error
+    try {
+      regex.replaceAllFnChunked('1 2', f, 1, progress)
+    } catch (err) {
+      error = err
+    }
+    assertEquals(error instanceof Error, true)
+  }
+})
+
+Deno.test(`RRegex::replaceAllFnNamed`, () => {
+  const regex = new RRegex('(?P<key>\\w+)=(?P<value>\\d+)?')
+  const seen = []
+  const result = regex.replaceAllFnNamed('a=1 b=', (groups) => {
+    seen.push(groups)
+    return `${groups.value}:${groups.key}`
+  })
+  assertEquals(result, '1:a undefined:b')
+  assertEquals(seen[0], { 0: 'a=1', 1: 'a', 2: '1', key: 'a', value: '1' })
+  assertEquals(Object.keys(seen[1]).includes('value'), true)
+  assertEquals(seen[1].value, undefined)
+
+  try {
+    regex.replaceAllFnNamed('a=1', () => 1)
+    assertEquals(true, false)
+  } catch (error) {
+    assertEquals(error instanceof Error, true)
+  }
+})
+
+Deno.test(`RRegex::replaceAllFnPos`, () => {
+  const re = new RRegex('\\d+')
+  assertEquals(re.replaceAllFnPos('1 22 3', (caps, start, end) => `${caps.get[0].value}@${start}..${end}`), '1@0..1 22@2..4 3@5..6')
+  assertEquals(new RRegex('b').replaceAllFnPos('☃b', (_, start) => String(start)), '☃3')
+  assertEquals(re.replaceAllFnPos('none', () => 'x'), 'none')
+  try {
+    re.replaceAllFnPos('1', () => 1)
+    assertEquals(true, false)
+  } catch (error) {
+    assertEquals(error instanceof Error, true)
+  }
+})
+
+Deno.test(`RRegex::replaceAllIf`, () => {
+  const re = new RRegex('(?<n>\\d+)')
+  const seen = []
+  const result = re.replaceAllIf('1 20 300', (caps) => {
+    seen.push(caps.name.n.value)
+    return caps.get[1].value.length > 1
+  }, '<$n>')
+  assertEquals(result, '1 <20> <300>')
+  assertEquals(seen, ['1', '20', '300'])
+  assertEquals(re.replaceAllIf('1 2', () => 0, 'x'), '1 2')
+  assertEquals(re.replaceAllIf('1 2', () => 'yes', '$$'), '$ $')
+  try {
+    re.replaceAllIf('1', () => { throw new Error('boom') }, 'x')
+    assertEquals(true, false)
+  } catch (error) {
+    assertEquals(error.message, 'boom')
+  }
+})
+
+Deno.test(`RRegex::replaceMap`, () => {
+  assertEquals(RRegex.replaceMap('a cat category', { cat: 'dog', category: 'group' }), 'a dog group')
+  assertEquals(RRegex.replaceMap('a.b a*b', { 'a.b': 'x', 'a*b': '$1' }), 'x $1')
+  assertEquals(RRegex.replaceMap('über ü', { 'ü': 'ue', 'über': 'over' }), 'over ue')
+  assertEquals(RRegex.replaceMap('abc', {}), 'abc')
+  assertEquals(RRegex.replaceMap('abc', { '': 'x' }), 'abc')
+
+  let error
+  try {
+    RRegex.replaceMap('abc', { a: 1 })
+  } catch (err) {
+    error = err
+  }
+  assertEquals(error instanceof Error, true)
+})
+
+Deno.test(`RRegex::replaceByRanges`, () => {
+  const regex = new RRegex('\\d+')
+  const text = 'a1 b22 ä333'
+  assertEquals(regex.replaceByRanges(text, regex.matchIndices(text), ['one', 'two', 'three']), 'aone btwo äthree')
+  assertEquals(regex.replaceByRanges(text, [], []), text)
+  assertEquals(regex.replaceByRanges('abc', [0, 0, 3, 3], ['<', '>']), '<abc>')
+
+  const invalid = [
+    [[0, 1, 2], ['x']],
+    [[0, 1], ['x', 'y']],
+    [[2, 3, 0, 1], ['x', 'y']],
+    [[1, 0], ['x']],
+    [[8, 9], ['x']],
+    [[0, 100], ['x']],
+  ]
+
+  for (const [ranges, replacements] of invalid) {
+    let error
+    try {
+      regex.replaceByRanges(text, ranges, replacements)
+    } catch (err) {
+      error = err
+    }
+    assertEquals(error instanceof Error, true)
+  }
+})
+
+Deno.test(`RRegex::highlight`, () => {
+  const regex = new RRegex('\\d+')
+  assertEquals(regex.highlight('a1 b22', '<mark>', '</mark>'), 'a<mark>1</mark> b<mark>22</mark>')
+  assertEquals(regex.highlight('a1', '$0${1}', '\\$$'), 'a$0${1}1\\$$')
+  assertEquals(regex.highlight('abc', '[', ']'), 'abc')
+  assertEquals(new RRegex('x*').highlight('ab', '[', ']'), '[]a[]b[]')
 })
 
 Deno.test(`RRegex::replacen`, () => {
@@ -241,6 +1697,60 @@ Deno.test(`RRegex::replacen`, () => {
   assertEquals(regex.replacen('defdefdef', 2, 'z'), 'defdefdef')
 })
 
+Deno.test(`RRegex::replaceRange`, () => {
+  const digits = new RRegex('\\d')
+  assertEquals(digits.replaceRange('1 2 3 4 5', 1, 3, '#'), '1 # # 4 5')
+  assertEquals(digits.replaceRange('1 2 3 4 5', 0, 100, '#'), '# # # # #')
+  assertEquals(digits.replaceRange('1 2 3 4 5', 3, 3, '#'), '1 2 3 4 5')
+  assertEquals(digits.replaceRange('1 2 3 4 5', 4, 2, '#'), '1 2 3 4 5')
+  assertEquals(digits.replaceRange('1 2 3', 7, 9, '#'), '1 2 3')
+  assertEquals(new RRegex('(\\w)(\\d)').replaceRange('a1 b2 c3', 2, 3, '$2$1'), 'a1 b2 3c')
+})
+
+Deno.test(`RRegex::trimStartMatches`, () => {
+  const regex = new RRegex('[-_]+')
+  assertEquals(regex.trimStartMatches('--a-b--'), 'a-b--')
+  assertEquals(regex.trimStartMatches('a-b--'), 'a-b--')
+  assertEquals(regex.trimStartMatches('---'), '')
+})
+
+Deno.test(`RRegex::trimEndMatches`, () => {
+  const regex = new RRegex('[-_]+')
+  assertEquals(regex.trimEndMatches('--a-b--'), '--a-b')
+  assertEquals(regex.trimEndMatches('--a-b'), '--a-b')
+  assertEquals(regex.trimEndMatches('---'), '')
+})
+
+Deno.test(`RRegex::trimMatches`, () => {
+  const regex = new RRegex('[-_]+')
+  assertEquals(regex.trimMatches('--a-b__'), 'a-b')
+  assertEquals(regex.trimMatches('a-b'), 'a-b')
+  assertEquals(regex.trimMatches('---'), '')
+  assertEquals(regex.trimMatches(''), '')
+})
+
+Deno.test(`RRegex::partition`, () => {
+  const regex = new RRegex('\\d+')
+  assertEquals(regex.partition('a1b22'), [
+    { start: 0, end: 1, value: 'a', matched: false },
+    { start: 1, end: 2, value: '1', matched: true },
+    { start: 2, end: 3, value: 'b', matched: false },
+    { start: 3, end: 5, value: '22', matched: true },
+    { start: 5, end: 5, value: '', matched: false },
+  ])
+  assertEquals(regex.partition(''), [
+    { start: 0, end: 0, value: '', matched: false },
+  ])
+  assertEquals(new RRegex('x*').partition('ab'), [
+    { start: 0, end: 0, value: '', matched: true },
+    { start: 0, end: 1, value: 'a', matched: false },
+    { start: 1, end: 1, value: '', matched: true },
+    { start: 1, end: 2, value: 'b', matched: false },
+    { start: 2, end: 2, value: '', matched: true },
+    { start: 2, end: 2, value: '', matched: false },
+  ])
+})
+
 Deno.test(`RRegex::split`, () => {
   const regex = new RRegex('a')
   assertEquals(regex.split('abcabcabc'), ['', 'bc', 'bc', 'bc'])
@@ -251,6 +1761,78 @@ Deno.test(`RRegex::split`, () => {
   assertEquals(fields, ['a', 'b', 'c', 'd', 'e']);
 })
 
+Deno.test(`RRegex::splitMap`, () => {
+  const re = new RRegex(',')
+  assertEquals(re.splitMap(' 1, 2 ,3', (segment) => Number(segment.trim())), [1, 2, 3])
+  assertEquals(re.splitMap('a,,b', (segment, index) => [segment, index]), [['a', 0], ['', 1], ['b', 2]])
+  assertEquals(re.splitMap('a,b', (segment) => segment), re.split('a,b'))
+  try {
+    re.splitMap('a,b', () => { throw new Error('boom') })
+    assertEquals(true, false)
+  } catch (error) {
+    assertEquals(error.message, 'boom')
+  }
+})
+
+Deno.test(`RRegex::splitInclusive`, () => {
+  const regex = new RRegex('[.!?]')
+  assertEquals(regex.splitInclusive('Hi! Bye.'), ['Hi!', ' Bye.'])
+  assertEquals(regex.splitInclusive('Hi! Bye'), ['Hi!', ' Bye'])
+  assertEquals(regex.splitInclusive('a..b'), ['a.', '.', 'b'])
+  assertEquals(regex.splitInclusive('no punctuation'), ['no punctuation'])
+  assertEquals(regex.splitInclusive(''), [])
+  assertEquals(new RRegex('x*').splitInclusive('δx'), ['δx'])
+})
+
+Deno.test(`RRegex::splitOnLast`, () => {
+  const regex = new RRegex('/')
+  assertEquals(regex.splitOnLast('a/b/c'), ['a/b', 'c'])
+  assertEquals(regex.splitOnLast('a/'), ['a', ''])
+  assertEquals(regex.splitOnLast('abc'), ['abc'])
+  assertEquals(new RRegex('\\s+').splitOnLast('δ  x   ü'), ['δ  x', 'ü'])
+})
+
+Deno.test(`RRegex::splitIndices`, () => {
+  const regex = new RRegex('[ \\t]+')
+  assertEquals(regex.splitIndices('a b \tc'), [
+    { start: 0, end: 1, value: 'a' },
+    { start: 2, end: 3, value: 'b' },
+    { start: 5, end: 6, value: 'c' },
+  ])
+  assertEquals(regex.splitIndices(' a ').map((m) => m.value), regex.split(' a '))
+  assertEquals(new RRegex('x*').splitIndices('ab').map((m) => m.value), new RRegex('x*').split('ab'))
+})
+
+Deno.test(`RRegex::splitOffsets`, () => {
+  const re = new RRegex(',')
+  assertEquals(Array.from(re.splitOffsets('a,,b')), [0, 1, 2, 2, 3, 4])
+  assertEquals(Array.from(re.splitOffsets(',a,')), [0, 0, 1, 2, 3, 3])
+  assertEquals(Array.from(re.splitOffsets('abc')), [0, 3])
+  assertEquals(Array.from(re.splitOffsets('')), [0, 0])
+  const text = 'x, y,z'
+  const offsets = new RRegex(',\\s*').splitOffsets(text)
+  const pieces = []
+  for (let i = 0; i < offsets.length; i += 2) {
+    pieces.push(text.slice(offsets[i], offsets[i + 1]))
+  }
+  assertEquals(pieces, new RRegex(',\\s*').split(text))
+})
+
+Deno.test(`RRegex::splitFields`, () => {
+  const comma = new RRegex(',')
+  assertEquals(comma.splitFields('a,,b'), [
+    { value: 'a', start: 0, end: 1, empty: false },
+    { value: '', start: 2, end: 2, empty: true },
+    { value: 'b', start: 3, end: 4, empty: false },
+  ])
+  assertEquals(comma.splitFields(', ,'), [
+    { value: '', start: 0, end: 0, empty: true },
+    { value: ' ', start: 1, end: 2, empty: false },
+    { value: '', start: 3, end: 3, empty: true },
+  ])
+  assertEquals(comma.splitFields(''), [{ value: '', start: 0, end: 0, empty: true }])
+})
+
 Deno.test(`RRegex::splitn`, () => {
   const regex = new RRegex(',')
   assertEquals(regex.splitn('a,b,c', 0), [])
@@ -269,6 +1851,30 @@ Deno.test(`RRegex::splitn`, () => {
   assertEquals(fields, ['Hey', 'How', 'are you?'])
 })
 
+Deno.test(`RRegex::splitnInfo`, () => {
+  const regex = new RRegex(',')
+  assertEquals(regex.splitnInfo('a,b,c', 2), { parts: ['a', 'b,c'], truncated: true })
+  assertEquals(regex.splitnInfo('a,b,c', 3), { parts: ['a', 'b', 'c'], truncated: false })
+  assertEquals(regex.splitnInfo('a,b,c', 10), { parts: ['a', 'b', 'c'], truncated: false })
+  assertEquals(regex.splitnInfo('a,b,c', 1), { parts: ['a,b,c'], truncated: true })
+  assertEquals(regex.splitnInfo('abc', 1), { parts: ['abc'], truncated: false })
+  assertEquals(regex.splitnInfo('a,b', 0), { parts: [], truncated: true })
+  assertEquals(regex.splitnInfo('', 0), { parts: [], truncated: false })
+  assertEquals(regex.splitnInfo('a,', 2), { parts: ['a', ''], truncated: false })
+})
+
+Deno.test(`RRegex::splitBounded`, () => {
+  const comma = new RRegex(',')
+  assertEquals(comma.splitBounded('a,b,c', 2), { parts: ['a', 'b'], truncated: true })
+  assertEquals(comma.splitBounded('a,b,c', 3), { parts: ['a', 'b', 'c'], truncated: false })
+  assertEquals(comma.splitBounded('a,b,c', 10), { parts: ['a', 'b', 'c'], truncated: false })
+  assertEquals(comma.splitBounded('a,b,', 2), { parts: ['a', 'b'], truncated: true })
+  assertEquals(comma.splitBounded(',,,,,,', 1), { parts: [''], truncated: true })
+  assertEquals(comma.splitBounded('a', 0), { parts: [], truncated: true })
+  assertEquals(comma.splitBounded('', 0), { parts: [], truncated: false })
+  assertEquals(comma.splitBounded('', 1), { parts: [''], truncated: false })
+})
+
 Deno.test(`RRegex::shortestMatch`, () => {
   const regex = new RRegex('a')
   assertEquals(regex.shortestMatch('abcabcabc'), 1)
@@ -297,6 +1903,13 @@ Deno.test(`RRegex::shortestMatchAt`, () => {
   assertEquals(regex.shortestMatchAt('defdefdef', 2), undefined)
 })
 
+Deno.test(`RRegex::shortestMatchRange`, () => {
+  const regex = new RRegex('a+')
+  assertEquals(regex.shortestMatchRange('xaaaa'), { start: 1, end: 2 })
+  assertEquals(regex.shortestMatchRange('xyz'), undefined)
+  assertEquals(new RRegex('x*').shortestMatchRange('ab'), { start: 0, end: 0 })
+})
+
 Deno.test('regex_syntax::hir::Empty', () => {
   const regex = new RRegex('')
   assertEquals(regex.syntax(), {
@@ -1007,6 +2620,436 @@ Deno.test('regex_syntax::hir::Capture', () => {
 })
 
 
+Deno.test('RRegex::syntax', () => {
+  const regex = new RRegex('(?i)a|b')
+  const first = regex.syntax()
+  assertEquals(regex.syntax(), first)
+  assertEquals(regex.syntax(), new RRegex('(?i)a|b').syntax())
+})
+
+Deno.test(`RRegex::syntaxDebug`, () => {
+  const debug = new RRegex('a|[0-9]').syntaxDebug()
+  assertEquals(typeof debug, 'string')
+  assertEquals(debug.includes('\n'), true)
+  assertEquals(debug.includes('Alternation'), true)
+  assertEquals(debug.includes("'0'..='9'"), true)
+})
+
+Deno.test('RRegex::syntaxWithOptions', () => {
+  const regex = new RRegex('a')
+  assertEquals(regex.syntaxWithOptions({}), regex.syntax())
+  assertEquals(regex.syntaxWithOptions({ caseInsensitive: true }), {
+    "@name": "regex_syntax::hir::Hir",
+    "@type": "struct",
+    "kind": {
+      "@name": "regex_syntax::hir::HirKind",
+      "@type": "enum",
+      "@variant": "Class",
+      "@values": [
+        {
+          "@name": "regex_syntax::hir::Class",
+          "@type": "enum",
+          "@variant": "Unicode",
+          "@values": [
+            {
+              "@name": "regex_syntax::hir::ClassUnicode",
+              "@type": "struct",
+              "ranges": [
+                {
+                  "@name": "regex_syntax::hir::ClassUnicodeRange",
+                  "@type": "struct",
+                  "start": "A",
+                  "end": "A",
+                  "len": 1,
+                },
+                {
+                  "@name": "regex_syntax::hir::ClassUnicodeRange",
+                  "@type": "struct",
+                  "start": "a",
+                  "end": "a",
+                  "len": 1,
+                },
+              ]
+            }
+          ]
+        }
+      ],
+    }
+  })
+
+  const bytes = new RRegex('[a-c]')
+  assertEquals(bytes.syntaxWithOptions({ unicode: false }).kind['@values'][0]['@variant'], 'Bytes')
+})
+
+Deno.test(`RRegex::ast`, () => {
+  assertEquals(new RRegex('a|b').ast(), {
+    "@type": "enum",
+    "@name": "regex_syntax::ast::Ast",
+    "@variant": "Alternation",
+    "@values": [
+      {
+        "@type": "struct",
+        "@name": "regex_syntax::ast::Alternation",
+        "span": {
+          "@type": "struct",
+          "@name": "regex_syntax::ast::Span",
+          "start": {
+            "@type": "struct",
+            "@name": "regex_syntax::ast::Position",
+            "offset": 0,
+            "line": 1,
+            "column": 1
+          },
+          "end": {
+            "@type": "struct",
+            "@name": "regex_syntax::ast::Position",
+            "offset": 3,
+            "line": 1,
+            "column": 4
+          }
+        },
+        "asts": [
+          {
+            "@type": "enum",
+            "@name": "regex_syntax::ast::Ast",
+            "@variant": "Literal",
+            "@values": [
+              {
+                "@type": "struct",
+                "@name": "regex_syntax::ast::Literal",
+                "span": {
+                  "@type": "struct",
+                  "@name": "regex_syntax::ast::Span",
+                  "start": {
+                    "@type": "struct",
+                    "@name": "regex_syntax::ast::Position",
+                    "offset": 0,
+                    "line": 1,
+                    "column": 1
+                  },
+                  "end": {
+                    "@type": "struct",
+                    "@name": "regex_syntax::ast::Position",
+                    "offset": 1,
+                    "line": 1,
+                    "column": 2
+                  }
+                },
+                "kind": {
+                  "@type": "enum",
+                  "@name": "regex_syntax::ast::LiteralKind",
+                  "@variant": "Verbatim"
+                },
+                "c": "a"
+              }
+            ]
+          },
+          {
+            "@type": "enum",
+            "@name": "regex_syntax::ast::Ast",
+            "@variant": "Literal",
+            "@values": [
+              {
+                "@type": "struct",
+                "@name": "regex_syntax::ast::Literal",
+                "span": {
+                  "@type": "struct",
+                  "@name": "regex_syntax::ast::Span",
+                  "start": {
+                    "@type": "struct",
+                    "@name": "regex_syntax::ast::Position",
+                    "offset": 2,
+                    "line": 1,
+                    "column": 3
+                  },
+                  "end": {
+                    "@type": "struct",
+                    "@name": "regex_syntax::ast::Position",
+                    "offset": 3,
+                    "line": 1,
+                    "column": 4
+                  }
+                },
+                "kind": {
+                  "@type": "enum",
+                  "@name": "regex_syntax::ast::LiteralKind",
+                  "@variant": "Verbatim"
+                },
+                "c": "b"
+              }
+            ]
+          }
+        ]
+      }
+    ]
+  })
+
+  const group = new RRegex('(?P<x>[^b-d])+').ast()
+  assertEquals(group['@variant'], 'Repetition')
+  assertEquals(group['@values'][0].greedy, true)
+  const kind = group['@values'][0].ast['@values'][0].kind
+  assertEquals(kind['@variant'], 'CaptureName')
+  assertEquals(kind.startsWithP, true)
+  assertEquals(kind.name.name, 'x')
+  assertEquals(kind.name.index, 1)
+})
+
+Deno.test(`RRegex::ast -> Flags`, () => {
+  const span = (start, end) => ({
+    '@type': 'struct',
+    '@name': 'regex_syntax::ast::Span',
+    start: { '@type': 'struct', '@name': 'regex_syntax::ast::Position', offset: start, line: 1, column: start + 1 },
+    end: { '@type': 'struct', '@name': 'regex_syntax::ast::Position', offset: end, line: 1, column: end + 1 },
+  })
+  const flag = (variant, start) => ({
+    '@type': 'struct',
+    '@name': 'regex_syntax::ast::FlagsItem',
+    span: span(start, start + 1),
+    kind: {
+      '@type': 'enum',
+      '@name': 'regex_syntax::ast::FlagsItemKind',
+      '@variant': 'Flag',
+      '@values': [{ '@type': 'enum', '@name': 'regex_syntax::ast::Flag', '@variant': variant }],
+    },
+  })
+
+  const setFlags = new RRegex('(?i-s)a').ast()['@values'][0].asts[0]['@values'][0]
+  assertEquals(setFlags.flags, {
+    '@type': 'struct',
+    '@name': 'regex_syntax::ast::Flags',
+    span: span(2, 5),
+    items: [
+      flag('CaseInsensitive', 2),
+      {
+        '@type': 'struct',
+        '@name': 'regex_syntax::ast::FlagsItem',
+        span: span(3, 4),
+        kind: { '@type': 'enum', '@name': 'regex_syntax::ast::FlagsItemKind', '@variant': 'Negation' },
+      },
+      flag('DotMatchesNewLine', 4),
+    ],
+  })
+
+  const group = new RRegex('(?mxURu:a)').ast()['@values'][0]
+  assertEquals(
+    group.kind['@values'][0].items.map((item) => item.kind['@values'][0]['@variant']),
+    ['MultiLine', 'IgnoreWhitespace', 'SwapGreed', 'CRLF', 'Unicode'],
+  )
+})
+
+Deno.test(`RRegex::astWithComments`, () => {
+  const position = (offset, line, column) => ({ '@type': 'struct', '@name': 'regex_syntax::ast::Position', offset, line, column })
+  const regex = new RRegex('(?x)\\d+ # digits\n[a-z] # letter')
+  const { ast, comments, ...rest } = regex.astWithComments()
+  assertEquals(rest, { '@type': 'struct', '@name': 'regex_syntax::ast::WithComments' })
+  assertEquals(ast, regex.ast())
+  assertEquals(comments, [
+    {
+      '@type': 'struct',
+      '@name': 'regex_syntax::ast::Comment',
+      span: { '@type': 'struct', '@name': 'regex_syntax::ast::Span', start: position(8, 1, 9), end: position(17, 2, 1) },
+      comment: ' digits',
+    },
+    {
+      '@type': 'struct',
+      '@name': 'regex_syntax::ast::Comment',
+      span: { '@type': 'struct', '@name': 'regex_syntax::ast::Span', start: position(23, 2, 7), end: position(31, 2, 15) },
+      comment: ' letter',
+    },
+  ])
+
+  assertEquals(new RRegex('a#b').astWithComments().comments, [])
+})
+
+Deno.test(`RRegex::isAnchoredStart`, () => {
+  assertEquals(new RRegex('^abc').isAnchoredStart(), true)
+  assertEquals(new RRegex('\\Afoo|\\Abar').isAnchoredStart(), true)
+  assertEquals(new RRegex('^foo|bar').isAnchoredStart(), false)
+  assertEquals(new RRegex('(?m)^abc').isAnchoredStart(), false)
+})
+
+Deno.test(`RRegex::isAnchoredEnd`, () => {
+  assertEquals(new RRegex('abc$').isAnchoredEnd(), true)
+  assertEquals(new RRegex('foo\\z|bar\\z').isAnchoredEnd(), true)
+  assertEquals(new RRegex('abc').isAnchoredEnd(), false)
+})
+
+Deno.test(`RRegex::isLiteral`, () => {
+  assertEquals(new RRegex('foo').isLiteral(), true)
+  assertEquals(new RRegex('fo+').isLiteral(), false)
+  assertEquals(new RRegex('foo|bar').isLiteral(), false)
+  assertEquals(new RRegex('^foo').isLiteral(), false)
+})
+
+Deno.test(`RRegex::minMatchLength`, () => {
+  assertEquals(new RRegex('a{3,}').minMatchLength(), 3)
+  assertEquals(new RRegex('a|bcd').minMatchLength(), 1)
+  assertEquals(new RRegex('δ|bcd').minMatchLength(), 2)
+  assertEquals(new RRegex('foo\\d+bar').minMatchLength(), 7)
+  assertEquals(new RRegex('a*').minMatchLength(), 0)
+  assertEquals(new RRegex('^$').minMatchLength(), 0)
+  assertEquals(new RRegex('[a&&b]').minMatchLength(), undefined)
+})
+
+Deno.test(`RRegex::usesLineAnchors`, () => {
+  assertEquals(new RRegex('(?m)^a').usesLineAnchors(), true)
+  assertEquals(new RRegex('(?mR)a$').usesLineAnchors(), true)
+  assertEquals(new RRegexBuilder('b|a$').multiLine(true).build().usesLineAnchors(), true)
+  assertEquals(new RRegex('^a$').usesLineAnchors(), false)
+  assertEquals(new RRegex('a\\b').usesLineAnchors(), false)
+})
+
+Deno.test(`RRegex::usesTextAnchors`, () => {
+  assertEquals(new RRegex('\\Aa').usesTextAnchors(), true)
+  assertEquals(new RRegex('a\\z').usesTextAnchors(), true)
+  assertEquals(new RRegex('^a').usesTextAnchors(), true)
+  assertEquals(new RRegex('(?m)^a$').usesTextAnchors(), false)
+  assertEquals(new RRegex('(?m)\\A^a').usesTextAnchors(), true)
+  assertEquals(new RRegex('abc').usesTextAnchors(), false)
+})
+
+Deno.test(`RRegex::unicodeProperties`, () => {
+  const re = new RRegex('\\p{Greek}+[\\pL\\P{sc:Latin}]\\p{Greek}')
+  assertEquals(re.unicodeProperties(), ['Greek', 'L', 'sc=Latin'])
+  assertEquals(new RRegex('[[\\p{N}--\\p{Nd}]&&\\p{Script=Arabic}]').unicodeProperties(), ['N', 'Nd', 'Script=Arabic'])
+  assertEquals(new RRegex('\\w+\\d').unicodeProperties(), [])
+})
+
+Deno.test(`RRegex::rootKind`, () => {
+  const cases = [['', 'Empty'], ['abc', 'Literal'], ['[a-z]', 'Class'], ['^', 'Look'], ['a+', 'Repetition'], ['(a)', 'Capture'], ['a+b', 'Concat'], ['ab|cd', 'Alternation']]
+  for (const [pattern, kind] of cases) {
+    const regex = new RRegex(pattern)
+    assertEquals(regex.rootKind(), kind)
+    assertEquals(regex.syntax().kind['@variant'], kind)
+  }
+})
+
+Deno.test(`RRegex::literalAlternatives`, () => {
+  assertEquals(new RRegex('foo|bar|baz').literalAlternatives(), ['foo', 'bar', 'baz'])
+  assertEquals(new RRegex('foo').literalAlternatives(), ['foo'])
+  assertEquals(new RRegex('(foo)|b(?:a)r|').literalAlternatives(), ['foo', 'bar', ''])
+  assertEquals(new RRegex('a|c|b|long').literalAlternatives(), ['a', 'c', 'b', 'long'])
+  assertEquals(new RRegex('a|c|b').literalAlternatives(), ['a', 'b', 'c'])
+  assertEquals(new RRegex('foo|ba[a-z]').literalAlternatives(), undefined)
+  assertEquals(new RRegex('[a-z]').literalAlternatives(), undefined)
+  assertEquals(new RRegex('foo+|bar').literalAlternatives(), undefined)
+  assertEquals(new RRegex('^foo|bar').literalAlternatives(), undefined)
+  assertEquals(new RRegex('(?:ab|cd)ef').literalAlternatives(), undefined)
+})
+
+Deno.test(`RRegex::canonicalize`, () => {
+  assertEquals(new RRegex('(?:[a-a])').canonicalize(), 'a')
+  assertEquals(new RRegex('a').canonicalize(), 'a')
+  assertEquals(new RRegex('a|b').canonicalize(), new RRegex('[ba]').canonicalize())
+  assertEquals(new RRegex('[c-ea-c]').canonicalize(), new RRegex('[a-e]').canonicalize())
+  assertEquals(new RRegex('(?i)a').canonicalize(), new RRegexBuilder('a').caseInsensitive(true).build().canonicalize())
+  assertEquals(new RRegex('a+').canonicalize() === new RRegex('aa*').canonicalize(), false)
+  for (const pattern of ['(?i)\\w+@\\d{2,}', '(?m)^(a|bc)?$', '(?<name>x)\\b']) {
+    const canonical = new RRegex(pattern).canonicalize()
+    assertEquals(new RRegex(canonical).canonicalize(), canonical)
+  }
+})
+
+Deno.test(`RRegex::complexity`, () => {
+  assertEquals(new RRegex('(ab|cd+)*x{3}').complexity(), { repetitions: 3, unboundedRepetitions: 2, alternations: 1, maxDepth: 2 })
+  assertEquals(new RRegex('abc').complexity(), { repetitions: 0, unboundedRepetitions: 0, alternations: 0, maxDepth: 0 })
+  assertEquals(new RRegex('((a+)+)+').complexity().maxDepth, 3)
+  assertEquals(new RRegex('a|b').complexity().alternations, 0)
+  assertEquals(new RRegex('(a{1000}){10}').complexity(), { repetitions: 2, unboundedRepetitions: 0, alternations: 0, maxDepth: 2 })
+})
+
+Deno.test(`RRegex::approximateSize`, () => {
+  const small = new RRegex('a').approximateSize()
+  assertEquals(Number.isInteger(small), true)
+  assertEquals(new RRegex('a').approximateSize(), small)
+  const large = new RRegex('\\w{20}')
+  assertEquals(large.approximateSize() > small, true)
+  assertEquals(large.approximateSize(), large.approximateSize())
+  assertEquals(new RRegex('(?-u:\\w{20})').approximateSize() < large.approximateSize(), true)
+})
+
+Deno.test(`RRegex::isAlwaysUtf8`, () => {
+  assertEquals(new RRegex('a.c').isAlwaysUtf8(), true)
+  assertEquals(new RRegex('(?-u:a)').isAlwaysUtf8(), true)
+})
+
+Deno.test(`hirToString`, () => {
+  assertEquals(hirToString(new RRegex('a|bc').syntax()), '(?:a|(?:bc))')
+  assertEquals(hirToString(new RRegex('^(?P<x>[a-c])*$').syntax()), '(?:\\A(?P<x>[a-c])*\\z)')
+  assertEquals(hirToString(new RRegex('').syntax()), '(?:)')
+
+  const regex = new RRegex(hirToString(new RRegex('(?i)k\\d{2,5}?').syntax()))
+  assertEquals(regex.find('xK12345').start, 1)
+  assertEquals(regex.find('xK12345').end, 4)
+})
+
+Deno.test(`classContains`, () => {
+  const unicode = (pattern) => new RRegex(pattern).syntax().kind['@values'][0]['@values'][0]
+  const letters = unicode('[a-cδ😀]')
+  assertEquals(classContains(letters, 'a'), true)
+  assertEquals(classContains(letters, 'c'), true)
+  assertEquals(classContains(letters, 'd'), false)
+  assertEquals(classContains(letters, 'δ'), true)
+  assertEquals(classContains(letters, '😀'), true)
+  assertEquals(classContains(unicode('[^a]'), 'a'), false)
+  assertEquals(classContains(unicode('[^a]'), '\u{10FFFF}'), true)
+
+  for (const ch of ['', 'ab', '\uD83D', 'a\u0301']) {
+    try {
+      classContains(letters, ch)
+      assertEquals(ch, false)
+    } catch (error) {
+      assertEquals(error instanceof Error, true)
+    }
+  }
+})
+
+Deno.test(`byteClassContains`, () => {
+  const hir = new RRegex('(?-u)[\\x00-\\x1F\\x7F]').syntax()
+  const [bytes] = hir.kind['@values'][0]['@values']
+  assertEquals(byteClassContains(bytes, 0x7f), true)
+  assertEquals(byteClassContains(bytes, 0), true)
+  assertEquals(byteClassContains(bytes, 0x20), false)
+  for (const byte of [-1, 256, 1.5]) {
+    try {
+      byteClassContains(bytes, byte)
+      assertEquals(true, false)
+    } catch (error) {
+      assertEquals(error instanceof Error, true)
+    }
+  }
+})
+
+Deno.test(`byteClassRanges`, () => {
+  const ranges = byteClassRanges({
+    '@type': 'struct',
+    '@name': 'regex_syntax::hir::ClassBytes',
+    ranges: [{ start: 20, end: 30 }, { start: 5, end: 9 }, { start: 0, end: 4 }, { start: 25, end: 40 }],
+  })
+  assertEquals(ranges.map(({ start, end, len }) => [start, end, len]), [[0, 9, 10], [20, 40, 21]])
+
+  const [bytes] = new RRegex('(?-u)[a-c]').syntax().kind['@values'][0]['@values']
+  assertEquals(byteClassRanges(bytes), bytes.ranges)
+})
+
+Deno.test(`classCaseFold`, () => {
+  assertEquals(hirToString(classCaseFold(new RRegex('[a-c]').syntax())), '[A-Ca-c]')
+  assertEquals(new RRegex(hirToString(classCaseFold(new RRegex('x(?:[k-l]|\\d)+').syntax()))).find('xL\u212A1k').value, 'xL\u212A1k')
+  assertEquals(classCaseFold(new RRegex('(?i)[a-c]').syntax()), new RRegex('(?i)[a-c]').syntax())
+  assertEquals(classCaseFold(new RRegex('ab').syntax()), new RRegex('ab').syntax())
+  assertEquals(new RRegex(hirToString(classCaseFold(new RRegex('^(?<x>[a-c]+)$').syntax()))).isMatch('aBc'), true)
+})
+
+Deno.test(`classNegate`, () => {
+  const syntax = (pattern) => new RRegex(pattern).syntax()
+  assertEquals(classNegate(syntax('[^abc]')), syntax('[abc]'))
+  assertEquals(classNegate(syntax('[abc]')), syntax('[^abc]'))
+  assertEquals(hirToString(classNegate(syntax('(?-u:[a-c])'))), '(?-u:[\\x00-`d-\\xFF])')
+  assertEquals(classNegate(classNegate(syntax('x(?<d>\\d+)|[a-z]*'))), syntax('x(?<d>\\d+)|[a-z]*'))
+  assertEquals(new RRegex(hirToString(classNegate(syntax('^[a-z]+$')))).isMatch('ABC'), true)
+  assertEquals(classNegate(syntax('ab')), syntax('ab'))
+})
+
 Deno.test('regex_syntax -> Empty character class', () => {
   const regex = new RRegex('[a&&b]')
   assertEquals(regex.syntax(), {
@@ -1053,6 +3096,75 @@ Deno.test(`RRegexSet::matches`, () => {
   assertEquals(set.matches('foobar'), [0, 2, 3, 4, 6])
 })
 
+Deno.test(`RRegexSet::matchesAt`, () => {
+  const set = new RRegexSet(['\\d+', '[a-z]+', '^a', '\\bb'])
+  assertEquals(set.matchesAt('a1', 0), [0, 1, 2])
+  assertEquals(set.matchesAt('a1', 1), [0])
+  assertEquals(set.matchesAt('ab', 1), [1])
+  assertEquals(set.matchesAt('a1', 2), [])
+  assertEquals(set.matchesAt('a1', 3), [])
+  assertEquals(set.matchesAt('a1', 100), [])
+})
+
+Deno.test(`RRegexSet::matchesOrderedByPosition`, () => {
+  const set = new RRegexSet(['[a-z]+', '\\d+', '\\w+', 'zzz'])
+  assertEquals(set.matchesOrderedByPosition('42 apples'), [1, 2, 0])
+  assertEquals(set.matches('42 apples'), [0, 1, 2])
+  assertEquals(set.matchesOrderedByPosition('!!!'), [])
+  const builder = new RRegexSetBuilder(['B', 'a']).caseInsensitive(true).build()
+  assertEquals(builder.matchesOrderedByPosition('xAb'), [1, 0])
+  const large = new RRegexSetBuilder(['\\w{3000}', '\\d']).sizeLimit(1 << 30).build()
+  assertEquals(large.matchesOrderedByPosition(`1 ${'a'.repeat(3000)}`), [1, 0])
+})
+
+Deno.test(`RRegexSet::matchesBitset`, () => {
+  const set = new RRegexSet(['\\w+', '\\d+', 'foo'])
+  assertEquals(set.matchesBitset('foo'), new Uint8Array([0b101]))
+  assertEquals(set.matchesBitset('☃'), new Uint8Array([0]))
+
+  const patterns = Array.from({ length: 20 }, (_, i) => `^${i}$`)
+  const bitset = new RRegexSet(patterns).matchesBitset('9')
+  assertEquals(bitset, new Uint8Array([0, 0b10, 0]))
+  assertEquals(new RRegexSet([]).matchesBitset('a'), new Uint8Array([]))
+})
+
+Deno.test(`RRegexSet::conflictingGroupNames`, () => {
+  const set = new RRegexSet(['(?<id>\\d+)', '(?<name>\\w+)', '#(?<id>\\w+)', '(?<name>a)|(?<id>b)'])
+  assertEquals(set.conflictingGroupNames(), [
+    { name: 'id', patterns: [0, 2, 3] },
+    { name: 'name', patterns: [1, 3] },
+  ])
+  assertEquals(new RRegexSet(['(?<a>x)', '(?<b>y)', 'z']).conflictingGroupNames(), [])
+  const large = new RRegexSetBuilder(['(?<a>\\w{3000})', '(?<a>b)']).sizeLimit(1 << 30).build()
+  assertEquals(large.conflictingGroupNames(), [{ name: 'a', patterns: [0, 1] }])
+})
+
+Deno.test(`RRegexSetBuilder::build`, () => {
+  const set = new RRegexSetBuilder(['foo', 'bar', '\\w+']).caseInsensitive(true).build()
+  assertEquals(set.matches('FOO'), [0, 2])
+  assertEquals(new RRegexSetBuilder(['foo']).build().isMatch('FOO'), false)
+
+  const ascii = new RRegexSetBuilder(['^\\w+$']).unicode(false).build()
+  assertEquals(ascii.isMatch('abc'), true)
+  assertEquals(ascii.isMatch('δ'), false)
+
+  assertEquals(new RRegexSetBuilder(['a{100}']).sizeLimit(1 << 20).dfaSizeLimit(1 << 20).build().isMatch('a'.repeat(100)), true)
+
+  for (const build of [
+    () => new RRegexSetBuilder(['\\w{1000}']).sizeLimit(100).build(),
+    () => new RRegexSetBuilder(['(']).build(),
+    () => new RRegexSetBuilder(['a', 1]),
+  ]) {
+    let error
+    try {
+      build()
+    } catch (err) {
+      error = err
+    }
+    assertEquals(error instanceof Error, true)
+  }
+})
+
 Deno.test("Match -> UTF-8 (äöü)", () => {
   const re = new RRegex("ä")
   const m = re.find("äöü") // { start: 0, end: 2 }